@@ -0,0 +1,132 @@
+use crate::components::modes::Modal;
+use crate::utils::{ChatMode, Theme};
+use dioxus::prelude::*;
+
+/// Pick the round to fork from and the mode the new session should use
+#[component]
+pub fn ForkDialog(
+    theme: Signal<Theme>,
+    open: Signal<bool>,
+    session_title: String,
+    source_mode: ChatMode,
+    rounds: Vec<String>,
+    on_confirm: EventHandler<(usize, ChatMode)>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let mut open_signal = open;
+    // None means the latest round / the source mode
+    let mut selected_round = use_signal(|| None::<usize>);
+    let mut selected_mode = use_signal(|| None::<ChatMode>);
+
+    let last_round = rounds.len().saturating_sub(1);
+    let round = selected_round().unwrap_or(last_round).min(last_round);
+    let mode = selected_mode().unwrap_or(source_mode);
+
+    let mut close = move || {
+        selected_round.set(None);
+        selected_mode.set(None);
+        open_signal.set(false);
+    };
+
+    rsx! {
+        Modal {
+            theme,
+            open,
+            on_close: move |_| {
+                close();
+                on_cancel.call(());
+            },
+
+            div {
+                class: "p-6",
+
+                h3 {
+                    class: "text-lg font-semibold text-[var(--color-base-content)] mb-1",
+                    "Fork \"{session_title}\""
+                }
+                p {
+                    class: "text-sm text-[var(--color-base-content)]/70 mb-4",
+                    "The new chat keeps everything up to the chosen round. Forking into another mode carries those rounds as context."
+                }
+
+                if rounds.is_empty() {
+                    p {
+                        class: "text-sm text-[var(--color-base-content)]/70 mb-6",
+                        "This chat has no rounds to fork from yet."
+                    }
+                } else {
+                    div {
+                        class: "text-xs font-semibold text-[var(--color-base-content)]/70 mb-2",
+                        "Fork after round"
+                    }
+                    div {
+                        class: "max-h-56 overflow-y-auto space-y-1 mb-4",
+                        for (idx, prompt) in rounds.iter().enumerate() {
+                            button {
+                                key: "{idx}",
+                                class: if idx == round {
+                                    "w-full text-left px-3 py-2 rounded-lg text-sm border border-[var(--color-primary)] bg-[var(--color-primary)]/10 text-[var(--color-base-content)]"
+                                } else {
+                                    "w-full text-left px-3 py-2 rounded-lg text-sm border border-[var(--color-base-300)] text-[var(--color-base-content)] hover:bg-[var(--color-base-200)]"
+                                },
+                                onclick: move |_| selected_round.set(Some(idx)),
+                                span {
+                                    class: "font-medium mr-2",
+                                    "{idx + 1}."
+                                }
+                                span {
+                                    class: "truncate",
+                                    "{prompt}"
+                                }
+                            }
+                        }
+                    }
+
+                    div {
+                        class: "text-xs font-semibold text-[var(--color-base-content)]/70 mb-2",
+                        "Into mode"
+                    }
+                    div {
+                        class: "flex flex-wrap gap-2 mb-6",
+                        for target in ChatMode::all() {
+                            button {
+                                key: "{target.name()}",
+                                class: if target == mode {
+                                    "px-3 py-1.5 rounded-lg text-sm bg-[var(--color-primary)] text-[var(--color-primary-content)]"
+                                } else {
+                                    "px-3 py-1.5 rounded-lg text-sm bg-[var(--color-base-200)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]"
+                                },
+                                title: "{target.description()}",
+                                onclick: move |_| selected_mode.set(Some(target)),
+                                "{target.name()}"
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "flex justify-end gap-3",
+
+                    button {
+                        class: "px-4 py-2 rounded-lg text-sm font-medium bg-[var(--color-base-300)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/80 transition-colors",
+                        onclick: move |_| {
+                            close();
+                            on_cancel.call(());
+                        },
+                        "Cancel"
+                    }
+
+                    button {
+                        class: "px-4 py-2 rounded-lg text-sm font-medium bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 transition-colors disabled:opacity-60 disabled:cursor-not-allowed",
+                        disabled: rounds.is_empty(),
+                        onclick: move |_| {
+                            close();
+                            on_confirm.call((round, mode));
+                        },
+                        "Fork"
+                    }
+                }
+            }
+        }
+    }
+}
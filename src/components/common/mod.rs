@@ -1,5 +1,7 @@
 pub mod confirm_dialog;
+pub mod fork_dialog;
 pub mod toast;
 
 pub use confirm_dialog::ConfirmDialog;
+pub use fork_dialog::ForkDialog;
 pub use toast::{ToastContainer, ToastMessage, ToastType, add_toast};
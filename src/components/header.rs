@@ -8,6 +8,7 @@ pub fn Header(
     on_toggle_mode: EventHandler<()>,
     on_theme_change: EventHandler<Theme>,
     on_settings: EventHandler<()>,
+    on_leaderboard: EventHandler<()>,
     client: ReadSignal<Option<Arc<OpenRouterClient>>>,
 ) -> Element {
     let theme_val = *theme.read();
@@ -256,6 +257,18 @@ pub fn Header(
                             }
                         }
 
+                        // Leaderboard button
+                        button {
+                            onclick: move |_| on_leaderboard.call(()),
+                            class: "w-8 h-8 flex items-center justify-center hover:bg-[var(--color-base-300)]/50 rounded-full transition-colors",
+                            title: "Leaderboard",
+                            img {
+                                src: asset!("/assets/competitive.svg"),
+                                class: "w-4 h-4",
+                                alt: "Leaderboard"
+                            }
+                        }
+
                         // Settings button
                         button {
                             onclick: move |_| on_settings.call(()),
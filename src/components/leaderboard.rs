@@ -0,0 +1,190 @@
+use crate::utils::{ChatSession, LeaderboardFilter, ModelRatings, ModelStanding, Theme};
+use dioxus::prelude::*;
+
+/// Model ratings from PvP judgments and Competitive and Choice votes
+#[component]
+pub fn Leaderboard(
+    theme: Signal<Theme>,
+    sessions: Signal<Vec<ChatSession>>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let _ = theme.read();
+    let mut since = use_signal(String::new);
+    let mut until = use_signal(String::new);
+    let mut tag = use_signal(|| None::<String>);
+    let mut ratings = use_signal(ModelRatings::default);
+    let mut error = use_signal(|| None::<String>);
+    let mut is_loading = use_signal(|| true);
+
+    // Re-runs whenever a session is saved; only new or changed sessions are read again.
+    // A filter change starts over from empty ratings.
+    use_effect(move || {
+        let _ = sessions.read();
+        let filter = LeaderboardFilter::from_dates(&since.read(), &until.read(), tag.read().clone());
+        let mut next = if ratings.peek().filter() == &filter {
+            ratings.peek().clone()
+        } else {
+            ModelRatings::new(filter)
+        };
+        spawn(async move {
+            is_loading.set(true);
+            match tokio::task::spawn_blocking(move || next.refresh().map(|_| next)).await {
+                Ok(Ok(updated)) => {
+                    ratings.set(updated);
+                    error.set(None);
+                }
+                Ok(Err(e)) => error.set(Some(e)),
+                Err(e) => error.set(Some(format!("Failed to compute ratings: {}", e))),
+            }
+            is_loading.set(false);
+        });
+    });
+
+    let mut all_tags: Vec<String> = sessions
+        .read()
+        .iter()
+        .flat_map(|session| session.labels.tags.iter().cloned())
+        .collect();
+    all_tags.sort_by_key(|t| t.to_lowercase());
+    all_tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    let standings = ratings.read().standings();
+    let selected_tag = tag.read().clone().unwrap_or_default();
+
+    rsx! {
+        div {
+            class: "flex flex-col h-full overflow-y-auto",
+
+            div {
+                class: "max-w-4xl mx-auto w-full p-6",
+
+                // Header
+                div {
+                    class: "flex items-center justify-between mb-6",
+                    h1 {
+                        class: "text-3xl font-bold text-[var(--color-base-content)]",
+                        "Leaderboard"
+                    }
+                    button {
+                        onclick: move |_| on_close.call(()),
+                        class: "p-2 hover:bg-[var(--color-base-300)] rounded-lg transition-colors",
+                        title: "Close leaderboard",
+                        img {
+                            src: asset!("/assets/close.svg"),
+                            class: "w-6 h-6",
+                            alt: "Close"
+                        }
+                    }
+                }
+
+                p {
+                    class: "text-sm text-[var(--color-base-content)]/70 mb-4",
+                    "Every PvP judgment and every Competitive or LLM's Choice vote counts as a head-to-head result. Ratings use TrueSkill: the range shows where a model's skill lies with 95% confidence, and models are ranked by the low end so a few lucky wins don't top the table."
+                }
+
+                // Filters
+                div {
+                    class: "flex flex-wrap items-end gap-4 mb-6",
+                    label {
+                        class: "flex flex-col gap-1 text-xs text-[var(--color-base-content)]/70",
+                        "Created from"
+                        input {
+                            r#type: "date",
+                            value: "{since}",
+                            onchange: move |evt| since.set(evt.value()),
+                            class: "px-3 py-1.5 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] text-sm",
+                        }
+                    }
+                    label {
+                        class: "flex flex-col gap-1 text-xs text-[var(--color-base-content)]/70",
+                        "to"
+                        input {
+                            r#type: "date",
+                            value: "{until}",
+                            onchange: move |evt| until.set(evt.value()),
+                            class: "px-3 py-1.5 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] text-sm",
+                        }
+                    }
+                    label {
+                        class: "flex flex-col gap-1 text-xs text-[var(--color-base-content)]/70",
+                        "Tag"
+                        select {
+                            value: "{selected_tag}",
+                            onchange: move |evt| {
+                                let value = evt.value();
+                                tag.set(if value.is_empty() { None } else { Some(value) });
+                            },
+                            class: "px-3 py-1.5 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] text-sm",
+                            option { value: "", "All chats" }
+                            for t in all_tags {
+                                option { key: "{t}", value: "{t}", selected: t.eq_ignore_ascii_case(&selected_tag), "{t}" }
+                            }
+                        }
+                    }
+                    button {
+                        onclick: move |_| {
+                            since.set(String::new());
+                            until.set(String::new());
+                            tag.set(None);
+                        },
+                        class: "px-3 py-1.5 rounded-lg bg-[var(--color-base-300)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/80 text-sm",
+                        "Clear filters"
+                    }
+                    if *is_loading.read() {
+                        span { class: "text-xs text-[var(--color-base-content)]/60 pb-2", "Updating..." }
+                    }
+                }
+
+                if let Some(e) = error.read().clone() {
+                    p { class: "text-sm text-[var(--color-error)] mb-4", "{e}" }
+                }
+
+                if standings.is_empty() {
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)] text-sm text-[var(--color-base-content)]/60",
+                        "No judged rounds yet. Finish a PvP round with a moderator, or a Competitive round, to see ratings."
+                    }
+                } else {
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg border border-[var(--color-base-300)] overflow-x-auto",
+                        table {
+                            class: "w-full text-sm text-[var(--color-base-content)]",
+                            thead {
+                                tr {
+                                    class: "text-left text-xs uppercase text-[var(--color-base-content)]/60 border-b border-[var(--color-base-300)]",
+                                    th { class: "py-2 px-4", "#" }
+                                    th { class: "py-2 pr-4", "Model" }
+                                    th { class: "py-2 pr-4 text-right", "Rating" }
+                                    th { class: "py-2 pr-4 text-right", "95% range" }
+                                    th { class: "py-2 pr-4 text-right", "Won" }
+                                    th { class: "py-2 pr-4 text-right", "Lost" }
+                                }
+                            }
+                            tbody {
+                                for (rank, standing) in standings.into_iter().enumerate() {
+                                    StandingRow { key: "{standing.model_id}", rank: rank + 1, standing }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn StandingRow(rank: usize, standing: ModelStanding) -> Element {
+    let (low, high) = standing.rating.interval();
+
+    rsx! {
+        tr {
+            class: "border-b border-[var(--color-base-300)]/50 last:border-b-0",
+            td { class: "py-2 px-4 text-[var(--color-base-content)]/60", "{rank}" }
+            td { class: "py-2 pr-4 font-mono text-xs break-all", "{standing.model_id}" }
+            td { class: "py-2 pr-4 text-right font-semibold", "{standing.rating.mu:.1}" }
+            td { class: "py-2 pr-4 text-right text-[var(--color-base-content)]/70", "{low:.1} – {high:.1}" }
+            td { class: "py-2 pr-4 text-right", "{standing.wins}" }
+            td { class: "py-2 pr-4 text-right", "{standing.losses}" }
+        }
+    }
+}
@@ -1,12 +1,18 @@
 pub mod common;
 mod header;
+mod leaderboard;
 pub mod modes;
 mod sidebar;
+mod trash_panel;
+mod unlock_screen;
 
-pub use common::{ConfirmDialog, ToastContainer, ToastMessage, ToastType, add_toast};
+pub use common::{ConfirmDialog, ForkDialog, ToastContainer, ToastMessage, ToastType, add_toast};
 pub use header::Header;
+pub use leaderboard::Leaderboard;
 pub use modes::{
     ChatDisplay, ChatInput, Choice, Collaborative, Competitive, LLMSelection, Modal, ModelSelector, NewChat, PvP,
     Settings, Standard,
 };
 pub use sidebar::Sidebar;
+pub use trash_panel::TrashPanel;
+pub use unlock_screen::UnlockScreen;
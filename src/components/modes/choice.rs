@@ -1,12 +1,12 @@
 use super::common::{
-    ChatInput, FormattedText, Modal, ModelSelector, ModelResponseCard, ThinkingIndicator,
-    AUTO_FIT_RESPONSE_GRID,
+    ChatInput, ContextPolicySelect, CustomPromptsBadge, ForkedContext, FormattedText, Modal, ModelSelector, ModelResponseCard, discard_checkpoint, ResumeBanner,
+    save_session_prompts, ThinkingIndicator, AUTO_FIT_RESPONSE_GRID,
 };
 use crate::utils::{
-    create_run_id, find_run_for_session, next_stream_event_with_cancel,
+    create_run_id, find_run_for_session, generate_title_in_background, next_stream_event_with_cancel,
     recv_multi_event_with_cancel, register_active_run, remove_run, set_run_status,
     try_signal_read, try_signal_set, try_signal_update, upsert_session, ActiveRunRecord,
-    ChatHistory, ChatMessage, ChatMode, ChatSession, InputSettings, OpenRouterClient, RunStatus,
+    ChatHistory, ChatMessage, ChatMode, ChatSession, Checkpointer, ChoicePrompts, ContextPolicy, PriorRound, RunCheckpoint, SessionLabels, SessionStats, InputSettings, OpenRouterClient, ResponseTiming, RunStatus,
     SessionData, StreamEvent, Theme,
 };
 use dioxus::prelude::*;
@@ -16,20 +16,21 @@ use std::sync::{
     Arc,
 };
 
-#[derive(Clone, Debug, PartialEq)]
-struct SystemPrompts {
-    decision: String,
-    collaborative: String,
-    competitive: String,
-}
-
-impl Default for SystemPrompts {
-    fn default() -> Self {
-        Self {
-            decision: "You are part of a team of AI models deciding on the best approach to answer a question. Consider whether collaboration or competition would yield better results.".to_string(),
-            collaborative: "You are part of a collaborative AI team working together to provide the best answer.".to_string(),
-            competitive: "You are in a competitive challenge. Provide your best solution and vote fairly for the best proposal.".to_string(),
-        }
+/// Checkpoint phase names for an LLM's Choice round
+const DECISION_PHASE: &str = "decision";
+const COLLABORATIVE_INITIAL_PHASE: &str = "collaborative_initial";
+const COLLABORATIVE_REVIEW_PHASE: &str = "collaborative_review";
+const COLLABORATIVE_CONSENSUS_PHASE: &str = "collaborative_consensus";
+const COMPETITIVE_PROPOSAL_PHASE: &str = "competitive_proposal";
+const COMPETITIVE_VOTING_PHASE: &str = "competitive_voting";
+
+fn phase_label(phase: &str) -> &'static str {
+    if phase.starts_with("collaborative") {
+        "collaborative execution"
+    } else if phase.starts_with("competitive") {
+        "competitive execution"
+    } else {
+        "the strategy decision"
     }
 }
 
@@ -56,6 +57,7 @@ struct ModelDecision {
     decision: Option<Strategy>,
     reasoning: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -63,6 +65,7 @@ struct ModelResponse {
     model_id: String,
     content: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -70,6 +73,7 @@ struct ModelProposal {
     model_id: String,
     content: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -78,6 +82,7 @@ struct ModelVote {
     voted_for: Option<String>,
     raw_response: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -140,6 +145,10 @@ struct ChoiceRound {
     collaborative_result: Option<CollaborativeRound>,
     competitive_result: Option<CompetitiveRound>,
     current_phase: ChoicePhase,
+    /// Final answer as loaded from history. Rounds saved before the strategy results
+    /// were kept have only this, so it is shown and written back as it was.
+    saved_answer: Option<String>,
+    system_prompts: Option<ChoicePrompts>,
 }
 
 // ============================================================================
@@ -209,6 +218,208 @@ fn determine_strategy(decisions: &[ModelDecision]) -> Strategy {
     }
 }
 
+impl Strategy {
+    /// Name stored in the session history
+    fn as_str(&self) -> &'static str {
+        match self {
+            Strategy::Collaborate => "collaborate",
+            Strategy::Compete => "compete",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "collaborate" => Some(Strategy::Collaborate),
+            "compete" => Some(Strategy::Compete),
+            _ => None,
+        }
+    }
+}
+
+/// Saved form of a round, keeping every decision, phase and vote so a reload renders the same
+fn to_history_round(round: &ChoiceRound) -> crate::utils::LLMChoiceRound {
+    let response = |r: &ModelResponse| crate::utils::ModelResponse {
+        model_id: r.model_id.clone(),
+        content: r.content.clone(),
+        error_message: r.error_message.clone(),
+        timing: r.timing.clone(),
+    };
+    let collaborative = round.collaborative_result.as_ref().map(|c| crate::utils::ChoiceCollaborativeResult {
+        initial_responses: c.phase1_responses.iter().map(response).collect(),
+        reviews: c.phase2_reviews.iter().map(response).collect(),
+        consensus: c.phase3_consensus.as_ref().map(response),
+    });
+    let competitive = round.competitive_result.as_ref().map(|c| crate::utils::ChoiceCompetitiveResult {
+        proposals: c
+            .phase1_proposals
+            .iter()
+            .map(|p| crate::utils::ModelProposal {
+                model_id: p.model_id.clone(),
+                content: p.content.clone(),
+                error_message: p.error_message.clone(),
+                timing: p.timing.clone(),
+            })
+            .collect(),
+        votes: c
+            .phase2_votes
+            .iter()
+            .map(|v| crate::utils::ModelVote {
+                voter_id: v.voter_id.clone(),
+                voted_for: v.voted_for.clone(),
+                ranking: Vec::new(),
+                raw_response: v.raw_response.clone(),
+                error_message: v.error_message.clone(),
+                timing: v.timing.clone(),
+            })
+            .collect(),
+        vote_tallies: c
+            .vote_tallies
+            .iter()
+            .map(|t| crate::utils::VoteTally {
+                model_id: t.model_id.clone(),
+                vote_count: t.vote_count,
+                voters: t.voters.clone(),
+                round_counts: Vec::new(),
+                eliminated_in: None,
+            })
+            .collect(),
+        winners: c.winners.clone(),
+    });
+
+    crate::utils::LLMChoiceRound {
+        user_message: round.user_question.clone(),
+        decision: round.chosen_strategy.as_ref().map_or("undecided", Strategy::as_str).to_string(),
+        content: final_answer(round),
+        decisions: round
+            .decisions
+            .iter()
+            .map(|d| crate::utils::ChoiceDecision {
+                model_id: d.model_id.clone(),
+                decision: d.decision.as_ref().map(|s| s.as_str().to_string()),
+                reasoning: d.reasoning.clone(),
+                error_message: d.error_message.clone(),
+                timing: d.timing.clone(),
+            })
+            .collect(),
+        collaborative,
+        competitive,
+        system_prompts: round.system_prompts.clone(),
+    }
+}
+
+/// The final answer: the consensus, or the first winner's proposal, or whatever was
+/// saved for a round that has neither
+fn final_answer(round: &ChoiceRound) -> Option<String> {
+    round
+        .collaborative_result
+        .as_ref()
+        .and_then(|c| c.phase3_consensus.as_ref())
+        .map(|c| c.content.clone())
+        .or_else(|| {
+            let comp = round.competitive_result.as_ref()?;
+            let winner = comp.winners.first()?;
+            comp.phase1_proposals
+                .iter()
+                .find(|p| &p.model_id == winner)
+                .map(|p| p.content.clone())
+        })
+        .or_else(|| round.saved_answer.clone())
+}
+
+/// An earlier round as the context policy sees it: whichever strategy ran, then its answer
+fn prior_round(round: &ChoiceRound) -> PriorRound {
+    let mut prior = PriorRound::new(round.user_question.clone());
+    if let Some(collab) = &round.collaborative_result {
+        for response in collab.phase1_responses.iter().filter(|r| r.error_message.is_none()) {
+            prior.section(response.model_id.clone(), &response.content);
+        }
+        for review in collab.phase2_reviews.iter().filter(|r| r.error_message.is_none()) {
+            prior.section(format!("{} (review)", review.model_id), &review.content);
+        }
+        if let Some(consensus) = collab.phase3_consensus.as_ref().filter(|c| c.error_message.is_none()) {
+            prior.section("Consensus", &consensus.content);
+        }
+    }
+    if let Some(comp) = &round.competitive_result {
+        for proposal in comp.phase1_proposals.iter().filter(|p| p.error_message.is_none()) {
+            prior.section(proposal.model_id.clone(), &proposal.content);
+        }
+        if !comp.winners.is_empty() {
+            prior.section("Vote result", &format!("Winner: {}", comp.winners.join(", ")));
+        }
+    }
+    prior.final_answer = final_answer(round);
+    prior
+}
+
+/// Rebuild a live round from its saved form
+fn from_history_round(round: &crate::utils::LLMChoiceRound) -> ChoiceRound {
+    let response = |r: &crate::utils::ModelResponse| ModelResponse {
+        model_id: r.model_id.clone(),
+        content: r.content.clone(),
+        error_message: r.error_message.clone(),
+        timing: r.timing.clone(),
+    };
+    ChoiceRound {
+        user_question: round.user_message.clone(),
+        decisions: round
+            .decisions
+            .iter()
+            .map(|d| ModelDecision {
+                model_id: d.model_id.clone(),
+                decision: d.decision.as_deref().and_then(Strategy::from_name),
+                reasoning: d.reasoning.clone(),
+                error_message: d.error_message.clone(),
+                timing: d.timing.clone(),
+            })
+            .collect(),
+        chosen_strategy: Strategy::from_name(&round.decision),
+        collaborative_result: round.collaborative.as_ref().map(|c| CollaborativeRound {
+            user_question: round.user_message.clone(),
+            phase1_responses: c.initial_responses.iter().map(response).collect(),
+            phase2_reviews: c.reviews.iter().map(response).collect(),
+            phase3_consensus: c.consensus.as_ref().map(response),
+        }),
+        competitive_result: round.competitive.as_ref().map(|c| CompetitiveRound {
+            user_question: round.user_message.clone(),
+            phase1_proposals: c
+                .proposals
+                .iter()
+                .map(|p| ModelProposal {
+                    model_id: p.model_id.clone(),
+                    content: p.content.clone(),
+                    error_message: p.error_message.clone(),
+                    timing: p.timing.clone(),
+                })
+                .collect(),
+            phase2_votes: c
+                .votes
+                .iter()
+                .map(|v| ModelVote {
+                    voter_id: v.voter_id.clone(),
+                    voted_for: v.voted_for.clone(),
+                    raw_response: v.raw_response.clone(),
+                    error_message: v.error_message.clone(),
+                    timing: v.timing.clone(),
+                })
+                .collect(),
+            vote_tallies: c
+                .vote_tallies
+                .iter()
+                .map(|t| VoteTally {
+                    model_id: t.model_id.clone(),
+                    vote_count: t.vote_count,
+                    voters: t.voters.clone(),
+                })
+                .collect(),
+            winners: c.winners.clone(),
+        }),
+        current_phase: ChoicePhase::Complete,
+        saved_answer: round.content.clone(),
+        system_prompts: round.system_prompts.clone(),
+    }
+}
+
 fn parse_vote(response: &str, voter_id: &str, valid_model_ids: &[String]) -> Option<String> {
     let response = response.trim();
     
@@ -318,15 +529,20 @@ pub fn Choice(props: ChoiceProps) -> Element {
     let mut current_streaming_responses = use_signal(|| HashMap::<String, String>::new());
     let mut current_phase = use_signal(|| ChoicePhase::Decision);
     let mut current_run_id = use_signal(|| None::<String>);
+    // Round left unfinished by a crash or restart, offered for resuming
+    let mut interrupted = use_signal(|| None::<RunCheckpoint>);
     
     // System prompts
-    let mut system_prompts = use_signal(SystemPrompts::default);
+    let mut system_prompts = use_signal(ChoicePrompts::default);
     let mut prompt_editor_open = use_signal(|| false);
     let mut editing_prompt_target = use_signal(|| PromptEditTarget::Decision);
     let mut temp_prompt = use_signal(String::new);
     
     // Track the currently loaded session to avoid reloading on every render
     let mut loaded_session_id = use_signal(|| None::<String>);
+    // Exchanges carried over when this session was forked from another one
+    let mut fork_context = use_signal(|| Vec::<(String, String)>::new());
+    let mut context_policy = use_signal(ContextPolicy::default);
     
     // Load history if session_id changes (not on every render)
     let session_id = props.session_id.clone();
@@ -354,26 +570,13 @@ pub fn Choice(props: ChoiceProps) -> Element {
                     if let ChatHistory::LLMChoice(history) = &session_data.history {
                         loaded_session_id.set(current_sid.clone());
                         selected_models.set(history.selected_models.clone());
-                        let converted_rounds: Vec<ChoiceRound> = history
-                            .rounds
-                            .iter()
-                            .map(|r| {
-                                let strategy = match r.decision.as_str() {
-                                    "collaborate" => Some(Strategy::Collaborate),
-                                    "compete" => Some(Strategy::Compete),
-                                    _ => None,
-                                };
-                                ChoiceRound {
-                                    user_question: r.user_message.clone(),
-                                    decisions: vec![],
-                                    chosen_strategy: strategy,
-                                    collaborative_result: None,
-                                    competitive_result: None,
-                                    current_phase: ChoicePhase::Complete,
-                                }
-                            })
-                            .collect();
+                        system_prompts.set(history.system_prompts.clone().or_defaults());
+                        let converted_rounds: Vec<ChoiceRound> =
+                            history.rounds.iter().map(from_history_round).collect();
                         conversation_history.set(converted_rounds);
+                        fork_context.set(history.context.clone());
+                        context_policy.set(history.context_policy);
+                        interrupted.set(session_data.checkpoint.clone());
                         if !history.selected_models.is_empty() {
                             selection_step.set(1);
                         }
@@ -384,7 +587,10 @@ pub fn Choice(props: ChoiceProps) -> Element {
                     loaded_session_id.set(current_sid);
                     selected_models.set(Vec::new());
                     conversation_history.set(Vec::new());
-                    system_prompts.set(SystemPrompts::default());
+                    fork_context.set(Vec::new());
+                    context_policy.set(ContextPolicy::default());
+                    interrupted.set(None);
+                    system_prompts.set(ChoicePrompts::default());
                     selection_step.set(0);
                 }
             }
@@ -393,7 +599,10 @@ pub fn Choice(props: ChoiceProps) -> Element {
         loaded_session_id.set(None);
         selected_models.set(Vec::new());
         conversation_history.set(Vec::new());
-        system_prompts.set(SystemPrompts::default());
+        fork_context.set(Vec::new());
+        context_policy.set(ContextPolicy::default());
+        interrupted.set(None);
+        system_prompts.set(ChoicePrompts::default());
         selection_step.set(0);
     }
 
@@ -416,14 +625,23 @@ pub fn Choice(props: ChoiceProps) -> Element {
         prompt_editor_open.set(true);
     };
     
+    let on_prompt_save_error = props.on_save_error;
     let save_prompt = move |_| {
-        let mut prompts = system_prompts.write();
-        match *editing_prompt_target.read() {
-            PromptEditTarget::Decision => prompts.decision = temp_prompt(),
-            PromptEditTarget::Collaborative => prompts.collaborative = temp_prompt(),
-            PromptEditTarget::Competitive => prompts.competitive = temp_prompt(),
-        }
+        let edited = {
+            let mut prompts = system_prompts.write();
+            match *editing_prompt_target.read() {
+                PromptEditTarget::Decision => prompts.decision = temp_prompt(),
+                PromptEditTarget::Collaborative => prompts.collaborative = temp_prompt(),
+                PromptEditTarget::Competitive => prompts.competitive = temp_prompt(),
+            }
+            prompts.clone()
+        };
         prompt_editor_open.set(false);
+        save_session_prompts(loaded_session_id(), on_prompt_save_error, move |history| {
+            if let ChatHistory::LLMChoice(h) = history {
+                h.system_prompts = edited;
+            }
+        });
     };
 
     let active_run_for_session = find_run_for_session(active_runs, &props.session_id, ChatMode::LLMChoice);
@@ -459,8 +677,10 @@ pub fn Choice(props: ChoiceProps) -> Element {
         });
     }
 
-    // Send message handler
-    let send_message = move |text: String| {
+    // Run a round, or pick up an interrupted one from its checkpoint
+    let session_id = props.session_id.clone();
+    let on_save_error = props.on_save_error;
+    let run_round = use_callback(move |(text, resume): (String, Option<RunCheckpoint>)| {
         if text.trim().is_empty() || *is_processing.read() || run_is_active {
             return;
         }
@@ -474,17 +694,28 @@ pub fn Choice(props: ChoiceProps) -> Element {
             let client = client_arc.clone();
             let user_msg = text.clone();
             let prompts = system_prompts.read().clone();
+            let carried_context = fork_context.read().clone();
+            let context_policy_for_task = *context_policy.read();
+            // Fork context first, then whatever the policy keeps of this session's earlier rounds
+            let prior_rounds: Vec<PriorRound> = conversation_history.read().iter().map(prior_round).collect();
+            let round_context: Vec<(String, String)> = carried_context
+                .iter()
+                .cloned()
+                .chain(context_policy_for_task.exchanges(&prior_rounds))
+                .collect();
             let mut is_processing_clone = is_processing.clone();
             let mut current_phase_clone = current_phase.clone();
             let mut current_streaming_clone = current_streaming_responses.clone();
             let mut conversation_history_clone = conversation_history.clone();
-            let session_id_for_save = props.session_id.clone();
+            let session_id_for_save = session_id.clone();
             let mut sessions_for_task = sessions.clone();
             let selected_models_for_save = selected_models.read().clone();
-            let on_save_error_for_task = props.on_save_error.clone();
-            let run_id = create_run_id(ChatMode::LLMChoice, &props.session_id);
+            let on_save_error_for_task = on_save_error.clone();
+            let run_id = create_run_id(ChatMode::LLMChoice, &session_id);
             current_run_id.set(Some(run_id.clone()));
             let cancel_flag = Arc::new(AtomicBool::new(false));
+            interrupted.set(None);
+            let checkpoint = resume.unwrap_or_else(|| RunCheckpoint::new(&user_msg, DECISION_PHASE));
 
             // Initialize new round
             {
@@ -496,6 +727,8 @@ pub fn Choice(props: ChoiceProps) -> Element {
                     collaborative_result: None,
                     competitive_result: None,
                     current_phase: ChoicePhase::Decision,
+                    saved_answer: None,
+                    system_prompts: Some(prompts.clone()),
                 });
             } // Drop the write borrow before spawning
 
@@ -503,9 +736,11 @@ pub fn Choice(props: ChoiceProps) -> Element {
             let mut active_runs_for_task = active_runs.clone();
             let cancel_flag_for_task = cancel_flag.clone();
             let task = spawn(async move {
+                let checkpointer = Checkpointer::new(session_id_for_save.clone(), checkpoint);
                 try_signal_set(&mut is_processing_clone, true);
                 try_signal_set(&mut current_phase_clone, ChoicePhase::Decision);
                 let _ = try_signal_update(&mut current_streaming_clone, |responses| responses.clear());
+                checkpointer.enter_phase(DECISION_PHASE).await;
 
                 // ========================================================
                 // PHASE 1: Strategy Decision
@@ -521,16 +756,32 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                     user_msg
                                 );
 
-                                let messages = vec![
-                                    ChatMessage::system(prompts.decision.clone()),
-                                    ChatMessage::user(decision_prompt)
-                                ];
+                                let messages = ChatMessage::with_context(
+                                    prompts.decision.clone(),
+                                    &round_context,
+                                    decision_prompt,
+                                );
                 let mut decisions: Vec<ModelDecision> = Vec::new();
 
-                match client.stream_chat_completion_multi(models.clone(), messages).await {
+                // Models that decided before an interruption keep their answer; only the rest are asked
+                let mut decision_responses: HashMap<String, String> = models
+                    .iter()
+                    .filter_map(|id| {
+                        checkpointer
+                            .succeeded(DECISION_PHASE, id)
+                            .map(|done| (id.clone(), done.content.clone()))
+                    })
+                    .collect();
+                let pending_models: Vec<String> = models
+                    .iter()
+                    .filter(|id| !decision_responses.contains_key(*id))
+                    .cloned()
+                    .collect();
+
+                match client.stream_chat_completion_multi(pending_models.clone(), messages).await {
                     Ok(mut rx) => {
                         let mut done_models = std::collections::HashSet::new();
-                        let mut decision_responses: HashMap<String, String> = HashMap::new();
+                        let mut decision_timings: HashMap<String, ResponseTiming> = HashMap::new();
 
                         // Buffer content locally to throttle updates
                         let mut content_buffer: HashMap<String, String> = HashMap::new();
@@ -558,12 +809,13 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                             let _ = try_signal_update(&mut current_streaming_clone, |responses| {
                                                 responses.insert(model_id.clone(), accumulated.clone());
                                             });
+                                            checkpointer.partial(&model_id, accumulated).await;
                                         }
 
                                         last_update = std::time::Instant::now();
                                     }
                                 }
-                                StreamEvent::Done => {
+                                StreamEvent::Done(timing) => {
                                     // Flush final accumulated content
                                     if let Some(accumulated) = content_buffer.get(&model_id) {
                                         let _ = try_signal_update(&mut current_streaming_clone, |responses| {
@@ -576,10 +828,14 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                     })
                                     .unwrap_or_default();
 
+                                    checkpointer.finished(&model_id, &final_content, None).await;
                                     decision_responses.insert(model_id.clone(), final_content.clone());
+                                    if let Some(timing) = timing {
+                                        decision_timings.insert(model_id.clone(), timing);
+                                    }
                                     done_models.insert(model_id.clone());
 
-                                    if done_models.len() >= models.len() {
+                                    if done_models.len() >= pending_models.len() {
                                         break;
                                     }
                                 }
@@ -587,11 +843,13 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                     if e == "Cancelled" {
                                         break;
                                     }
+                                    checkpointer.finished(&model_id, "", Some(e.clone())).await;
                                     decisions.push(ModelDecision {
                                         model_id: model_id.clone(),
                                         decision: None,
                                         reasoning: String::new(),
                                         error_message: Some(e),
+                                        timing: None,
                                     });
                                     done_models.insert(model_id);
                                 }
@@ -614,6 +872,7 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                     decision: decision.clone(),
                                     reasoning: response.clone(),
                                     error_message: None,
+                                    timing: decision_timings.remove(model_id),
                                 });
                             }
                         }
@@ -648,10 +907,14 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                 execute_collaborative(
                                     &client,
                                     &models,
-                                    &user_msg,
-                                    &prompts.collaborative,
+                                    StrategyPrompt {
+                                        user_msg: &user_msg,
+                                        system_prompt: &prompts.collaborative,
+                                        context: &round_context,
+                                    },
                                     current_streaming_clone,
                                     conversation_history_clone,
+                                    &checkpointer,
                                     cancel_flag_for_task.clone(),
                                 ).await;
                             }
@@ -667,10 +930,14 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                 execute_competitive(
                                     &client,
                                     &models,
-                                    &user_msg,
-                                    &prompts.competitive,
+                                    StrategyPrompt {
+                                        user_msg: &user_msg,
+                                        system_prompt: &prompts.competitive,
+                                        context: &round_context,
+                                    },
                                     current_streaming_clone,
                                     conversation_history_clone,
+                                    &checkpointer,
                                     cancel_flag_for_task.clone(),
                                 ).await;
                             }
@@ -689,28 +956,16 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             let history_rounds: Vec<crate::utils::LLMChoiceRound> = try_signal_read(&conversation_history_clone, |history| history.clone())
                                 .unwrap_or_default()
                                 .iter()
-                                .map(|r| {
-                                    let decision = match r.chosen_strategy {
-                                        Some(Strategy::Collaborate) => "collaborate".to_string(),
-                                        Some(Strategy::Compete) => "compete".to_string(),
-                                        None => "undecided".to_string(),
-                                    };
-                                    let content = r.collaborative_result.as_ref()
-                                        .and_then(|cr| cr.phase3_consensus.as_ref().map(|c| c.content.clone()))
-                                        .or_else(|| {
-                                            r.competitive_result.as_ref()
-                                                .and_then(|cr| cr.winners.first().map(|_| "Competitive round completed".to_string()))
-                                        });
-                                    crate::utils::LLMChoiceRound {
-                                        user_message: r.user_question.clone(),
-                                        decision,
-                                        content,
-                                    }
-                                })
+                                .map(to_history_round)
                                 .collect();
                             let history = crate::utils::LLMChoiceHistory {
                                 rounds: history_rounds,
                                 selected_models: selected_models_for_save.clone(),
+                                // Current prompts, which may have been edited while the round ran
+                                system_prompts: try_signal_read(&system_prompts, |prompts| prompts.clone())
+                                    .unwrap_or_else(|| prompts.clone()),
+                                context: carried_context.clone(),
+                                context_policy: context_policy_for_task,
                             };
                             let history_enum = ChatHistory::LLMChoice(history.clone());
                             if ChatHistory::has_content(&history_enum) {
@@ -720,18 +975,27 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                     title: summary,
                                     mode: ChatMode::LLMChoice,
                                     timestamp: ChatHistory::format_timestamp(),
+                                    stats: SessionStats::default(),
+                                    labels: SessionLabels::default(),
                                 };
                                 let session_data = SessionData {
-                                    session: session.clone(),
-                                    history: history_enum,
+                                    session,
+                                    history: history_enum.clone(),
                                     created_at: ChatHistory::session_timestamp_from_id(&sid)
                                         .unwrap_or_else(ChatHistory::format_timestamp),
                                     updated_at: ChatHistory::format_timestamp(),
+                                    forked_from: None,
+                                    renamed: false,
+                                    title_generated: false,
+                                    checkpoint: None,
                                 };
                                 match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
                                     Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
                                     Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                                    Ok(Ok(_)) => upsert_session(sessions_for_task, session),
+                                    Ok(Ok(saved_session)) => {
+                                        upsert_session(sessions_for_task, saved_session);
+                                        generate_title_in_background(client.clone(), sessions_for_task, sid.clone(), history_enum);
+                                    }
                                 }
                             }
                         }
@@ -747,6 +1011,7 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                         decision: None,
                                         reasoning: String::new(),
                                         error_message: Some(e.clone()),
+                                        timing: None,
                                     })
                                     .collect();
                             }
@@ -758,28 +1023,16 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             let history_rounds: Vec<crate::utils::LLMChoiceRound> = try_signal_read(&conversation_history_clone, |history| history.clone())
                                 .unwrap_or_default()
                                 .iter()
-                                .map(|r| {
-                                    let decision = match r.chosen_strategy {
-                                        Some(Strategy::Collaborate) => "collaborate".to_string(),
-                                        Some(Strategy::Compete) => "compete".to_string(),
-                                        None => "undecided".to_string(),
-                                    };
-                                    let content = r.collaborative_result.as_ref()
-                                        .and_then(|cr| cr.phase3_consensus.as_ref().map(|c| c.content.clone()))
-                                        .or_else(|| {
-                                            r.competitive_result.as_ref()
-                                                .and_then(|cr| cr.winners.first().map(|_| "Competitive round completed".to_string()))
-                                        });
-                                    crate::utils::LLMChoiceRound {
-                                        user_message: r.user_question.clone(),
-                                        decision,
-                                        content,
-                                    }
-                                })
+                                .map(to_history_round)
                                 .collect();
                             let history = crate::utils::LLMChoiceHistory {
                                 rounds: history_rounds,
                                 selected_models: selected_models_for_save.clone(),
+                                // Current prompts, which may have been edited while the round ran
+                                system_prompts: try_signal_read(&system_prompts, |prompts| prompts.clone())
+                                    .unwrap_or_else(|| prompts.clone()),
+                                context: carried_context.clone(),
+                                context_policy: context_policy_for_task,
                             };
                             let history_enum = ChatHistory::LLMChoice(history.clone());
                             if ChatHistory::has_content(&history_enum) {
@@ -789,18 +1042,27 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                     title: summary,
                                     mode: ChatMode::LLMChoice,
                                     timestamp: ChatHistory::format_timestamp(),
+                                    stats: SessionStats::default(),
+                                    labels: SessionLabels::default(),
                                 };
                                 let session_data = SessionData {
-                                    session: session.clone(),
-                                    history: history_enum,
+                                    session,
+                                    history: history_enum.clone(),
                                     created_at: ChatHistory::session_timestamp_from_id(&sid)
                                         .unwrap_or_else(ChatHistory::format_timestamp),
                                     updated_at: ChatHistory::format_timestamp(),
+                                    forked_from: None,
+                                    renamed: false,
+                                    title_generated: false,
+                                    checkpoint: None,
                                 };
                                 match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
                                     Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
                                     Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                                    Ok(Ok(_)) => upsert_session(sessions_for_task, session),
+                                    Ok(Ok(saved_session)) => {
+                                        upsert_session(sessions_for_task, saved_session);
+                                        generate_title_in_background(client.clone(), sessions_for_task, sid.clone(), history_enum);
+                                    }
                                 }
                             }
                         }
@@ -816,15 +1078,24 @@ pub fn Choice(props: ChoiceProps) -> Element {
             register_active_run(
                 active_runs,
                 run_id,
-                props.session_id.clone(),
+                session_id.clone(),
                 ChatMode::LLMChoice,
                 "LLM choice round".to_string(),
                 task,
                 cancel_flag,
             );
         }
+    });
+    let send_message = move |text: String| run_round.call((text, None));
+
+    let resume_round = move |_| {
+        if let Some(checkpoint) = interrupted() {
+            run_round.call((checkpoint.user_message.clone(), Some(checkpoint)));
+        }
     };
 
+    let discard_checkpoint = move |_| discard_checkpoint(interrupted, loaded_session_id());
+
     rsx! {
         div {
             class: "flex flex-col h-full",
@@ -860,13 +1131,20 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             class: "text-sm font-semibold text-[var(--color-base-content)]",
                             "System Prompts"
                         }
-                        button {
-                            onclick: move |_| {
-                                selection_step.set(0);
-                                conversation_history.write().clear();
-                            },
-                            class: "text-xs text-[var(--color-primary)] hover:underline",
-                            "Change Models"
+                        div {
+                            class: "flex items-center gap-3",
+                            ContextPolicySelect {
+                                policy: context_policy,
+                                disabled: *is_processing.read(),
+                            }
+                            button {
+                                onclick: move |_| {
+                                    selection_step.set(0);
+                                    conversation_history.write().clear();
+                                },
+                                class: "text-xs text-[var(--color-primary)] hover:underline",
+                                "Change Models"
+                            }
                         }
                     }
                     
@@ -881,6 +1159,12 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                 span {
                                     class: "text-xs font-semibold text-[var(--color-base-content)]",
                                     "Decision Phase"
+                                    if system_prompts.read().decision != ChoicePrompts::default().decision {
+                                        span {
+                                            class: "ml-1 px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                                            "Custom"
+                                        }
+                                    }
                                 }
                                 button {
                                     onclick: move |_| open_prompt_editor(PromptEditTarget::Decision),
@@ -890,6 +1174,7 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             }
                             div {
                                 class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                                title: "{system_prompts.read().decision}",
                                 "{system_prompts.read().decision}"
                             }
                         }
@@ -902,6 +1187,12 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                 span {
                                     class: "text-xs font-semibold text-[var(--color-base-content)]",
                                     "Collaborative"
+                                    if system_prompts.read().collaborative != ChoicePrompts::default().collaborative {
+                                        span {
+                                            class: "ml-1 px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                                            "Custom"
+                                        }
+                                    }
                                 }
                                 button {
                                     onclick: move |_| open_prompt_editor(PromptEditTarget::Collaborative),
@@ -911,6 +1202,7 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             }
                             div {
                                 class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                                title: "{system_prompts.read().collaborative}",
                                 "{system_prompts.read().collaborative}"
                             }
                         }
@@ -923,6 +1215,12 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                 span {
                                     class: "text-xs font-semibold text-[var(--color-base-content)]",
                                     "Competitive"
+                                    if system_prompts.read().competitive != ChoicePrompts::default().competitive {
+                                        span {
+                                            class: "ml-1 px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                                            "Custom"
+                                        }
+                                    }
                                 }
                                 button {
                                     onclick: move |_| open_prompt_editor(PromptEditTarget::Competitive),
@@ -932,6 +1230,7 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             }
                             div {
                                 class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                                title: "{system_prompts.read().competitive}",
                                 "{system_prompts.read().competitive}"
                             }
                         }
@@ -942,6 +1241,11 @@ pub fn Choice(props: ChoiceProps) -> Element {
                 div {
                     class: "flex-1 min-h-0 overflow-y-auto p-4",
 
+                    ForkedContext {
+                        theme,
+                        context: fork_context(),
+                    }
+
                     if conversation_history.read().is_empty() {
                         // Empty state
                         div {
@@ -977,10 +1281,11 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             for (round_idx, round) in conversation_history.read().iter().enumerate() {
                                 div {
                                     key: "{round_idx}",
+                                    id: "round-{round_idx}",
 
                                     // User message
                                     div {
-                                        class: "flex justify-end mb-4",
+                                        class: "flex flex-col items-end gap-1 mb-4",
                                         div {
                                             class: "max-w-[85%] bg-[var(--color-primary)] text-[var(--color-primary-content)] px-3 sm:px-4 md:px-5 py-2 sm:py-3 rounded-lg text-sm sm:text-base",
                                             FormattedText {
@@ -988,6 +1293,9 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                                 content: round.user_question.clone(),
                                             }
                                         }
+                                        CustomPromptsBadge {
+                                            prompts: round.system_prompts.as_ref().map(ChoicePrompts::customized).unwrap_or_default(),
+                                        }
                                     }
 
                                     // Decision Phase
@@ -1260,6 +1568,26 @@ pub fn Choice(props: ChoiceProps) -> Element {
                                             }
                                         }
                                     }
+
+                                    // Rounds saved with only their final answer
+                                    if round.collaborative_result.is_none() && round.competitive_result.is_none() {
+                                        if let Some(answer) = &round.saved_answer {
+                                            div {
+                                                class: "mb-6 bg-green-500/10 rounded-lg p-4 border-2 border-green-500/50",
+                                                div {
+                                                    class: "text-sm font-bold text-[var(--color-base-content)] mb-2",
+                                                    "🎯 Final Answer"
+                                                }
+                                                div {
+                                                    class: "text-sm text-[var(--color-base-content)]",
+                                                    FormattedText {
+                                                        theme,
+                                                        content: answer.clone(),
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
                             }
 
@@ -1305,6 +1633,19 @@ pub fn Choice(props: ChoiceProps) -> Element {
                             }
                         }
                     }
+
+                    if let Some(checkpoint) = interrupted() {
+                        if !*is_processing.read() {
+                            ResumeBanner {
+                                theme,
+                                phase_label: phase_label(&checkpoint.phase).to_string(),
+                                checkpoint,
+                                can_resume: client.is_some() && !run_is_active && selected_models.read().len() >= 2,
+                                on_resume: resume_round,
+                                on_discard: discard_checkpoint,
+                            }
+                        }
+                    }
                 }
 
                 if let Some((active_run_id, is_cancelling)) = cancel_bar_run.clone() {
@@ -1411,7 +1752,7 @@ pub fn Choice(props: ChoiceProps) -> Element {
                         class: "flex justify-between items-center gap-3",
                         button {
                             onclick: move |_| {
-                                let defaults = SystemPrompts::default();
+                                let defaults = ChoicePrompts::default();
                                 let default_prompt = match *editing_prompt_target.read() {
                                     PromptEditTarget::Decision => defaults.decision,
                                     PromptEditTarget::Collaborative => defaults.collaborative,
@@ -1448,28 +1789,49 @@ pub fn Choice(props: ChoiceProps) -> Element {
 // Workflow Execution Functions
 // ============================================================================
 
+/// The question and prompts shared by every phase of a strategy workflow
+struct StrategyPrompt<'a> {
+    user_msg: &'a str,
+    system_prompt: &'a str,
+    context: &'a [(String, String)],
+}
+
 async fn execute_collaborative(
     client: &Arc<OpenRouterClient>,
     models: &[String],
-    user_msg: &str,
-    system_prompt: &str,
+    prompt: StrategyPrompt<'_>,
     mut current_streaming: Signal<HashMap<String, String>>,
     mut conversation_history: Signal<Vec<ChoiceRound>>,
+    checkpointer: &Checkpointer,
     cancel_flag: Arc<AtomicBool>,
 ) {
+    let StrategyPrompt { user_msg, system_prompt, context } = prompt;
     // Phase 1: Initial Responses
     let initial_prompt = format!(
         "Provide your best answer to this question:\n\n{}",
         user_msg
     );
-    let messages = vec![
-        ChatMessage::system(system_prompt.to_string()),
-        ChatMessage::user(initial_prompt)
-    ];
+    let messages = ChatMessage::with_context(system_prompt, context, initial_prompt);
 
+    checkpointer.enter_phase(COLLABORATIVE_INITIAL_PHASE).await;
     let mut phase1_results: HashMap<String, ModelResponse> = HashMap::new();
+    for model_id in models {
+        if let Some(done) = checkpointer.succeeded(COLLABORATIVE_INITIAL_PHASE, model_id) {
+            phase1_results.insert(model_id.clone(), ModelResponse {
+                model_id: model_id.clone(),
+                content: done.content.clone(),
+                error_message: None,
+                timing: None,
+            });
+        }
+    }
+    let pending_models: Vec<String> = models
+        .iter()
+        .filter(|id| !phase1_results.contains_key(*id))
+        .cloned()
+        .collect();
 
-    if let Ok(mut rx) = client.stream_chat_completion_multi(models.to_vec(), messages).await {
+    if let Ok(mut rx) = client.stream_chat_completion_multi(pending_models.clone(), messages).await {
         let mut done_models = std::collections::HashSet::new();
         
         // Buffer content locally to throttle updates
@@ -1495,11 +1857,12 @@ async fn execute_collaborative(
                             let _ = try_signal_update(&mut current_streaming, |responses| {
                                 responses.insert(model_id.clone(), accumulated.clone());
                             });
+                            checkpointer.partial(&model_id, accumulated).await;
                         }
                         last_update = std::time::Instant::now();
                     }
                 }
-                StreamEvent::Done => {
+                StreamEvent::Done(timing) => {
                     if let Some(accumulated) = content_buffer.get(&model_id) {
                         let _ = try_signal_update(&mut current_streaming, |responses| {
                             responses.insert(model_id.clone(), accumulated.clone());
@@ -1511,17 +1874,19 @@ async fn execute_collaborative(
                     })
                     .unwrap_or_default();
 
+                    checkpointer.finished(&model_id, &final_content, None).await;
                     phase1_results.insert(
                         model_id.clone(),
                         ModelResponse {
                             model_id: model_id.clone(),
                             content: final_content,
                             error_message: None,
+                            timing,
                         },
                     );
                     done_models.insert(model_id);
 
-                    if done_models.len() >= models.len() {
+                    if done_models.len() >= pending_models.len() {
                         break;
                     }
                 }
@@ -1529,12 +1894,14 @@ async fn execute_collaborative(
                     if e == "Cancelled" {
                         break;
                     }
+                    checkpointer.finished(&model_id, "", Some(e.clone())).await;
                     phase1_results.insert(
                         model_id.clone(),
                         ModelResponse {
                             model_id: model_id.clone(),
                             content: String::new(),
                             error_message: Some(e),
+                            timing: None,
                         },
                     );
                     done_models.insert(model_id);
@@ -1564,8 +1931,19 @@ async fn execute_collaborative(
         .filter(|r| r.error_message.is_none())
         .collect();
 
+    checkpointer.enter_phase(COLLABORATIVE_REVIEW_PHASE).await;
     if successful_phase1.len() >= 2 {
         for model_id in models {
+            if let Some(done) = checkpointer.succeeded(COLLABORATIVE_REVIEW_PHASE, model_id) {
+                phase2_reviews.push(ModelResponse {
+                    model_id: model_id.clone(),
+                    content: done.content.clone(),
+                    error_message: None,
+                    timing: None,
+                });
+                continue;
+            }
+
             let other_responses: String = successful_phase1
                 .iter()
                 .filter(|r| &r.model_id != model_id)
@@ -1578,10 +1956,7 @@ async fn execute_collaborative(
                 user_msg, other_responses
             );
 
-            let review_messages = vec![
-                ChatMessage::system(system_prompt.to_string()),
-                ChatMessage::user(review_prompt)
-            ];
+            let review_messages = ChatMessage::with_context(system_prompt, context, review_prompt);
 
             if let Ok(mut stream) = client.stream_chat_completion(model_id.clone(), review_messages).await {
                 let mut review_content = String::new();
@@ -1592,12 +1967,15 @@ async fn execute_collaborative(
                     match event {
                         StreamEvent::Content(content) => {
                             review_content.push_str(&content);
+                            checkpointer.partial(model_id, &review_content).await;
                         }
-                        StreamEvent::Done => {
+                        StreamEvent::Done(timing) => {
+                            checkpointer.finished(model_id, &review_content, None).await;
                             phase2_reviews.push(ModelResponse {
                                 model_id: model_id.clone(),
                                 content: review_content,
                                 error_message: None,
+                                timing,
                             });
                             break;
                         }
@@ -1605,10 +1983,12 @@ async fn execute_collaborative(
                             if e == "Cancelled" {
                                 break;
                             }
+                            checkpointer.finished(model_id, "", Some(e.clone())).await;
                             phase2_reviews.push(ModelResponse {
                                 model_id: model_id.clone(),
                                 content: String::new(),
                                 error_message: Some(e),
+                                timing: None,
                             });
                             break;
                         }
@@ -1646,37 +2026,44 @@ async fn execute_collaborative(
         user_msg, initial_responses_text, reviews_text
     );
 
-    let consensus_messages = vec![
-        ChatMessage::system(system_prompt.to_string()),
-        ChatMessage::user(consensus_prompt)
-    ];
+    let consensus_messages = ChatMessage::with_context(system_prompt, context, consensus_prompt);
     let mut consensus_content = String::new();
     let mut consensus_error: Option<String> = None;
+    let mut consensus_timing = None;
 
-    match client.stream_chat_completion(synthesizer_id.clone(), consensus_messages).await {
-        Ok(mut stream) => {
-            while let Some(event) = next_stream_event_with_cancel(&mut stream, &cancel_flag).await {
-                if cancel_flag.load(Ordering::SeqCst) {
-                    break;
-                }
-                match event {
-                    StreamEvent::Content(content) => {
-                        consensus_content.push_str(&content);
-                    }
-                    StreamEvent::Done => {
+    checkpointer.enter_phase(COLLABORATIVE_CONSENSUS_PHASE).await;
+    if let Some(done) = checkpointer.succeeded(COLLABORATIVE_CONSENSUS_PHASE, synthesizer_id) {
+        consensus_content = done.content.clone();
+    } else {
+        match client.stream_chat_completion(synthesizer_id.clone(), consensus_messages).await {
+            Ok(mut stream) => {
+                while let Some(event) = next_stream_event_with_cancel(&mut stream, &cancel_flag).await {
+                    if cancel_flag.load(Ordering::SeqCst) {
                         break;
                     }
-                    StreamEvent::Error(e) => {
-                        if e != "Cancelled" {
-                            consensus_error = Some(e);
+                    match event {
+                        StreamEvent::Content(content) => {
+                            consensus_content.push_str(&content);
+                            checkpointer.partial(synthesizer_id, &consensus_content).await;
+                        }
+                        StreamEvent::Done(timing) => {
+                            checkpointer.finished(synthesizer_id, &consensus_content, None).await;
+                            consensus_timing = timing;
+                            break;
+                        }
+                        StreamEvent::Error(e) => {
+                            if e != "Cancelled" {
+                                checkpointer.finished(synthesizer_id, "", Some(e.clone())).await;
+                                consensus_error = Some(e);
+                            }
+                            break;
                         }
-                        break;
                     }
                 }
             }
-        }
-        Err(e) => {
-            consensus_error = Some(e);
+            Err(e) => {
+                consensus_error = Some(e);
+            }
         }
     }
 
@@ -1691,6 +2078,7 @@ async fn execute_collaborative(
                     model_id: synthesizer_id.clone(),
                     content: consensus_content,
                     error_message: consensus_error,
+                    timing: consensus_timing,
                 }),
             });
         }
@@ -1700,25 +2088,39 @@ async fn execute_collaborative(
 async fn execute_competitive(
     client: &Arc<OpenRouterClient>,
     models: &[String],
-    user_msg: &str,
-    system_prompt: &str,
+    prompt: StrategyPrompt<'_>,
     mut current_streaming: Signal<HashMap<String, String>>,
     mut conversation_history: Signal<Vec<ChoiceRound>>,
+    checkpointer: &Checkpointer,
     cancel_flag: Arc<AtomicBool>,
 ) {
+    let StrategyPrompt { user_msg, system_prompt, context } = prompt;
     // Phase 1: Proposals
     let proposal_prompt = format!(
         "Provide your best solution:\n\n{}",
         user_msg
     );
-    let messages = vec![
-        ChatMessage::system(system_prompt.to_string()),
-        ChatMessage::user(proposal_prompt)
-    ];
+    let messages = ChatMessage::with_context(system_prompt, context, proposal_prompt);
 
+    checkpointer.enter_phase(COMPETITIVE_PROPOSAL_PHASE).await;
     let mut phase1_results: HashMap<String, ModelProposal> = HashMap::new();
+    for model_id in models {
+        if let Some(done) = checkpointer.succeeded(COMPETITIVE_PROPOSAL_PHASE, model_id) {
+            phase1_results.insert(model_id.clone(), ModelProposal {
+                model_id: model_id.clone(),
+                content: done.content.clone(),
+                error_message: None,
+                timing: None,
+            });
+        }
+    }
+    let pending_models: Vec<String> = models
+        .iter()
+        .filter(|id| !phase1_results.contains_key(*id))
+        .cloned()
+        .collect();
 
-    if let Ok(mut rx) = client.stream_chat_completion_multi(models.to_vec(), messages).await {
+    if let Ok(mut rx) = client.stream_chat_completion_multi(pending_models, messages).await {
         // Buffer content locally to throttle updates
         let mut content_buffer: HashMap<String, String> = HashMap::new();
         let mut last_update = std::time::Instant::now();
@@ -1742,11 +2144,12 @@ async fn execute_competitive(
                             let _ = try_signal_update(&mut current_streaming, |responses| {
                                 responses.insert(model_id.clone(), accumulated.clone());
                             });
+                            checkpointer.partial(&model_id, accumulated).await;
                         }
                         last_update = std::time::Instant::now();
                     }
                 }
-                StreamEvent::Done => {
+                StreamEvent::Done(timing) => {
                     if let Some(accumulated) = content_buffer.get(&model_id) {
                         let _ = try_signal_update(&mut current_streaming, |responses| {
                             responses.insert(model_id.clone(), accumulated.clone());
@@ -1757,12 +2160,14 @@ async fn execute_competitive(
                         responses.get(&model_id).cloned().unwrap_or_default()
                     })
                     .unwrap_or_default();
+                    checkpointer.finished(&model_id, &final_content, None).await;
                     phase1_results.insert(
                         model_id.clone(),
                         ModelProposal {
                             model_id: model_id.clone(),
                             content: final_content,
                             error_message: None,
+                            timing,
                         },
                     );
                     let _ = try_signal_update(&mut current_streaming, |responses| {
@@ -1773,12 +2178,14 @@ async fn execute_competitive(
                     if error == "Cancelled" {
                         break;
                     }
+                    checkpointer.finished(&model_id, "", Some(error.clone())).await;
                     phase1_results.insert(
                         model_id.clone(),
                         ModelProposal {
                             model_id: model_id.clone(),
                             content: String::new(),
                             error_message: Some(error),
+                            timing: None,
                         },
                     );
                     let _ = try_signal_update(&mut current_streaming, |responses| {
@@ -1810,7 +2217,19 @@ async fn execute_competitive(
 
         let mut phase2_votes = Vec::new();
 
+        checkpointer.enter_phase(COMPETITIVE_VOTING_PHASE).await;
         for model_id in models {
+            if let Some(done) = checkpointer.succeeded(COMPETITIVE_VOTING_PHASE, model_id) {
+                phase2_votes.push(ModelVote {
+                    voter_id: model_id.clone(),
+                    voted_for: parse_vote(&done.content, model_id, models),
+                    raw_response: done.content.clone(),
+                    error_message: None,
+                    timing: None,
+                });
+                continue;
+            }
+
             let your_proposal = phase1_results
                 .get(model_id)
                 .map(|p| p.content.clone())
@@ -1821,10 +2240,7 @@ async fn execute_competitive(
                 user_msg, all_proposals_text, your_proposal
             );
 
-            let voting_messages = vec![
-                ChatMessage::system(system_prompt.to_string()),
-                ChatMessage::user(voting_prompt)
-            ];
+            let voting_messages = ChatMessage::with_context(system_prompt, context, voting_prompt);
 
             if let Ok(mut stream) = client.stream_chat_completion(model_id.clone(), voting_messages).await {
                 let mut vote_response = String::new();
@@ -1835,14 +2251,17 @@ async fn execute_competitive(
                     match event {
                         StreamEvent::Content(content) => {
                             vote_response.push_str(&content);
+                            checkpointer.partial(model_id, &vote_response).await;
                         }
-                        StreamEvent::Done => {
+                        StreamEvent::Done(timing) => {
+                            checkpointer.finished(model_id, &vote_response, None).await;
                             let voted_for = parse_vote(&vote_response, model_id, models);
                             phase2_votes.push(ModelVote {
                                 voter_id: model_id.clone(),
                                 voted_for,
                                 raw_response: vote_response,
                                 error_message: None,
+                                timing,
                             });
                             break;
                         }
@@ -1850,11 +2269,13 @@ async fn execute_competitive(
                             if e == "Cancelled" {
                                 break;
                             }
+                            checkpointer.finished(model_id, "", Some(e.clone())).await;
                             phase2_votes.push(ModelVote {
                                 voter_id: model_id.clone(),
                                 voted_for: None,
                                 raw_response: String::new(),
                                 error_message: Some(e),
+                                timing: None,
                             });
                             break;
                         }
@@ -1891,3 +2312,21 @@ async fn execute_competitive(
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_round_keeps_its_answer_through_load_and_save() {
+        let stored: crate::utils::LLMChoiceRound = serde_json::from_str(
+            r#"{"user_message": "Question", "decision": "collaborate", "content": "The agreed answer"}"#,
+        )
+        .unwrap();
+
+        let live = from_history_round(&stored);
+        assert_eq!(live.saved_answer.as_deref(), Some("The agreed answer"));
+        assert_eq!(prior_round(&live).final_answer.as_deref(), Some("The agreed answer"));
+        assert_eq!(to_history_round(&live), stored);
+    }
+}
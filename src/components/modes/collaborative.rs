@@ -1,13 +1,13 @@
 use super::common::{
-    ChatInput, FormattedText, PromptCard, PromptEditorModal, PromptType, ThinkingIndicator,
-    AUTO_FIT_RESPONSE_GRID,
+    ChatInput, ContextPolicySelect, CustomPromptsBadge, ForkedContext, FormattedText, PromptCard, PromptEditorModal, PromptType, discard_checkpoint, ResumeBanner,
+    save_session_prompts, ThinkingIndicator, AUTO_FIT_RESPONSE_GRID,
 };
 use crate::utils::{
-    create_run_id, find_run_for_session, next_stream_event_with_cancel,
+    create_run_id, find_run_for_session, generate_title_in_background, next_stream_event_with_cancel,
     recv_multi_event_with_cancel, register_active_run, remove_run, set_run_status,
     try_signal_read, try_signal_set, try_signal_update, upsert_session, ActiveRunRecord,
-    ChatHistory, ChatMessage, ChatMode, ChatSession, InputSettings, Model, OpenRouterClient,
-    RunStatus, SessionData, StreamEvent, Theme,
+    ChatHistory, ChatMessage, ChatMode, ChatSession, Checkpointer, ContextPolicy, PriorRound, SessionLabels, SessionStats, InputSettings, Model,
+    OpenRouterClient, ResponseTiming, RunCheckpoint, RunStatus, SessionData, StreamEvent, Theme,
 };
 use dioxus::core::spawn_forever;
 use dioxus::prelude::*;
@@ -30,12 +30,26 @@ struct PromptTemplates {
 
 impl Default for PromptTemplates {
     fn default() -> Self {
-        Self {
-            initial_response: "You are part of a collaborative AI team working together to answer questions. Provide your best answer to this question:\n\n{user_question}".to_string(),
+        crate::utils::CollaborativePrompts::default().into()
+    }
+}
 
-            cross_review: "Review the following responses from other AI models. Provide constructive feedback on their strengths and areas for improvement.\n\nUser Question: {user_question}\n\nOther responses:\n{other_responses}\n\nProvide your analysis:".to_string(),
+impl From<crate::utils::CollaborativePrompts> for PromptTemplates {
+    fn from(prompts: crate::utils::CollaborativePrompts) -> Self {
+        Self {
+            initial_response: prompts.initial_response,
+            cross_review: prompts.cross_review,
+            consensus: prompts.consensus,
+        }
+    }
+}
 
-            consensus: "Based on all the initial responses and reviews below, synthesize a final collaborative answer that combines the best insights from all models.\n\nUser Question: {user_question}\n\nInitial Responses:\n{initial_responses}\n\nReviews:\n{reviews}\n\nSynthesize the best collaborative answer:".to_string(),
+impl From<&PromptTemplates> for crate::utils::CollaborativePrompts {
+    fn from(templates: &PromptTemplates) -> Self {
+        Self {
+            initial_response: templates.initial_response.clone(),
+            cross_review: templates.cross_review.clone(),
+            consensus: templates.consensus.clone(),
         }
     }
 }
@@ -63,6 +77,7 @@ struct ModelResponse {
     model_id: String,
     content: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -72,6 +87,23 @@ struct CollaborativeRound {
     phase2_reviews: Vec<ModelResponse>,
     phase3_consensus: Option<ModelResponse>,
     current_phase: CollaborativePhase,
+    prompt_templates: Option<crate::utils::CollaborativePrompts>,
+}
+
+/// An earlier round as the context policy sees it; only the consensus counts as its answer
+fn prior_round(round: &CollaborativeRound) -> PriorRound {
+    let mut prior = PriorRound::new(round.user_question.clone());
+    for response in round.phase1_responses.iter().filter(|r| r.error_message.is_none()) {
+        prior.section(response.model_id.clone(), &response.content);
+    }
+    for review in round.phase2_reviews.iter().filter(|r| r.error_message.is_none()) {
+        prior.section(format!("{} (review)", review.model_id), &review.content);
+    }
+    if let Some(consensus) = round.phase3_consensus.as_ref().filter(|c| c.error_message.is_none()) {
+        prior.section("Consensus", &consensus.content);
+        prior.final_answer = Some(consensus.content.clone());
+    }
+    prior
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -100,6 +132,25 @@ impl CollaborativePhase {
             CollaborativePhase::Complete => "bg-gray-500",
         }
     }
+
+    /// Phase name stored in run checkpoints
+    fn checkpoint_key(&self) -> &'static str {
+        match self {
+            CollaborativePhase::Initial => "initial",
+            CollaborativePhase::Review => "review",
+            CollaborativePhase::Consensus => "consensus",
+            CollaborativePhase::Complete => "complete",
+        }
+    }
+
+    fn from_checkpoint_key(key: &str) -> Self {
+        match key {
+            "review" => CollaborativePhase::Review,
+            "consensus" => CollaborativePhase::Consensus,
+            "complete" => CollaborativePhase::Complete,
+            _ => CollaborativePhase::Initial,
+        }
+    }
 }
 
 // ============================================================================
@@ -160,6 +211,11 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
     let mut current_run_id = use_signal(|| None::<String>);
 
     let mut loaded_session_id = use_signal(|| None::<String>);
+    // Round left unfinished by a crash or restart, offered for resuming
+    let mut interrupted = use_signal(|| None::<RunCheckpoint>);
+    // Exchanges carried over when this session was forked from another one
+    let mut fork_context = use_signal(|| Vec::<(String, String)>::new());
+    let mut context_policy = use_signal(ContextPolicy::default);
     let session_id = props.session_id.clone();
     let session_loader = use_resource(move || {
         let session_id = session_id.clone();
@@ -185,6 +241,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                     if let ChatHistory::Collaborative(history) = &session_data.history {
                         loaded_session_id.set(current_sid.clone());
                         selected_models.set(history.selected_models.clone());
+                        prompt_templates.set(history.prompt_templates.clone().or_defaults().into());
                         let converted_rounds: Vec<CollaborativeRound> = history
                             .rounds
                             .iter()
@@ -196,6 +253,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         model_id: mr.model_id.clone(),
                                         content: mr.content.clone(),
                                         error_message: mr.error_message.clone(),
+                                        timing: mr.timing.clone(),
                                     })
                                     .collect();
                                 CollaborativeRound {
@@ -206,12 +264,17 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         model_id: "consensus".to_string(),
                                         content: consensus.clone(),
                                         error_message: None,
+                                        timing: None,
                                     }),
                                     current_phase: CollaborativePhase::Complete,
+                                    prompt_templates: r.prompt_templates.clone(),
                                 }
                             })
                             .collect();
                         conversation_history.set(converted_rounds);
+                        fork_context.set(history.context.clone());
+                        context_policy.set(history.context_policy);
+                        interrupted.set(session_data.checkpoint.clone());
                         if !history.selected_models.is_empty() {
                             selection_step.set(1);
                         }
@@ -222,6 +285,9 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                     loaded_session_id.set(current_sid);
                     selected_models.set(Vec::new());
                     conversation_history.set(Vec::new());
+                    fork_context.set(Vec::new());
+                    context_policy.set(ContextPolicy::default());
+                    interrupted.set(None);
                     prompt_templates.set(PromptTemplates::default());
                     selection_step.set(0);
                 }
@@ -231,6 +297,9 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
         loaded_session_id.set(None);
         selected_models.set(Vec::new());
         conversation_history.set(Vec::new());
+        fork_context.set(Vec::new());
+        context_policy.set(ContextPolicy::default());
+        interrupted.set(None);
         prompt_templates.set(PromptTemplates::default());
         selection_step.set(0);
     }
@@ -271,9 +340,18 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
         prompt_editor_open.set(true);
     };
 
+    let on_prompt_save_error = props.on_save_error;
     let save_prompt = move |new_prompt: String| {
-        let mut templates = prompt_templates.write();
-        templates.set(*editing_prompt_type.read(), new_prompt);
+        let edited: crate::utils::CollaborativePrompts = {
+            let mut templates = prompt_templates.write();
+            templates.set(*editing_prompt_type.read(), new_prompt);
+            (&*templates).into()
+        };
+        save_session_prompts(loaded_session_id(), on_prompt_save_error, move |history| {
+            if let ChatHistory::Collaborative(h) = history {
+                h.prompt_templates = edited;
+            }
+        });
     };
 
     let active_run_for_session = find_run_for_session(active_runs, &props.session_id, ChatMode::Collaborative);
@@ -310,8 +388,8 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
         });
     }
 
-    // Send message handler
-    let send_message = move |text: String| {
+    // Run a round, or pick up an interrupted one from its checkpoint
+    let run_round = use_callback(move |(text, resume): (String, Option<RunCheckpoint>)| {
         if text.trim().is_empty() || *is_processing.read() || run_is_active {
             return;
         }
@@ -329,6 +407,16 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
             let mut current_streaming_clone = current_streaming_responses.clone();
             let mut conversation_history_clone = conversation_history.clone();
             let templates = prompt_templates.read().clone();
+            let templates_for_save = templates.clone();
+            let carried_context = fork_context.read().clone();
+            let context_policy_for_task = *context_policy.read();
+            // Fork context first, then whatever the policy keeps of this session's earlier rounds
+            let prior_rounds: Vec<PriorRound> = conversation_history.read().iter().map(prior_round).collect();
+            let round_context: Vec<(String, String)> = carried_context
+                .iter()
+                .cloned()
+                .chain(context_policy_for_task.exchanges(&prior_rounds))
+                .collect();
             let session_id_for_save = props.session_id.clone();
             let mut sessions_for_task = sessions.clone();
             let selected_models_for_save = selected_models.read().clone();
@@ -336,15 +424,33 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
             let run_id = create_run_id(ChatMode::Collaborative, &props.session_id);
             current_run_id.set(Some(run_id.clone()));
             let cancel_flag = Arc::new(AtomicBool::new(false));
+            interrupted.set(None);
 
-            // Initialize new round
+            // Initialize new round, showing whatever an interrupted run already finished
+            let checkpoint = resume.unwrap_or_else(|| {
+                RunCheckpoint::new(&user_msg, CollaborativePhase::Initial.checkpoint_key())
+            });
+            let resumed = |phase: CollaborativePhase| -> Vec<ModelResponse> {
+                models
+                    .iter()
+                    .filter_map(|id| checkpoint.succeeded(phase.checkpoint_key(), id))
+                    .map(|r| ModelResponse {
+                        model_id: r.model_id.clone(),
+                        content: r.content.clone(),
+                        error_message: None,
+                        timing: None,
+                    })
+                    .collect()
+            };
             conversation_history_clone.write().push(CollaborativeRound {
                 user_question: user_msg.clone(),
-                phase1_responses: vec![],
-                phase2_reviews: vec![],
+                phase1_responses: resumed(CollaborativePhase::Initial),
+                phase2_reviews: resumed(CollaborativePhase::Review),
                 phase3_consensus: None,
-                current_phase: CollaborativePhase::Initial,
+                current_phase: CollaborativePhase::from_checkpoint_key(&checkpoint.phase),
+                prompt_templates: Some((&templates).into()),
             });
+            let checkpointer = Checkpointer::new(session_id_for_save.clone(), checkpoint);
 
             let run_id_for_task = run_id.clone();
             let mut active_runs_for_task = active_runs.clone();
@@ -353,6 +459,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                 try_signal_set(&mut is_processing_clone, true);
                 try_signal_set(&mut current_phase_clone, CollaborativePhase::Initial);
                 let _ = try_signal_update(&mut current_streaming_clone, |responses| responses.clear());
+                checkpointer.enter_phase(CollaborativePhase::Initial.checkpoint_key()).await;
 
                 // ========================================================
                 // PHASE 1: Initial Responses (Parallel)
@@ -361,15 +468,32 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                 let initial_prompt = templates.initial_response
                     .replace("{user_question}", &user_msg);
 
-                let messages = vec![
-                    ChatMessage::system("You are part of a collaborative AI workflow. Follow each phase instruction precisely.".to_string()),
-                    ChatMessage::user(initial_prompt),
-                ];
+                let messages = ChatMessage::with_context("You are part of a collaborative AI workflow. Follow each phase instruction precisely.".to_string(), &round_context, initial_prompt);
+
+                // Only models without a finished response are asked (all of them on a fresh round)
+                let mut phase1_results: HashMap<String, ModelResponse> = HashMap::new();
+                for model_id in &models {
+                    if let Some(done) = checkpointer.succeeded(CollaborativePhase::Initial.checkpoint_key(), model_id) {
+                        phase1_results.insert(
+                            model_id.clone(),
+                            ModelResponse {
+                                model_id: model_id.clone(),
+                                content: done.content.clone(),
+                                error_message: None,
+                                timing: None,
+                            },
+                        );
+                    }
+                }
+                let pending_models: Vec<String> = models
+                    .iter()
+                    .filter(|id| !phase1_results.contains_key(*id))
+                    .cloned()
+                    .collect();
 
-                match client.stream_chat_completion_multi(models.clone(), messages).await {
+                match client.stream_chat_completion_multi(pending_models.clone(), messages).await {
                     Ok(mut rx) => {
                         let mut done_models = std::collections::HashSet::new();
-                        let mut phase1_results: HashMap<String, ModelResponse> = HashMap::new();
 
                         // Buffer content locally to throttle updates
                         let mut content_buffer: HashMap<String, String> = HashMap::new();
@@ -389,6 +513,9 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         .entry(model_id.clone())
                                         .and_modify(|s| s.push_str(&content))
                                         .or_insert(content);
+                                    if let Some(accumulated) = content_buffer.get(&model_id) {
+                                        checkpointer.partial(&model_id, accumulated).await;
+                                    }
 
                                         // Throttle updates: only write to signal every 16ms
                                         if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
@@ -400,7 +527,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                             last_update = std::time::Instant::now();
                                         }
                                 }
-                                StreamEvent::Done => {
+                                StreamEvent::Done(timing) => {
                                     // Flush any remaining buffered content before marking done
                                     if let Some(accumulated) = content_buffer.remove(&model_id) {
                                         let _ = try_signal_update(&mut current_streaming_clone, |responses| {
@@ -413,17 +540,19 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                     })
                                     .unwrap_or_default();
 
+                                    checkpointer.finished(&model_id, &final_content, None).await;
                                     phase1_results.insert(
                                         model_id.clone(),
                                         ModelResponse {
                                             model_id: model_id.clone(),
                                             content: final_content,
                                             error_message: None,
+                                            timing,
                                         },
                                     );
                                     done_models.insert(model_id.clone());
 
-                                    if done_models.len() >= models.len() {
+                                    if done_models.len() >= pending_models.len() {
                                         break;
                                     }
                                 }
@@ -431,12 +560,14 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                     if e == "Cancelled" {
                                         break;
                                     }
+                                    checkpointer.finished(&model_id, "", Some(e.clone())).await;
                                     phase1_results.insert(
                                         model_id.clone(),
                                         ModelResponse {
                                             model_id: model_id.clone(),
                                             content: String::new(),
                                             error_message: Some(e),
+                                            timing: None,
                                         },
                                     );
                                     done_models.insert(model_id);
@@ -473,6 +604,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                 last_round.current_phase = CollaborativePhase::Review;
                             }
                         });
+                        checkpointer.enter_phase(CollaborativePhase::Review.checkpoint_key()).await;
 
                         let successful_phase1: Vec<_> = phase1_results
                             .values()
@@ -483,6 +615,16 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             let mut phase2_results = Vec::new();
 
                             for model_id in &models {
+                                if let Some(done) = checkpointer.succeeded(CollaborativePhase::Review.checkpoint_key(), model_id) {
+                                    phase2_results.push(ModelResponse {
+                                        model_id: model_id.clone(),
+                                        content: done.content.clone(),
+                                        error_message: None,
+                                        timing: None,
+                                    });
+                                    continue;
+                                }
+
                                 // Build "other responses" text
                                 let other_responses: String = successful_phase1
                                     .iter()
@@ -495,10 +637,11 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                     .replace("{user_question}", &user_msg)
                                     .replace("{other_responses}", &other_responses);
 
-                                let review_messages = vec![
-                                    ChatMessage::system("You are part of a collaborative AI workflow. Follow each phase instruction precisely.".to_string()),
-                                    ChatMessage::user(review_prompt),
-                                ];
+                                let review_messages = ChatMessage::with_context(
+                                    "You are part of a collaborative AI workflow. Follow each phase instruction precisely.".to_string(),
+                                    &round_context,
+                                    review_prompt,
+                                );
 
                                 match client.stream_chat_completion(model_id.clone(), review_messages).await {
                                     Ok(mut stream) => {
@@ -515,6 +658,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                             match event {
                                                 StreamEvent::Content(content) => {
                                                     review_content.push_str(&content);
+                                                    checkpointer.partial(model_id, &review_content).await;
 
                                                     // Throttle updates: only write to signal every 16ms
                                                     if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
@@ -524,11 +668,13 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                         last_update = std::time::Instant::now();
                                                     }
                                                 }
-                                                StreamEvent::Done => {
+                                                StreamEvent::Done(timing) => {
+                                                    checkpointer.finished(model_id, &review_content, None).await;
                                                     phase2_results.push(ModelResponse {
                                                         model_id: model_id.clone(),
                                                         content: review_content.clone(),
                                                         error_message: None,
+                                                        timing,
                                                     });
                                                     break;
                                                 }
@@ -536,10 +682,12 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                     if e == "Cancelled" {
                                                         break;
                                                     }
+                                                    checkpointer.finished(model_id, "", Some(e.clone())).await;
                                                     phase2_results.push(ModelResponse {
                                                         model_id: model_id.clone(),
                                                         content: String::new(),
                                                         error_message: Some(e),
+                                                        timing: None,
                                                     });
                                                     break;
                                                 }
@@ -551,6 +699,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                             model_id: model_id.clone(),
                                             content: String::new(),
                                             error_message: Some(e),
+                                            timing: None,
                                         });
                                     }
                                 }
@@ -593,6 +742,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                     last_round.current_phase = CollaborativePhase::Consensus;
                                 }
                             });
+                            checkpointer.enter_phase(CollaborativePhase::Consensus.checkpoint_key()).await;
 
                             // Use first model as synthesizer
                             let synthesizer_id = &models[0];
@@ -621,11 +771,28 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                 .replace("{initial_responses}", &initial_responses_text)
                                 .replace("{reviews}", &reviews_text);
 
-                            let consensus_messages = vec![
-                                ChatMessage::system("You are part of a collaborative AI workflow. Follow each phase instruction precisely.".to_string()),
-                                ChatMessage::user(consensus_prompt),
-                            ];
+                            let consensus_messages = ChatMessage::with_context(
+                                "You are part of a collaborative AI workflow. Follow each phase instruction precisely.".to_string(),
+                                &round_context,
+                                consensus_prompt,
+                            );
 
+                            let finished_consensus = checkpointer
+                                .succeeded(CollaborativePhase::Consensus.checkpoint_key(), synthesizer_id)
+                                .map(|done| done.content.clone());
+                            if let Some(consensus_content) = finished_consensus {
+                                let _ = try_signal_update(&mut conversation_history_clone, |history| {
+                                    if let Some(last_round) = history.last_mut() {
+                                        last_round.phase3_consensus = Some(ModelResponse {
+                                            model_id: synthesizer_id.clone(),
+                                            content: consensus_content,
+                                            error_message: None,
+                                            timing: None,
+                                        });
+                                        last_round.current_phase = CollaborativePhase::Complete;
+                                    }
+                                });
+                            } else {
                             match client.stream_chat_completion(synthesizer_id.clone(), consensus_messages).await {
                                 Ok(mut stream) => {
                                     let mut consensus_content = String::new();
@@ -641,6 +808,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         match event {
                                             StreamEvent::Content(content) => {
                                                 consensus_content.push_str(&content);
+                                                checkpointer.partial(synthesizer_id, &consensus_content).await;
 
                                                 // Throttle updates: only write to signal every 16ms
                                                 if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
@@ -653,7 +821,8 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                     last_update = std::time::Instant::now();
                                                 }
                                             }
-                                            StreamEvent::Done => {
+                                            StreamEvent::Done(timing) => {
+                                                checkpointer.finished(synthesizer_id, &consensus_content, None).await;
                                                 // Flush final content
                                                 let _ = try_signal_update(&mut current_streaming_clone, |responses| {
                                                     responses.insert(
@@ -668,6 +837,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                             model_id: synthesizer_id.clone(),
                                                             content: consensus_content,
                                                             error_message: None,
+                                                            timing,
                                                         });
                                                         last_round.current_phase = CollaborativePhase::Complete;
                                                     }
@@ -684,6 +854,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                             model_id: synthesizer_id.clone(),
                                                             content: String::new(),
                                                             error_message: Some(e),
+                                                            timing: None,
                                                         });
                                                     }
                                                 });
@@ -699,11 +870,13 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                 model_id: synthesizer_id.clone(),
                                                 content: String::new(),
                                                 error_message: Some(e),
+                                                timing: None,
                                             });
                                         }
                                     });
                                 }
                             }
+                            }
                         }
 
                         let _ = try_signal_update(&mut current_streaming_clone, |responses| responses.clear());
@@ -721,6 +894,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                             model_id: mr.model_id.clone(),
                                             content: mr.content.clone(),
                                             error_message: mr.error_message.clone(),
+                                            timing: mr.timing.clone(),
                                         })
                                         .collect();
                                     let final_consensus = r.phase3_consensus.as_ref().map(|c| c.content.clone());
@@ -728,6 +902,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         user_message: r.user_question.clone(),
                                         model_responses,
                                         final_consensus,
+                                        prompt_templates: r.prompt_templates.clone(),
                                     }
                                 })
                                 .collect();
@@ -735,6 +910,11 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                 rounds: history_rounds,
                                 selected_models: selected_models_for_save.clone(),
                                 system_prompt: String::new(),
+                                // Current templates, which may have been edited while the round ran
+                                prompt_templates: try_signal_read(&prompt_templates, |templates| templates.into())
+                                    .unwrap_or_else(|| (&templates_for_save).into()),
+                                context: carried_context.clone(),
+                                context_policy: context_policy_for_task,
                             };
                             let history_enum = ChatHistory::Collaborative(history.clone());
                             if ChatHistory::has_content(&history_enum) {
@@ -744,18 +924,27 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                     title: summary,
                                     mode: ChatMode::Collaborative,
                                     timestamp: ChatHistory::format_timestamp(),
+                                    stats: SessionStats::default(),
+                                    labels: SessionLabels::default(),
                                 };
                                 let session_data = SessionData {
-                                    session: session.clone(),
-                                    history: history_enum,
+                                    session,
+                                    history: history_enum.clone(),
                                     created_at: ChatHistory::session_timestamp_from_id(&sid)
                                         .unwrap_or_else(ChatHistory::format_timestamp),
                                     updated_at: ChatHistory::format_timestamp(),
+                                    forked_from: None,
+                                    renamed: false,
+                                    title_generated: false,
+                                    checkpoint: None,
                                 };
                                 match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
                                     Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
                                     Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                                    Ok(Ok(_)) => upsert_session(sessions_for_task, session),
+                                    Ok(Ok(saved_session)) => {
+                                        upsert_session(sessions_for_task, saved_session);
+                                        generate_title_in_background(client.clone(), sessions_for_task, sid.clone(), history_enum);
+                                    }
                                 }
                             }
                         }
@@ -770,6 +959,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         model_id: id.clone(),
                                         content: String::new(),
                                         error_message: Some(e.clone()),
+                                        timing: None,
                                     })
                                     .collect();
                             }
@@ -787,6 +977,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                             model_id: mr.model_id.clone(),
                                             content: mr.content.clone(),
                                             error_message: mr.error_message.clone(),
+                                            timing: mr.timing.clone(),
                                         })
                                         .collect();
                                     let final_consensus = r.phase3_consensus.as_ref().map(|c| c.content.clone());
@@ -794,6 +985,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                         user_message: r.user_question.clone(),
                                         model_responses,
                                         final_consensus,
+                                        prompt_templates: r.prompt_templates.clone(),
                                     }
                                 })
                                 .collect();
@@ -801,6 +993,11 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                 rounds: history_rounds,
                                 selected_models: selected_models_for_save.clone(),
                                 system_prompt: String::new(),
+                                // Current templates, which may have been edited while the round ran
+                                prompt_templates: try_signal_read(&prompt_templates, |templates| templates.into())
+                                    .unwrap_or_else(|| (&templates_for_save).into()),
+                                context: carried_context.clone(),
+                                context_policy: context_policy_for_task,
                             };
                             let history_enum = ChatHistory::Collaborative(history.clone());
                             if ChatHistory::has_content(&history_enum) {
@@ -810,18 +1007,27 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                     title: summary,
                                     mode: ChatMode::Collaborative,
                                     timestamp: ChatHistory::format_timestamp(),
+                                    stats: SessionStats::default(),
+                                    labels: SessionLabels::default(),
                                 };
                                 let session_data = SessionData {
-                                    session: session.clone(),
-                                    history: history_enum,
+                                    session,
+                                    history: history_enum.clone(),
                                     created_at: ChatHistory::session_timestamp_from_id(&sid)
                                         .unwrap_or_else(ChatHistory::format_timestamp),
                                     updated_at: ChatHistory::format_timestamp(),
+                                    forked_from: None,
+                                    renamed: false,
+                                    title_generated: false,
+                                    checkpoint: None,
                                 };
                                 match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
                                     Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
                                     Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                                    Ok(Ok(_)) => upsert_session(sessions_for_task, session),
+                                    Ok(Ok(saved_session)) => {
+                                        upsert_session(sessions_for_task, saved_session);
+                                        generate_title_in_background(client.clone(), sessions_for_task, sid.clone(), history_enum);
+                                    }
                                 }
                             }
                         }
@@ -844,8 +1050,17 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                 cancel_flag,
             );
         }
+    });
+    let send_message = move |text: String| run_round.call((text, None));
+
+    let resume_round = move |_| {
+        if let Some(checkpoint) = interrupted() {
+            run_round.call((checkpoint.user_message.clone(), Some(checkpoint)));
+        }
     };
 
+    let discard_checkpoint = move |_| discard_checkpoint(interrupted, loaded_session_id());
+
     // Get filtered models for display
     let models_result = available_models.read();
     let loading = models_result.is_none();
@@ -1054,13 +1269,20 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             class: "text-sm font-semibold text-[var(--color-base-content)]",
                             "Prompt Templates (Click to customize)"
                         }
-                        button {
-                            onclick: move |_| {
-                                selection_step.set(0);
-                                conversation_history.write().clear();
-                            },
-                            class: "text-xs text-[var(--color-primary)] hover:underline",
-                            "Change Models"
+                        div {
+                            class: "flex items-center gap-3",
+                            ContextPolicySelect {
+                                policy: context_policy,
+                                disabled: *is_processing.read(),
+                            }
+                            button {
+                                onclick: move |_| {
+                                    selection_step.set(0);
+                                    conversation_history.write().clear();
+                                },
+                                class: "text-xs text-[var(--color-primary)] hover:underline",
+                                "Change Models"
+                            }
                         }
                     }
 
@@ -1072,6 +1294,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             title: "Initial Response".to_string(),
                             phase_number: 1,
                             prompt: prompt_templates.read().initial_response.clone(),
+                            customized: prompt_templates.read().initial_response != PromptTemplates::default().initial_response,
                             on_edit: move |_| open_prompt_editor(PromptType::Initial),
                         }
 
@@ -1080,6 +1303,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             title: "Review Feedback".to_string(),
                             phase_number: 2,
                             prompt: prompt_templates.read().cross_review.clone(),
+                            customized: prompt_templates.read().cross_review != PromptTemplates::default().cross_review,
                             on_edit: move |_| open_prompt_editor(PromptType::Review),
                         }
 
@@ -1088,6 +1312,7 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             title: "Consensus Synthesis".to_string(),
                             phase_number: 3,
                             prompt: prompt_templates.read().consensus.clone(),
+                            customized: prompt_templates.read().consensus != PromptTemplates::default().consensus,
                             on_edit: move |_| open_prompt_editor(PromptType::Consensus),
                         }
                     }
@@ -1097,6 +1322,11 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                 div {
                     class: "flex-1 min-h-0 overflow-y-auto p-4",
 
+                    ForkedContext {
+                        theme,
+                        context: fork_context(),
+                    }
+
                     if conversation_history.read().is_empty() {
                         // Empty state
                         div {
@@ -1124,10 +1354,11 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             for (round_idx, round) in conversation_history.read().iter().enumerate() {
                                 div {
                                     key: "{round_idx}",
+                                    id: "round-{round_idx}",
 
                                     // User message
                                     div {
-                                        class: "flex justify-end mb-4",
+                                        class: "flex flex-col items-end gap-1 mb-4",
                                         div {
                                             class: "max-w-[85%] bg-[var(--color-primary)] text-[var(--color-primary-content)] px-3 sm:px-4 md:px-5 py-2 sm:py-3 rounded-lg text-sm sm:text-base",
                                             FormattedText {
@@ -1135,6 +1366,13 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                                                 content: round.user_question.clone(),
                                             }
                                         }
+                                        CustomPromptsBadge {
+                                            prompts: round
+                                                .prompt_templates
+                                                .as_ref()
+                                                .map(crate::utils::CollaborativePrompts::customized)
+                                                .unwrap_or_default(),
+                                        }
                                     }
 
                                     // Phase 1: Initial Responses
@@ -1355,6 +1593,19 @@ pub fn Collaborative(props: CollaborativeProps) -> Element {
                             }
                         }
                     }
+
+                    if let Some(checkpoint) = interrupted() {
+                        if !*is_processing.read() {
+                            ResumeBanner {
+                                theme,
+                                phase_label: CollaborativePhase::from_checkpoint_key(&checkpoint.phase).name().to_string(),
+                                checkpoint,
+                                can_resume: client.is_some() && !run_is_active && selected_models.read().len() >= 2,
+                                on_resume: resume_round,
+                                on_discard: discard_checkpoint,
+                            }
+                        }
+                    }
                 }
 
                 if let Some((active_run_id, is_cancelling)) = cancel_bar_run.clone() {
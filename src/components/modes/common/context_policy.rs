@@ -0,0 +1,29 @@
+use crate::utils::ContextPolicy;
+use dioxus::prelude::*;
+
+/// Compact picker for how much of the session's earlier rounds a new round is sent
+#[component]
+pub fn ContextPolicySelect(policy: Signal<ContextPolicy>, disabled: bool) -> Element {
+    let current = *policy.read();
+
+    rsx! {
+        label {
+            class: "flex items-center gap-1.5 text-xs text-[var(--color-base-content)]/80",
+            title: "What each new round is told about the earlier rounds in this chat, so follow-up questions make sense",
+            "History:"
+            select {
+                disabled,
+                onchange: move |evt| policy.set(ContextPolicy::from_key(&evt.value())),
+                class: "px-1 py-0.5 rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] text-xs",
+                for option_policy in ContextPolicy::ALL {
+                    option {
+                        key: "{option_policy.key()}",
+                        value: option_policy.key(),
+                        selected: option_policy == current,
+                        {option_policy.label()}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::FormattedText;
+use crate::utils::Theme;
+use dioxus::prelude::*;
+
+/// Collapsible summary of the exchanges a forked session carries over from its parent
+#[component]
+pub fn ForkedContext(theme: Signal<Theme>, context: Vec<(String, String)>) -> Element {
+    let mut expanded = use_signal(|| false);
+
+    if context.is_empty() {
+        return rsx! {};
+    }
+
+    let count = context.len();
+    let label = if count == 1 { "exchange" } else { "exchanges" };
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-lg border border-dashed border-[var(--color-base-300)] bg-[var(--color-base-200)]/50 p-3",
+            div {
+                class: "flex items-center justify-between gap-2",
+                span {
+                    class: "text-xs text-[var(--color-base-content)]/70",
+                    "↳ Forked with {count} earlier {label} as context"
+                }
+                button {
+                    onclick: move |_| {
+                        let open = expanded();
+                        expanded.set(!open);
+                    },
+                    class: "text-xs text-[var(--color-primary)] hover:underline",
+                    if expanded() { "Hide" } else { "Show" }
+                }
+            }
+            if expanded() {
+                div {
+                    class: "mt-3 space-y-3",
+                    for (idx, (user_msg, answer)) in context.iter().enumerate() {
+                        div {
+                            key: "{idx}",
+                            class: "space-y-1 text-sm",
+                            div {
+                                class: "font-medium text-[var(--color-base-content)]",
+                                "{user_msg}"
+                            }
+                            div {
+                                class: "pl-3 border-l-2 border-[var(--color-base-300)] text-[var(--color-base-content)]/80",
+                                FormattedText {
+                                    theme,
+                                    content: answer.clone(),
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
 mod chat;
+mod context_policy;
+mod forked_context;
 mod input;
 mod modal;
 mod model_response_card;
@@ -6,6 +8,7 @@ mod model_selector;
 mod phase_indicator;
 mod prompt_card;
 mod prompt_editor;
+mod resume_banner;
 mod selection;
 mod streaming_indicator;
 mod vote_display;
@@ -15,13 +18,16 @@ pub const AUTO_FIT_RESPONSE_GRID: &str =
     "grid grid-cols-[repeat(auto-fit,minmax(18rem,1fr))] w-full";
 
 pub use chat::{ChatDisplay, FormattedText};
+pub use context_policy::ContextPolicySelect;
+pub use forked_context::ForkedContext;
 pub use input::ChatInput;
 pub use modal::Modal;
 pub use model_response_card::ModelResponseCard;
 pub use model_selector::ModelSelector;
 pub use phase_indicator::PhaseIndicator;
-pub use prompt_card::PromptCard;
+pub use prompt_card::{save_session_prompts, CustomPromptsBadge, PromptCard};
 pub use prompt_editor::{PromptEditorModal, PromptType};
+pub use resume_banner::{discard_checkpoint, ResumeBanner};
 pub use selection::LLMSelection;
 pub use streaming_indicator::{StreamingIndicator, ThinkingIndicator};
 pub use vote_display::VoteDisplay;
@@ -1,4 +1,4 @@
-use crate::utils::Theme;
+use crate::utils::{ChatHistory, Theme};
 use dioxus::prelude::*;
 
 #[component]
@@ -7,6 +7,8 @@ pub fn PromptCard(
     title: String,
     phase_number: u8,
     prompt: String,
+    /// Edited away from the default; rounds from now on run with it
+    customized: bool,
     on_edit: EventHandler<()>,
 ) -> Element {
     let _ = theme.read();
@@ -28,6 +30,12 @@ pub fn PromptCard(
                         class: "font-semibold text-sm text-[var(--color-base-content)]",
                         "{title}"
                     }
+                    if customized {
+                        span {
+                            class: "px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                            "Custom"
+                        }
+                    }
                 }
                 button {
                     class: "text-[var(--color-primary)] hover:opacity-70 text-sm transition-opacity flex items-center gap-1",
@@ -52,3 +60,40 @@ pub fn PromptCard(
         }
     }
 }
+
+/// Marks a round that ran with prompts edited away from the defaults; hovering shows them
+#[component]
+pub fn CustomPromptsBadge(prompts: Vec<(&'static str, String)>) -> Element {
+    let details = prompts
+        .iter()
+        .map(|(label, prompt)| format!("{}:\n{}", label, prompt))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    rsx! {
+        if !prompts.is_empty() {
+            span {
+                class: "px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)] cursor-help",
+                title: "{details}",
+                "Custom prompts"
+            }
+        }
+    }
+}
+
+/// Write edited prompts into the saved session straight away, so a reload before the
+/// next round does not bring back the old ones
+pub fn save_session_prompts(
+    session_id: Option<String>,
+    on_save_error: EventHandler<String>,
+    update: impl FnOnce(&mut ChatHistory) + Send + 'static,
+) {
+    let Some(sid) = session_id else { return };
+    spawn(async move {
+        match tokio::task::spawn_blocking(move || ChatHistory::update_history(&sid, update)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => on_save_error.call(format!("Failed to save prompts: {}", e)),
+            Err(e) => on_save_error.call(format!("Failed to save prompts: {}", e)),
+        }
+    });
+}
@@ -0,0 +1,123 @@
+use super::FormattedText;
+use crate::utils::{ChatHistory, RunCheckpoint, Theme};
+use dioxus::prelude::*;
+
+/// Notice for a round that was interrupted mid-stream, with the text saved so far
+/// and actions to resume it or throw it away
+#[component]
+pub fn ResumeBanner(
+    theme: Signal<Theme>,
+    checkpoint: RunCheckpoint,
+    phase_label: String,
+    can_resume: bool,
+    on_resume: EventHandler<()>,
+    on_discard: EventHandler<()>,
+) -> Element {
+    let mut expanded = use_signal(|| false);
+
+    let finished = checkpoint.finished.len();
+    let partial = checkpoint.partial.len();
+    let saved_label = match (finished, partial) {
+        (0, 0) => "No responses had arrived yet.".to_string(),
+        (f, 0) => format!("{} finished response(s) were saved.", f),
+        (f, p) => format!(
+            "{} finished and {} partial response(s) were saved; unfinished models will be asked again.",
+            f, p
+        ),
+    };
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-lg border border-amber-500/60 bg-amber-500/10 p-3",
+            div {
+                class: "flex items-start justify-between gap-3",
+                div {
+                    class: "min-w-0 space-y-1",
+                    div {
+                        class: "text-sm font-medium text-[var(--color-base-content)]",
+                        "This round was interrupted during {phase_label}"
+                    }
+                    div {
+                        class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                        "“{checkpoint.user_message}”"
+                    }
+                    div {
+                        class: "text-xs text-[var(--color-base-content)]/70",
+                        "{saved_label}"
+                    }
+                }
+                div {
+                    class: "flex shrink-0 items-center gap-2",
+                    button {
+                        onclick: move |_| on_discard.call(()),
+                        class: "px-3 py-1.5 rounded-lg text-xs text-[var(--color-base-content)]/70 hover:bg-[var(--color-base-300)]/50",
+                        "Discard"
+                    }
+                    button {
+                        onclick: move |_| on_resume.call(()),
+                        disabled: !can_resume,
+                        class: "px-3 py-1.5 rounded-lg text-xs font-medium bg-[var(--color-primary)] text-[var(--color-primary-content)] disabled:opacity-50 disabled:cursor-not-allowed",
+                        "Resume"
+                    }
+                }
+            }
+            if finished + partial > 0 {
+                button {
+                    onclick: move |_| {
+                        let open = expanded();
+                        expanded.set(!open);
+                    },
+                    class: "mt-2 text-xs text-[var(--color-primary)] hover:underline",
+                    if expanded() { "Hide saved text" } else { "Show saved text" }
+                }
+            }
+            if expanded() {
+                div {
+                    class: "mt-3 space-y-3",
+                    for (idx, response) in checkpoint.finished.iter().chain(checkpoint.partial.iter()).enumerate() {
+                        div {
+                            key: "{idx}",
+                            class: "space-y-1 text-sm",
+                            div {
+                                class: "text-xs font-medium text-[var(--color-base-content)]/80",
+                                if idx >= finished {
+                                    "{response.model_id} · {response.phase} (incomplete)"
+                                } else {
+                                    "{response.model_id} · {response.phase}"
+                                }
+                            }
+                            if let Some(error) = &response.error_message {
+                                div {
+                                    class: "text-xs text-red-500",
+                                    "{error}"
+                                }
+                            } else {
+                                div {
+                                    class: "pl-3 border-l-2 border-[var(--color-base-300)] text-[var(--color-base-content)]/80",
+                                    FormattedText {
+                                        theme,
+                                        content: response.content.clone(),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Hide the banner and drop the session's saved checkpoint in the background
+pub fn discard_checkpoint(mut interrupted: Signal<Option<RunCheckpoint>>, session_id: Option<String>) {
+    interrupted.set(None);
+    if let Some(sid) = session_id {
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatHistory::save_checkpoint(&sid, None)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("Failed to discard checkpoint: {}", e),
+                Err(e) => eprintln!("Failed to discard checkpoint: {}", e),
+            }
+        });
+    }
+}
@@ -1,11 +1,20 @@
+use crate::utils::VotingMethod;
 use dioxus::prelude::*;
 
 #[component]
 pub fn VoteDisplay(
     voter_id: String,
     voted_for: Option<String>,
+    /// Full ballot for ranked and approval voting; empty for plurality
+    ranking: Vec<String>,
+    method: VotingMethod,
     raw_response: Option<String>,
 ) -> Element {
+    let ballot = match method {
+        VotingMethod::Approval => ranking.join(", "),
+        _ => ranking.join(" > "),
+    };
+
     rsx! {
         div {
             class: "flex items-center gap-3 p-3 rounded-lg bg-[var(--color-base-100)] border border-[var(--color-base-300)]",
@@ -23,7 +32,19 @@ pub fn VoteDisplay(
             }
 
             // Voted for or error
-            if let Some(vote) = voted_for {
+            if ranking.len() > 1 {
+                div {
+                    class: "flex items-center gap-2 flex-1",
+                    div {
+                        class: "font-mono text-sm font-semibold text-[var(--color-primary)] break-all",
+                        "{ballot}"
+                    }
+                    div {
+                        class: "text-green-500 text-xl",
+                        "✓"
+                    }
+                }
+            } else if let Some(vote) = voted_for {
                 div {
                     class: "flex items-center gap-2 flex-1",
                     div {
@@ -1,9 +1,12 @@
+use crate::utils::VotingMethod;
 use dioxus::prelude::*;
 
 #[derive(Props, Clone, PartialEq)]
 pub struct VoteTallyProps {
-    pub tallies: Vec<(String, usize, Vec<String>)>,  // (model_id, vote_count, voters)
+    pub tallies: Vec<crate::utils::VoteTally>,
     pub winners: Vec<String>,
+    /// Decides whether counts read as votes, points or approvals
+    pub method: VotingMethod,
 }
 
 #[component]
@@ -43,10 +46,10 @@ pub fn VoteTally(props: VoteTallyProps) -> Element {
                 class: "space-y-3",
                 div {
                     class: "text-sm font-semibold text-[var(--color-base-content)]/70 mb-2",
-                    "Vote Breakdown:"
+                    "Vote Breakdown ({props.method.label()}):"
                 }
 
-                for (model_id, vote_count, voters) in props.tallies.iter() {
+                for tally in props.tallies.iter() {
                     div {
                         class: "p-3 rounded-lg bg-[var(--color-base-200)] border border-[var(--color-base-300)]",
 
@@ -54,24 +57,48 @@ pub fn VoteTally(props: VoteTallyProps) -> Element {
                             class: "flex items-center justify-between mb-2",
                             div {
                                 class: "font-mono text-sm font-semibold text-[var(--color-base-content)]",
-                                "{model_id}"
+                                "{tally.model_id}"
                             }
                             div {
                                 class: "flex items-center gap-2",
                                 div {
                                     class: "text-sm font-bold text-[var(--color-primary)]",
-                                    {format!("{vote_count} vote{}", if *vote_count == 1 { "" } else { "s" })}
+                                    {props.method.count_label(tally.vote_count)}
                                 }
-                                if props.winners.contains(model_id) {
+                                if props.winners.contains(&tally.model_id) {
                                     div { class: "text-xl", "🏆" }
                                 }
                             }
                         }
 
-                        if !voters.is_empty() {
+                        if !tally.voters.is_empty() {
                             div {
                                 class: "text-xs text-[var(--color-base-content)]/60",
-                                "Voted by: {voters.join(\", \")}"
+                                "Voted by: {tally.voters.join(\", \")}"
+                            }
+                        }
+
+                        // Instant runoff: the count in each round, and when the proposal dropped out
+                        if !tally.round_counts.is_empty() {
+                            div {
+                                class: "mt-1 flex flex-wrap items-center gap-2 text-xs text-[var(--color-base-content)]/60",
+                                span {
+                                    {
+                                        tally
+                                            .round_counts
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(idx, count)| format!("R{}: {}", idx + 1, count))
+                                            .collect::<Vec<_>>()
+                                            .join(" → ")
+                                    }
+                                }
+                                if let Some(round) = tally.eliminated_in {
+                                    span {
+                                        class: "px-1.5 py-0.5 rounded bg-[var(--color-base-300)] text-[var(--color-base-content)]/70",
+                                        "Eliminated in round {round}"
+                                    }
+                                }
                             }
                         }
                     }
@@ -1,13 +1,14 @@
 use super::common::{
-    ChatInput, FormattedText, ModelResponseCard, PhaseIndicator, PromptCard, PromptEditorModal,
-    ThinkingIndicator, VoteDisplay, VoteTally, AUTO_FIT_RESPONSE_GRID,
+    ChatInput, ContextPolicySelect, CustomPromptsBadge, ForkedContext, FormattedText, ModelResponseCard, PhaseIndicator, PromptCard, PromptEditorModal,
+    discard_checkpoint, ResumeBanner, save_session_prompts, ThinkingIndicator, VoteDisplay, VoteTally, AUTO_FIT_RESPONSE_GRID,
 };
 use crate::utils::{
-    create_run_id, find_run_for_session, next_stream_event_with_cancel,
+    create_run_id, find_run_for_session, generate_title_in_background, next_stream_event_with_cancel,
     recv_multi_event_with_cancel, register_active_run, remove_run, set_run_status,
     try_signal_read, try_signal_set, try_signal_update, upsert_session, ActiveRunRecord,
-    ChatMessage, ChatHistory, ChatMode, ChatSession, CompetitiveHistory, InputSettings, Model,
-    OpenRouterClient, RunStatus, SessionData, StreamEvent, Theme,
+    ChatMessage, ChatHistory, ChatMode, ChatSession, Checkpointer, SessionLabels, SessionStats, CompetitiveHistory,
+    ContextPolicy, PriorRound, TieBreak, VotingMethod,
+    InputSettings, Model, OpenRouterClient, ResponseTiming, RunCheckpoint, RunStatus, SessionData, StreamEvent, Theme,
 };
 use dioxus::core::spawn_forever;
 use dioxus::prelude::*;
@@ -58,10 +59,24 @@ struct PromptTemplates {
 
 impl Default for PromptTemplates {
     fn default() -> Self {
+        crate::utils::PromptTemplates::default().into()
+    }
+}
+
+impl From<crate::utils::PromptTemplates> for PromptTemplates {
+    fn from(templates: crate::utils::PromptTemplates) -> Self {
         Self {
-            proposal: "You are participating in a competitive problem-solving challenge with other AI models. Provide your best solution to this question:\n\n{user_question}".to_string(),
+            proposal: templates.proposal,
+            voting: templates.voting,
+        }
+    }
+}
 
-            voting: "You are voting on the best solution among the proposals below. You CANNOT vote for your own response.\n\nUser Question: {user_question}\n\nAll Proposals:\n{all_proposals}\n\nYour Proposal:\n{your_proposal}\n\nVote for the BEST proposal by responding with ONLY the model ID of your choice (e.g., 'anthropic/claude-3.5-sonnet'). Do not vote for yourself.".to_string(),
+impl From<&PromptTemplates> for crate::utils::PromptTemplates {
+    fn from(templates: &PromptTemplates) -> Self {
+        Self {
+            proposal: templates.proposal.clone(),
+            voting: templates.voting.clone(),
         }
     }
 }
@@ -87,14 +102,18 @@ struct ModelProposal {
     model_id: String,
     content: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 struct ModelVote {
     voter_id: String,
     voted_for: Option<String>,
+    /// Full ballot for ranked and approval voting
+    ranking: Vec<String>,
     raw_response: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -102,6 +121,33 @@ struct VoteTally {
     model_id: String,
     vote_count: usize,
     voters: Vec<String>,
+    round_counts: Vec<usize>,
+    eliminated_in: Option<usize>,
+}
+
+impl From<crate::utils::ModelVote> for ModelVote {
+    fn from(vote: crate::utils::ModelVote) -> Self {
+        Self {
+            voter_id: vote.voter_id,
+            voted_for: vote.voted_for,
+            ranking: vote.ranking,
+            raw_response: vote.raw_response,
+            error_message: vote.error_message,
+            timing: vote.timing,
+        }
+    }
+}
+
+impl From<crate::utils::VoteTally> for VoteTally {
+    fn from(tally: crate::utils::VoteTally) -> Self {
+        Self {
+            model_id: tally.model_id,
+            vote_count: tally.vote_count,
+            voters: tally.voters,
+            round_counts: tally.round_counts,
+            eliminated_in: tally.eliminated_in,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -112,6 +158,44 @@ struct CompetitiveRound {
     vote_tallies: Vec<VoteTally>,
     winners: Vec<String>,
     current_phase: CompetitivePhase,
+    voting_method: VotingMethod,
+    tie_break: TieBreak,
+    runoffs: Vec<RunoffPhase>,
+    prompt_templates: Option<crate::utils::PromptTemplates>,
+}
+
+/// A tie-break phase held after the vote; `CompetitiveRound::winners` holds the final outcome
+#[derive(Clone, Debug, PartialEq)]
+struct RunoffPhase {
+    tie_break: TieBreak,
+    candidates: Vec<String>,
+    votes: Vec<ModelVote>,
+    vote_tallies: Vec<VoteTally>,
+    winners: Vec<String>,
+}
+
+/// An earlier round as the context policy sees it; the winning proposals are its answer
+fn prior_round(round: &CompetitiveRound) -> PriorRound {
+    let mut prior = PriorRound::new(round.user_question.clone());
+    let proposals: Vec<&ModelProposal> = round.phase1_proposals.iter().filter(|p| p.error_message.is_none()).collect();
+    for proposal in &proposals {
+        prior.section(proposal.model_id.clone(), &proposal.content);
+    }
+    let winning: Vec<&&ModelProposal> = proposals.iter().filter(|p| round.winners.contains(&p.model_id)).collect();
+    if !winning.is_empty() {
+        prior.section("Vote result", &format!("Winner: {}", round.winners.join(", ")));
+    }
+    prior.final_answer = match winning.as_slice() {
+        [] => None,
+        [only] => Some(only.content.clone()),
+        many => Some(
+            many.iter()
+                .map(|p| format!("**{}**:\n{}", p.model_id, p.content))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        ),
+    };
+    prior
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -119,6 +203,7 @@ enum CompetitivePhase {
     Proposal,
     Voting,
     Tallying,
+    TieBreak,
     Complete,
 }
 
@@ -128,6 +213,7 @@ impl CompetitivePhase {
             CompetitivePhase::Proposal => "Phase 1: Proposals",
             CompetitivePhase::Voting => "Phase 2: Voting",
             CompetitivePhase::Tallying => "Tallying Results",
+            CompetitivePhase::TieBreak => "Breaking the Tie",
             CompetitivePhase::Complete => "Complete",
         }
     }
@@ -137,6 +223,7 @@ impl CompetitivePhase {
             CompetitivePhase::Proposal => "bg-blue-500",
             CompetitivePhase::Voting => "bg-purple-500",
             CompetitivePhase::Tallying => "bg-yellow-500",
+            CompetitivePhase::TieBreak => "bg-orange-500",
             CompetitivePhase::Complete => "bg-gray-500",
         }
     }
@@ -146,7 +233,29 @@ impl CompetitivePhase {
             CompetitivePhase::Proposal => 1,
             CompetitivePhase::Voting => 2,
             CompetitivePhase::Tallying => 3,
-            CompetitivePhase::Complete => 4,
+            CompetitivePhase::TieBreak => 4,
+            CompetitivePhase::Complete => 5,
+        }
+    }
+
+    /// Phase name stored in saved rounds and run checkpoints
+    fn key(&self) -> &'static str {
+        match self {
+            CompetitivePhase::Proposal => "proposal",
+            CompetitivePhase::Voting => "voting",
+            CompetitivePhase::Tallying => "tallying",
+            CompetitivePhase::TieBreak => "tie_break",
+            CompetitivePhase::Complete => "complete",
+        }
+    }
+
+    fn from_key(key: &str) -> Self {
+        match key {
+            "proposal" => CompetitivePhase::Proposal,
+            "voting" => CompetitivePhase::Voting,
+            "tallying" => CompetitivePhase::Tallying,
+            "tie_break" => CompetitivePhase::TieBreak,
+            _ => CompetitivePhase::Complete,
         }
     }
 }
@@ -155,74 +264,214 @@ impl CompetitivePhase {
 // Helper Functions
 // ============================================================================
 
-fn parse_vote(response: &str, voter_id: &str, valid_model_ids: &[String]) -> Option<String> {
-    let response = response.trim();
+/// A vote as a (voter, ballot) pair for counting; plurality votes carry only their first choice
+fn ballots(votes: &[ModelVote]) -> Vec<(String, Vec<String>)> {
+    votes
+        .iter()
+        .filter(|vote| vote.error_message.is_none())
+        .map(|vote| {
+            let ballot = if vote.ranking.is_empty() {
+                vote.voted_for.iter().cloned().collect()
+            } else {
+                vote.ranking.clone()
+            };
+            (vote.voter_id.clone(), ballot)
+        })
+        .collect()
+}
 
-    // Try exact match first
-    for model_id in valid_model_ids {
-        if response.contains(model_id) {
-            // Prevent self-voting
-            if model_id == voter_id {
-                return None;
-            }
-            return Some(model_id.clone());
-        }
+fn to_saved_vote(vote: &ModelVote) -> crate::utils::ModelVote {
+    crate::utils::ModelVote {
+        voter_id: vote.voter_id.clone(),
+        voted_for: vote.voted_for.clone(),
+        ranking: vote.ranking.clone(),
+        raw_response: vote.raw_response.clone(),
+        error_message: vote.error_message.clone(),
+        timing: vote.timing.clone(),
     }
+}
 
-    // Try fuzzy matching (last part of model ID)
-    for model_id in valid_model_ids {
-        let model_name = model_id.split('/').last().unwrap_or(model_id);
-        if response.to_lowercase().contains(&model_name.to_lowercase()) {
-            if model_id == voter_id {
-                return None;
-            }
-            return Some(model_id.clone());
-        }
+fn to_saved_tally(tally: &VoteTally) -> crate::utils::VoteTally {
+    crate::utils::VoteTally {
+        model_id: tally.model_id.clone(),
+        vote_count: tally.vote_count,
+        voters: tally.voters.clone(),
+        round_counts: tally.round_counts.clone(),
+        eliminated_in: tally.eliminated_in,
     }
+}
 
-    None
+fn from_saved_round(r: &crate::utils::CompetitiveRound) -> CompetitiveRound {
+    CompetitiveRound {
+        user_question: r.user_question.clone(),
+        phase1_proposals: r.phase1_proposals.iter().map(|p| ModelProposal {
+            model_id: p.model_id.clone(),
+            content: p.content.clone(),
+            error_message: p.error_message.clone(),
+            timing: p.timing.clone(),
+        }).collect(),
+        phase2_votes: r.phase2_votes.iter().cloned().map(ModelVote::from).collect(),
+        vote_tallies: r.vote_tallies.iter().cloned().map(VoteTally::from).collect(),
+        winners: r.winners.clone(),
+        current_phase: CompetitivePhase::from_key(&r.current_phase),
+        voting_method: r.voting_method,
+        tie_break: r.tie_break,
+        runoffs: r.runoffs.iter().map(|runoff| RunoffPhase {
+            tie_break: runoff.tie_break,
+            candidates: runoff.candidates.clone(),
+            votes: runoff.votes.iter().cloned().map(ModelVote::from).collect(),
+            vote_tallies: runoff.vote_tallies.iter().cloned().map(VoteTally::from).collect(),
+            winners: runoff.winners.clone(),
+        }).collect(),
+        prompt_templates: r.prompt_templates.clone(),
+    }
 }
 
-fn compute_tallies(votes: &[ModelVote], model_ids: &[String]) -> (Vec<VoteTally>, Vec<String>) {
-    let mut tally_map: HashMap<String, (usize, Vec<String>)> = HashMap::new();
+/// Session settings saved alongside the rounds
+struct CompetitiveSettings {
+    selected_models: Vec<String>,
+    prompt_templates: PromptTemplates,
+    context: Vec<(String, String)>,
+    context_policy: ContextPolicy,
+    voting_method: VotingMethod,
+    tie_break: TieBreak,
+    tie_break_chair: Option<String>,
+}
 
-    // Initialize all models with 0 votes
-    for model_id in model_ids {
-        tally_map.insert(model_id.clone(), (0, Vec::new()));
+fn to_saved_history(rounds: &[CompetitiveRound], settings: CompetitiveSettings) -> CompetitiveHistory {
+    CompetitiveHistory {
+        rounds: rounds
+            .iter()
+            .map(|r| crate::utils::CompetitiveRound {
+                user_question: r.user_question.clone(),
+                phase1_proposals: r.phase1_proposals.iter()
+                    .map(|p| crate::utils::ModelProposal {
+                        model_id: p.model_id.clone(),
+                        content: p.content.clone(),
+                        error_message: p.error_message.clone(),
+                        timing: p.timing.clone(),
+                    })
+                    .collect(),
+                phase2_votes: r.phase2_votes.iter().map(to_saved_vote).collect(),
+                vote_tallies: r.vote_tallies.iter().map(to_saved_tally).collect(),
+                winners: r.winners.clone(),
+                current_phase: r.current_phase.key().to_string(),
+                voting_method: r.voting_method,
+                tie_break: r.tie_break,
+                runoffs: r.runoffs.iter()
+                    .map(|runoff| crate::utils::RunoffPhase {
+                        tie_break: runoff.tie_break,
+                        candidates: runoff.candidates.clone(),
+                        votes: runoff.votes.iter().map(to_saved_vote).collect(),
+                        vote_tallies: runoff.vote_tallies.iter().map(to_saved_tally).collect(),
+                        winners: runoff.winners.clone(),
+                    })
+                    .collect(),
+                prompt_templates: r.prompt_templates.clone(),
+            })
+            .collect(),
+        selected_models: settings.selected_models,
+        prompt_templates: (&settings.prompt_templates).into(),
+        context: settings.context,
+        context_policy: settings.context_policy,
+        voting_method: settings.voting_method,
+        tie_break: settings.tie_break,
+        tie_break_chair: settings.tie_break_chair,
     }
+}
+
+async fn save_competitive_session(
+    session_id: String,
+    history: CompetitiveHistory,
+    sessions: Signal<Vec<ChatSession>>,
+    on_save_error: EventHandler<String>,
+) {
+    let history_enum = ChatHistory::Competitive(history);
+    if !ChatHistory::has_content(&history_enum) {
+        return;
+    }
+    let summary = ChatHistory::generate_chat_summary(&history_enum);
+    let session = ChatSession {
+        id: session_id.clone(),
+        title: summary,
+        mode: ChatMode::Competitive,
+        timestamp: ChatHistory::format_timestamp(),
+        stats: SessionStats::default(),
+        labels: SessionLabels::default(),
+    };
+    let session_data = SessionData {
+        session,
+        history: history_enum,
+        created_at: ChatHistory::session_timestamp_from_id(&session_id)
+            .unwrap_or_else(ChatHistory::format_timestamp),
+        updated_at: ChatHistory::format_timestamp(),
+        forked_from: None,
+        renamed: false,
+        title_generated: false,
+        checkpoint: None,
+    };
+    match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
+        Err(e) => { let _ = on_save_error.call(format!("Failed to save session: {}", e)); }
+        Ok(Err(e)) => { let _ = on_save_error.call(format!("Failed to save session: {}", e)); }
+        Ok(Ok(saved_session)) => upsert_session(sessions, saved_session),
+    }
+}
 
-    // Count votes
-    for vote in votes {
-        if let Some(voted_for) = &vote.voted_for {
-            if let Some((count, voters)) = tally_map.get_mut(voted_for) {
-                *count += 1;
-                voters.push(vote.voter_id.clone());
+/// Ask one model to choose among tied proposals, streaming its reply as it arrives;
+/// None when the run was cancelled
+async fn tie_break_vote(
+    client: &OpenRouterClient,
+    model_id: &str,
+    messages: Vec<ChatMessage>,
+    candidates: &[String],
+    streaming: &mut Signal<HashMap<String, String>>,
+    cancel_flag: &Arc<AtomicBool>,
+) -> Option<ModelVote> {
+    let mut vote = ModelVote {
+        voter_id: model_id.to_string(),
+        voted_for: None,
+        ranking: Vec::new(),
+        raw_response: String::new(),
+        error_message: None,
+        timing: None,
+    };
+    let mut stream = match client.stream_chat_completion(model_id.to_string(), messages).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("Error breaking tie with {}: {}", model_id, e);
+            vote.error_message = Some(e);
+            return Some(vote);
+        }
+    };
+    let mut last_update = std::time::Instant::now();
+    while let Some(event) = next_stream_event_with_cancel(&mut stream, cancel_flag).await {
+        match event {
+            StreamEvent::Content(content) => {
+                vote.raw_response.push_str(&content);
+                if last_update.elapsed().as_millis() >= 50 {
+                    let _ = try_signal_update(streaming, |responses| {
+                        responses.insert(model_id.to_string(), vote.raw_response.clone());
+                    });
+                    last_update = std::time::Instant::now();
+                }
+            }
+            StreamEvent::Done(timing) => vote.timing = timing,
+            StreamEvent::Error(error) => {
+                if error == "Cancelled" {
+                    break;
+                }
+                vote.error_message = Some(error);
             }
         }
     }
-
-    // Convert to Vec<VoteTally>
-    let mut tallies: Vec<VoteTally> = tally_map
-        .into_iter()
-        .map(|(model_id, (vote_count, voters))| VoteTally {
-            model_id,
-            vote_count,
-            voters,
-        })
-        .collect();
-
-    // Sort by vote count descending
-    tallies.sort_by(|a, b| b.vote_count.cmp(&a.vote_count));
-
-    // Find winners (all with max votes)
-    let max_votes = tallies.first().map(|t| t.vote_count).unwrap_or(0);
-    let winners: Vec<String> = tallies
-        .iter()
-        .filter(|t| t.vote_count == max_votes && max_votes > 0)
-        .map(|t| t.model_id.clone())
-        .collect();
-
-    (tallies, winners)
+    let _ = try_signal_update(streaming, |responses| {
+        responses.remove(model_id);
+    });
+    if cancel_flag.load(Ordering::SeqCst) {
+        return None;
+    }
+    vote.voted_for = VotingMethod::Plurality.parse_ballot(&vote.raw_response, model_id, candidates).into_iter().next();
+    Some(vote)
 }
 
 // ============================================================================
@@ -250,6 +499,15 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
     
     // Track the currently loaded session to avoid reloading on every render
     let mut loaded_session_id = use_signal(|| None::<String>);
+    // Round left unfinished by a crash or restart, offered for resuming
+    let mut interrupted = use_signal(|| None::<RunCheckpoint>);
+    // Exchanges carried over when this session was forked from another one
+    let mut fork_context = use_signal(|| Vec::<(String, String)>::new());
+    let mut context_policy = use_signal(ContextPolicy::default);
+    let mut voting_method = use_signal(VotingMethod::default);
+    let mut tie_break = use_signal(TieBreak::default);
+    // None means the first selected model chairs
+    let mut tie_break_chair = use_signal(|| None::<String>);
     
     // Load history if session_id changes (not on every render)
     let session_id_for_load = session_id.clone();
@@ -278,41 +536,15 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                         loaded_session_id.set(current_sid.clone());
                         let selected_models_clone = history.selected_models.clone();
                         selected_models.set(selected_models_clone.clone());
-                        prompt_templates.set(PromptTemplates {
-                            proposal: history.prompt_templates.proposal.clone(),
-                            voting: history.prompt_templates.voting.clone(),
-                        });
-                        let converted_rounds: Vec<CompetitiveRound> = history
-                            .rounds
-                            .iter()
-                            .map(|r| CompetitiveRound {
-                                user_question: r.user_question.clone(),
-                                phase1_proposals: r.phase1_proposals.iter().map(|p| ModelProposal {
-                                    model_id: p.model_id.clone(),
-                                    content: p.content.clone(),
-                                    error_message: p.error_message.clone(),
-                                }).collect(),
-                                phase2_votes: r.phase2_votes.iter().map(|v| ModelVote {
-                                    voter_id: v.voter_id.clone(),
-                                    voted_for: v.voted_for.clone(),
-                                    raw_response: v.raw_response.clone(),
-                                    error_message: v.error_message.clone(),
-                                }).collect(),
-                                vote_tallies: r.vote_tallies.iter().map(|t| VoteTally {
-                                    model_id: t.model_id.clone(),
-                                    vote_count: t.vote_count,
-                                    voters: t.voters.clone(),
-                                }).collect(),
-                                winners: r.winners.clone(),
-                                current_phase: match r.current_phase.as_str() {
-                                    "proposal" => CompetitivePhase::Proposal,
-                                    "voting" => CompetitivePhase::Voting,
-                                    "tallying" => CompetitivePhase::Tallying,
-                                    _ => CompetitivePhase::Complete,
-                                },
-                            })
-                            .collect();
+                        prompt_templates.set(history.prompt_templates.clone().or_defaults().into());
+                        let converted_rounds: Vec<CompetitiveRound> = history.rounds.iter().map(from_saved_round).collect();
                         conversation_history.set(converted_rounds);
+                        fork_context.set(history.context.clone());
+                        context_policy.set(history.context_policy);
+                        voting_method.set(history.voting_method);
+                        tie_break.set(history.tie_break);
+                        tie_break_chair.set(history.tie_break_chair.clone());
+                        interrupted.set(session_data.checkpoint.clone());
                         if !selected_models_clone.is_empty() {
                             selection_step.set(1);
                         }
@@ -323,6 +555,12 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                     loaded_session_id.set(current_sid);
                     selected_models.set(Vec::new());
                     conversation_history.set(Vec::new());
+                    fork_context.set(Vec::new());
+                    context_policy.set(ContextPolicy::default());
+                    voting_method.set(VotingMethod::default());
+                    tie_break.set(TieBreak::default());
+                    tie_break_chair.set(None);
+                    interrupted.set(None);
                     prompt_templates.set(PromptTemplates::default());
                     selection_step.set(0);
                 }
@@ -332,6 +570,12 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
         loaded_session_id.set(None);
         selected_models.set(Vec::new());
         conversation_history.set(Vec::new());
+        fork_context.set(Vec::new());
+        context_policy.set(ContextPolicy::default());
+        voting_method.set(VotingMethod::default());
+        tie_break.set(TieBreak::default());
+        tie_break_chair.set(None);
+        interrupted.set(None);
         prompt_templates.set(PromptTemplates::default());
         selection_step.set(0);
     }
@@ -350,8 +594,16 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
     };
     
     let save_prompt = move |new_prompt: String| {
-        let mut templates = prompt_templates.write();
-        templates.set(*editing_prompt_type.read(), new_prompt);
+        let edited: crate::utils::PromptTemplates = {
+            let mut templates = prompt_templates.write();
+            templates.set(*editing_prompt_type.read(), new_prompt);
+            (&*templates).into()
+        };
+        save_session_prompts(loaded_session_id(), on_save_error, move |history| {
+            if let ChatHistory::Competitive(h) = history {
+                h.prompt_templates = edited;
+            }
+        });
     };
 
     let active_run_for_session = find_run_for_session(active_runs, &session_id, ChatMode::Competitive);
@@ -402,8 +654,10 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
         }
     });
 
-    // Handle message sending
-    let mut send_message = move |user_msg: String| {
+    let session_id_for_pick = session_id.clone();
+
+    // Run a round, or pick up an interrupted one from its checkpoint
+    let run_round = use_callback(move |(user_msg, resume): (String, Option<RunCheckpoint>)| {
         if user_msg.trim().is_empty() || *is_processing.read() || run_is_active {
             return;
         }
@@ -423,19 +677,35 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
             let mut current_streaming_clone = current_streaming_responses.clone();
             let mut current_phase_clone = current_phase.clone();
             let templates = prompt_templates();
+            let carried_context = fork_context.read().clone();
+            let context_policy_for_task = *context_policy.read();
+            let voting_method_for_task = *voting_method.read();
+            let tie_break_for_task = *tie_break.read();
+            let tie_break_chair_for_task = tie_break_chair.read().clone();
+            // Fork context first, then whatever the policy keeps of this session's earlier rounds
+            let prior_rounds: Vec<PriorRound> = conversation_history.read().iter().map(prior_round).collect();
+            let round_context: Vec<(String, String)> = carried_context
+                .iter()
+                .cloned()
+                .chain(context_policy_for_task.exchanges(&prior_rounds))
+                .collect();
             let session_id_for_save = session_id.clone();
-            let mut sessions_for_task = sessions.clone();
+            let sessions_for_task = sessions.clone();
             let selected_models_for_save = selected_models.read().clone();
             let prompt_templates_for_save = prompt_templates.read().clone();
             let run_id = create_run_id(ChatMode::Competitive, &session_id);
             current_run_id.set(Some(run_id.clone()));
             let cancel_flag = Arc::new(AtomicBool::new(false));
+            interrupted.set(None);
+            let checkpoint = resume.unwrap_or_else(|| RunCheckpoint::new(&user_msg, CompetitivePhase::Proposal.key()));
 
             let run_id_for_task = run_id.clone();
             let mut active_runs_for_task = active_runs.clone();
             let cancel_flag_for_task = cancel_flag.clone();
             let on_save_error_for_task = on_save_error.clone();
             let task = spawn_forever(async move {
+            let checkpointer = Checkpointer::new(session_id_for_save.clone(), checkpoint);
+
             // Create new round
             let mut round = CompetitiveRound {
                 user_question: user_msg.clone(),
@@ -444,20 +714,38 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                 vote_tallies: Vec::new(),
                 winners: Vec::new(),
                 current_phase: CompetitivePhase::Proposal,
+                voting_method: voting_method_for_task,
+                tie_break: tie_break_for_task,
+                runoffs: Vec::new(),
+                prompt_templates: Some((&templates).into()),
             };
 
             // PHASE 1: Proposals (Parallel)
             try_signal_set(&mut current_phase_clone, CompetitivePhase::Proposal);
+            checkpointer.enter_phase(CompetitivePhase::Proposal.key()).await;
 
             let proposal_prompt = templates.proposal.replace("{user_question}", &user_msg);
-            let messages = vec![
-                ChatMessage::system("You are in a competitive evaluation workflow. Follow the phase instructions exactly.".to_string()),
-                ChatMessage::user(proposal_prompt),
-            ];
+            let messages = ChatMessage::with_context("You are in a competitive evaluation workflow. Follow the phase instructions exactly.".to_string(), &round_context, proposal_prompt);
 
+            // Only models without a finished proposal are asked (all of them on a fresh round)
             let mut phase1_results: HashMap<String, ModelProposal> = HashMap::new();
+            for model_id in &models {
+                if let Some(done) = checkpointer.succeeded(CompetitivePhase::Proposal.key(), model_id) {
+                    phase1_results.insert(model_id.clone(), ModelProposal {
+                        model_id: model_id.clone(),
+                        content: done.content.clone(),
+                        error_message: None,
+                        timing: None,
+                    });
+                }
+            }
+            let pending_models: Vec<String> = models
+                .iter()
+                .filter(|id| !phase1_results.contains_key(*id))
+                .cloned()
+                .collect();
 
-            match client.stream_chat_completion_multi(models.clone(), messages).await {
+            match client.stream_chat_completion_multi(pending_models, messages).await {
                 Ok(mut rx) => {
                     // Buffer content locally to throttle updates
                     let mut content_buffer: HashMap<String, String> = HashMap::new();
@@ -477,6 +765,9 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                     .entry(model_id.clone())
                                     .and_modify(|s| s.push_str(&content))
                                     .or_insert(content);
+                                if let Some(accumulated) = content_buffer.get(&model_id) {
+                                    checkpointer.partial(&model_id, accumulated).await;
+                                }
 
                                     // Throttle updates: only write to signal every 16ms
                                     if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
@@ -490,7 +781,7 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                         last_update = std::time::Instant::now();
                                     }
                             }
-                                StreamEvent::Done => {
+                                StreamEvent::Done(timing) => {
                                     // Flush any remaining buffered content before marking done
                                     if let Some(accumulated) = content_buffer.remove(&model_id) {
                                         let _ = try_signal_update(&mut current_streaming_clone, |responses| {
@@ -502,10 +793,12 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                     responses.get(&model_id).cloned().unwrap_or_default()
                                 })
                                 .unwrap_or_default();
+                                checkpointer.finished(&model_id, &final_content, None).await;
                                 phase1_results.insert(model_id.clone(), ModelProposal {
                                     model_id: model_id.clone(),
                                     content: final_content,
                                     error_message: None,
+                                    timing,
                                 });
                                 let _ = try_signal_update(&mut current_streaming_clone, |responses| {
                                     responses.remove(&model_id);
@@ -515,10 +808,12 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                 if error == "Cancelled" {
                                     break;
                                 }
+                                checkpointer.finished(&model_id, "", Some(error.clone())).await;
                                 phase1_results.insert(model_id.clone(), ModelProposal {
                                     model_id: model_id.clone(),
                                     content: String::new(),
                                     error_message: Some(error),
+                                    timing: None,
                                 });
                                 let _ = try_signal_update(&mut current_streaming_clone, |responses| {
                                     responses.remove(&model_id);
@@ -565,6 +860,10 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
             // PHASE 2: Voting (Sequential)
             try_signal_set(&mut current_phase_clone, CompetitivePhase::Voting);
             round.current_phase = CompetitivePhase::Voting;
+            checkpointer.enter_phase(CompetitivePhase::Voting.key()).await;
+            let valid_model_ids: Vec<String> = successful_proposals.iter()
+                .map(|p| p.model_id.clone())
+                .collect();
 
             // Build all_proposals text
             let all_proposals_text: String = successful_proposals
@@ -580,15 +879,30 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                     None => continue,
                 };
 
-                let voting_prompt = templates.voting
+                if let Some(done) = checkpointer.succeeded(CompetitivePhase::Voting.key(), model_id) {
+                    let ranking = voting_method_for_task.parse_ballot(&done.content, model_id, &valid_model_ids);
+                    round.phase2_votes.push(ModelVote {
+                        voter_id: model_id.clone(),
+                        voted_for: ranking.first().cloned(),
+                        ranking: if voting_method_for_task == VotingMethod::Plurality { Vec::new() } else { ranking },
+                        raw_response: done.content.clone(),
+                        error_message: None,
+                        timing: None,
+                    });
+                    continue;
+                }
+
+                let mut voting_prompt = templates.voting
                     .replace("{user_question}", &user_msg)
                     .replace("{all_proposals}", &all_proposals_text)
                     .replace("{your_proposal}", &my_proposal.content);
+                let others: Vec<String> = valid_model_ids.iter().filter(|id| *id != model_id).cloned().collect();
+                if let Some(instructions) = voting_method_for_task.ballot_instructions(&others) {
+                    voting_prompt.push_str("\n\n");
+                    voting_prompt.push_str(&instructions);
+                }
 
-                let messages = vec![
-                    ChatMessage::system("You are in a competitive evaluation workflow. Follow the phase instructions exactly.".to_string()),
-                    ChatMessage::user(voting_prompt),
-                ];
+                let messages = ChatMessage::with_context("You are in a competitive evaluation workflow. Follow the phase instructions exactly.".to_string(), &round_context, voting_prompt);
                 let _ = try_signal_update(&mut current_streaming_clone, |responses| responses.clear());
 
                 match client.stream_chat_completion(model_id.clone(), messages).await {
@@ -606,6 +920,7 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                             match event {
                                 StreamEvent::Content(content) => {
                                     vote_response.push_str(&content);
+                                    checkpointer.partial(model_id, &vote_response).await;
 
                                     // Throttle updates: only write to signal every 16ms
                                     if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
@@ -616,30 +931,30 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                         last_update = std::time::Instant::now();
                                     }
                                 }
-                                StreamEvent::Done => {
+                                StreamEvent::Done(timing) => {
                                     // Flush final content and remove from streaming
                                     let _ = try_signal_update(&mut current_streaming_clone, |responses| {
                                         responses.insert(model_id.clone(), vote_response.clone());
                                         responses.remove(model_id.as_str());
                                     });
 
-                                    let valid_model_ids: Vec<String> = successful_proposals.iter()
-                                        .map(|p| p.model_id.clone())
-                                        .collect();
-
-                                    let voted_for = parse_vote(&vote_response, model_id, &valid_model_ids);
+                                    checkpointer.finished(model_id, &vote_response, None).await;
+                                    let ranking = voting_method_for_task.parse_ballot(&vote_response, model_id, &valid_model_ids);
 
                                     round.phase2_votes.push(ModelVote {
                                         voter_id: model_id.clone(),
-                                        voted_for,
+                                        voted_for: ranking.first().cloned(),
+                                        ranking: if voting_method_for_task == VotingMethod::Plurality { Vec::new() } else { ranking },
                                         raw_response: vote_response.clone(),
                                         error_message: None,
+                                        timing,
                                     });
                                 }
                                 StreamEvent::Error(error) => {
                                     if error == "Cancelled" {
                                         break;
                                     }
+                                    checkpointer.finished(model_id, "", Some(error.clone())).await;
                                     let _ = try_signal_update(&mut current_streaming_clone, |responses| {
                                         responses.remove(model_id);
                                     });
@@ -647,8 +962,10 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                     round.phase2_votes.push(ModelVote {
                                         voter_id: model_id.clone(),
                                         voted_for: None,
+                                        ranking: Vec::new(),
                                         raw_response: String::new(),
                                         error_message: Some(error),
+                                        timing: None,
                                     });
                                 }
                             }
@@ -659,8 +976,10 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                         round.phase2_votes.push(ModelVote {
                             voter_id: model_id.clone(),
                             voted_for: None,
+                            ranking: Vec::new(),
                             raw_response: String::new(),
                             error_message: Some(e.to_string()),
+                            timing: None,
                         });
                     }
                 }
@@ -682,14 +1001,76 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
             try_signal_set(&mut current_phase_clone, CompetitivePhase::Tallying);
             round.current_phase = CompetitivePhase::Tallying;
 
-            let valid_model_ids: Vec<String> = successful_proposals.iter()
-                .map(|p| p.model_id.clone())
-                .collect();
-
-            let (tallies, winners) = compute_tallies(&round.phase2_votes, &valid_model_ids);
-            round.vote_tallies = tallies;
+            let (tallies, winners) = voting_method_for_task.tally(&ballots(&round.phase2_votes), &valid_model_ids);
+            round.vote_tallies = tallies.into_iter().map(VoteTally::from).collect();
             round.winners = winners;
 
+            // Settle a tied vote; a user decision is made afterwards from the finished round
+            if round.winners.len() > 1 && matches!(tie_break_for_task, TieBreak::Runoff | TieBreak::Chair) {
+                try_signal_set(&mut current_phase_clone, CompetitivePhase::TieBreak);
+                round.current_phase = CompetitivePhase::TieBreak;
+                let voters: Vec<String> = match tie_break_for_task {
+                    TieBreak::Chair => tie_break_chair_for_task
+                        .clone()
+                        .filter(|chair| models.contains(chair))
+                        .or_else(|| models.first().cloned())
+                        .into_iter()
+                        .collect(),
+                    _ => valid_model_ids.clone(),
+                };
+                let phases = if tie_break_for_task == TieBreak::Runoff { TieBreak::MAX_RUNOFFS } else { 1 };
+
+                for _ in 0..phases {
+                    let candidates = round.winners.clone();
+                    let tied: Vec<(String, String)> = round.phase1_proposals
+                        .iter()
+                        .filter(|p| candidates.contains(&p.model_id))
+                        .map(|p| (p.model_id.clone(), p.content.clone()))
+                        .collect();
+                    let mut votes = Vec::new();
+                    for voter in &voters {
+                        let messages = ChatMessage::with_context(
+                            "You are in a competitive evaluation workflow. Follow the phase instructions exactly.".to_string(),
+                            &round_context,
+                            tie_break_for_task.prompt(&user_msg, &tied, voter),
+                        );
+                        match tie_break_vote(&client, voter, messages, &candidates, &mut current_streaming_clone, &cancel_flag_for_task).await {
+                            Some(vote) => votes.push(vote),
+                            None => break,
+                        }
+                    }
+                    if cancel_flag_for_task.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    let (tallies, winners) = VotingMethod::Plurality.tally(&ballots(&votes), &candidates);
+                    if !winners.is_empty() {
+                        round.winners = winners.clone();
+                    }
+                    round.runoffs.push(RunoffPhase {
+                        tie_break: tie_break_for_task,
+                        candidates,
+                        votes,
+                        // The chair's single pick needs no tally
+                        vote_tallies: if tie_break_for_task == TieBreak::Chair {
+                            Vec::new()
+                        } else {
+                            tallies.into_iter().map(VoteTally::from).collect()
+                        },
+                        winners,
+                    });
+                    if round.winners.len() <= 1 {
+                        break;
+                    }
+                }
+
+                if cancel_flag_for_task.load(Ordering::SeqCst) {
+                    try_signal_set(&mut is_processing_clone, false);
+                    set_run_status(active_runs_for_task, &run_id_for_task, RunStatus::Cancelled);
+                    return;
+                }
+            }
+
             // Complete
             round.current_phase = CompetitivePhase::Complete;
             try_signal_set(&mut current_phase_clone, CompetitivePhase::Complete);
@@ -698,71 +1079,23 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
             
             // Auto-save only when there is content (spawn_blocking to avoid blocking async runtime)
             if let Some(sid) = session_id_for_save {
-                let history = CompetitiveHistory {
-                    rounds: try_signal_read(&conversation_history_clone, |history| history.clone())
-                        .unwrap_or_default()
-                        .iter()
-                        .map(|r| crate::utils::CompetitiveRound {
-                            user_question: r.user_question.clone(),
-                            phase1_proposals: r.phase1_proposals.iter()
-                                .map(|p| crate::utils::ModelProposal {
-                                    model_id: p.model_id.clone(),
-                                    content: p.content.clone(),
-                                    error_message: p.error_message.clone(),
-                                })
-                                .collect(),
-                            phase2_votes: r.phase2_votes.iter()
-                                .map(|v| crate::utils::ModelVote {
-                                    voter_id: v.voter_id.clone(),
-                                    voted_for: v.voted_for.clone(),
-                                    raw_response: v.raw_response.clone(),
-                                    error_message: v.error_message.clone(),
-                                })
-                                .collect(),
-                            vote_tallies: r.vote_tallies.iter()
-                                .map(|t| crate::utils::VoteTally {
-                                    model_id: t.model_id.clone(),
-                                    vote_count: t.vote_count,
-                                    voters: t.voters.clone(),
-                                })
-                                .collect(),
-                            winners: r.winners.clone(),
-                            current_phase: match r.current_phase {
-                                CompetitivePhase::Proposal => "proposal",
-                                CompetitivePhase::Voting => "voting",
-                                CompetitivePhase::Tallying => "tallying",
-                                CompetitivePhase::Complete => "complete",
-                            }.to_string(),
-                        })
-                        .collect(),
-                    selected_models: selected_models_for_save.clone(),
-                    prompt_templates: crate::utils::PromptTemplates {
-                        proposal: prompt_templates_for_save.proposal.clone(),
-                        voting: prompt_templates_for_save.voting.clone(),
+                let history = to_saved_history(
+                    &try_signal_read(&conversation_history_clone, |history| history.clone()).unwrap_or_default(),
+                    CompetitiveSettings {
+                        selected_models: selected_models_for_save.clone(),
+                        // Current templates, which may have been edited while the round ran
+                        prompt_templates: try_signal_read(&prompt_templates, |templates| templates.clone())
+                            .unwrap_or_else(|| prompt_templates_for_save.clone()),
+                        context: carried_context.clone(),
+                        context_policy: context_policy_for_task,
+                        voting_method: voting_method_for_task,
+                        tie_break: tie_break_for_task,
+                        tie_break_chair: tie_break_chair_for_task.clone(),
                     },
-                };
-                let history_enum = ChatHistory::Competitive(history.clone());
-                if ChatHistory::has_content(&history_enum) {
-                    let summary = ChatHistory::generate_chat_summary(&history_enum);
-                    let session = ChatSession {
-                        id: sid.clone(),
-                        title: summary,
-                        mode: ChatMode::Competitive,
-                        timestamp: ChatHistory::format_timestamp(),
-                    };
-                    let session_data = SessionData {
-                        session: session.clone(),
-                        history: history_enum,
-                        created_at: ChatHistory::session_timestamp_from_id(&sid)
-                            .unwrap_or_else(ChatHistory::format_timestamp),
-                        updated_at: ChatHistory::format_timestamp(),
-                    };
-                    match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
-                        Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                        Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                        Ok(Ok(_)) => upsert_session(sessions_for_task, session),
-                    }
-                }
+                );
+                let title_history = ChatHistory::Competitive(history.clone());
+                save_competitive_session(sid.clone(), history, sessions_for_task, on_save_error_for_task).await;
+                generate_title_in_background(client.clone(), sessions_for_task, sid, title_history);
             }
             if cancel_flag_for_task.load(Ordering::SeqCst) {
                 set_run_status(active_runs_for_task, &run_id_for_task, RunStatus::Cancelled);
@@ -781,8 +1114,51 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                 cancel_flag,
             );
         }
+    });
+    let send_message = move |text: String| run_round.call((text, None));
+
+    // Settle a tied round under `TieBreak::User` with the user's pick
+    let pick_winner = use_callback(move |(round_idx, model_id): (usize, String)| {
+        let mut history = conversation_history.write();
+        let Some(round) = history.get_mut(round_idx) else {
+            return;
+        };
+        if !round.winners.contains(&model_id) {
+            return;
+        }
+        round.runoffs.push(RunoffPhase {
+            tie_break: TieBreak::User,
+            candidates: round.winners.clone(),
+            votes: Vec::new(),
+            vote_tallies: Vec::new(),
+            winners: vec![model_id.clone()],
+        });
+        round.winners = vec![model_id];
+        if let Some(sid) = session_id_for_pick.clone() {
+            let saved = to_saved_history(
+                &history,
+                CompetitiveSettings {
+                    selected_models: selected_models.read().clone(),
+                    prompt_templates: prompt_templates.read().clone(),
+                    context: fork_context.read().clone(),
+                    context_policy: *context_policy.read(),
+                    voting_method: *voting_method.read(),
+                    tie_break: *tie_break.read(),
+                    tie_break_chair: tie_break_chair.read().clone(),
+                },
+            );
+            spawn(save_competitive_session(sid, saved, sessions, on_save_error));
+        }
+    });
+
+    let resume_round = move |_| {
+        if let Some(checkpoint) = interrupted() {
+            run_round.call((checkpoint.user_message.clone(), Some(checkpoint)));
+        }
     };
 
+    let discard_checkpoint = move |_| discard_checkpoint(interrupted, loaded_session_id());
+
     // Get filtered models for display
     let models_result = available_models.read();
     let loading = models_result.is_none();
@@ -986,13 +1362,79 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                             class: "text-sm font-semibold text-[var(--color-base-content)]",
                             "Prompt Templates (Click to customize)"
                         }
-                        button {
-                            onclick: move |_| {
-                                selection_step.set(0);
-                                conversation_history.write().clear();
-                            },
-                            class: "text-xs text-[var(--color-primary)] hover:underline",
-                            "Change Models"
+                        div {
+                            class: "flex items-center gap-3",
+                            label {
+                                class: "flex items-center gap-1.5 text-xs text-[var(--color-base-content)]/80",
+                                title: "How models cast their votes and how the votes are counted",
+                                "Voting:"
+                                select {
+                                    disabled: *is_processing.read(),
+                                    onchange: move |evt| voting_method.set(VotingMethod::from_key(&evt.value())),
+                                    class: "px-1 py-0.5 rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] text-xs",
+                                    for method in VotingMethod::ALL {
+                                        option {
+                                            key: "{method.key()}",
+                                            value: method.key(),
+                                            selected: *voting_method.read() == method,
+                                            {method.label()}
+                                        }
+                                    }
+                                }
+                            }
+                            label {
+                                class: "flex items-center gap-1.5 text-xs text-[var(--color-base-content)]/80",
+                                title: "What happens when the vote ends in a tie",
+                                "Ties:"
+                                select {
+                                    disabled: *is_processing.read(),
+                                    onchange: move |evt| tie_break.set(TieBreak::from_key(&evt.value())),
+                                    class: "px-1 py-0.5 rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] text-xs",
+                                    for policy in TieBreak::ALL {
+                                        option {
+                                            key: "{policy.key()}",
+                                            value: policy.key(),
+                                            selected: *tie_break.read() == policy,
+                                            {policy.label()}
+                                        }
+                                    }
+                                }
+                            }
+                            if *tie_break.read() == TieBreak::Chair {
+                                label {
+                                    class: "flex items-center gap-1.5 text-xs text-[var(--color-base-content)]/80",
+                                    title: "Model that picks the winner when the vote ties; it can't pick its own proposal",
+                                    "Chair:"
+                                    select {
+                                        disabled: *is_processing.read(),
+                                        onchange: move |evt| tie_break_chair.set(Some(evt.value())),
+                                        class: "px-1 py-0.5 rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] text-xs",
+                                        for (idx, model_id) in selected_models.read().iter().enumerate() {
+                                            option {
+                                                key: "{model_id}",
+                                                value: "{model_id}",
+                                                selected: match tie_break_chair.read().as_ref() {
+                                                    Some(chair) => chair == model_id,
+                                                    None => idx == 0,
+                                                },
+                                                "{model_id}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            ContextPolicySelect {
+                                policy: context_policy,
+                                disabled: *is_processing.read(),
+                            }
+                            button {
+                                onclick: move |_| {
+                                    selection_step.set(0);
+                                    conversation_history.write().clear();
+                                },
+                                class: "text-xs text-[var(--color-primary)] hover:underline",
+                                "Change Models"
+                            }
                         }
                     }
                     
@@ -1004,6 +1446,7 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                             title: "Proposal".to_string(),
                             phase_number: 1,
                             prompt: prompt_templates.read().proposal.clone(),
+                            customized: prompt_templates.read().proposal != PromptTemplates::default().proposal,
                             on_edit: move |_| open_prompt_editor(CompetitivePromptType::Proposal),
                         }
                         
@@ -1012,6 +1455,7 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                             title: "Voting".to_string(),
                             phase_number: 2,
                             prompt: prompt_templates.read().voting.clone(),
+                            customized: prompt_templates.read().voting != PromptTemplates::default().voting,
                             on_edit: move |_| open_prompt_editor(CompetitivePromptType::Voting),
                         }
                     }
@@ -1021,6 +1465,11 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                 div {
                     class: "flex-1 min-h-0 overflow-y-auto p-4",
 
+                    ForkedContext {
+                        theme,
+                        context: fork_context(),
+                    }
+
                     if conversation_history.read().is_empty() && !*is_processing.read() {
                         // Empty state
                         div {
@@ -1054,13 +1503,15 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                             class: "space-y-6 w-full",
 
                         // Render each round
-                        for round in conversation_history.read().iter() {
+                        for (round_idx, round) in conversation_history.read().iter().enumerate() {
                             div {
+                                key: "{round_idx}",
+                                id: "round-{round_idx}",
                                 class: "space-y-6",
 
                                 // User question
                                 div {
-                                    class: "flex justify-end",
+                                    class: "flex flex-col items-end gap-1",
                                     div {
                                         class: "max-w-2xl p-3 sm:p-4 md:p-5 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] text-sm sm:text-base",
                                         FormattedText {
@@ -1068,6 +1519,13 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                             content: round.user_question.clone(),
                                         }
                                     }
+                                    CustomPromptsBadge {
+                                        prompts: round
+                                            .prompt_templates
+                                            .as_ref()
+                                            .map(crate::utils::PromptTemplates::customized)
+                                            .unwrap_or_default(),
+                                    }
                                 }
 
                                 // Phase 1: Proposals
@@ -1109,6 +1567,8 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                                 VoteDisplay {
                                                     voter_id: vote.voter_id.clone(),
                                                     voted_for: vote.voted_for.clone(),
+                                                    ranking: vote.ranking.clone(),
+                                                    method: round.voting_method,
                                                     raw_response: Some(vote.raw_response.clone()),
                                                 }
                                             }
@@ -1126,10 +1586,43 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                         }
 
                                         VoteTally {
-                                            tallies: round.vote_tallies.iter()
-                                                .map(|t| (t.model_id.clone(), t.vote_count, t.voters.clone()))
-                                                .collect::<Vec<_>>(),
-                                            winners: round.winners.clone(),
+                                            tallies: round.vote_tallies.iter().map(to_saved_tally).collect::<Vec<_>>(),
+                                            // After a tie-break the vote's own winners are the tied proposals
+                                            winners: round.runoffs.first().map(|r| r.candidates.clone()).unwrap_or_else(|| round.winners.clone()),
+                                            method: round.voting_method,
+                                        }
+                                    }
+                                }
+
+                                // Tie-break phases
+                                for (runoff_idx, runoff) in round.runoffs.iter().enumerate() {
+                                    RunoffPhaseView {
+                                        key: "{runoff_idx}",
+                                        number: runoff_idx + 1,
+                                        runoff: runoff.clone(),
+                                    }
+                                }
+
+                                if round.tie_break == TieBreak::User && round.winners.len() > 1 && !*is_processing.read() {
+                                    div {
+                                        class: "p-3 rounded-lg border border-orange-500/40 bg-orange-500/10 space-y-2",
+                                        div {
+                                            class: "text-sm text-[var(--color-base-content)]",
+                                            "The vote is tied. Pick the winning proposal:"
+                                        }
+                                        div {
+                                            class: "flex flex-wrap gap-2",
+                                            for model_id in round.winners.clone() {
+                                                button {
+                                                    key: "{model_id}",
+                                                    onclick: {
+                                                        let model_id = model_id.clone();
+                                                        move |_| pick_winner.call((round_idx, model_id.clone()))
+                                                    },
+                                                    class: "px-3 py-1.5 rounded-lg bg-orange-500 text-white text-sm font-medium hover:bg-orange-500/90",
+                                                    "{model_id}"
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -1199,6 +1692,29 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                                                 }
                                             }
                                         },
+                                        CompetitivePhase::TieBreak => rsx! {
+                                            div {
+                                                PhaseIndicator {
+                                                    phase_name: CompetitivePhase::TieBreak.name().to_string(),
+                                                    phase_number: CompetitivePhase::TieBreak.number(),
+                                                    badge_color: CompetitivePhase::TieBreak.badge_color().to_string(),
+                                                }
+
+                                                div {
+                                                    class: "space-y-2",
+                                                    for (model_id, content) in current_streaming_responses.read().clone() {
+                                                        ModelResponseCard {
+                                                            key: "{model_id}",
+                                                            theme,
+                                                            model_id,
+                                                            content,
+                                                            error_message: None,
+                                                            is_streaming: true,
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        },
                                         _ => rsx! {
                                             div {
                                                 class: "text-sm text-[var(--color-base-content)]/70",
@@ -1210,6 +1726,19 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
                             }
                         }
                     }
+
+                    if let Some(checkpoint) = interrupted() {
+                        if !*is_processing.read() {
+                            ResumeBanner {
+                                theme,
+                                phase_label: CompetitivePhase::from_key(&checkpoint.phase).name().to_string(),
+                                checkpoint,
+                                can_resume: client.is_some() && !run_is_active && selected_models.read().len() >= 2,
+                                on_resume: resume_round,
+                                on_discard: discard_checkpoint,
+                            }
+                        }
+                    }
                 }
 
                 if let Some((active_run_id, is_cancelling)) = cancel_bar_run.clone() {
@@ -1261,3 +1790,56 @@ pub fn Competitive(theme: Signal<Theme>, client: Option<Arc<OpenRouterClient>>,
         }
     }
 }
+
+/// One tie-break phase of a finished round: the runoff votes and tally, or who decided
+#[component]
+fn RunoffPhaseView(number: usize, runoff: RunoffPhase) -> Element {
+    let decision = match runoff.winners.as_slice() {
+        [] => "No decision; the tie stands".to_string(),
+        [winner] => format!("Winner: {}", winner),
+        many => format!("Still tied: {}", many.join(", ")),
+    };
+    let tied = runoff.candidates.join(", ");
+
+    rsx! {
+        div {
+            PhaseIndicator {
+                phase_name: runoff.tie_break.phase_title(number),
+                phase_number: CompetitivePhase::TieBreak.number(),
+                badge_color: CompetitivePhase::TieBreak.badge_color().to_string(),
+            }
+
+            div {
+                class: "space-y-2",
+
+                div {
+                    class: "text-xs text-[var(--color-base-content)]/70",
+                    "Tied: {tied}"
+                }
+
+                for vote in runoff.votes.iter() {
+                    VoteDisplay {
+                        voter_id: vote.voter_id.clone(),
+                        voted_for: vote.voted_for.clone(),
+                        ranking: Vec::new(),
+                        method: VotingMethod::Plurality,
+                        raw_response: Some(vote.raw_response.clone()),
+                    }
+                }
+
+                if !runoff.vote_tallies.is_empty() {
+                    VoteTally {
+                        tallies: runoff.vote_tallies.iter().map(to_saved_tally).collect::<Vec<_>>(),
+                        winners: runoff.winners.clone(),
+                        method: VotingMethod::Plurality,
+                    }
+                } else {
+                    div {
+                        class: "text-sm font-medium text-[var(--color-base-content)]",
+                        "{decision}"
+                    }
+                }
+            }
+        }
+    }
+}
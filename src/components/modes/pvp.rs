@@ -1,32 +1,275 @@
-use super::common::{ChatInput, FormattedText, Modal, ThinkingIndicator, AUTO_FIT_RESPONSE_GRID};
+use super::common::{
+    ChatInput, ContextPolicySelect, CustomPromptsBadge, ForkedContext, FormattedText, Modal, discard_checkpoint, save_session_prompts, ResumeBanner, ThinkingIndicator, AUTO_FIT_RESPONSE_GRID,
+};
 use crate::utils::{
-    create_run_id, find_run_for_session, next_stream_event_with_cancel, recv_multi_event_with_cancel,
+    create_run_id, find_run_for_session, generate_title_in_background, next_stream_event_with_cancel, recv_multi_event_with_cancel,
     register_active_run, remove_run, set_run_status, try_signal_read, try_signal_set,
     try_signal_update, upsert_session, ActiveRunRecord, ChatMessage, ChatHistory, ChatMode,
-    ChatSession, InputSettings, Model, OpenRouterClient, PvPHistory, RunStatus, SessionData,
-    StreamEvent, Theme,
+    ChatSession, Checkpointer, ContextPolicy, PriorRound, RunCheckpoint, SessionLabels, SessionStats, InputSettings, Model, OpenRouterClient, PvPHistory, ResponseTiming, RunStatus, SessionData,
+    agreement, DebateFormat, DebateTurn, JudgePanel, JudgeResponse, JudgingOptions, PanelAggregation, Presentation, Rubric, RubricCriterion, StreamEvent, SystemPrompts, Theme, Verdict,
 };
 use dioxus::core::spawn_forever;
 use dioxus::prelude::*;
+use futures::future::join_all;
 use std::collections::HashMap;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
 
-#[derive(Clone, Debug, PartialEq)]
-struct SystemPrompts {
-    bot: String,
-    moderator: String,
+/// Checkpoint phase names for a PvP round
+const BOTS_PHASE: &str = "bots";
+const MODERATOR_PHASE: &str = "moderator";
+/// Judging with bot 2 shown first
+const MODERATOR_REVERSED_PHASE: &str = "moderator_reversed";
+
+/// Most rebuttal turns offered in the format picker
+const MAX_REBUTTALS: u32 = 3;
+
+fn phase_label(phase: &str) -> &'static str {
+    if phase == MODERATOR_PHASE || phase == MODERATOR_REVERSED_PHASE {
+        "judging"
+    } else if phase == BOTS_PHASE {
+        "the bot responses"
+    } else {
+        "the debate turns"
+    }
+}
+
+/// Checkpoint phase for a judging pass, so a resumed round knows which order it used
+fn judging_phase(bot2_first: bool) -> &'static str {
+    if bot2_first {
+        MODERATOR_REVERSED_PHASE
+    } else {
+        MODERATOR_PHASE
+    }
+}
+
+fn coin_flip() -> bool {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    RandomState::new().hash_one(std::time::SystemTime::now()) & 1 == 1
+}
+
+/// Stream one model's answer in `phase`, or reuse its checkpointed result, passing the
+/// text so far to `live_text` as it arrives. `None` when cancelled.
+async fn stream_single(
+    client: &OpenRouterClient,
+    model_id: &str,
+    phase: &str,
+    messages: Vec<ChatMessage>,
+    mut live_text: impl FnMut(String),
+    checkpointer: &Checkpointer,
+    cancel_flag: &Arc<AtomicBool>,
+) -> Option<Result<(String, Option<ResponseTiming>), String>> {
+    checkpointer.enter_phase(phase).await;
+    if let Some(done) = checkpointer.succeeded(phase, model_id) {
+        return Some(Ok((done.content.clone(), None)));
+    }
+    live_text(String::new());
+
+    let mut stream = match client.stream_chat_completion(model_id.to_string(), messages).await {
+        Ok(stream) => stream,
+        Err(e) => return Some(Err(e)),
+    };
+    let mut content = String::new();
+
+    // Throttle updates: only write to signal every 50ms
+    let mut last_update = std::time::Instant::now();
+    const UPDATE_INTERVAL_MS: u64 = 50; // ~20fps
+
+    while let Some(event) = next_stream_event_with_cancel(&mut stream, cancel_flag).await {
+        if cancel_flag.load(Ordering::SeqCst) {
+            break;
+        }
+        match event {
+            StreamEvent::Content(chunk) => {
+                content.push_str(&chunk);
+
+                if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
+                    live_text(content.clone());
+                    checkpointer.partial(model_id, &content).await;
+
+                    last_update = std::time::Instant::now();
+                }
+            }
+            StreamEvent::Done(timing) => {
+                checkpointer.finished(model_id, &content, None).await;
+                return Some(Ok((content, timing)));
+            }
+            StreamEvent::Error(e) => {
+                if e == "Cancelled" {
+                    break;
+                }
+                checkpointer.finished(model_id, "", Some(e.clone())).await;
+                return Some(Err(e));
+            }
+        }
+    }
+    None
+}
+
+/// What each judge is shown for one PvP round
+struct JudgingRound<'a> {
+    rubric: &'a Rubric,
+    bot1_id: &'a str,
+    bot2_id: &'a str,
+    presentation: Presentation,
+    messages: Vec<ChatMessage>,
+    /// Set when the round is judged again with the order swapped
+    swapped_messages: Option<Vec<ChatMessage>>,
+}
+
+/// Show a judge's streaming text under its model ID
+fn show_judge(mut live_text: Signal<HashMap<String, String>>, judge_id: &str) -> impl FnMut(String) {
+    let judge_id = judge_id.to_string();
+    move |text| {
+        let _ = try_signal_update(&mut live_text, |texts| {
+            texts.insert(judge_id.clone(), text);
+        });
+    }
 }
 
-impl Default for SystemPrompts {
-    fn default() -> Self {
-        Self {
-            bot: "You are a competitive AI assistant in a debate. Provide the best possible answer to demonstrate your capabilities.".to_string(),
-            moderator: "You are an impartial judge evaluating responses from AI models. Be objective, fair, and thorough in your analysis.".to_string(),
+/// Run every judge over a round at once, then judge again with the order swapped when
+/// asked. The swapped pass starts once the first has finished, so each pass is one
+/// checkpoint phase. `None` when cancelled.
+async fn run_panel(
+    client: &OpenRouterClient,
+    judges: &[String],
+    round: &JudgingRound<'_>,
+    live_text: Signal<HashMap<String, String>>,
+    mut is_swap_check: Signal<bool>,
+    checkpointer: &Checkpointer,
+    cancel_flag: &Arc<AtomicBool>,
+) -> Option<Vec<JudgeResponse>> {
+    let phase = judging_phase(round.presentation.bot2_first);
+    let outcomes = join_all(judges.iter().map(|judge_id| {
+        stream_single(client, judge_id, phase, round.messages.clone(), show_judge(live_text, judge_id), checkpointer, cancel_flag)
+    }))
+    .await;
+    let mut responses = Vec::with_capacity(judges.len());
+    for (judge_id, outcome) in judges.iter().zip(outcomes) {
+        responses.push(match outcome? {
+            Ok((content, timing)) => {
+                // Keep the free-text reasoning when the moderator skipped the JSON block
+                let (content, verdict) = match round.rubric.parse_verdict(&content, round.bot1_id, round.bot2_id, round.presentation) {
+                    Some((reasoning, verdict)) => (reasoning, Some(verdict)),
+                    None => (content, None),
+                };
+                JudgeResponse {
+                    model_id: judge_id.clone(),
+                    content,
+                    error_message: None,
+                    timing,
+                    verdict,
+                    swapped_verdict: None,
+                    inconsistent: false,
+                }
+            }
+            Err(e) => JudgeResponse {
+                model_id: judge_id.clone(),
+                content: String::new(),
+                error_message: Some(e),
+                timing: None,
+                verdict: None,
+                swapped_verdict: None,
+                inconsistent: false,
+            },
+        });
+    }
+
+    let Some(messages) = &round.swapped_messages else {
+        return Some(responses);
+    };
+    // Only structured verdicts can be compared across the two orders
+    let swapped = round.presentation.swapped();
+    let mut rejudged: Vec<&mut JudgeResponse> = responses.iter_mut().filter(|r| r.verdict.is_some()).collect();
+    try_signal_set(&mut is_swap_check, true);
+    let outcomes = join_all(rejudged.iter().map(|response| {
+        stream_single(
+            client,
+            &response.model_id,
+            judging_phase(swapped.bot2_first),
+            messages.clone(),
+            show_judge(live_text, &response.model_id),
+            checkpointer,
+            cancel_flag,
+        )
+    }))
+    .await;
+    try_signal_set(&mut is_swap_check, false);
+    for (response, outcome) in rejudged.iter_mut().zip(outcomes) {
+        match outcome? {
+            Ok((content, _)) => {
+                let swapped_verdict = round
+                    .rubric
+                    .parse_verdict(&content, round.bot1_id, round.bot2_id, swapped)
+                    .map(|(_, verdict)| verdict);
+                response.inconsistent = match (&swapped_verdict, &response.verdict) {
+                    (Some(swapped), Some(first)) => swapped.winner != first.winner,
+                    _ => false,
+                };
+                response.swapped_verdict = swapped_verdict;
+            }
+            Err(e) => eprintln!("Failed to judge with the order swapped: {}", e),
+        }
+    }
+    Some(responses)
+}
+
+/// The round's judgment: a lone judge's response as is, or a panel's verdicts combined
+fn panel_judgment(
+    panel: &JudgePanel,
+    rubric: &Rubric,
+    mut judges: Vec<JudgeResponse>,
+    bot1_id: &str,
+    bot2_id: &str,
+    shown_first: String,
+) -> Result<ModeratorResponse, String> {
+    if judges.len() == 1 {
+        let judge = judges.remove(0);
+        if let Some(e) = judge.error_message {
+            return Err(e);
         }
+        return Ok(ModeratorResponse {
+            content: judge.content,
+            error_message: None,
+            timing: judge.timing,
+            verdict: judge.verdict,
+            shown_first: Some(shown_first),
+            swapped_verdict: judge.swapped_verdict,
+            inconsistent: judge.inconsistent,
+            judges: Vec::new(),
+            agreement: None,
+        });
+    }
+    if judges.iter().all(|j| j.error_message.is_some()) {
+        return Err(judges
+            .iter()
+            .find_map(|j| j.error_message.clone())
+            .unwrap_or_else(|| "No judge responded".to_string()));
     }
+
+    let verdicts: Vec<&Verdict> = judges.iter().filter_map(|j| j.verdict.as_ref()).collect();
+    let swapped_verdicts: Vec<&Verdict> = judges.iter().filter_map(|j| j.swapped_verdict.as_ref()).collect();
+    let verdict = panel.aggregate(rubric, &verdicts, bot1_id, bot2_id);
+    let swapped_verdict = panel.aggregate(rubric, &swapped_verdicts, bot1_id, bot2_id);
+    let inconsistent = match (&verdict, &swapped_verdict) {
+        (Some(verdict), Some(swapped)) => verdict.winner != swapped.winner,
+        _ => false,
+    };
+    Ok(ModeratorResponse {
+        content: String::new(),
+        error_message: None,
+        timing: None,
+        agreement: agreement(&verdicts),
+        verdict,
+        shown_first: Some(shown_first),
+        swapped_verdict,
+        inconsistent,
+        judges,
+    })
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -40,6 +283,7 @@ struct BotResponse {
     model_id: String,
     content: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -47,13 +291,55 @@ struct ConversationRound {
     user_message: String,
     bot1_response: BotResponse,
     bot2_response: BotResponse,
+    turns: Vec<DebateTurn>,
     moderator_judgment: Option<ModeratorResponse>,
+    system_prompts: Option<SystemPrompts>,
+}
+
+impl From<&ConversationRound> for crate::utils::ConversationRound {
+    fn from(r: &ConversationRound) -> Self {
+        crate::utils::ConversationRound {
+            user_message: r.user_message.clone(),
+            bot1_response: crate::utils::BotResponse {
+                model_id: r.bot1_response.model_id.clone(),
+                content: r.bot1_response.content.clone(),
+                error_message: r.bot1_response.error_message.clone(),
+                timing: r.bot1_response.timing.clone(),
+            },
+            bot2_response: crate::utils::BotResponse {
+                model_id: r.bot2_response.model_id.clone(),
+                content: r.bot2_response.content.clone(),
+                error_message: r.bot2_response.error_message.clone(),
+                timing: r.bot2_response.timing.clone(),
+            },
+            turns: r.turns.clone(),
+            moderator_judgment: r.moderator_judgment.as_ref().map(|m| crate::utils::ModeratorResponse {
+                content: m.content.clone(),
+                error_message: m.error_message.clone(),
+                timing: m.timing.clone(),
+                verdict: m.verdict.clone(),
+                shown_first: m.shown_first.clone(),
+                swapped_verdict: m.swapped_verdict.clone(),
+                inconsistent: m.inconsistent,
+                judges: m.judges.clone(),
+                agreement: m.agreement,
+            }),
+            system_prompts: r.system_prompts.clone(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 struct ModeratorResponse {
     content: String,
     error_message: Option<String>,
+    timing: Option<ResponseTiming>,
+    verdict: Option<Verdict>,
+    shown_first: Option<String>,
+    swapped_verdict: Option<Verdict>,
+    inconsistent: bool,
+    judges: Vec<JudgeResponse>,
+    agreement: Option<f64>,
 }
 
 #[derive(Props, Clone)]
@@ -86,7 +372,7 @@ pub fn PvP(props: PvPProps) -> Element {
 
     // Model selection state
     let mut bot_models = use_signal(|| Vec::<String>::new());
-    let mut moderator_model = use_signal(|| None::<String>);
+    let mut panel = use_signal(JudgePanel::default);
     let mut selection_step = use_signal(|| 0); // 0 = select bots, 1 = select moderator, 2 = chat
 
     // Model list state
@@ -98,16 +384,35 @@ pub fn PvP(props: PvPProps) -> Element {
     let mut is_streaming_bots = use_signal(|| false);
     let mut is_streaming_moderator = use_signal(|| false);
     let mut current_bot_responses = use_signal(|| HashMap::<String, String>::new());
-    let mut current_moderator_response = use_signal(|| String::new());
+    // Streaming text of each judge, by model ID
+    let current_moderator_response = use_signal(HashMap::<String, String>::new);
     let mut current_run_id = use_signal(|| None::<String>);
+    // Round left unfinished by a crash or restart, offered for resuming
+    let mut interrupted = use_signal(|| None::<RunCheckpoint>);
     
     // System prompts
     let mut system_prompts = use_signal(SystemPrompts::default);
     let mut prompt_editor_open = use_signal(|| false);
     let mut editing_prompt_target = use_signal(|| PromptEditTarget::Bot);
     let mut temp_prompt = use_signal(String::new);
+
+    // Judging rubric
+    let mut rubric = use_signal(Rubric::default);
+    let mut rubric_editor_open = use_signal(|| false);
+    let mut temp_rubric = use_signal(Vec::<RubricCriterion>::new);
+    let mut judging = use_signal(JudgingOptions::default);
+    // The moderator is re-judging the round with the responses in the opposite order
+    let is_swap_check = use_signal(|| false);
+
+    // Debate format, and the turn being streamed as (label, model ID)
+    let mut debate = use_signal(DebateFormat::default);
+    let current_turn = use_signal(|| None::<(String, String)>);
+    let current_turn_text = use_signal(String::new);
     
     let mut loaded_session_id = use_signal(|| None::<String>);
+    // Exchanges carried over when this session was forked from another one
+    let mut fork_context = use_signal(|| Vec::<(String, String)>::new());
+    let mut context_policy = use_signal(ContextPolicy::default);
     let session_id = props.session_id.clone();
     let session_loader = use_resource(move || {
         let session_id = session_id.clone();
@@ -133,13 +438,15 @@ pub fn PvP(props: PvPProps) -> Element {
                     if let ChatHistory::PvP(history) = &session_data.history {
                         loaded_session_id.set(current_sid.clone());
                         let bot_models_clone = history.bot_models.clone();
-                        let moderator_model_clone = history.moderator_model.clone();
+                        let judges = history.judges();
                         bot_models.set(bot_models_clone.clone());
-                        moderator_model.set(moderator_model_clone.clone());
-                        system_prompts.set(SystemPrompts {
-                            bot: history.system_prompts.bot.clone(),
-                            moderator: history.system_prompts.moderator.clone(),
-                        });
+                        panel.set(JudgePanel { judges: judges.clone(), aggregation: history.panel.aggregation });
+                        system_prompts.set(history.system_prompts.clone().or_defaults());
+                        rubric.set(history.rubric.clone());
+                        judging.set(history.judging);
+                        debate.set(history.debate);
+                        fork_context.set(history.context.clone());
+                        context_policy.set(history.context_policy);
 
                         let converted_rounds: Vec<ConversationRound> = history
                             .rounds
@@ -150,21 +457,33 @@ pub fn PvP(props: PvPProps) -> Element {
                                     model_id: r.bot1_response.model_id.clone(),
                                     content: r.bot1_response.content.clone(),
                                     error_message: r.bot1_response.error_message.clone(),
+                                    timing: r.bot1_response.timing.clone(),
                                 },
                                 bot2_response: BotResponse {
                                     model_id: r.bot2_response.model_id.clone(),
                                     content: r.bot2_response.content.clone(),
                                     error_message: r.bot2_response.error_message.clone(),
+                                    timing: r.bot2_response.timing.clone(),
                                 },
+                                turns: r.turns.clone(),
                                 moderator_judgment: r.moderator_judgment.as_ref().map(|m| ModeratorResponse {
                                     content: m.content.clone(),
                                     error_message: m.error_message.clone(),
+                                    timing: m.timing.clone(),
+                                    verdict: m.verdict.clone(),
+                                    shown_first: m.shown_first.clone(),
+                                    swapped_verdict: m.swapped_verdict.clone(),
+                                    inconsistent: m.inconsistent,
+                                    judges: m.judges.clone(),
+                                    agreement: m.agreement,
                                 }),
+                                system_prompts: r.system_prompts.clone(),
                             })
                             .collect();
                         conversation_history.set(converted_rounds);
+                        interrupted.set(session_data.checkpoint.clone());
 
-                        if bot_models_clone.len() == 2 && moderator_model_clone.is_some() {
+                        if bot_models_clone.len() == 2 && !judges.is_empty() {
                             selection_step.set(2);
                         }
                     }
@@ -173,9 +492,15 @@ pub fn PvP(props: PvPProps) -> Element {
                     eprintln!("Failed to load session: {}", e);
                     loaded_session_id.set(current_sid);
                     bot_models.set(Vec::new());
-                    moderator_model.set(None);
+                    panel.set(JudgePanel::default());
                     conversation_history.set(Vec::new());
+                    interrupted.set(None);
                     system_prompts.set(SystemPrompts::default());
+                    rubric.set(Rubric::default());
+                    judging.set(JudgingOptions::default());
+                    debate.set(DebateFormat::default());
+                    fork_context.set(Vec::new());
+                    context_policy.set(ContextPolicy::default());
                     selection_step.set(0);
                 }
             }
@@ -183,9 +508,15 @@ pub fn PvP(props: PvPProps) -> Element {
     } else if props.session_id.is_none() && loaded_session_id.read().is_some() {
         loaded_session_id.set(None);
         bot_models.set(Vec::new());
-        moderator_model.set(None);
+        panel.set(JudgePanel::default());
         conversation_history.set(Vec::new());
+        interrupted.set(None);
         system_prompts.set(SystemPrompts::default());
+        rubric.set(Rubric::default());
+        judging.set(JudgingOptions::default());
+        debate.set(DebateFormat::default());
+        fork_context.set(Vec::new());
+        context_policy.set(ContextPolicy::default());
         selection_step.set(0);
     }
 
@@ -211,13 +542,13 @@ pub fn PvP(props: PvPProps) -> Element {
         }
     };
 
-    // Select moderator (only 1)
-    let mut select_moderator = move |model_id: String| {
-        let current = moderator_model.read().clone();
-        if current.as_ref() == Some(&model_id) {
-            moderator_model.set(None);
+    // Toggle a moderator on the judging panel
+    let mut toggle_moderator = move |model_id: String| {
+        let mut current = panel.write();
+        if let Some(pos) = current.judges.iter().position(|id| id == &model_id) {
+            current.judges.remove(pos);
         } else {
-            moderator_model.set(Some(model_id));
+            current.judges.push(model_id);
         }
     };
 
@@ -237,7 +568,7 @@ pub fn PvP(props: PvPProps) -> Element {
 
     // Start chat
     let start_chat = move |_| {
-        if bot_models.read().len() == 2 && moderator_model.read().is_some() {
+        if bot_models.read().len() == 2 && !panel.read().judges.is_empty() {
             selection_step.set(2);
         }
     };
@@ -253,13 +584,32 @@ pub fn PvP(props: PvPProps) -> Element {
         prompt_editor_open.set(true);
     };
     
+    let on_prompt_save_error = props.on_save_error;
     let save_prompt = move |_| {
-        let mut prompts = system_prompts.write();
-        match *editing_prompt_target.read() {
-            PromptEditTarget::Bot => prompts.bot = temp_prompt(),
-            PromptEditTarget::Moderator => prompts.moderator = temp_prompt(),
-        }
+        let edited = {
+            let mut prompts = system_prompts.write();
+            match *editing_prompt_target.read() {
+                PromptEditTarget::Bot => prompts.bot = temp_prompt(),
+                PromptEditTarget::Moderator => prompts.moderator = temp_prompt(),
+            }
+            prompts.clone()
+        };
         prompt_editor_open.set(false);
+        save_session_prompts(loaded_session_id(), on_prompt_save_error, move |history| {
+            if let ChatHistory::PvP(h) = history {
+                h.system_prompts = edited;
+            }
+        });
+    };
+
+    let open_rubric_editor = move |_| {
+        temp_rubric.set(rubric.read().criteria.clone());
+        rubric_editor_open.set(true);
+    };
+
+    let save_rubric = move |_| {
+        rubric.set(Rubric { criteria: temp_rubric() }.cleaned());
+        rubric_editor_open.set(false);
     };
 
     let active_run_for_session = find_run_for_session(active_runs, &props.session_id, ChatMode::PvP);
@@ -296,9 +646,16 @@ pub fn PvP(props: PvPProps) -> Element {
         });
     }
 
-    // Send message handler
-    let send_message = move |text: String| {
-        if text.trim().is_empty() || *is_streaming_bots.read() || *is_streaming_moderator.read() || run_is_active {
+    // Run a round, or pick up an interrupted one from its checkpoint
+    let session_id = props.session_id.clone();
+    let on_save_error = props.on_save_error.clone();
+    let run_round = use_callback(move |(text, resume): (String, Option<RunCheckpoint>)| {
+        if text.trim().is_empty()
+            || *is_streaming_bots.read()
+            || *is_streaming_moderator.read()
+            || current_turn.read().is_some()
+            || run_is_active
+        {
             return;
         }
 
@@ -308,29 +665,49 @@ pub fn PvP(props: PvPProps) -> Element {
 
         let bot1_id = bot_models.read()[0].clone();
         let bot2_id = bot_models.read()[1].clone();
-        let mod_id = match moderator_model.read().clone() {
-            Some(model) => model,
-            None => return,
-        };
+        let panel_for_task = panel.read().clone();
+        if panel_for_task.judges.is_empty() {
+            return;
+        }
 
         if let Some(client_arc) = &client_for_send {
             let client = client_arc.clone();
             let user_msg = text.clone();
             let prompts = system_prompts.read().clone();
+            let carried_context = fork_context.read().clone();
+            let context_policy_for_task = *context_policy.read();
+            // Fork context first, then whatever the policy keeps of this session's earlier rounds
+            let prior_rounds: Vec<PriorRound> = conversation_history
+                .read()
+                .iter()
+                .map(|round| crate::utils::ConversationRound::from(round).prior_round())
+                .collect();
+            let round_context: Vec<(String, String)> = carried_context
+                .iter()
+                .cloned()
+                .chain(context_policy_for_task.exchanges(&prior_rounds))
+                .collect();
             let mut is_streaming_bots_clone = is_streaming_bots.clone();
             let mut is_streaming_moderator_clone = is_streaming_moderator.clone();
             let mut current_bot_responses_clone = current_bot_responses.clone();
             let mut current_moderator_response_clone = current_moderator_response.clone();
             let mut conversation_history_clone = conversation_history.clone();
-            let session_id_for_save = props.session_id.clone();
+            let session_id_for_save = session_id.clone();
             let mut sessions_for_task = sessions.clone();
             let bot_models_for_save = bot_models.read().clone();
-            let moderator_model_for_save = moderator_model.read().clone();
             let system_prompts_for_save = system_prompts.read().clone();
-            let on_save_error_for_task = props.on_save_error.clone();
+            let rubric_for_task = rubric.read().clone();
+            let judging_for_task = *judging.read();
+            let is_swap_check_clone = is_swap_check.clone();
+            let debate_for_task = *debate.read();
+            let mut current_turn_clone = current_turn.clone();
+            let mut current_turn_text_clone = current_turn_text.clone();
+            let on_save_error_for_task = on_save_error.clone();
             let cancel_flag = Arc::new(AtomicBool::new(false));
-            let run_id = create_run_id(ChatMode::PvP, &props.session_id);
+            let run_id = create_run_id(ChatMode::PvP, &session_id);
             current_run_id.set(Some(run_id.clone()));
+            interrupted.set(None);
+            let checkpoint = resume.unwrap_or_else(|| RunCheckpoint::new(&user_msg, BOTS_PHASE));
 
             // Immediately add the user message and empty bot responses to show in UI
             conversation_history_clone.write().push(ConversationRound {
@@ -339,329 +716,387 @@ pub fn PvP(props: PvPProps) -> Element {
                     model_id: bot1_id.clone(),
                     content: String::new(),
                     error_message: None,
+                    timing: None,
                 },
                 bot2_response: BotResponse {
                     model_id: bot2_id.clone(),
                     content: String::new(),
                     error_message: None,
+                    timing: None,
                 },
+                turns: Vec::new(),
                 moderator_judgment: None,
+                system_prompts: Some(system_prompts_for_save.clone()),
             });
 
             let run_id_for_task = run_id.clone();
             let mut active_runs_for_task = active_runs.clone();
             let cancel_flag_for_task = cancel_flag.clone();
             let task = spawn_forever(async move {
+                let checkpointer = Checkpointer::new(session_id_for_save.clone(), checkpoint);
                 try_signal_set(&mut is_streaming_bots_clone, true);
                 let _ = try_signal_update(&mut current_bot_responses_clone, |responses| responses.clear());
+                checkpointer.enter_phase(BOTS_PHASE).await;
+
+                // Bots that answered before an interruption keep their answer; only the rest are asked
+                let mut bot_results: HashMap<String, BotResponse> = HashMap::new();
+                for model_id in [&bot1_id, &bot2_id] {
+                    if let Some(done) = checkpointer.succeeded(BOTS_PHASE, model_id) {
+                        let content = done.content.clone();
+                        let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
+                            responses.insert(model_id.clone(), content.clone());
+                        });
+                        bot_results.insert(model_id.clone(), BotResponse {
+                            model_id: model_id.clone(),
+                            content,
+                            error_message: None,
+                            timing: None,
+                        });
+                    }
+                }
+                let pending_bots: Vec<String> = [bot1_id.clone(), bot2_id.clone()]
+                    .into_iter()
+                    .filter(|id| !bot_results.contains_key(id))
+                    .collect();
+
+                if !pending_bots.is_empty() {
+                    // Send to the remaining bots in parallel with system prompt
+                    let messages = ChatMessage::with_context(prompts.bot.clone(), &round_context, user_msg.clone());
+
+                    match client.stream_chat_completion_multi(pending_bots.clone(), messages).await {
+                        Ok(mut rx) => {
+                            // Buffer content locally to throttle updates
+                            let mut content_buffer: HashMap<String, String> = HashMap::new();
+                            let mut last_update = std::time::Instant::now();
+                            const UPDATE_INTERVAL_MS: u64 = 50; // ~20fps
+
+                            while let Some(event) = recv_multi_event_with_cancel(&mut rx, &cancel_flag_for_task).await {
+                                if cancel_flag_for_task.load(Ordering::SeqCst) {
+                                    break;
+                                }
+                                let model_id = event.model_id.clone();
+
+                                match event.event {
+                                    StreamEvent::Content(content) => {
+                                        // Accumulate in buffer instead of writing immediately
+                                        content_buffer
+                                            .entry(model_id.clone())
+                                            .and_modify(|s| s.push_str(&content))
+                                            .or_insert(content);
+
+                                        // Throttle updates: only write to signal every 50ms
+                                        if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
+                                            // Flush only the active model to reduce cloning work.
+                                            if let Some(accumulated) = content_buffer.get(&model_id) {
+                                                let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
+                                                    responses.insert(model_id.clone(), accumulated.clone());
+                                                });
+                                                checkpointer.partial(&model_id, accumulated).await;
+                                            }
 
-                // Send to both bots in parallel with system prompt
-                let messages = vec![
-                    ChatMessage::system(prompts.bot.clone()),
-                    ChatMessage::user(user_msg.clone())
-                ];
-                let bot_ids = vec![bot1_id.clone(), bot2_id.clone()];
-
-                match client.stream_chat_completion_multi(bot_ids.clone(), messages).await {
-                    Ok(mut rx) => {
-                        let mut done_bots = std::collections::HashSet::new();
-
-                        // Buffer content locally to throttle updates
-                        let mut content_buffer: HashMap<String, String> = HashMap::new();
-                        let mut last_update = std::time::Instant::now();
-                        const UPDATE_INTERVAL_MS: u64 = 50; // ~20fps
-
-                        while let Some(event) = recv_multi_event_with_cancel(&mut rx, &cancel_flag_for_task).await {
-                            if cancel_flag_for_task.load(Ordering::SeqCst) {
-                                break;
-                            }
-                            let model_id = event.model_id.clone();
-
-                            match event.event {
-                                StreamEvent::Content(content) => {
-                                    // Accumulate in buffer instead of writing immediately
-                                    content_buffer
-                                        .entry(model_id.clone())
-                                        .and_modify(|s| s.push_str(&content))
-                                        .or_insert(content);
-                                    
-                                    // Throttle updates: only write to signal every 16ms
-                                    if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
-                                        // Flush only the active model to reduce cloning work.
-                                        if let Some(accumulated) = content_buffer.get(&model_id) {
-                                            let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
-                                                responses.insert(model_id.clone(), accumulated.clone());
-                                            });
+                                            last_update = std::time::Instant::now();
                                         }
-                                        
-                                        last_update = std::time::Instant::now();
                                     }
-                                }
-                                StreamEvent::Done => {
-                                    // Flush any remaining buffered content before marking done
-                                    if let Some(accumulated) = content_buffer.remove(&model_id) {
+                                    StreamEvent::Done(timing) => {
+                                        // Flush any remaining buffered content before marking done
+                                        let content = content_buffer.remove(&model_id).unwrap_or_default();
                                         let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
-                                            responses.insert(model_id.clone(), accumulated);
+                                            responses.insert(model_id.clone(), content.clone());
+                                        });
+                                        checkpointer.finished(&model_id, &content, None).await;
+                                        bot_results.insert(model_id.clone(), BotResponse {
+                                            model_id,
+                                            content,
+                                            error_message: None,
+                                            timing,
                                         });
                                     }
-                                    
-                                    done_bots.insert(model_id);
-
-                                    // Check if both bots are done
-                                    if done_bots.len() >= 2 {
-                                        try_signal_set(&mut is_streaming_bots_clone, false);
-
-                                        // Get final bot responses
-                                        let (bot1_final, bot1_error, bot2_final, bot2_error) = try_signal_read(
-                                            &current_bot_responses_clone,
-                                            |responses| {
-                                                let bot1_content = responses.get(&bot1_id).cloned().unwrap_or_default();
-                                                let bot2_content = responses.get(&bot2_id).cloned().unwrap_or_default();
-
-                                                // Check for errors
-                                                let (bot1_final, bot1_error) = if bot1_content.starts_with("Error: ") {
-                                                    (String::new(), Some(bot1_content.strip_prefix("Error: ").unwrap_or(&bot1_content).to_string()))
-                                                } else {
-                                                    (bot1_content, None)
-                                                };
-
-                                                let (bot2_final, bot2_error) = if bot2_content.starts_with("Error: ") {
-                                                    (String::new(), Some(bot2_content.strip_prefix("Error: ").unwrap_or(&bot2_content).to_string()))
-                                                } else {
-                                                    (bot2_content, None)
-                                                };
-
-                                                (bot1_final, bot1_error, bot2_final, bot2_error)
-                                            },
-                                        )
-                                        .unwrap_or_else(|| {
-                                            (
-                                                String::new(),
-                                                Some("Background view was closed".to_string()),
-                                                String::new(),
-                                                Some("Background view was closed".to_string()),
-                                            )
+                                    StreamEvent::Error(e) => {
+                                        if e == "Cancelled" {
+                                            break;
+                                        }
+                                        let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
+                                            responses.insert(model_id.clone(), format!("Error: {}", e));
                                         });
-
-                                        // Update the last conversation round with bot responses
-                                        let _ = try_signal_update(&mut conversation_history_clone, |history| {
-                                            if let Some(last_round) = history.last_mut() {
-                                                last_round.bot1_response = BotResponse {
-                                                    model_id: bot1_id.clone(),
-                                                    content: bot1_final.clone(),
-                                                    error_message: bot1_error.clone(),
-                                                };
-                                                last_round.bot2_response = BotResponse {
-                                                    model_id: bot2_id.clone(),
-                                                    content: bot2_final.clone(),
-                                                    error_message: bot2_error.clone(),
-                                                };
-                                            }
+                                        checkpointer.finished(&model_id, "", Some(e.clone())).await;
+                                        bot_results.insert(model_id.clone(), BotResponse {
+                                            model_id,
+                                            content: String::new(),
+                                            error_message: Some(e),
+                                            timing: None,
                                         });
-
-                                        // Now send to moderator if both bots succeeded
-                                        if bot1_error.is_none() && bot2_error.is_none() {
-                                            try_signal_set(&mut is_streaming_moderator_clone, true);
-                                            try_signal_set(&mut current_moderator_response_clone, String::new());
-
-                                            let moderator_prompt = format!(
-                                                "User Question: {}\n\n\
-                                                {} Response:\n{}\n\n\
-                                                {} Response:\n{}\n\n\
-                                                Please evaluate both responses and determine which one is better. \
-                                                Explain your reasoning and declare a winner. Be specific about what makes \
-                                                one response superior to the other.",
-                                                user_msg, bot1_id, bot1_final, bot2_id, bot2_final
-                                            );
-
-                                            let moderator_messages = vec![
-                                                ChatMessage::system(prompts.moderator.clone()),
-                                                ChatMessage::user(moderator_prompt)
-                                            ];
-
-                                            match client.stream_chat_completion(mod_id.clone(), moderator_messages).await {
-                                                Ok(mut stream) => {
-                                                    let mut mod_content = String::new();
-                                                    
-                                                    // Throttle updates: only write to signal every 16ms
-                                                    let mut last_update = std::time::Instant::now();
-                                                    const UPDATE_INTERVAL_MS: u64 = 50; // ~20fps
-
-                                                    while let Some(event) = next_stream_event_with_cancel(&mut stream, &cancel_flag_for_task).await {
-                                                        if cancel_flag_for_task.load(Ordering::SeqCst) {
-                                                            break;
-                                                        }
-                                                        match event {
-                                                            StreamEvent::Content(content) => {
-                                                                mod_content.push_str(&content);
-                                                                
-                                                                // Throttle updates: only write to signal every 16ms
-                                                                if last_update.elapsed().as_millis() >= UPDATE_INTERVAL_MS as u128 {
-                                                                    try_signal_set(&mut current_moderator_response_clone, mod_content.clone());
-                                                                    
-                                                                    last_update = std::time::Instant::now();
-                                                                }
-                                                            }
-                                                            StreamEvent::Done => {
-                                                                // Flush final content
-                                                                try_signal_set(&mut current_moderator_response_clone, mod_content.clone());
-                                                                
-                                                                // Update the last conversation round with moderator response
-                                                                let _ = try_signal_update(&mut conversation_history_clone, |history| {
-                                                                    if let Some(last_round) = history.last_mut() {
-                                                                        last_round.moderator_judgment = Some(ModeratorResponse {
-                                                                            content: mod_content.clone(),
-                                                                            error_message: None,
-                                                                        });
-                                                                    }
-                                                                });
-                                                                try_signal_set(&mut current_moderator_response_clone, String::new());
-                                                                try_signal_set(&mut is_streaming_moderator_clone, false);
-                                                                
-                                                                // Auto-save only when there is content (spawn_blocking to avoid blocking async runtime)
-                                                                if let Some(sid) = session_id_for_save {
-                                                                    let history = PvPHistory {
-                                                                        rounds: try_signal_read(&conversation_history_clone, |history| history.clone())
-                                                                            .unwrap_or_default()
-                                                                            .iter()
-                                                                            .map(|r| crate::utils::ConversationRound {
-                                                                                user_message: r.user_message.clone(),
-                                                                                bot1_response: crate::utils::BotResponse {
-                                                                                    model_id: r.bot1_response.model_id.clone(),
-                                                                                    content: r.bot1_response.content.clone(),
-                                                                                    error_message: r.bot1_response.error_message.clone(),
-                                                                                },
-                                                                                bot2_response: crate::utils::BotResponse {
-                                                                                    model_id: r.bot2_response.model_id.clone(),
-                                                                                    content: r.bot2_response.content.clone(),
-                                                                                    error_message: r.bot2_response.error_message.clone(),
-                                                                                },
-                                                                                moderator_judgment: r.moderator_judgment.as_ref().map(|m| crate::utils::ModeratorResponse {
-                                                                                    content: m.content.clone(),
-                                                                                    error_message: m.error_message.clone(),
-                                                                                }),
-                                                                            })
-                                                                            .collect(),
-                                                                        bot_models: bot_models_for_save.clone(),
-                                                                        moderator_model: moderator_model_for_save.clone(),
-                                                                        system_prompts: crate::utils::SystemPrompts {
-                                                                            bot: system_prompts_for_save.bot.clone(),
-                                                                            moderator: system_prompts_for_save.moderator.clone(),
-                                                                        },
-                                                                    };
-                                                                    let history_enum = ChatHistory::PvP(history.clone());
-                                                                    if ChatHistory::has_content(&history_enum) {
-                                                                        let summary = ChatHistory::generate_chat_summary(&history_enum);
-                                                                        let session = ChatSession {
-                                                                            id: sid.clone(),
-                                                                            title: summary,
-                                                                            mode: ChatMode::PvP,
-                                                                            timestamp: ChatHistory::format_timestamp(),
-                                                                        };
-                                                                        let session_data = SessionData {
-                                                                            session: session.clone(),
-                                                                            history: history_enum,
-                                                                            created_at: ChatHistory::session_timestamp_from_id(&sid)
-                                                                                .unwrap_or_else(ChatHistory::format_timestamp),
-                                                                            updated_at: ChatHistory::format_timestamp(),
-                                                                        };
-                                                                        match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
-                                                                            Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                                                                            Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                                                                            Ok(Ok(_)) => upsert_session(sessions_for_task, session),
-                                                                        }
-                                                                    }
-                                                                }
-                                                                
-                                                                break;
-                                                            }
-                                                            StreamEvent::Error(e) => {
-                                                                if e == "Cancelled" {
-                                                                    break;
-                                                                }
-                                                                let _ = try_signal_update(&mut conversation_history_clone, |history| {
-                                                                    if let Some(last_round) = history.last_mut() {
-                                                                        last_round.moderator_judgment = Some(ModeratorResponse {
-                                                                            content: String::new(),
-                                                                            error_message: Some(e.clone()),
-                                                                        });
-                                                                    }
-                                                                });
-                                                                try_signal_set(&mut current_moderator_response_clone, String::new());
-                                                                try_signal_set(&mut is_streaming_moderator_clone, false);
-                                                                break;
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    let _ = try_signal_update(&mut conversation_history_clone, |history| {
-                                                        if let Some(last_round) = history.last_mut() {
-                                                            last_round.moderator_judgment = Some(ModeratorResponse {
-                                                                content: String::new(),
-                                                                error_message: Some(e),
-                                                            });
-                                                        }
-                                                    });
-                                                    try_signal_set(&mut is_streaming_moderator_clone, false);
-                                                }
-                                            }
-                                        } else {
-                                            // If either bot had an error, don't call moderator
-                                            let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
-                                                responses.clear()
-                                            });
-                                        }
-
-                                        break;
                                     }
                                 }
-                                StreamEvent::Error(e) => {
-                                    if e == "Cancelled" {
-                                        break;
-                                    }
-                                    let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
-                                        responses.insert(model_id.clone(), format!("Error: {}", e));
-                                    });
-                                    done_bots.insert(model_id);
-                                    if done_bots.len() >= 2 {
-                                        try_signal_set(&mut is_streaming_bots_clone, false);
-                                    }
+
+                                if bot_results.len() >= 2 {
+                                    break;
                                 }
                             }
                         }
-                    }
-                    Err(e) => {
-                        try_signal_set(&mut is_streaming_bots_clone, false);
-
-                        // Update the last conversation round with error responses
-                        let _ = try_signal_update(&mut conversation_history_clone, |history| {
-                            if let Some(last_round) = history.last_mut() {
-                                last_round.bot1_response = BotResponse {
-                                    model_id: bot1_id,
+                        Err(e) => {
+                            for model_id in &pending_bots {
+                                bot_results.insert(model_id.clone(), BotResponse {
+                                    model_id: model_id.clone(),
                                     content: String::new(),
                                     error_message: Some(e.clone()),
+                                    timing: None,
+                                });
+                            }
+                        }
+                    }
+                }
+                try_signal_set(&mut is_streaming_bots_clone, false);
+
+                if !cancel_flag_for_task.load(Ordering::SeqCst) {
+                    let response_for = |model_id: &String| {
+                        bot_results.get(model_id).cloned().unwrap_or_else(|| BotResponse {
+                            model_id: model_id.clone(),
+                            content: String::new(),
+                            error_message: Some("No response received".to_string()),
+                            timing: None,
+                        })
+                    };
+                    let bot1 = response_for(&bot1_id);
+                    let bot2 = response_for(&bot2_id);
+
+                    // Update the last conversation round with bot responses
+                    let _ = try_signal_update(&mut conversation_history_clone, |history| {
+                        if let Some(last_round) = history.last_mut() {
+                            last_round.bot1_response = bot1.clone();
+                            last_round.bot2_response = bot2.clone();
+                        }
+                    });
+
+                    // Debate turns: each bot answers the opponent's latest argument
+                    let mut turns: Vec<DebateTurn> = Vec::new();
+                    let mut all_answered = bot1.error_message.is_none() && bot2.error_message.is_none();
+                    if all_answered {
+                        let mut latest = [bot1.content.clone(), bot2.content.clone()];
+                        'stages: for kind in debate_for_task.stages() {
+                            let phase = kind.phase();
+                            let mut responses = Vec::with_capacity(2);
+                            for (side, model_id) in [&bot1_id, &bot2_id].into_iter().enumerate() {
+                                try_signal_set(&mut current_turn_clone, Some((kind.label(), model_id.clone())));
+                                let messages = ChatMessage::with_context(
+                                    prompts.bot.clone(),
+                                    &round_context,
+                                    kind.prompt(&user_msg, &latest[side], &latest[1 - side]),
+                                );
+                                let Some(outcome) = stream_single(
+                                    &client,
+                                    model_id,
+                                    &phase,
+                                    messages,
+                                    |text| {
+                                        try_signal_set(&mut current_turn_text_clone, text);
+                                    },
+                                    &checkpointer,
+                                    &cancel_flag_for_task,
+                                )
+                                .await
+                                else {
+                                    all_answered = false;
+                                    break 'stages;
                                 };
-                                last_round.bot2_response = BotResponse {
-                                    model_id: bot2_id,
-                                    content: String::new(),
-                                    error_message: Some(e),
-                                };
+                                responses.push(match outcome {
+                                    Ok((content, timing)) => crate::utils::BotResponse {
+                                        model_id: model_id.clone(),
+                                        content,
+                                        error_message: None,
+                                        timing,
+                                    },
+                                    Err(e) => crate::utils::BotResponse {
+                                        model_id: model_id.clone(),
+                                        content: String::new(),
+                                        error_message: Some(e),
+                                        timing: None,
+                                    },
+                                });
+                            }
+                            let bot2_response = responses.remove(1);
+                            let bot1_response = responses.remove(0);
+                            all_answered = bot1_response.error_message.is_none() && bot2_response.error_message.is_none();
+                            latest = [bot1_response.content.clone(), bot2_response.content.clone()];
+                            turns.push(DebateTurn { kind, bot1_response, bot2_response });
+                            let _ = try_signal_update(&mut conversation_history_clone, |history| {
+                                if let Some(last_round) = history.last_mut() {
+                                    last_round.turns = turns.clone();
+                                }
+                            });
+                            if !all_answered {
+                                break;
                             }
+                        }
+                        try_signal_set(&mut current_turn_clone, None);
+                        try_signal_set(&mut current_turn_text_clone, String::new());
+                    }
+
+                    // Now send to moderator if every turn succeeded
+                    if all_answered {
+                        try_signal_set(&mut is_streaming_moderator_clone, true);
+                        let _ = try_signal_update(&mut current_moderator_response_clone, |texts| texts.clear());
+
+                        // A resumed round keeps the order its first judging pass used
+                        let resumed_order = [false, true].into_iter().find(|&bot2_first| {
+                            panel_for_task.judges.iter().any(|judge_id| {
+                                checkpointer.succeeded(judging_phase(bot2_first), judge_id).is_some()
+                            })
+                        });
+                        let presentation = Presentation {
+                            bot2_first: resumed_order.unwrap_or_else(|| judging_for_task.randomize_order && coin_flip()),
+                            anonymous: judging_for_task.anonymize,
+                        };
+                        // In a debate the moderator reads everything each side said
+                        let bot1_transcript = DebateTurn::side_transcript(&bot1.content, &turns, false);
+                        let bot2_transcript = DebateTurn::side_transcript(&bot2.content, &turns, true);
+                        // Earlier rounds name the bots, so anonymized judging only gets the fork context
+                        let moderator_context = if judging_for_task.anonymize { &carried_context } else { &round_context };
+                        let moderator_messages = |presentation: Presentation| {
+                            let moderator_prompt = presentation.moderator_prompt(
+                                &user_msg,
+                                (&bot1_id, &bot1_transcript),
+                                (&bot2_id, &bot2_transcript),
+                                &rubric_for_task,
+                            );
+                            ChatMessage::with_context(prompts.moderator.clone(), moderator_context, moderator_prompt)
+                        };
+                        let judging_round = JudgingRound {
+                            rubric: &rubric_for_task,
+                            bot1_id: &bot1_id,
+                            bot2_id: &bot2_id,
+                            presentation,
+                            messages: moderator_messages(presentation),
+                            swapped_messages: judging_for_task
+                                .swap_check
+                                .then(|| moderator_messages(presentation.swapped())),
+                        };
+
+                        let judge_responses = run_panel(
+                            &client,
+                            &panel_for_task.judges,
+                            &judging_round,
+                            current_moderator_response_clone,
+                            is_swap_check_clone,
+                            &checkpointer,
+                            &cancel_flag_for_task,
+                        )
+                        .await;
+
+                        let judgment = if cancel_flag_for_task.load(Ordering::SeqCst) {
+                            None
+                        } else {
+                            let shown_first = presentation.order(&bot1_id, &bot2_id).0.clone();
+                            judge_responses.map(|judges| panel_judgment(&panel_for_task, &rubric_for_task, judges, &bot1_id, &bot2_id, shown_first))
+                        };
+
+                        let _ = try_signal_update(&mut current_moderator_response_clone, |texts| texts.clear());
+                        try_signal_set(&mut is_streaming_moderator_clone, false);
+
+                        match judgment {
+                            Some(Ok(moderator_response)) => {
+                                // Update the last conversation round with moderator response
+                                let _ = try_signal_update(&mut conversation_history_clone, |history| {
+                                    if let Some(last_round) = history.last_mut() {
+                                        last_round.moderator_judgment = Some(moderator_response.clone());
+                                    }
+                                });
+
+                                // Auto-save only when there is content (spawn_blocking to avoid blocking async runtime)
+                                if let Some(sid) = session_id_for_save {
+                                    let history = PvPHistory {
+                                        rounds: try_signal_read(&conversation_history_clone, |history| history.clone())
+                                            .unwrap_or_default()
+                                            .iter()
+                                            .map(crate::utils::ConversationRound::from)
+                                            .collect(),
+                                        bot_models: bot_models_for_save.clone(),
+                                        moderator_model: panel_for_task.judges.first().cloned(),
+                                        // Current prompts, which may have been edited while the round ran
+                                        system_prompts: try_signal_read(&system_prompts, |prompts| prompts.clone())
+                                            .unwrap_or_else(|| system_prompts_for_save.clone()),
+                                        context: carried_context.clone(),
+                                        rubric: rubric_for_task.clone(),
+                                        judging: judging_for_task,
+                                        panel: panel_for_task.clone(),
+                                        debate: debate_for_task,
+                                        context_policy: context_policy_for_task,
+                                    };
+                                    let history_enum = ChatHistory::PvP(history.clone());
+                                    if ChatHistory::has_content(&history_enum) {
+                                        let summary = ChatHistory::generate_chat_summary(&history_enum);
+                                        let session = ChatSession {
+                                            id: sid.clone(),
+                                            title: summary,
+                                            mode: ChatMode::PvP,
+                                            timestamp: ChatHistory::format_timestamp(),
+                                            stats: SessionStats::default(),
+                                            labels: SessionLabels::default(),
+                                        };
+                                        let session_data = SessionData {
+                                            session,
+                                            history: history_enum.clone(),
+                                            created_at: ChatHistory::session_timestamp_from_id(&sid)
+                                                .unwrap_or_else(ChatHistory::format_timestamp),
+                                            updated_at: ChatHistory::format_timestamp(),
+                                            forked_from: None,
+                                            renamed: false,
+                                            title_generated: false,
+                                            checkpoint: None,
+                                        };
+                                        match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
+                                            Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
+                                            Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
+                                            Ok(Ok(saved_session)) => {
+                                                upsert_session(sessions_for_task, saved_session);
+                                                generate_title_in_background(client.clone(), sessions_for_task, sid.clone(), history_enum);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            Some(Err(e)) => {
+                                let _ = try_signal_update(&mut conversation_history_clone, |history| {
+                                    if let Some(last_round) = history.last_mut() {
+                                        last_round.moderator_judgment = Some(ModeratorResponse {
+                                            content: String::new(),
+                                            error_message: Some(e.clone()),
+                                            timing: None,
+                                            verdict: None,
+                                            shown_first: None,
+                                            swapped_verdict: None,
+                                            inconsistent: false,
+                                            judges: Vec::new(),
+                                            agreement: None,
+                                        });
+                                    }
+                                });
+                            }
+                            None => {}
+                        }
+                    } else {
+                        // If either bot had an error, don't call moderator
+                        let _ = try_signal_update(&mut current_bot_responses_clone, |responses| {
+                            responses.clear()
                         });
                     }
                 }
-
                 if cancel_flag_for_task.load(Ordering::SeqCst) {
                     try_signal_set(&mut is_streaming_bots_clone, false);
                     try_signal_set(&mut is_streaming_moderator_clone, false);
-                    try_signal_set(&mut current_moderator_response_clone, String::new());
+                    let _ = try_signal_update(&mut current_moderator_response_clone, |texts| texts.clear());
                     let _ = try_signal_update(&mut conversation_history_clone, |history| {
                         if let Some(last_round) = history.last_mut() {
                             if last_round.moderator_judgment.is_none() {
                                 last_round.moderator_judgment = Some(ModeratorResponse {
                                     content: String::new(),
                                     error_message: Some("Cancelled".to_string()),
+                                    timing: None,
+                                    verdict: None,
+                                    shown_first: None,
+                                    swapped_verdict: None,
+                                    inconsistent: false,
+                                    judges: Vec::new(),
+                                    agreement: None,
                                 });
                             }
                         }
@@ -675,15 +1110,24 @@ pub fn PvP(props: PvPProps) -> Element {
             register_active_run(
                 active_runs,
                 run_id,
-                props.session_id.clone(),
+                session_id.clone(),
                 ChatMode::PvP,
                 "PvP round".to_string(),
                 task,
                 cancel_flag,
             );
         }
+    });
+    let send_message = move |text: String| run_round.call((text, None));
+
+    let resume_round = move |_| {
+        if let Some(checkpoint) = interrupted() {
+            run_round.call((checkpoint.user_message.clone(), Some(checkpoint)));
+        }
     };
 
+    let discard_checkpoint = move |_| discard_checkpoint(interrupted, loaded_session_id());
+
     // Get filtered models for display
     let models_result = available_models.read();
     let loading = models_result.is_none();
@@ -738,7 +1182,7 @@ pub fn PvP(props: PvPProps) -> Element {
                                     "flex items-center gap-2 px-3 py-1 rounded-full bg-[var(--color-base-300)] text-[var(--color-base-content)]"
                                 },
                                 span { "2" }
-                                span { class: "text-xs font-medium", "Select Moderator" }
+                                span { class: "text-xs font-medium", "Select Moderators" }
                             }
                         }
 
@@ -754,11 +1198,11 @@ pub fn PvP(props: PvPProps) -> Element {
                         } else {
                             h2 {
                                 class: "text-lg font-bold text-[var(--color-base-content)] mb-1",
-                                "Select 1 Moderator"
+                                "Select Moderators"
                             }
                             p {
                                 class: "text-xs text-[var(--color-base-content)]/70",
-                                "Choose 1 AI model that will judge which bot gives the better response."
+                                "Choose one or more AI models to judge which bot gives the better response. A panel's verdicts are combined."
                             }
                         }
                     }
@@ -857,12 +1301,12 @@ pub fn PvP(props: PvPProps) -> Element {
                                             }
                                         } else {
                                             // Moderator selection
-                                            let is_selected = moderator_model.read().as_ref() == Some(&model_id);
+                                            let is_selected = panel.read().judges.contains(&model_id);
 
                                             rsx! {
                                                 button {
                                                     key: "{model_id}",
-                                                    onclick: move |_| select_moderator(model_id.clone()),
+                                                    onclick: move |_| toggle_moderator(model_id.clone()),
                                                     class: if is_selected {
                                                         "p-3 rounded border-2 border-[var(--color-primary)] bg-[var(--color-primary)]/10 transition-all text-left"
                                                     } else {
@@ -925,7 +1369,7 @@ pub fn PvP(props: PvPProps) -> Element {
                                         onclick: proceed_to_moderator,
                                         disabled: bot_models.read().len() != 2,
                                         class: "px-4 py-2 text-sm rounded bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed font-medium transition-all",
-                                        "Next: Select Moderator"
+                                        "Next: Select Moderators"
                                     }
                                 } else {
                                     button {
@@ -937,15 +1381,15 @@ pub fn PvP(props: PvPProps) -> Element {
                                         class: "flex items-center gap-3",
                                         div {
                                             class: "text-sm text-[var(--color-base-content)]/70",
-                                            if moderator_model.read().is_some() {
-                                                "1 moderator selected"
-                                            } else {
-                                                "Select a moderator"
+                                            match panel.read().judges.len() {
+                                                0 => "Select at least one moderator".to_string(),
+                                                1 => "1 moderator selected".to_string(),
+                                                n => format!("{} moderators selected", n),
                                             }
                                         }
                                         button {
                                             onclick: start_chat,
-                                            disabled: moderator_model.read().is_none(),
+                                            disabled: panel.read().judges.is_empty(),
                                             class: "px-4 py-2 text-sm rounded bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed font-medium transition-all",
                                             "Start PvP Chat"
                                         }
@@ -993,6 +1437,12 @@ pub fn PvP(props: PvPProps) -> Element {
                                 span {
                                     class: "text-xs font-semibold text-[var(--color-base-content)]",
                                     "Bot Prompt"
+                                    if system_prompts.read().bot != SystemPrompts::default().bot {
+                                        span {
+                                            class: "ml-1 px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                                            "Custom"
+                                        }
+                                    }
                                 }
                                 button {
                                     onclick: move |_| open_prompt_editor(PromptEditTarget::Bot),
@@ -1002,6 +1452,7 @@ pub fn PvP(props: PvPProps) -> Element {
                             }
                             div {
                                 class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                                title: "{system_prompts.read().bot}",
                                 "{system_prompts.read().bot}"
                             }
                         }
@@ -1014,6 +1465,12 @@ pub fn PvP(props: PvPProps) -> Element {
                                 span {
                                     class: "text-xs font-semibold text-[var(--color-base-content)]",
                                     "Moderator Prompt"
+                                    if system_prompts.read().moderator != SystemPrompts::default().moderator {
+                                        span {
+                                            class: "ml-1 px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                                            "Custom"
+                                        }
+                                    }
                                 }
                                 button {
                                     onclick: move |_| open_prompt_editor(PromptEditTarget::Moderator),
@@ -1023,16 +1480,167 @@ pub fn PvP(props: PvPProps) -> Element {
                             }
                             div {
                                 class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                                title: "{system_prompts.read().moderator}",
                                 "{system_prompts.read().moderator}"
                             }
                         }
                     }
+
+                    // Debate format
+                    div {
+                        class: "mt-2 flex flex-wrap items-center gap-x-4 gap-y-1 text-xs text-[var(--color-base-content)]/80",
+                        span { class: "font-semibold text-[var(--color-base-content)]", "Format" }
+                        select {
+                            onchange: move |evt| debate.write().rebuttals = evt.value().parse().unwrap_or(0),
+                            class: "px-1 py-0.5 rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] text-xs",
+                            for rebuttals in 0..=MAX_REBUTTALS {
+                                option {
+                                    key: "{rebuttals}",
+                                    value: "{rebuttals}",
+                                    selected: debate.read().rebuttals == rebuttals,
+                                    match rebuttals {
+                                        0 => "Single answer".to_string(),
+                                        1 => "Debate: 1 rebuttal".to_string(),
+                                        n => format!("Debate: {} rebuttals", n),
+                                    }
+                                }
+                            }
+                        }
+                        label {
+                            class: "flex items-center gap-1.5 cursor-pointer",
+                            title: "Each bot ends with a short closing statement",
+                            input {
+                                r#type: "checkbox",
+                                checked: debate.read().closing,
+                                onchange: move |evt| debate.write().closing = evt.checked(),
+                                class: "w-3.5 h-3.5 accent-[var(--color-primary)]"
+                            }
+                            "Closing statements"
+                        }
+                        ContextPolicySelect {
+                            policy: context_policy,
+                            disabled: run_is_active,
+                        }
+                        if debate.read().is_debate() {
+                            span {
+                                class: "text-[var(--color-base-content)]/60",
+                                "Bots answer each other's latest argument; the moderator judges the whole debate"
+                            }
+                        }
+                    }
+
+                    // Judging rubric
+                    div {
+                        class: "mt-2 bg-[var(--color-base-200)] rounded p-2 border border-[var(--color-base-300)]",
+                        div {
+                            class: "flex items-center justify-between mb-1",
+                            span {
+                                class: "text-xs font-semibold text-[var(--color-base-content)]",
+                                "Judging Rubric"
+                                if *rubric.read() != Rubric::default() {
+                                    span {
+                                        class: "ml-1 px-1.5 py-0.5 rounded text-[10px] font-medium bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                                        "Custom"
+                                    }
+                                }
+                            }
+                            button {
+                                onclick: open_rubric_editor,
+                                class: "text-xs text-[var(--color-primary)] hover:underline",
+                                "Edit"
+                            }
+                        }
+                        div {
+                            class: "text-xs text-[var(--color-base-content)]/70 truncate",
+                            if rubric.read().criteria.is_empty() {
+                                "No criteria; the moderator only picks a winner"
+                            } else {
+                                {
+                                    rubric
+                                        .read()
+                                        .criteria
+                                        .iter()
+                                        .map(|c| format!("{} ×{}", c.name, c.weight))
+                                        .collect::<Vec<_>>()
+                                        .join(" · ")
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex flex-wrap gap-x-4 gap-y-1 mt-2 text-xs text-[var(--color-base-content)]/80",
+                            label {
+                                class: "flex items-center gap-1.5 cursor-pointer",
+                                title: "Show the responses as Response A and B without model IDs",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: judging.read().anonymize,
+                                    onchange: move |evt| judging.write().anonymize = evt.checked(),
+                                    class: "w-3.5 h-3.5 accent-[var(--color-primary)]"
+                                }
+                                "Anonymize bots"
+                            }
+                            label {
+                                class: "flex items-center gap-1.5 cursor-pointer",
+                                title: "Pick at random which response the moderator reads first",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: judging.read().randomize_order,
+                                    onchange: move |evt| judging.write().randomize_order = evt.checked(),
+                                    class: "w-3.5 h-3.5 accent-[var(--color-primary)]"
+                                }
+                                "Randomize order"
+                            }
+                            label {
+                                class: "flex items-center gap-1.5 cursor-pointer",
+                                title: "Judge again with the order swapped and flag rounds whose winner changes",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: judging.read().swap_check,
+                                    onchange: move |evt| judging.write().swap_check = evt.checked(),
+                                    class: "w-3.5 h-3.5 accent-[var(--color-primary)]"
+                                }
+                                "Re-judge swapped"
+                            }
+                            if panel.read().judges.len() > 1 {
+                                label {
+                                    class: "flex items-center gap-1.5",
+                                    title: "How the panel's verdicts are combined",
+                                    "Panel:"
+                                    select {
+                                        onchange: move |evt| {
+                                            panel.write().aggregation = if evt.value() == "mean" {
+                                                PanelAggregation::MeanScore
+                                            } else {
+                                                PanelAggregation::Majority
+                                            };
+                                        },
+                                        class: "px-1 py-0.5 rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] text-xs",
+                                        option {
+                                            value: "majority",
+                                            selected: panel.read().aggregation == PanelAggregation::Majority,
+                                            {PanelAggregation::Majority.label()}
+                                        }
+                                        option {
+                                            value: "mean",
+                                            selected: panel.read().aggregation == PanelAggregation::MeanScore,
+                                            {PanelAggregation::MeanScore.label()}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
                 
                 // Chat interface
                 div {
                     class: "flex-1 min-h-0 overflow-y-auto p-4",
 
+                    ForkedContext {
+                        theme,
+                        context: fork_context(),
+                    }
+
                     if conversation_history.read().is_empty() {
                         // Empty state
                         div {
@@ -1051,7 +1659,11 @@ pub fn PvP(props: PvPProps) -> Element {
                             }
                             p {
                                 class: "text-sm sm:text-base text-[var(--color-base-content)]/70 mb-4",
-                                "Moderator: {moderator_model.read().as_deref().unwrap_or(\"Not selected\")}"
+                                if panel.read().judges.len() > 1 {
+                                    "Moderators: {panel.read().judges.join(\", \")}"
+                                } else {
+                                    "Moderator: {panel.read().judges.first().map(String::as_str).unwrap_or(\"Not selected\")}"
+                                }
                             }
                             button {
                                 onclick: move |_| { selection_step.set(0); conversation_history.write().clear(); },
@@ -1067,10 +1679,11 @@ pub fn PvP(props: PvPProps) -> Element {
                             for (idx, round) in conversation_history.read().iter().enumerate() {
                                 div {
                                     key: "{idx}",
+                                    id: "round-{idx}",
 
                                     // User message
                                     div {
-                                        class: "flex justify-end mb-4",
+                                        class: "flex flex-col items-end gap-1 mb-4",
                                         div {
                                             class: "max-w-[85%] bg-[var(--color-primary)] text-[var(--color-primary-content)] px-3 sm:px-4 md:px-5 py-2 sm:py-3 rounded-lg text-sm sm:text-base",
                                             FormattedText {
@@ -1078,6 +1691,16 @@ pub fn PvP(props: PvPProps) -> Element {
                                                 content: round.user_message.clone(),
                                             }
                                         }
+                                        CustomPromptsBadge {
+                                            prompts: round.system_prompts.as_ref().map(SystemPrompts::customized).unwrap_or_default(),
+                                        }
+                                    }
+
+                                    if !round.turns.is_empty() {
+                                        div {
+                                            class: "text-xs font-semibold uppercase tracking-wide text-[var(--color-base-content)]/60 mb-2",
+                                            "Opening"
+                                        }
                                     }
 
                                     // Bot responses in a grid
@@ -1139,6 +1762,14 @@ pub fn PvP(props: PvPProps) -> Element {
                                         }
                                     }
 
+                                    for (turn_idx, turn) in round.turns.iter().enumerate() {
+                                        DebateTurnView {
+                                            key: "{turn_idx}",
+                                            theme,
+                                            turn: turn.clone(),
+                                        }
+                                    }
+
                                     // Moderator judgment
                                     if let Some(judgment) = &round.moderator_judgment {
                                         div {
@@ -1149,7 +1780,11 @@ pub fn PvP(props: PvPProps) -> Element {
                                             },
                                             div {
                                                 class: "text-sm sm:text-base font-bold text-[var(--color-base-content)] mb-2",
-                                                "Moderator Judgment ({moderator_model.read().as_deref().unwrap_or(\"Not selected\")})"
+                                                if judgment.judges.is_empty() {
+                                                    "Moderator Judgment ({panel.read().judges.first().map(String::as_str).unwrap_or(\"Not selected\")})"
+                                                } else {
+                                                    "Panel Verdict ({judgment.judges.len()} judges)"
+                                                }
                                             }
                                             if let Some(error) = &judgment.error_message {
                                                 div {
@@ -1157,11 +1792,89 @@ pub fn PvP(props: PvPProps) -> Element {
                                                     "Error: {error}"
                                                 }
                                             } else {
-                                                div {
-                                                    class: "text-sm sm:text-base text-[var(--color-base-content)]",
-                                                    FormattedText {
-                                                        theme,
-                                                        content: judgment.content.clone(),
+                                                if !judgment.content.is_empty() {
+                                                    div {
+                                                        class: "text-sm sm:text-base text-[var(--color-base-content)]",
+                                                        FormattedText {
+                                                            theme,
+                                                            content: judgment.content.clone(),
+                                                        }
+                                                    }
+                                                }
+                                                if let Some(verdict) = &judgment.verdict {
+                                                    VerdictTable {
+                                                        verdict: verdict.clone(),
+                                                        bot1: round.bot1_response.model_id.clone(),
+                                                        bot2: round.bot2_response.model_id.clone(),
+                                                    }
+                                                }
+                                                if let Some(shown_first) = &judgment.shown_first {
+                                                    div {
+                                                        class: "mt-2 text-xs text-[var(--color-base-content)]/60",
+                                                        {
+                                                            let shown_second = if *shown_first == round.bot1_response.model_id {
+                                                                &round.bot2_response.model_id
+                                                            } else {
+                                                                &round.bot1_response.model_id
+                                                            };
+                                                            format!("Response A: {} · Response B: {}", shown_first, shown_second)
+                                                        }
+                                                    }
+                                                }
+                                                if let Some(swapped) = &judgment.swapped_verdict {
+                                                    SwapCheckNote {
+                                                        swapped: swapped.clone(),
+                                                        inconsistent: judgment.inconsistent,
+                                                    }
+                                                }
+                                                if let Some(agreement) = judgment.agreement {
+                                                    div {
+                                                        class: "mt-2 text-xs text-[var(--color-base-content)]/60",
+                                                        "Judge agreement: {agreement * 100.0:.0}% of pairs picked the same winner"
+                                                    }
+                                                }
+                                                for judge in judgment.judges.iter() {
+                                                    details {
+                                                        key: "{judge.model_id}",
+                                                        class: "mt-3 rounded border border-[var(--color-base-300)] bg-[var(--color-base-100)] p-2",
+                                                        summary {
+                                                            class: "cursor-pointer text-sm font-semibold text-[var(--color-base-content)]",
+                                                            {
+                                                                let outcome = match (&judge.error_message, &judge.verdict) {
+                                                                    (Some(_), _) => "failed".to_string(),
+                                                                    (None, Some(verdict)) => verdict.winner.clone().unwrap_or_else(|| "tie".to_string()),
+                                                                    (None, None) => "no structured verdict".to_string(),
+                                                                };
+                                                                format!("{} — {}", judge.model_id, outcome)
+                                                            }
+                                                        }
+                                                        if let Some(error) = &judge.error_message {
+                                                            div {
+                                                                class: "mt-2 text-sm text-red-500",
+                                                                "Error: {error}"
+                                                            }
+                                                        } else {
+                                                            div {
+                                                                class: "mt-2 text-sm text-[var(--color-base-content)]",
+                                                                FormattedText {
+                                                                    theme,
+                                                                    content: judge.content.clone(),
+                                                                }
+                                                            }
+                                                            if let Some(verdict) = &judge.verdict {
+                                                                VerdictTable {
+                                                                    verdict: verdict.clone(),
+                                                                    bot1: round.bot1_response.model_id.clone(),
+                                                                    bot2: round.bot2_response.model_id.clone(),
+                                                                }
+                                                            }
+                                                            if let Some(swapped) = &judge.swapped_verdict {
+                                                                SwapCheckNote {
+                                                                    swapped: swapped.clone(),
+                                                                    inconsistent: judge.inconsistent,
+                                                                }
+                                                            }
+                                                        }
                                                     }
                                                 }
                                             }
@@ -1171,7 +1884,7 @@ pub fn PvP(props: PvPProps) -> Element {
                             }
 
                             // Streaming indicators
-                            if *is_streaming_bots.read() || *is_streaming_moderator.read() {
+                            if *is_streaming_bots.read() || *is_streaming_moderator.read() || current_turn.read().is_some() {
                                 div {
                                     if *is_streaming_bots.read() {
                                         div {
@@ -1225,26 +1938,66 @@ pub fn PvP(props: PvPProps) -> Element {
                                         }
                                     }
 
-                                    if *is_streaming_moderator.read() {
+                                    if let Some((label, model_id)) = current_turn() {
                                         div {
-                                            class: "bg-[var(--color-base-200)] rounded-lg p-3 sm:p-4 border border-[var(--color-base-300)]",
+                                            class: "bg-[var(--color-base-200)] rounded-lg p-3 sm:p-4 border border-[var(--color-base-300)] mb-4",
                                             div {
-                                                class: "text-sm sm:text-base font-bold text-[var(--color-base-content)] mb-2 flex items-center gap-2",
-                                                span { "Moderator Judgment ({moderator_model.read().as_deref().unwrap_or(\"Not selected\")})" }
+                                                class: "text-sm sm:text-base font-bold text-[var(--color-base-content)] mb-2 flex items-center gap-2 truncate",
+                                                span { "{label}: {model_id}" }
                                                 span {
-                                                    class: "inline-block w-2 h-2 bg-[var(--color-primary)] rounded-full animate-pulse"
+                                                    class: "inline-block w-2 h-2 bg-[var(--color-primary)] rounded-full animate-pulse flex-shrink-0"
                                                 }
                                             }
                                             div {
                                                 class: "text-sm sm:text-base text-[var(--color-base-content)] min-h-[3rem]",
-                                                if current_moderator_response().is_empty() {
-                                                        ThinkingIndicator {}
-                                                    } else {
-                                                        div {
-                                                            class: "whitespace-pre-wrap break-words",
-                                                            "{current_moderator_response()}"
+                                                if current_turn_text().is_empty() {
+                                                    ThinkingIndicator {}
+                                                } else {
+                                                    div {
+                                                        class: "whitespace-pre-wrap break-words",
+                                                        "{current_turn_text()}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    if *is_streaming_moderator.read() {
+                                        for (index, judge_id) in panel.read().judges.iter().enumerate() {
+                                            div {
+                                                key: "{judge_id}",
+                                                class: "bg-[var(--color-base-200)] rounded-lg p-3 sm:p-4 border border-[var(--color-base-300)]",
+                                                div {
+                                                    class: "text-sm sm:text-base font-bold text-[var(--color-base-content)] mb-2 flex items-center gap-2",
+                                                    span { "Moderator Judgment ({judge_id})" }
+                                                    if panel.read().judges.len() > 1 {
+                                                        span {
+                                                            class: "text-xs font-normal text-[var(--color-base-content)]/60",
+                                                            "judge {index + 1} of {panel.read().judges.len()}"
                                                         }
                                                     }
+                                                    if *is_swap_check.read() {
+                                                        span {
+                                                            class: "text-xs font-normal text-[var(--color-base-content)]/60",
+                                                            "re-judging with the order swapped"
+                                                        }
+                                                    }
+                                                    span {
+                                                        class: "inline-block w-2 h-2 bg-[var(--color-primary)] rounded-full animate-pulse"
+                                                    }
+                                                }
+                                                div {
+                                                    class: "text-sm sm:text-base text-[var(--color-base-content)] min-h-[3rem]",
+                                                    match current_moderator_response.read().get(judge_id).filter(|text| !text.is_empty()) {
+                                                        Some(text) => rsx! {
+                                                            div {
+                                                                class: "whitespace-pre-wrap break-words",
+                                                                "{text}"
+                                                            }
+                                                        },
+                                                        None => rsx! { ThinkingIndicator {} },
+                                                    }
+                                                }
                                             }
                                         }
                                     }
@@ -1252,6 +2005,19 @@ pub fn PvP(props: PvPProps) -> Element {
                             }
                         }
                     }
+
+                    if let Some(checkpoint) = interrupted() {
+                        if !*is_streaming_bots.read() && !*is_streaming_moderator.read() {
+                            ResumeBanner {
+                                theme,
+                                phase_label: phase_label(&checkpoint.phase).to_string(),
+                                checkpoint,
+                                can_resume: client.is_some() && !run_is_active,
+                                on_resume: resume_round,
+                                on_discard: discard_checkpoint,
+                            }
+                        }
+                    }
                 }
 
                 if let Some((active_run_id, is_cancelling)) = cancel_bar_run.clone() {
@@ -1288,7 +2054,7 @@ pub fn PvP(props: PvPProps) -> Element {
                     theme,
                     input_settings,
                     on_send: send_message,
-                    is_streaming: *is_streaming_bots.read() || *is_streaming_moderator.read(),
+                    is_streaming: *is_streaming_bots.read() || *is_streaming_moderator.read() || current_turn.read().is_some(),
                 }
             }
             
@@ -1388,6 +2154,236 @@ pub fn PvP(props: PvPProps) -> Element {
                     }
                 }
             }
+
+            // Rubric Editor Modal
+            Modal {
+                theme,
+                open: rubric_editor_open,
+                on_close: move |_| {
+                    rubric_editor_open.set(false);
+                },
+
+                div {
+                    class: "p-6",
+
+                    // Header
+                    div {
+                        class: "flex items-start justify-between mb-4",
+                        div {
+                            h2 {
+                                class: "text-xl font-bold text-[var(--color-base-content)]",
+                                "Edit Judging Rubric"
+                            }
+                            p {
+                                class: "text-sm text-[var(--color-base-content)]/70 mt-1",
+                                "The moderator scores both responses from 1 to 10 on each criterion; weights set how much each score counts"
+                            }
+                        }
+                        button {
+                            class: "text-2xl text-[var(--color-base-content)]/70 hover:text-[var(--color-base-content)] transition-colors",
+                            onclick: move |_| {
+                                rubric_editor_open.set(false);
+                            },
+                            "×"
+                        }
+                    }
+
+                    // Criteria
+                    div {
+                        class: "space-y-2 mb-4",
+                        for (idx, criterion) in temp_rubric.read().iter().enumerate() {
+                            div {
+                                key: "{idx}",
+                                class: "flex items-center gap-2",
+                                input {
+                                    r#type: "text",
+                                    value: "{criterion.name}",
+                                    oninput: move |evt| {
+                                        if let Some(c) = temp_rubric.write().get_mut(idx) {
+                                            c.name = evt.value();
+                                        }
+                                    },
+                                    placeholder: "Criterion",
+                                    class: "flex-1 px-3 py-2 text-sm rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                }
+                                input {
+                                    r#type: "number",
+                                    min: "0",
+                                    step: "0.5",
+                                    value: "{criterion.weight}",
+                                    oninput: move |evt| {
+                                        if let (Some(c), Ok(weight)) = (temp_rubric.write().get_mut(idx), evt.value().parse::<f64>()) {
+                                            c.weight = weight;
+                                        }
+                                    },
+                                    title: "Weight",
+                                    class: "w-20 px-3 py-2 text-sm rounded bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                }
+                                button {
+                                    onclick: move |_| {
+                                        temp_rubric.write().remove(idx);
+                                    },
+                                    class: "px-2 text-lg text-[var(--color-base-content)]/60 hover:text-red-500 transition-colors",
+                                    title: "Remove criterion",
+                                    "×"
+                                }
+                            }
+                        }
+                        button {
+                            onclick: move |_| {
+                                temp_rubric.write().push(RubricCriterion { name: String::new(), weight: 1.0 });
+                            },
+                            class: "text-sm text-[var(--color-primary)] hover:underline",
+                            "+ Add criterion"
+                        }
+                    }
+
+                    // Action buttons
+                    div {
+                        class: "flex justify-between items-center gap-3",
+                        button {
+                            onclick: move |_| temp_rubric.set(Rubric::default().criteria),
+                            class: "px-4 py-2 text-sm rounded border border-[var(--color-base-300)] bg-[var(--color-base-200)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)] transition-colors",
+                            "Reset to Default"
+                        }
+                        div {
+                            class: "flex gap-2",
+                            button {
+                                onclick: move |_| {
+                                    rubric_editor_open.set(false);
+                                },
+                                class: "px-4 py-2 text-sm rounded border border-[var(--color-base-300)] bg-[var(--color-base-200)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)] transition-colors",
+                                "Cancel"
+                            }
+                            button {
+                                onclick: save_rubric,
+                                class: "px-4 py-2 text-sm rounded bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 transition-colors font-medium",
+                                "Save Rubric"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Per-criterion scores from a structured moderator verdict
+#[component]
+fn VerdictTable(verdict: Verdict, bot1: String, bot2: String) -> Element {
+    let winner = match &verdict.winner {
+        Some(model_id) => format!("Winner: {}", model_id),
+        None => "Tie".to_string(),
+    };
+
+    rsx! {
+        div {
+            class: "mt-3 overflow-x-auto",
+            if !verdict.scores.is_empty() {
+                table {
+                    class: "w-full text-xs sm:text-sm text-[var(--color-base-content)]",
+                    thead {
+                        tr {
+                            class: "text-left text-[var(--color-base-content)]/60 border-b border-[var(--color-base-300)]",
+                            th { class: "py-1 pr-3", "Criterion" }
+                            th { class: "py-1 pr-3 text-right", "Weight" }
+                            th { class: "py-1 pr-3 text-right truncate max-w-[10rem]", title: "{bot1}", "{bot1}" }
+                            th { class: "py-1 text-right truncate max-w-[10rem]", title: "{bot2}", "{bot2}" }
+                        }
+                    }
+                    tbody {
+                        for score in verdict.scores.iter() {
+                            tr {
+                                key: "{score.criterion}",
+                                class: "border-b border-[var(--color-base-300)]/50",
+                                td { class: "py-1 pr-3", "{score.criterion}" }
+                                td { class: "py-1 pr-3 text-right text-[var(--color-base-content)]/60", "{score.weight}" }
+                                td { class: "py-1 pr-3 text-right", "{score.bot1}" }
+                                td { class: "py-1 text-right", "{score.bot2}" }
+                            }
+                        }
+                        if let Some((total1, total2)) = verdict.totals() {
+                            tr {
+                                class: "font-semibold",
+                                td { class: "py-1 pr-3", "Weighted total" }
+                                td { class: "py-1 pr-3" }
+                                td { class: "py-1 pr-3 text-right", "{total1:.1}" }
+                                td { class: "py-1 text-right", "{total2:.1}" }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "mt-2 inline-block px-2 py-0.5 rounded text-xs font-semibold bg-[var(--color-primary)]/15 text-[var(--color-primary)]",
+                "{winner}"
+            }
+        }
+    }
+}
+
+/// Outcome of judging a round again with the responses in the opposite order
+#[component]
+fn SwapCheckNote(swapped: Verdict, inconsistent: bool) -> Element {
+    let swapped_outcome = match &swapped.winner {
+        Some(model_id) => format!("picked {}", model_id),
+        None => "called a tie".to_string(),
+    };
+
+    rsx! {
+        div {
+            class: if inconsistent {
+                "mt-2 px-2 py-1 rounded text-xs bg-red-500/10 text-red-500 border border-red-500/40"
+            } else {
+                "mt-2 text-xs text-[var(--color-base-content)]/60"
+            },
+            if inconsistent {
+                "⚠ Inconsistent: with the order swapped the moderator {swapped_outcome}. This round is left out of the leaderboard."
+            } else {
+                "✓ Same verdict with the order swapped"
+            }
+        }
+    }
+}
+
+/// Both bots' answers for one rebuttal or closing turn
+#[component]
+fn DebateTurnView(theme: Signal<Theme>, turn: DebateTurn) -> Element {
+    rsx! {
+        div {
+            class: "text-xs font-semibold uppercase tracking-wide text-[var(--color-base-content)]/60 mb-2",
+            "{turn.kind.label()}"
+        }
+        div {
+            class: "{AUTO_FIT_RESPONSE_GRID} gap-3 mb-4",
+            for response in [turn.bot1_response, turn.bot2_response] {
+                div {
+                    key: "{response.model_id}",
+                    class: if response.error_message.is_some() {
+                        "bg-red-500/10 rounded-lg p-3 sm:p-4 border-2 border-red-500/50"
+                    } else {
+                        "bg-[var(--color-base-200)] rounded-lg p-3 sm:p-4 border border-[var(--color-base-300)]"
+                    },
+                    div {
+                        class: "text-sm sm:text-base font-bold text-[var(--color-base-content)] mb-2 truncate",
+                        "{response.model_id}"
+                    }
+                    if let Some(error) = &response.error_message {
+                        div {
+                            class: "text-sm sm:text-base text-red-500",
+                            "Error: {error}"
+                        }
+                    } else {
+                        div {
+                            class: "text-sm sm:text-base text-[var(--color-base-content)]",
+                            FormattedText {
+                                theme,
+                                content: response.content.clone(),
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -1,4 +1,7 @@
-use crate::utils::{InputSettings, Settings as AppSettings, Theme};
+use crate::utils::{
+    Backup, BackupSchedule, InputSettings, LatencyStats, ModelLatency, ResponseTiming, RestoreMode,
+    Settings as AppSettings, Theme, TitleSettings, VaultCommand,
+};
 use dioxus::prelude::*;
 
 #[component]
@@ -8,12 +11,51 @@ pub fn Settings(
     input_settings: Signal<InputSettings>,
     on_settings_change: EventHandler<InputSettings>,
     on_api_key_save: EventHandler<String>,
+    on_import: EventHandler<String>,
+    on_trash_retention_change: EventHandler<u32>,
+    vault_enabled: Signal<bool>,
+    vault_busy: Signal<bool>,
+    on_vault_command: EventHandler<VaultCommand>,
+    backup_busy: Signal<bool>,
+    on_backup: EventHandler<bool>,
+    on_backup_schedule_change: EventHandler<BackupSchedule>,
+    on_restore: EventHandler<(String, RestoreMode)>,
+    on_title_settings_change: EventHandler<TitleSettings>,
     on_close: EventHandler<()>,
 ) -> Element {
     let _ = theme.read();
     let current_settings = *input_settings.read();
     let mut api_key_input = use_signal(|| String::new());
     let mut show_api_key = use_signal(|| false);
+    let mut import_path_input = use_signal(|| String::new());
+    let retention_days = app_settings.read().trash_retention_days;
+    let backup_schedule = app_settings.read().backup.clone();
+    let title_settings = app_settings.read().titles.clone();
+    let backups_dir = Backup::backups_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let is_backup_busy = *backup_busy.read();
+    let mut restore_path_input = use_signal(String::new);
+    let mut restore_mode = use_signal(|| RestoreMode::Merge);
+    let mut current_passphrase = use_signal(String::new);
+    let mut new_passphrase = use_signal(String::new);
+    let mut confirm_passphrase = use_signal(String::new);
+    let is_vault_enabled = *vault_enabled.read();
+    let is_vault_busy = *vault_busy.read();
+    let new_passphrase_ok = new_passphrase.read().chars().count() >= 8
+        && *new_passphrase.read() == *confirm_passphrase.read();
+    // Per-model latency across every saved session, recomputed on Refresh
+    let mut latency_stats = use_resource(|| async {
+        match tokio::task::spawn_blocking(LatencyStats::collect).await {
+            Ok(result) => result,
+            Err(e) => Err(format!("Failed to collect latency stats: {}", e)),
+        }
+    });
+    let mut clear_passphrases = move || {
+        current_passphrase.set(String::new());
+        new_passphrase.set(String::new());
+        confirm_passphrase.set(String::new());
+    };
 
     rsx! {
         div {
@@ -174,6 +216,456 @@ pub fn Settings(
                         }
                     }
 
+                    // Import conversations
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
+
+                        h2 {
+                            class: "text-xl font-semibold text-[var(--color-base-content)] mb-4",
+                            "Import Conversations"
+                        }
+
+                        div {
+                            class: "space-y-2",
+                            p {
+                                class: "text-sm text-[var(--color-base-content)]/70",
+                                "Import a ChatGPT conversations.json, an OpenAI messages JSON file or a GTLLM export bundle. Conversations that were already imported are skipped."
+                            }
+                            input {
+                                r#type: "text",
+                                value: "{import_path_input}",
+                                oninput: move |evt| import_path_input.set(evt.value().clone()),
+                                placeholder: "/path/to/conversations.json",
+                                class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                            }
+                            button {
+                                onclick: move |_| {
+                                    let path = import_path_input.read().trim().to_string();
+                                    if !path.is_empty() {
+                                        on_import.call(path);
+                                        import_path_input.set(String::new());
+                                    }
+                                },
+                                disabled: import_path_input.read().trim().is_empty(),
+                                class: "px-4 py-2 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                                "Import"
+                            }
+                        }
+                    }
+
+                    // Encryption at rest
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
+
+                        h2 {
+                            class: "text-xl font-semibold text-[var(--color-base-content)] mb-4",
+                            "Encryption"
+                        }
+
+                        div {
+                            class: "space-y-2",
+                            if is_vault_enabled {
+                                div {
+                                    class: "flex items-center gap-2 text-sm text-green-500",
+                                    span { "✓" }
+                                    span { "Chats and API key are encrypted" }
+                                }
+                                p {
+                                    class: "text-sm text-[var(--color-base-content)]/70",
+                                    "Enter your current passphrase to change it or to turn encryption off."
+                                }
+                                input {
+                                    r#type: "password",
+                                    value: "{current_passphrase}",
+                                    oninput: move |evt| current_passphrase.set(evt.value()),
+                                    placeholder: "Current passphrase",
+                                    class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                }
+                            } else {
+                                p {
+                                    class: "text-sm text-[var(--color-base-content)]/70",
+                                    "Encrypt saved chats and the API key with a passphrase. You will be asked for it each time GTLLM starts; it cannot be recovered if forgotten."
+                                }
+                            }
+                            input {
+                                r#type: "password",
+                                value: "{new_passphrase}",
+                                oninput: move |evt| new_passphrase.set(evt.value()),
+                                placeholder: if is_vault_enabled { "New passphrase" } else { "Passphrase (at least 8 characters)" },
+                                class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                            }
+                            input {
+                                r#type: "password",
+                                value: "{confirm_passphrase}",
+                                oninput: move |evt| confirm_passphrase.set(evt.value()),
+                                placeholder: "Confirm passphrase",
+                                class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                            }
+                            div {
+                                class: "flex flex-wrap gap-2",
+                                if is_vault_enabled {
+                                    button {
+                                        onclick: move |_| {
+                                            on_vault_command.call(VaultCommand::ChangePassphrase {
+                                                current: current_passphrase.read().clone(),
+                                                new: new_passphrase.read().clone(),
+                                            });
+                                            clear_passphrases();
+                                        },
+                                        disabled: is_vault_busy || current_passphrase.read().is_empty() || !new_passphrase_ok,
+                                        class: "px-4 py-2 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                                        "Change Passphrase"
+                                    }
+                                    button {
+                                        onclick: move |_| {
+                                            on_vault_command.call(VaultCommand::Disable {
+                                                current: current_passphrase.read().clone(),
+                                            });
+                                            clear_passphrases();
+                                        },
+                                        disabled: is_vault_busy || current_passphrase.read().is_empty(),
+                                        class: "px-4 py-2 rounded-lg text-red-500 hover:bg-red-500/10 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                                        "Turn Off Encryption"
+                                    }
+                                } else {
+                                    button {
+                                        onclick: move |_| {
+                                            on_vault_command.call(VaultCommand::Enable {
+                                                passphrase: new_passphrase.read().clone(),
+                                            });
+                                            clear_passphrases();
+                                        },
+                                        disabled: is_vault_busy || !new_passphrase_ok,
+                                        class: "px-4 py-2 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                                        "Encrypt Data"
+                                    }
+                                }
+                                if is_vault_busy {
+                                    span {
+                                        class: "self-center text-sm text-[var(--color-base-content)]/70",
+                                        "Working..."
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Trash retention
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
+
+                        h2 {
+                            class: "text-xl font-semibold text-[var(--color-base-content)] mb-4",
+                            "Trash"
+                        }
+
+                        div {
+                            class: "space-y-2",
+                            p {
+                                class: "text-sm text-[var(--color-base-content)]/70",
+                                "Deleted chats move to the trash in the sidebar and can be restored until they are purged. Set to 0 to keep them until you empty the trash."
+                            }
+                            div {
+                                class: "flex items-center gap-3",
+                                input {
+                                    r#type: "number",
+                                    min: "0",
+                                    value: "{retention_days}",
+                                    onchange: move |evt| {
+                                        if let Ok(days) = evt.value().trim().parse::<u32>() {
+                                            on_trash_retention_change.call(days);
+                                        }
+                                    },
+                                    class: "w-24 px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                }
+                                span {
+                                    class: "text-sm text-[var(--color-base-content)]",
+                                    "days before deleted chats are purged"
+                                }
+                            }
+                        }
+                    }
+
+                    // Backup and restore
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
+
+                        h2 {
+                            class: "text-xl font-semibold text-[var(--color-base-content)] mb-4",
+                            "Backup & Restore"
+                        }
+
+                        div {
+                            class: "space-y-4",
+                            p {
+                                class: "text-sm text-[var(--color-base-content)]/70",
+                                "Backups are compressed archives of your settings, chats and trash with a checksum for every file. They are saved in {backups_dir}. Encrypted chats stay encrypted inside the backup."
+                            }
+                            label {
+                                class: "flex items-center gap-3 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: backup_schedule.include_api_key,
+                                    onchange: {
+                                        let schedule = backup_schedule.clone();
+                                        move |evt: Event<FormData>| {
+                                            on_backup_schedule_change.call(BackupSchedule { include_api_key: evt.checked(), ..schedule.clone() });
+                                        }
+                                    },
+                                    class: "w-4 h-4 accent-[var(--color-primary)]"
+                                }
+                                span {
+                                    class: "text-sm text-[var(--color-base-content)]",
+                                    "Include the API key in backups"
+                                }
+                            }
+                            button {
+                                onclick: {
+                                    let include_api_key = backup_schedule.include_api_key;
+                                    move |_| on_backup.call(include_api_key)
+                                },
+                                disabled: is_backup_busy,
+                                class: "px-4 py-2 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                                "Back Up Now"
+                            }
+
+                            div {
+                                class: "space-y-2 pt-2 border-t border-[var(--color-base-300)]",
+                                label {
+                                    class: "flex items-center gap-3 cursor-pointer",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: backup_schedule.enabled,
+                                        onchange: {
+                                            let schedule = backup_schedule.clone();
+                                            move |evt: Event<FormData>| {
+                                                on_backup_schedule_change.call(BackupSchedule { enabled: evt.checked(), ..schedule.clone() });
+                                            }
+                                        },
+                                        class: "w-4 h-4 accent-[var(--color-primary)]"
+                                    }
+                                    span {
+                                        class: "text-sm text-[var(--color-base-content)]",
+                                        "Back up automatically"
+                                    }
+                                }
+                                div {
+                                    class: "flex items-center gap-3",
+                                    span {
+                                        class: "text-sm text-[var(--color-base-content)]",
+                                        "Every"
+                                    }
+                                    input {
+                                        r#type: "number",
+                                        min: "1",
+                                        value: "{backup_schedule.interval_hours}",
+                                        onchange: {
+                                            let schedule = backup_schedule.clone();
+                                            move |evt: Event<FormData>| {
+                                                if let Ok(hours) = evt.value().trim().parse::<u32>() {
+                                                    on_backup_schedule_change.call(BackupSchedule { interval_hours: hours.max(1), ..schedule.clone() });
+                                                }
+                                            }
+                                        },
+                                        class: "w-24 px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                    }
+                                    span {
+                                        class: "text-sm text-[var(--color-base-content)]",
+                                        "hours, keeping the newest"
+                                    }
+                                    input {
+                                        r#type: "number",
+                                        min: "1",
+                                        value: "{backup_schedule.keep}",
+                                        onchange: {
+                                            let schedule = backup_schedule.clone();
+                                            move |evt: Event<FormData>| {
+                                                if let Ok(keep) = evt.value().trim().parse::<usize>() {
+                                                    on_backup_schedule_change.call(BackupSchedule { keep: keep.max(1), ..schedule.clone() });
+                                                }
+                                            }
+                                        },
+                                        class: "w-24 px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                    }
+                                }
+                            }
+
+                            div {
+                                class: "space-y-2 pt-2 border-t border-[var(--color-base-300)]",
+                                p {
+                                    class: "text-sm text-[var(--color-base-content)]/70",
+                                    "Restoring checks every file against the backup's manifest first. Merge adds chats you don't have; Replace swaps your settings and chats for the backup's after saving a backup of the current ones."
+                                }
+                                input {
+                                    r#type: "text",
+                                    value: "{restore_path_input}",
+                                    oninput: move |evt| restore_path_input.set(evt.value().clone()),
+                                    placeholder: "/path/to/gtllm-backup.tar.gz",
+                                    class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                                }
+                                div {
+                                    class: "flex items-center gap-4",
+                                    label {
+                                        class: "flex items-center gap-2 cursor-pointer text-sm text-[var(--color-base-content)]",
+                                        input {
+                                            r#type: "radio",
+                                            name: "restore-mode",
+                                            checked: *restore_mode.read() == RestoreMode::Merge,
+                                            onchange: move |_| restore_mode.set(RestoreMode::Merge),
+                                            class: "accent-[var(--color-primary)]"
+                                        }
+                                        "Merge"
+                                    }
+                                    label {
+                                        class: "flex items-center gap-2 cursor-pointer text-sm text-[var(--color-base-content)]",
+                                        input {
+                                            r#type: "radio",
+                                            name: "restore-mode",
+                                            checked: *restore_mode.read() == RestoreMode::Replace,
+                                            onchange: move |_| restore_mode.set(RestoreMode::Replace),
+                                            class: "accent-[var(--color-primary)]"
+                                        }
+                                        "Replace"
+                                    }
+                                }
+                                button {
+                                    onclick: move |_| {
+                                        let path = restore_path_input.read().trim().to_string();
+                                        if !path.is_empty() {
+                                            on_restore.call((path, restore_mode()));
+                                            restore_path_input.set(String::new());
+                                        }
+                                    },
+                                    disabled: is_backup_busy || restore_path_input.read().trim().is_empty(),
+                                    class: "px-4 py-2 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+
+                    // Session titles
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
+
+                        h2 {
+                            class: "text-xl font-semibold text-[var(--color-base-content)] mb-4",
+                            "Session Titles"
+                        }
+
+                        div {
+                            class: "space-y-4",
+                            p {
+                                class: "text-sm text-[var(--color-base-content)]/70",
+                                "New chats are named after their first message. A model can write a short title instead once the first round finishes; the first-message title stays if the request fails or you have renamed the chat."
+                            }
+                            label {
+                                class: "flex items-center gap-3 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: title_settings.enabled,
+                                    onchange: {
+                                        let titles = title_settings.clone();
+                                        move |evt: Event<FormData>| {
+                                            on_title_settings_change.call(TitleSettings { enabled: evt.checked(), ..titles.clone() });
+                                        }
+                                    },
+                                    class: "w-4 h-4 accent-[var(--color-primary)]"
+                                }
+                                span {
+                                    class: "text-sm text-[var(--color-base-content)]",
+                                    "Generate titles with a model"
+                                }
+                            }
+                            div {
+                                class: "space-y-2",
+                                label {
+                                    class: "block text-sm font-medium text-[var(--color-base-content)]",
+                                    "Title model"
+                                }
+                                input {
+                                    r#type: "text",
+                                    value: "{title_settings.model}",
+                                    disabled: !title_settings.enabled,
+                                    onchange: {
+                                        let titles = title_settings.clone();
+                                        move |evt: Event<FormData>| {
+                                            let model = evt.value().trim().to_string();
+                                            if !model.is_empty() {
+                                                on_title_settings_change.call(TitleSettings { model, ..titles.clone() });
+                                            }
+                                        }
+                                    },
+                                    placeholder: "openai/gpt-4o-mini",
+                                    class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent disabled:opacity-50",
+                                }
+                                p {
+                                    class: "text-xs text-[var(--color-base-content)]/60",
+                                    "Any OpenRouter model ID. A small, cheap model is plenty."
+                                }
+                            }
+                        }
+                    }
+
+                    // Model latency
+                    div {
+                        class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
+
+                        div {
+                            class: "flex items-center justify-between mb-4",
+                            h2 {
+                                class: "text-xl font-semibold text-[var(--color-base-content)]",
+                                "Model Latency"
+                            }
+                            button {
+                                onclick: move |_| latency_stats.restart(),
+                                class: "px-3 py-1 rounded-lg bg-[var(--color-base-300)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/80 text-sm",
+                                "Refresh"
+                            }
+                        }
+
+                        p {
+                            class: "text-sm text-[var(--color-base-content)]/70 mb-4",
+                            "Time to first token and output speed for every response in your saved chats. Tokens per second are estimated from the response length."
+                        }
+
+                        match &*latency_stats.read() {
+                            None => rsx! {
+                                p { class: "text-sm text-[var(--color-base-content)]/60", "Loading..." }
+                            },
+                            Some(Err(e)) => rsx! {
+                                p { class: "text-sm text-[var(--color-error)]", "{e}" }
+                            },
+                            Some(Ok(stats)) if stats.is_empty() => rsx! {
+                                p { class: "text-sm text-[var(--color-base-content)]/60", "No timed responses yet." }
+                            },
+                            Some(Ok(stats)) => rsx! {
+                                div {
+                                    class: "overflow-x-auto",
+                                    table {
+                                        class: "w-full text-sm text-[var(--color-base-content)]",
+                                        thead {
+                                            tr {
+                                                class: "text-left text-xs uppercase text-[var(--color-base-content)]/60 border-b border-[var(--color-base-300)]",
+                                                th { class: "py-2 pr-4", "Model" }
+                                                th { class: "py-2 pr-4 text-right", "Responses" }
+                                                th { class: "py-2 pr-4 text-right", "TTFT p50" }
+                                                th { class: "py-2 pr-4 text-right", "TTFT p95" }
+                                                th { class: "py-2 pr-4 text-right", "Tok/s p50" }
+                                                th { class: "py-2 text-right", "Tok/s p95" }
+                                            }
+                                        }
+                                        tbody {
+                                            for row in stats.iter() {
+                                                LatencyRow { key: "{row.model_id}", row: row.clone() }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                        }
+                    }
+
                     // Theme info (read-only)
                     div {
                         class: "bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)]",
@@ -209,3 +701,21 @@ pub fn Settings(
         }
     }
 }
+
+#[component]
+fn LatencyRow(row: ModelLatency) -> Element {
+    let duration = |d: Option<std::time::Duration>| d.map(ResponseTiming::format_duration).unwrap_or_else(|| "-".to_string());
+    let rate = |r: Option<f64>| r.map(|r| format!("{:.0}", r)).unwrap_or_else(|| "-".to_string());
+
+    rsx! {
+        tr {
+            class: "border-b border-[var(--color-base-300)]/50",
+            td { class: "py-2 pr-4 font-mono text-xs break-all", "{row.model_id}" }
+            td { class: "py-2 pr-4 text-right", "{row.samples}" }
+            td { class: "py-2 pr-4 text-right", "{duration(row.ttft_p50)}" }
+            td { class: "py-2 pr-4 text-right", "{duration(row.ttft_p95)}" }
+            td { class: "py-2 pr-4 text-right", "{rate(row.tokens_per_second_p50)}" }
+            td { class: "py-2 text-right", "{rate(row.tokens_per_second_p95)}" }
+        }
+    }
+}
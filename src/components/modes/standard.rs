@@ -1,11 +1,12 @@
 use super::common::{
-    ChatInput, FormattedText, Modal, ModelSelector, ThinkingIndicator, AUTO_FIT_RESPONSE_GRID,
+    ChatInput, CustomPromptsBadge, save_session_prompts, FormattedText, ForkedContext, Modal, ModelSelector, discard_checkpoint, ResumeBanner, ThinkingIndicator,
+    AUTO_FIT_RESPONSE_GRID,
 };
 use crate::utils::{
-    create_run_id, find_run_for_session, next_stream_event_with_cancel, register_active_run,
+    create_run_id, find_run_for_session, generate_title_in_background, next_stream_event_with_cancel, register_active_run,
     remove_run, set_run_status, try_signal_read, try_signal_set, try_signal_update, upsert_session,
-    ActiveRunRecord, ChatMessage, ChatHistory, ChatMode, ChatSession, InputSettings, OpenRouterClient,
-    RunStatus, SessionData, StandardHistory, StreamEvent, Theme,
+    ActiveRunRecord, ChatMessage, ChatHistory, ChatMode, ChatSession, Checkpointer, RunCheckpoint, SessionLabels, SessionStats, InputSettings, OpenRouterClient,
+    ResponseTiming, RunStatus, SessionData, StandardHistory, StandardTurn, StreamEvent, Theme, DEFAULT_SYSTEM_PROMPT,
 };
 use dioxus::core::spawn_forever;
 use dioxus::prelude::*;
@@ -15,44 +16,28 @@ use std::sync::{
     Arc,
 };
 
+/// Checkpoint phase name for a Standard round
+const RESPONSES_PHASE: &str = "responses";
+
 #[derive(Clone, Debug, PartialEq)]
 struct ModelResponse {
     model_id: String,
     content: String,
     error_message: Option<String>,
-    metrics: Option<ResponseMetrics>,
+    timing: Option<ResponseTiming>,
 }
 
+/// Live time to first token while a response is still streaming
 #[derive(Clone, Debug, PartialEq)]
 struct ResponseMetrics {
     request_sent_at: std::time::Instant,
     first_token_at: Option<std::time::Instant>,
-    completed_at: Option<std::time::Instant>,
 }
 
 impl ResponseMetrics {
     fn time_to_first_token(&self) -> Option<std::time::Duration> {
         self.first_token_at.map(|ft| ft.duration_since(self.request_sent_at))
     }
-    
-    fn total_time(&self) -> Option<std::time::Duration> {
-        self.completed_at.map(|ct| ct.duration_since(self.request_sent_at))
-    }
-    
-    fn format_duration(duration: std::time::Duration) -> String {
-        let millis = duration.as_millis();
-        if millis < 1000 {
-            format!("{}ms", millis)
-        } else {
-            let secs = duration.as_secs_f64();
-            if secs < 60.0 {
-                format!("{:.2}s", secs)
-            } else {
-                let mins = secs / 60.0;
-                format!("{:.1}m", mins)
-            }
-        }
-    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -63,6 +48,147 @@ struct ConversationHistory {
     multi_model: HashMap<String, Vec<(String, String)>>,
 }
 
+/// A node of the conversation tree; turns sharing a parent are alternative branches
+#[derive(Clone, Debug, PartialEq)]
+struct Turn {
+    parent: Option<usize>,
+    user_message: String,
+    responses: Vec<ModelResponse>,
+    system_prompt: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BranchNav {
+    position: usize,
+    count: usize,
+    previous: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Follow the newest child of each turn from `start` down to a leaf
+fn branch_from(turns: &[Turn], start: usize) -> Vec<usize> {
+    let mut path = vec![start];
+    let mut current = start;
+    while let Some(child) = (0..turns.len()).rev().find(|&idx| turns[idx].parent == Some(current)) {
+        path.push(child);
+        current = child;
+    }
+    path
+}
+
+/// Turns sharing a parent with `turn`, in the order they were created
+fn siblings_of(turns: &[Turn], turn: usize) -> Vec<usize> {
+    let parent = turns[turn].parent;
+    (0..turns.len()).filter(|&idx| turns[idx].parent == parent).collect()
+}
+
+/// Rebuild the per-model context sent to the API for the turns on `path`
+fn conversation_for_path(turns: &[Turn], path: &[usize], models: &[String]) -> ConversationHistory {
+    let mut history = ConversationHistory {
+        single_model: Vec::new(),
+        multi_model: models.iter().map(|model_id| (model_id.clone(), Vec::new())).collect(),
+    };
+    for turn in path.iter().filter_map(|&idx| turns.get(idx)) {
+        if models.len() == 1 {
+            let response = turn.responses.iter()
+                .find(|r| r.model_id == models[0])
+                .or_else(|| turn.responses.first());
+            if let Some(response) = response.filter(|r| r.error_message.is_none()) {
+                history.single_model.push((turn.user_message.clone(), response.content.clone()));
+            }
+        } else {
+            for response in turn.responses.iter().filter(|r| r.error_message.is_none()) {
+                if let Some(model_history) = history.multi_model.get_mut(&response.model_id) {
+                    model_history.push((turn.user_message.clone(), response.content.clone()));
+                }
+            }
+        }
+    }
+    history
+}
+
+fn to_saved_response(response: &ModelResponse) -> crate::utils::ModelResponse {
+    crate::utils::ModelResponse {
+        model_id: response.model_id.clone(),
+        content: response.content.clone(),
+        error_message: response.error_message.clone(),
+        timing: response.timing.clone(),
+    }
+}
+
+/// Persisted form of the conversation; the flat message lists mirror the active branch
+fn to_saved_history(
+    turns: &[Turn],
+    active_path: &[usize],
+    selected_models: Vec<String>,
+    system_prompt: String,
+    conversation: &ConversationHistory,
+    context: &[(String, String)],
+) -> StandardHistory {
+    let branch: Vec<&Turn> = active_path.iter().filter_map(|&idx| turns.get(idx)).collect();
+    StandardHistory {
+        user_messages: branch.iter().map(|turn| turn.user_message.clone()).collect(),
+        model_responses: branch
+            .iter()
+            .map(|turn| turn.responses.iter().map(to_saved_response).collect())
+            .collect(),
+        selected_models,
+        system_prompt,
+        conversation_history: crate::utils::ConversationHistory {
+            single_model: conversation.single_model.clone(),
+            multi_model: conversation.multi_model.clone(),
+        },
+        turns: turns
+            .iter()
+            .map(|turn| StandardTurn {
+                parent: turn.parent,
+                user_message: turn.user_message.clone(),
+                model_responses: turn.responses.iter().map(to_saved_response).collect(),
+                system_prompt: turn.system_prompt.clone(),
+            })
+            .collect(),
+        active_path: active_path.to_vec(),
+        context: context.to_vec(),
+    }
+}
+
+async fn save_standard_session(
+    session_id: String,
+    history: StandardHistory,
+    sessions: Signal<Vec<ChatSession>>,
+    on_save_error: EventHandler<String>,
+) {
+    let history_enum = ChatHistory::Standard(history);
+    if !ChatHistory::has_content(&history_enum) {
+        return;
+    }
+    let summary = ChatHistory::generate_chat_summary(&history_enum);
+    let session = ChatSession {
+        id: session_id.clone(),
+        title: summary,
+        mode: ChatMode::Standard,
+        timestamp: ChatHistory::format_timestamp(),
+        stats: SessionStats::default(),
+        labels: SessionLabels::default(),
+    };
+    let session_data = SessionData {
+        session,
+        history: history_enum,
+        created_at: ChatHistory::session_timestamp_from_id(&session_id)
+            .unwrap_or_else(ChatHistory::format_timestamp),
+        updated_at: ChatHistory::format_timestamp(),
+        forked_from: None,
+        renamed: false,
+        title_generated: false,
+        checkpoint: None,
+    };
+    match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
+        Err(e) => { let _ = on_save_error.call(format!("Failed to save session: {}", e)); }
+        Ok(Err(e)) => { let _ = on_save_error.call(format!("Failed to save session: {}", e)); }
+        Ok(Ok(saved_session)) => upsert_session(sessions, saved_session),
+    }
+}
+
 #[derive(Props, Clone)]
 pub struct StandardProps {
     theme: Signal<Theme>,
@@ -94,6 +220,13 @@ pub fn Standard(props: StandardProps) -> Element {
     let mut selected_models = use_signal(|| Vec::<String>::new());
     let mut user_messages = use_signal(|| Vec::<String>::new());
     let mut model_responses = use_signal(|| Vec::<Vec<ModelResponse>>::new());
+    // Full conversation tree; user_messages/model_responses mirror the active branch
+    let mut turns = use_signal(|| Vec::<Turn>::new());
+    let mut active_path = use_signal(|| Vec::<usize>::new());
+    let mut editing_message = use_signal(|| None::<usize>);
+    // Exchanges carried over when this session was forked from another one
+    let mut fork_context = use_signal(|| Vec::<(String, String)>::new());
+    let mut edit_input = use_signal(|| String::new());
     #[derive(Clone, Debug)]
     struct StreamingResponse {
         content: String,
@@ -103,9 +236,13 @@ pub fn Standard(props: StandardProps) -> Element {
     let mut current_streaming_responses = use_signal(|| HashMap::<String, StreamingResponse>::new());
     let mut is_streaming = use_signal(|| false);
     let mut current_run_id = use_signal(|| None::<String>);
+    // Round left unfinished by a crash or restart, offered for resuming
+    let mut interrupted = use_signal(|| None::<RunCheckpoint>);
+    // Active-branch position and model of a single response being regenerated
+    let mut regenerating = use_signal(|| None::<(usize, String)>);
     
     // System prompt state
-    let mut system_prompt = use_signal(|| "You are a helpful AI assistant.".to_string());
+    let mut system_prompt = use_signal(|| DEFAULT_SYSTEM_PROMPT.to_string());
     let mut system_prompt_editor_open = use_signal(|| false);
     let mut temp_system_prompt = use_signal(|| String::new());
     
@@ -149,7 +286,12 @@ pub fn Standard(props: StandardProps) -> Element {
                         loaded_session_id.set(current_sid);
                         selected_models.set(history.selected_models.clone());
                         user_messages.set(history.user_messages.clone());
-                        system_prompt.set(history.system_prompt.clone());
+                        // Drafts created before prompts were filled in carry an empty one
+                        if history.system_prompt.is_empty() && history.user_messages.is_empty() {
+                            system_prompt.set(DEFAULT_SYSTEM_PROMPT.to_string());
+                        } else {
+                            system_prompt.set(history.system_prompt.clone());
+                        }
                         
                         // Convert ModelResponse from history to internal format
                         let converted_responses: Vec<Vec<ModelResponse>> = history.model_responses
@@ -160,12 +302,37 @@ pub fn Standard(props: StandardProps) -> Element {
                                         model_id: r.model_id.clone(),
                                         content: r.content.clone(),
                                         error_message: r.error_message.clone(),
-                                        metrics: None, // Historical responses don't have metrics
+                                        timing: r.timing.clone(),
                                     })
                                     .collect()
                             })
                             .collect();
                         model_responses.set(converted_responses);
+
+                        let (saved_turns, saved_path) = history.branch_tree();
+                        turns.set(
+                            saved_turns
+                                .into_iter()
+                                .map(|turn| Turn {
+                                    parent: turn.parent,
+                                    user_message: turn.user_message,
+                                    responses: turn.model_responses
+                                        .into_iter()
+                                        .map(|r| ModelResponse {
+                                            model_id: r.model_id,
+                                            content: r.content,
+                                            error_message: r.error_message,
+                                            timing: r.timing,
+                                        })
+                                        .collect(),
+                                    system_prompt: turn.system_prompt,
+                                })
+                                .collect(),
+                        );
+                        active_path.set(saved_path);
+                        editing_message.set(None);
+                        fork_context.set(history.context.clone());
+                        interrupted.set(session_data.checkpoint.clone());
                         
                         // Convert ConversationHistory
                         conversation_history.set(ConversationHistory {
@@ -180,7 +347,11 @@ pub fn Standard(props: StandardProps) -> Element {
                     selected_models.set(Vec::new());
                     user_messages.set(Vec::new());
                     model_responses.set(Vec::new());
-                    system_prompt.set("You are a helpful AI assistant.".to_string());
+                    turns.set(Vec::new());
+                    active_path.set(Vec::new());
+                    fork_context.set(Vec::new());
+                    interrupted.set(None);
+                    system_prompt.set(DEFAULT_SYSTEM_PROMPT.to_string());
                     conversation_history.set(ConversationHistory {
                         single_model: Vec::new(),
                         multi_model: HashMap::new(),
@@ -194,7 +365,12 @@ pub fn Standard(props: StandardProps) -> Element {
         selected_models.set(Vec::new());
         user_messages.set(Vec::new());
         model_responses.set(Vec::new());
-        system_prompt.set("You are a helpful AI assistant.".to_string());
+        turns.set(Vec::new());
+        active_path.set(Vec::new());
+        editing_message.set(None);
+        fork_context.set(Vec::new());
+        interrupted.set(None);
+        system_prompt.set(DEFAULT_SYSTEM_PROMPT.to_string());
         conversation_history.set(ConversationHistory {
             single_model: Vec::new(),
             multi_model: HashMap::new(),
@@ -219,9 +395,16 @@ pub fn Standard(props: StandardProps) -> Element {
         system_prompt_editor_open.set(true);
     };
     
+    let on_prompt_save_error = props.on_save_error;
     let save_system_prompt = move |_| {
-        system_prompt.set(temp_system_prompt());
+        let prompt = temp_system_prompt();
+        system_prompt.set(prompt.clone());
         system_prompt_editor_open.set(false);
+        save_session_prompts(loaded_session_id(), on_prompt_save_error, move |history| {
+            if let ChatHistory::Standard(h) = history {
+                h.system_prompt = prompt;
+            }
+        });
     };
 
     let active_run_for_session = find_run_for_session(active_runs, &props.session_id, ChatMode::Standard);
@@ -258,8 +441,77 @@ pub fn Standard(props: StandardProps) -> Element {
         });
     }
 
-    // Handle sending a message
-    let send_message = move |text: String| {
+    let is_busy = *is_streaming.read() || run_is_active || regenerating.read().is_some();
+
+    // Branch navigation for each turn on the active branch
+    let branch_navs: Vec<BranchNav> = {
+        let tree = turns.read();
+        active_path
+            .read()
+            .iter()
+            .map(|&turn| {
+                let siblings = siblings_of(&tree, turn);
+                let position = siblings.iter().position(|&idx| idx == turn).unwrap_or(0);
+                BranchNav {
+                    position: position + 1,
+                    count: siblings.len(),
+                    previous: position.checked_sub(1).map(|p| siblings[p]),
+                    next: siblings.get(position + 1).copied(),
+                }
+            })
+            .collect()
+    };
+    // Prompt each shown turn ran with, when it was not the default
+    let turn_prompts: Vec<Vec<(&'static str, String)>> = {
+        let tree = turns.read();
+        active_path
+            .read()
+            .iter()
+            .map(|&turn| {
+                tree.get(turn)
+                    .and_then(|turn| turn.system_prompt.clone())
+                    .filter(|prompt| prompt != DEFAULT_SYSTEM_PROMPT)
+                    .map(|prompt| vec![("System", prompt)])
+                    .unwrap_or_default()
+            })
+            .collect()
+    };
+
+    // Show another branch from the turn at `position`, following its newest replies
+    let switch_branch = {
+        let session_id = props.session_id.clone();
+        let on_save_error = props.on_save_error;
+        move |position: usize, turn: usize| {
+            if is_busy {
+                return;
+            }
+            let tree = turns.read().clone();
+            let mut path = active_path.read().clone();
+            path.truncate(position);
+            path.extend(branch_from(&tree, turn));
+
+            let models = selected_models.read().clone();
+            let context = conversation_for_path(&tree, &path, &models);
+            user_messages.set(path.iter().map(|&idx| tree[idx].user_message.clone()).collect());
+            model_responses.set(path.iter().map(|&idx| tree[idx].responses.clone()).collect());
+            active_path.set(path.clone());
+            editing_message.set(None);
+
+            // Persist the selected branch so it is restored on reload
+            if let Some(sid) = session_id.clone() {
+                let history = to_saved_history(&tree, &path, models, system_prompt(), &context, &fork_context.read());
+                spawn(save_standard_session(sid, history, sessions, on_save_error));
+            }
+            conversation_history.set(context);
+        }
+    };
+
+    // Send `text` as turn `branch_at` of the active branch. Anything after that point
+    // stays in the tree and the new turn becomes a sibling branch. A checkpoint from an
+    // interrupted run keeps the responses that had finished.
+    let session_id = props.session_id.clone();
+    let on_save_error = props.on_save_error;
+    let start_round = use_callback(move |(text, branch_at, resume): (String, usize, Option<RunCheckpoint>)| {
         if text.trim().is_empty() || *is_streaming.read() || run_is_active {
             return;
         }
@@ -269,6 +521,16 @@ pub fn Standard(props: StandardProps) -> Element {
             return;
         }
 
+        if branch_at < user_messages.read().len() {
+            user_messages.write().truncate(branch_at);
+            model_responses.write().truncate(branch_at);
+            active_path.write().truncate(branch_at);
+            let context = conversation_for_path(&turns.read(), &active_path.read(), &models);
+            conversation_history.set(context);
+        }
+        editing_message.set(None);
+        let parent_turn = active_path.read().last().copied();
+
         // Add user message
         user_messages.write().push(text.clone());
 
@@ -277,19 +539,24 @@ pub fn Standard(props: StandardProps) -> Element {
             let client = client_arc.clone();
             let is_single_model = models.len() == 1;
             let sys_prompt = system_prompt();
+            let turn_prompt = sys_prompt.clone();
+            let carried_context = fork_context.read().clone();
             let mut is_streaming_clone = is_streaming.clone();
             let mut current_streaming_responses_clone = current_streaming_responses.clone();
             let mut model_responses_clone = model_responses.clone();
             let mut conversation_history_clone = conversation_history.clone();
-            let session_id_for_save = props.session_id.clone();
-            let mut sessions_for_task = sessions.clone();
-            let user_messages_save = user_messages.clone();
+            let session_id_for_save = session_id.clone();
+            let sessions_for_task = sessions.clone();
+            let mut turns_clone = turns.clone();
+            let mut active_path_clone = active_path.clone();
             let selected_models_save = selected_models.clone();
             let system_prompt_save = system_prompt.clone();
-            let on_save_error_for_task = props.on_save_error.clone();
+            let on_save_error_for_task = on_save_error.clone();
             let cancel_flag = Arc::new(AtomicBool::new(false));
-            let run_id = create_run_id(ChatMode::Standard, &props.session_id);
+            let run_id = create_run_id(ChatMode::Standard, &session_id);
             current_run_id.set(Some(run_id.clone()));
+            interrupted.set(None);
+            let checkpoint = resume.unwrap_or_else(|| RunCheckpoint::new(&text, RESPONSES_PHASE));
 
             let run_id_for_task = run_id.clone();
             let mut active_runs_for_task = active_runs.clone();
@@ -305,9 +572,37 @@ pub fn Standard(props: StandardProps) -> Element {
                 // Since we can't use stream_chat_completion_multi with different messages per model,
                 // we'll stream each model individually and aggregate results
                 
-                let mut final_results: HashMap<String, (String, Option<String>, Option<ResponseMetrics>)> = HashMap::new();
+                let mut final_results: HashMap<String, (String, Option<String>, Option<ResponseTiming>)> = HashMap::new();
+
+                // Shared by the concurrent streams below
+                let checkpointer = Arc::new(Checkpointer::new(session_id_for_save.clone(), checkpoint));
+                checkpointer.enter_phase(RESPONSES_PHASE).await;
+
+                // Models that answered before an interruption keep their answer; only the rest are asked
+                for model_id in &models {
+                    if let Some(done) = checkpointer.succeeded(RESPONSES_PHASE, model_id) {
+                        let content = done.content.clone();
+                        let _ = try_signal_update(&mut current_streaming_responses_clone, |responses| {
+                            responses.insert(model_id.clone(), StreamingResponse {
+                                content: content.clone(),
+                                metrics: ResponseMetrics {
+                                    request_sent_at: std::time::Instant::now(),
+                                    first_token_at: None,
+                                },
+                            });
+                        });
+                        final_results.insert(model_id.clone(), (content, None, None));
+                    }
+                }
+                let pending_models: Vec<String> = models
+                    .iter()
+                    .filter(|id| !final_results.contains_key(*id))
+                    .cloned()
+                    .collect();
                 
-                if is_single_model {
+                if pending_models.is_empty() {
+                    // Everything finished before the interruption
+                } else if is_single_model {
                     // Single model with shared history
                     let history = try_signal_read(&conversation_history_clone, |history| history.clone())
                         .unwrap_or(ConversationHistory {
@@ -315,7 +610,7 @@ pub fn Standard(props: StandardProps) -> Element {
                             multi_model: HashMap::new(),
                         });
                     let mut messages = vec![ChatMessage::system(sys_prompt.clone())];
-                    for (user_msg, assistant_msg) in &history.single_model {
+                    for (user_msg, assistant_msg) in carried_context.iter().chain(&history.single_model) {
                         messages.push(ChatMessage::user(user_msg.clone()));
                         messages.push(ChatMessage::assistant(assistant_msg.clone()));
                     }
@@ -333,7 +628,6 @@ pub fn Standard(props: StandardProps) -> Element {
                             let mut metrics = ResponseMetrics {
                                 request_sent_at,
                                 first_token_at: None,
-                                completed_at: None,
                             };
                             
                             // Throttle updates: only write to signal every 16ms
@@ -359,19 +653,19 @@ pub fn Standard(props: StandardProps) -> Element {
                                                     metrics: metrics.clone(),
                                                 });
                                             });
+                                            checkpointer.partial(&model_id, &content).await;
                                             last_update = std::time::Instant::now();
                                         }
                                     }
-                                    StreamEvent::Done => {
-                                        metrics.completed_at = Some(std::time::Instant::now());
-                                        final_results.insert(model_id.clone(), (content.clone(), None, Some(metrics)));
+                                    StreamEvent::Done(timing) => {
+                                        checkpointer.finished(&model_id, &content, None).await;
+                                        final_results.insert(model_id.clone(), (content.clone(), None, timing));
                                         break;
                                     }
                                     StreamEvent::Error(e) => {
                                         if e == "Cancelled" {
                                             break;
                                         }
-                                        metrics.completed_at = Some(std::time::Instant::now());
                                         let error_msg = format!("Error: {}", e);
                                         // Immediately show error in streaming UI
                                         let _ = try_signal_update(&mut current_streaming_responses_clone, |responses| {
@@ -380,7 +674,8 @@ pub fn Standard(props: StandardProps) -> Element {
                                                 metrics: metrics.clone(),
                                             });
                                         });
-                                        final_results.insert(model_id.clone(), (String::new(), Some(e), Some(metrics)));
+                                        checkpointer.finished(&model_id, "", Some(e.clone())).await;
+                                        final_results.insert(model_id.clone(), (String::new(), Some(e), None));
                                         break;
                                     }
                                 }
@@ -390,7 +685,6 @@ pub fn Standard(props: StandardProps) -> Element {
                             let metrics = ResponseMetrics {
                                 request_sent_at,
                                 first_token_at: None,
-                                completed_at: Some(std::time::Instant::now()),
                             };
                             let error_msg = format!("Error: {}", e);
                             // Immediately show error in streaming UI
@@ -400,7 +694,7 @@ pub fn Standard(props: StandardProps) -> Element {
                                     metrics: metrics.clone(),
                                 });
                             });
-                            final_results.insert(model_id, (String::new(), Some(e), Some(metrics)));
+                            final_results.insert(model_id, (String::new(), Some(e), None));
                         }
                     }
                 } else {
@@ -412,15 +706,17 @@ pub fn Standard(props: StandardProps) -> Element {
                     let shared_results = Arc::new(Mutex::new(HashMap::new()));
                     let mut futures = Vec::new();
                     
-                    for model_id in &models {
+                    for model_id in &pending_models {
                         let client = client.clone();
                         let model_id = model_id.clone();
                         let sys_prompt = sys_prompt.clone();
+                        let carried_context = carried_context.clone();
                         let text = text.clone();
                         let conversation_history_clone = conversation_history_clone.clone();
                         let mut current_streaming_responses_clone = current_streaming_responses_clone.clone();
                         let shared_results = shared_results.clone();
                         let cancel_flag_for_model = cancel_flag_for_task.clone();
+                        let checkpointer = checkpointer.clone();
                         
                         let future = async move {
                             let request_sent_at = std::time::Instant::now();
@@ -431,12 +727,11 @@ pub fn Standard(props: StandardProps) -> Element {
                                     single_model: Vec::new(),
                                     multi_model: HashMap::new(),
                                 });
+                            let model_history = history.multi_model.get(&model_id).into_iter().flatten();
                             let mut messages = vec![ChatMessage::system(sys_prompt)];
-                            if let Some(model_history) = history.multi_model.get(&model_id) {
-                                for (user_msg, assistant_msg) in model_history {
-                                    messages.push(ChatMessage::user(user_msg.clone()));
-                                    messages.push(ChatMessage::assistant(assistant_msg.clone()));
-                                }
+                            for (user_msg, assistant_msg) in carried_context.iter().chain(model_history) {
+                                messages.push(ChatMessage::user(user_msg.clone()));
+                                messages.push(ChatMessage::assistant(assistant_msg.clone()));
                             }
                             messages.push(ChatMessage::user(text));
                             
@@ -448,7 +743,6 @@ pub fn Standard(props: StandardProps) -> Element {
                                     let mut metrics = ResponseMetrics {
                                         request_sent_at,
                                         first_token_at: None,
-                                        completed_at: None,
                                     };
                                     
                                     // Buffer content locally to throttle updates
@@ -474,11 +768,11 @@ pub fn Standard(props: StandardProps) -> Element {
                                                             metrics: metrics.clone(),
                                                         });
                                                     });
+                                                    checkpointer.partial(&model_id, &content).await;
                                                     last_update = std::time::Instant::now();
                                                 }
                                             }
-                                            StreamEvent::Done => {
-                                                metrics.completed_at = Some(std::time::Instant::now());
+                                            StreamEvent::Done(timing) => {
                                                 // Flush final content
                                                 let _ = try_signal_update(&mut current_streaming_responses_clone, |responses| {
                                                     responses.insert(model_id.clone(), StreamingResponse {
@@ -486,14 +780,14 @@ pub fn Standard(props: StandardProps) -> Element {
                                                         metrics: metrics.clone(),
                                                     });
                                                 });
-                                                shared_results.lock().await.insert(model_id.clone(), (content, None, Some(metrics)));
+                                                checkpointer.finished(&model_id, &content, None).await;
+                                                shared_results.lock().await.insert(model_id.clone(), (content, None, timing));
                                                 break;
                                             }
                                             StreamEvent::Error(e) => {
                                                 if e == "Cancelled" {
                                                     break;
                                                 }
-                                                metrics.completed_at = Some(std::time::Instant::now());
                                                 let error_msg = format!("Error: {}", e);
                                                 // Immediately show error in streaming UI
                                                 let _ = try_signal_update(&mut current_streaming_responses_clone, |responses| {
@@ -502,7 +796,8 @@ pub fn Standard(props: StandardProps) -> Element {
                                                         metrics: metrics.clone(),
                                                     });
                                                 });
-                                                shared_results.lock().await.insert(model_id.clone(), (String::new(), Some(e), Some(metrics)));
+                                                checkpointer.finished(&model_id, "", Some(e.clone())).await;
+                                                shared_results.lock().await.insert(model_id.clone(), (String::new(), Some(e), None));
                                                 break;
                                             }
                                         }
@@ -512,7 +807,6 @@ pub fn Standard(props: StandardProps) -> Element {
                                     let metrics = ResponseMetrics {
                                         request_sent_at,
                                         first_token_at: None,
-                                        completed_at: Some(std::time::Instant::now()),
                                     };
                                     let error_msg = format!("Error: {}", e);
                                     // Immediately show error in streaming UI
@@ -522,7 +816,7 @@ pub fn Standard(props: StandardProps) -> Element {
                                             metrics: metrics.clone(),
                                         });
                                     });
-                                    shared_results.lock().await.insert(model_id.clone(), (String::new(), Some(e), Some(metrics)));
+                                    shared_results.lock().await.insert(model_id.clone(), (String::new(), Some(e), None));
                                 }
                             }
                         };
@@ -544,14 +838,14 @@ pub fn Standard(props: StandardProps) -> Element {
                 let mut final_responses: Vec<ModelResponse> = models
                     .iter()
                     .map(|model_id| {
-                        let (content, error, metrics) = final_results.get(model_id)
+                        let (content, error, timing) = final_results.get(model_id)
                             .cloned()
                             .unwrap_or_else(|| (String::new(), Some("No response received".to_string()), None));
                         ModelResponse {
                             model_id: model_id.clone(),
                             content,
                             error_message: error,
-                            metrics,
+                            timing,
                         }
                     })
                     .collect();
@@ -575,6 +869,18 @@ pub fn Standard(props: StandardProps) -> Element {
                     }
                 });
                 
+                let new_turn = Turn {
+                    parent: parent_turn,
+                    user_message: text.clone(),
+                    responses: final_responses.clone(),
+                    system_prompt: Some(turn_prompt),
+                };
+                if let Some(turn_idx) = try_signal_update(&mut turns_clone, |turns| {
+                    turns.push(new_turn);
+                    turns.len() - 1
+                }) {
+                    let _ = try_signal_update(&mut active_path_clone, |path| path.push(turn_idx));
+                }
                 let _ = try_signal_update(&mut model_responses_clone, |responses| {
                     responses.push(final_responses)
                 });
@@ -585,60 +891,24 @@ pub fn Standard(props: StandardProps) -> Element {
                 
                 // Auto-save only when there is content (spawn_blocking + cloned signals for current state)
                 if let Some(sid) = session_id_for_save {
-                    let history = StandardHistory {
-                        user_messages: try_signal_read(&user_messages_save, |messages| messages.clone())
-                            .unwrap_or_default(),
-                        model_responses: try_signal_read(&model_responses_clone, |responses| responses.clone())
-                            .unwrap_or_default()
-                            .iter()
-                            .map(|responses| {
-                                responses.iter()
-                                    .map(|r| crate::utils::ModelResponse {
-                                        model_id: r.model_id.clone(),
-                                        content: r.content.clone(),
-                                        error_message: r.error_message.clone(),
-                                    })
-                                    .collect()
-                            })
-                            .collect(),
-                        selected_models: try_signal_read(&selected_models_save, |models| models.clone())
-                            .unwrap_or_default(),
-                        system_prompt: try_signal_read(&system_prompt_save, |prompt| prompt.clone())
-                            .unwrap_or_default(),
-                        conversation_history: crate::utils::ConversationHistory {
-                            single_model: try_signal_read(&conversation_history_clone, |history| {
-                                history.single_model.clone()
-                            })
-                            .unwrap_or_default(),
-                            multi_model: try_signal_read(&conversation_history_clone, |history| {
-                                history.multi_model.clone()
-                            })
-                            .unwrap_or_default(),
-                        },
-                    };
-                    let history_enum = ChatHistory::Standard(history.clone());
-                    if !ChatHistory::has_content(&history_enum) {
-                        return;
-                    }
-                    let summary = ChatHistory::generate_chat_summary(&history_enum);
-                    let session = ChatSession {
-                        id: sid.clone(),
-                        title: summary,
-                        mode: ChatMode::Standard,
-                        timestamp: ChatHistory::format_timestamp(),
-                    };
-                    let session_data = SessionData {
-                        session: session.clone(),
-                        history: history_enum,
-                        created_at: ChatHistory::session_timestamp_from_id(&sid)
-                            .unwrap_or_else(ChatHistory::format_timestamp),
-                        updated_at: ChatHistory::format_timestamp(),
-                    };
-                    match tokio::task::spawn_blocking(move || ChatHistory::save_session(&session_data)).await {
-                        Err(e) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                        Ok(Err(e)) => { let _ = on_save_error_for_task.call(format!("Failed to save session: {}", e)); }
-                        Ok(Ok(_)) => upsert_session(sessions_for_task, session),
-                    }
+                    let tree = try_signal_read(&turns_clone, |turns| turns.clone()).unwrap_or_default();
+                    let path = try_signal_read(&active_path_clone, |path| path.clone()).unwrap_or_default();
+                    let context = try_signal_read(&conversation_history_clone, |history| history.clone())
+                        .unwrap_or(ConversationHistory {
+                            single_model: Vec::new(),
+                            multi_model: HashMap::new(),
+                        });
+                    let history = to_saved_history(
+                        &tree,
+                        &path,
+                        try_signal_read(&selected_models_save, |models| models.clone()).unwrap_or_default(),
+                        try_signal_read(&system_prompt_save, |prompt| prompt.clone()).unwrap_or_default(),
+                        &context,
+                        &carried_context,
+                    );
+                    let title_history = ChatHistory::Standard(history.clone());
+                    save_standard_session(sid.clone(), history, sessions_for_task, on_save_error_for_task).await;
+                    generate_title_in_background(client.clone(), sessions_for_task, sid, title_history);
                 }
                 if cancel_flag_for_task.load(Ordering::SeqCst) {
                     set_run_status(active_runs_for_task, &run_id_for_task, RunStatus::Cancelled);
@@ -650,13 +920,198 @@ pub fn Standard(props: StandardProps) -> Element {
             register_active_run(
                 active_runs,
                 run_id,
-                props.session_id.clone(),
+                session_id.clone(),
                 ChatMode::Standard,
                 "Standard response".to_string(),
                 task,
                 cancel_flag,
             );
         }
+    });
+
+    // Ask one model again for the turn at `position` of the active branch. The new answer
+    // goes in a sibling turn that keeps the other models' responses, and that branch
+    // becomes active; the original turn and the turns after it stay on their own branch.
+    let session_id = props.session_id.clone();
+    let client_for_regenerate = client.clone();
+    let regenerate_response = use_callback(move |(position, model_id): (usize, String)| {
+        if *is_streaming.read() || run_is_active || regenerating.read().is_some() {
+            return;
+        }
+        let Some(client) = client_for_regenerate.clone() else { return };
+        let tree = turns.read().clone();
+        let path = active_path.read().clone();
+        let Some(&turn_idx) = path.get(position) else { return };
+        let text = tree[turn_idx].user_message.clone();
+
+        let selected = selected_models.read().clone();
+        let context_models = if selected.contains(&model_id) { selected } else { vec![model_id.clone()] };
+        let prior = conversation_for_path(&tree, &path[..position], &context_models);
+        let model_history = if context_models.len() == 1 {
+            prior.single_model
+        } else {
+            prior.multi_model.get(&model_id).cloned().unwrap_or_default()
+        };
+        let mut messages = vec![ChatMessage::system(system_prompt())];
+        for (user_msg, assistant_msg) in fork_context.read().iter().chain(&model_history) {
+            messages.push(ChatMessage::user(user_msg.clone()));
+            messages.push(ChatMessage::assistant(assistant_msg.clone()));
+        }
+        messages.push(ChatMessage::user(text));
+
+        regenerating.set(Some((position, model_id.clone())));
+        current_streaming_responses.write().clear();
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let run_id = create_run_id(ChatMode::Standard, &session_id);
+        current_run_id.set(Some(run_id.clone()));
+        let run_id_for_task = run_id.clone();
+        let cancel_flag_for_task = cancel_flag.clone();
+        let session_id_for_save = session_id.clone();
+        let carried_context = fork_context.read().clone();
+        let on_save_error_for_task = on_save_error;
+        let mut streaming_clone = current_streaming_responses;
+        let mut regenerating_clone = regenerating;
+        let mut turns_clone = turns;
+        let mut active_path_clone = active_path;
+        let mut user_messages_clone = user_messages;
+        let mut model_responses_clone = model_responses;
+        let mut conversation_history_clone = conversation_history;
+        let turn_prompt = system_prompt();
+        let task = spawn_forever(async move {
+            let request_sent_at = std::time::Instant::now();
+            let mut metrics = ResponseMetrics { request_sent_at, first_token_at: None };
+            let mut result = None;
+            match client.stream_chat_completion(model_id.clone(), messages).await {
+                Ok(mut stream) => {
+                    let mut content = String::new();
+                    let mut last_update = std::time::Instant::now();
+                    while let Some(event) = next_stream_event_with_cancel(&mut stream, &cancel_flag_for_task).await {
+                        match event {
+                            StreamEvent::Content(chunk) => {
+                                metrics.first_token_at.get_or_insert_with(std::time::Instant::now);
+                                content.push_str(&chunk);
+                                if last_update.elapsed().as_millis() >= 50 {
+                                    let _ = try_signal_update(&mut streaming_clone, |responses| {
+                                        responses.insert(model_id.clone(), StreamingResponse {
+                                            content: content.clone(),
+                                            metrics: metrics.clone(),
+                                        });
+                                    });
+                                    last_update = std::time::Instant::now();
+                                }
+                            }
+                            StreamEvent::Done(timing) => {
+                                result = Some((content.clone(), None, timing));
+                                break;
+                            }
+                            StreamEvent::Error(e) => {
+                                if e != "Cancelled" {
+                                    result = Some((String::new(), Some(e), None));
+                                }
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => result = Some((String::new(), Some(e), None)),
+            }
+
+            // A cancelled or failed regeneration keeps the previous response
+            match result {
+                Some((content, None, timing)) => {
+                    let response = ModelResponse { model_id: model_id.clone(), content, error_message: None, timing };
+                    let new_turn = try_signal_update(&mut turns_clone, |turns| {
+                        let original = turns.get(turn_idx)?.clone();
+                        let responses = original
+                            .responses
+                            .iter()
+                            .map(|r| if r.model_id == model_id { response.clone() } else { r.clone() })
+                            .collect();
+                        turns.push(Turn {
+                            parent: original.parent,
+                            user_message: original.user_message,
+                            responses,
+                            system_prompt: Some(turn_prompt),
+                        });
+                        Some(turns.len() - 1)
+                    })
+                    .flatten();
+                    if let Some(new_idx) = new_turn {
+                        let tree = try_signal_read(&turns_clone, |turns| turns.clone()).unwrap_or_default();
+                        let mut new_path = path[..position].to_vec();
+                        new_path.push(new_idx);
+                        try_signal_set(&mut active_path_clone, new_path.clone());
+                        try_signal_set(
+                            &mut user_messages_clone,
+                            new_path.iter().map(|&idx| tree[idx].user_message.clone()).collect(),
+                        );
+                        try_signal_set(
+                            &mut model_responses_clone,
+                            new_path.iter().map(|&idx| tree[idx].responses.clone()).collect(),
+                        );
+
+                        let models = try_signal_read(&selected_models, |models| models.clone()).unwrap_or_default();
+                        let context = conversation_for_path(&tree, &new_path, &models);
+                        try_signal_set(&mut conversation_history_clone, context.clone());
+                        if let Some(sid) = session_id_for_save {
+                            let prompt = try_signal_read(&system_prompt, |prompt| prompt.clone()).unwrap_or_default();
+                            let history = to_saved_history(&tree, &new_path, models, prompt, &context, &carried_context);
+                            save_standard_session(sid, history, sessions, on_save_error_for_task).await;
+                        }
+                    }
+                }
+                Some((_, Some(e), _)) => eprintln!("Failed to regenerate response: {}", e),
+                None => {}
+            }
+
+            let _ = try_signal_update(&mut streaming_clone, |responses| responses.clear());
+            try_signal_set(&mut regenerating_clone, None);
+            if cancel_flag_for_task.load(Ordering::SeqCst) {
+                set_run_status(active_runs, &run_id_for_task, RunStatus::Cancelled);
+            } else {
+                remove_run(active_runs, &run_id_for_task);
+            }
+        });
+
+        register_active_run(
+            active_runs,
+            run_id,
+            session_id.clone(),
+            ChatMode::Standard,
+            "Regenerate response".to_string(),
+            task,
+            cancel_flag,
+        );
+    });
+
+    let send_message = move |text: String| {
+        let branch_at = user_messages.read().len();
+        start_round.call((text, branch_at, None));
+    };
+
+    // An interrupted round was never added to the tree, so it continues the active branch
+    let resume_round = move |_| {
+        if let Some(checkpoint) = interrupted() {
+            let branch_at = user_messages.read().len();
+            start_round.call((checkpoint.user_message.clone(), branch_at, Some(checkpoint)));
+        }
+    };
+
+    let discard_checkpoint = move |_| discard_checkpoint(interrupted, loaded_session_id());
+
+    // Streamed text of the response being regenerated at `position`, if it is this one
+    let regenerating_preview = move |position: usize, model_id: &str| -> Option<String> {
+        match &*regenerating.read() {
+            Some((at, regen_model)) if *at == position && regen_model == model_id => Some(
+                current_streaming_responses
+                    .read()
+                    .get(model_id)
+                    .map(|streaming| streaming.content.clone())
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        }
     };
 
     rsx! {
@@ -717,6 +1172,11 @@ pub fn Standard(props: StandardProps) -> Element {
                 div {
                     class: "flex-1 min-h-0 overflow-y-auto p-4",
 
+                    ForkedContext {
+                        theme,
+                        context: fork_context(),
+                    }
+
                     if user_messages.read().is_empty() {
                         // Empty state
                         div {
@@ -747,15 +1207,95 @@ pub fn Standard(props: StandardProps) -> Element {
                             for (idx, user_msg) in user_messages.read().iter().enumerate() {
                                 div {
                                     key: "{idx}",
+                                    id: "round-{idx}",
 
                                     // User message
-                                    div {
-                                        class: "flex justify-end mb-4",
+                                    if *editing_message.read() == Some(idx) {
                                         div {
-                                            class: "max-w-[85%] bg-[var(--color-primary)] text-[var(--color-primary-content)] px-3 sm:px-4 md:px-5 py-2 sm:py-3 rounded-lg text-sm sm:text-base",
-                                            FormattedText {
-                                                theme,
-                                                content: user_msg.clone(),
+                                            class: "flex flex-col items-end gap-2 mb-4",
+                                            textarea {
+                                                value: "{edit_input}",
+                                                oninput: move |evt| edit_input.set(evt.value()),
+                                                rows: "3",
+                                                class: "w-full max-w-[85%] p-3 border-2 rounded-lg text-sm bg-[var(--color-base-100)] text-[var(--color-base-content)] border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent resize-y",
+                                                autofocus: true,
+                                            }
+                                            div {
+                                                class: "flex gap-2",
+                                                button {
+                                                    onclick: move |_| editing_message.set(None),
+                                                    class: "px-3 py-1 text-xs rounded border border-[var(--color-base-300)] bg-[var(--color-base-200)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)] transition-colors",
+                                                    "Cancel"
+                                                }
+                                                button {
+                                                    onclick: {
+                                                        move |_| start_round.call((edit_input(), idx, None))
+                                                    },
+                                                    disabled: is_busy || edit_input.read().trim().is_empty(),
+                                                    class: "px-3 py-1 text-xs rounded bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 transition-colors font-medium disabled:opacity-60 disabled:cursor-not-allowed",
+                                                    "Save & Submit"
+                                                }
+                                            }
+                                        }
+                                    } else {
+                                        div {
+                                            class: "flex flex-col items-end gap-1 mb-4",
+                                            div {
+                                                class: "max-w-[85%] bg-[var(--color-primary)] text-[var(--color-primary-content)] px-3 sm:px-4 md:px-5 py-2 sm:py-3 rounded-lg text-sm sm:text-base",
+                                                FormattedText {
+                                                    theme,
+                                                    content: user_msg.clone(),
+                                                }
+                                            }
+                                            div {
+                                                class: "flex items-center gap-2 text-xs text-[var(--color-base-content)]/60",
+                                                CustomPromptsBadge {
+                                                    prompts: turn_prompts.get(idx).cloned().unwrap_or_default(),
+                                                }
+                                                if let Some(nav) = branch_navs.get(idx).copied().filter(|nav| nav.count > 1) {
+                                                    button {
+                                                        onclick: {
+                                                            let mut switch_branch = switch_branch.clone();
+                                                            move |_| {
+                                                                if let Some(previous) = nav.previous {
+                                                                    switch_branch(idx, previous);
+                                                                }
+                                                            }
+                                                        },
+                                                        disabled: is_busy || nav.previous.is_none(),
+                                                        class: "px-1 hover:text-[var(--color-base-content)] disabled:opacity-40",
+                                                        title: "Previous branch",
+                                                        "‹"
+                                                    }
+                                                    span { "{nav.position}/{nav.count}" }
+                                                    button {
+                                                        onclick: {
+                                                            let mut switch_branch = switch_branch.clone();
+                                                            move |_| {
+                                                                if let Some(next) = nav.next {
+                                                                    switch_branch(idx, next);
+                                                                }
+                                                            }
+                                                        },
+                                                        disabled: is_busy || nav.next.is_none(),
+                                                        class: "px-1 hover:text-[var(--color-base-content)] disabled:opacity-40",
+                                                        title: "Next branch",
+                                                        "›"
+                                                    }
+                                                }
+                                                if !is_busy {
+                                                    button {
+                                                        onclick: {
+                                                            let text = user_msg.clone();
+                                                            move |_| {
+                                                                edit_input.set(text.clone());
+                                                                editing_message.set(Some(idx));
+                                                            }
+                                                        },
+                                                        class: "text-[var(--color-primary)] hover:underline",
+                                                        "Edit"
+                                                    }
+                                                }
                                             }
                                         }
                                     }
@@ -778,8 +1318,28 @@ pub fn Standard(props: StandardProps) -> Element {
                                                             span { "⚠️" }
                                                         }
                                                         span { "{responses[0].model_id}" }
+                                                        if !is_busy && client.is_some() {
+                                                            button {
+                                                                onclick: {
+                                                                    let model_id = responses[0].model_id.clone();
+                                                                    move |_| regenerate_response.call((idx, model_id.clone()))
+                                                                },
+                                                                class: "ml-auto text-[var(--color-primary)] hover:underline",
+                                                                title: "Ask this model again in a new branch",
+                                                                "↻"
+                                                            }
+                                                        }
                                                     }
-                                                    if let Some(error) = &responses[0].error_message {
+                                                    if let Some(preview) = regenerating_preview(idx, &responses[0].model_id) {
+                                                        if preview.is_empty() {
+                                                            ThinkingIndicator {}
+                                                        } else {
+                                                            div {
+                                                                class: "whitespace-pre-wrap break-words",
+                                                                "{preview}"
+                                                            }
+                                                        }
+                                                    } else if let Some(error) = &responses[0].error_message {
                                                         div {
                                                             class: "text-sm text-[var(--color-base-content)] p-3 bg-red-500/20 rounded",
                                                             div {
@@ -802,21 +1362,27 @@ pub fn Standard(props: StandardProps) -> Element {
                                                                 content: responses[0].content.clone(),
                                                             }
                                                         }
-                                                        if let Some(metrics) = &responses[0].metrics {
+                                                        if let Some(timing) = &responses[0].timing {
                                                             div {
                                                                 class: "mt-2 pt-2 border-t border-[var(--color-base-300)] text-xs text-[var(--color-base-content)]/60 flex flex-wrap gap-2",
-                                                                if let Some(ttft) = metrics.time_to_first_token() {
+                                                                if let Some(ttft) = timing.time_to_first_token() {
                                                                     span {
                                                                         class: "flex items-center gap-1",
                                                                         span { "⚡" }
-                                                                        span { "TTFT: {ResponseMetrics::format_duration(ttft)}" }
+                                                                        span { "TTFT: {ResponseTiming::format_duration(ttft)}" }
                                                                     }
                                                                 }
-                                                                if let Some(total) = metrics.total_time() {
+                                                                span {
+                                                                    class: "flex items-center gap-1",
+                                                                    span { "⏱️" }
+                                                                    span { "Total: {ResponseTiming::format_duration(timing.total_time())}" }
+                                                                }
+                                                                if let Some(tps) = timing.tokens_per_second() {
                                                                     span {
                                                                         class: "flex items-center gap-1",
-                                                                        span { "⏱️" }
-                                                                        span { "Total: {ResponseMetrics::format_duration(total)}" }
+                                                                        title: "Estimated output tokens per second",
+                                                                        span { "🚀" }
+                                                                        span { "{tps:.0} tok/s" }
                                                                     }
                                                                 }
                                                             }
@@ -842,8 +1408,28 @@ pub fn Standard(props: StandardProps) -> Element {
                                                                 span { "⚠️" }
                                                             }
                                                             span { "{response.model_id}" }
+                                                            if !is_busy && client.is_some() {
+                                                                button {
+                                                                    onclick: {
+                                                                        let model_id = response.model_id.clone();
+                                                                        move |_| regenerate_response.call((idx, model_id.clone()))
+                                                                    },
+                                                                    class: "ml-auto font-normal text-[var(--color-primary)] hover:underline",
+                                                                    title: "Ask this model again in a new branch",
+                                                                    "↻"
+                                                                }
+                                                            }
                                                         }
-                                                        if let Some(error) = &response.error_message {
+                                                        if let Some(preview) = regenerating_preview(idx, &response.model_id) {
+                                                            if preview.is_empty() {
+                                                                ThinkingIndicator {}
+                                                            } else {
+                                                                div {
+                                                                    class: "text-sm sm:text-base text-[var(--color-base-content)] flex-1 whitespace-pre-wrap break-words",
+                                                                    "{preview}"
+                                                                }
+                                                            }
+                                                        } else if let Some(error) = &response.error_message {
                                                             div {
                                                                 class: "text-xs text-[var(--color-base-content)] p-2 bg-red-500/20 rounded",
                                                                 div {
@@ -867,21 +1453,27 @@ pub fn Standard(props: StandardProps) -> Element {
                                                                     content: response.content.clone(),
                                                                 }
                                                             }
-                                                            if let Some(metrics) = &response.metrics {
+                                                            if let Some(timing) = &response.timing {
                                                                 div {
                                                                     class: "mt-2 pt-2 border-t border-[var(--color-base-300)] text-xs text-[var(--color-base-content)]/60 flex flex-wrap gap-2",
-                                                                    if let Some(ttft) = metrics.time_to_first_token() {
+                                                                    if let Some(ttft) = timing.time_to_first_token() {
                                                                         span {
                                                                             class: "flex items-center gap-1",
                                                                             span { "⚡" }
-                                                                            span { "TTFT: {ResponseMetrics::format_duration(ttft)}" }
+                                                                            span { "TTFT: {ResponseTiming::format_duration(ttft)}" }
                                                                         }
                                                                     }
-                                                                    if let Some(total) = metrics.total_time() {
+                                                                    span {
+                                                                        class: "flex items-center gap-1",
+                                                                        span { "⏱️" }
+                                                                        span { "Total: {ResponseTiming::format_duration(timing.total_time())}" }
+                                                                    }
+                                                                    if let Some(tps) = timing.tokens_per_second() {
                                                                         span {
                                                                             class: "flex items-center gap-1",
-                                                                            span { "⏱️" }
-                                                                            span { "Total: {ResponseMetrics::format_duration(total)}" }
+                                                                            title: "Estimated output tokens per second",
+                                                                            span { "🚀" }
+                                                                            span { "{tps:.0} tok/s" }
                                                                         }
                                                                     }
                                                                 }
@@ -997,7 +1589,7 @@ pub fn Standard(props: StandardProps) -> Element {
                                                                     span {
                                                                         class: "flex items-center gap-1",
                                                                         span { "⚡" }
-                                                                        span { "TTFT: {ResponseMetrics::format_duration(ttft)}" }
+                                                                        span { "TTFT: {ResponseTiming::format_duration(ttft)}" }
                                                                     }
                                                                 }
                                                                 span {
@@ -1075,7 +1667,7 @@ pub fn Standard(props: StandardProps) -> Element {
                                                                         span {
                                                                             class: "flex items-center gap-1",
                                                                             span { "⚡" }
-                                                                            span { "TTFT: {ResponseMetrics::format_duration(ttft)}" }
+                                                                            span { "TTFT: {ResponseTiming::format_duration(ttft)}" }
                                                                         }
                                                                     }
                                                                     span {
@@ -1093,10 +1685,37 @@ pub fn Standard(props: StandardProps) -> Element {
                                             }
                                         }
                                     }
+                                    if !is_busy && idx < model_responses.read().len() {
+                                        div {
+                                            class: "flex justify-start mt-2",
+                                            button {
+                                                onclick: {
+                                                    let text = user_msg.clone();
+                                                    move |_| start_round.call((text.clone(), idx, None))
+                                                },
+                                                class: "text-xs text-[var(--color-primary)] hover:underline",
+                                                title: "Generate a new set of responses as a separate branch",
+                                                "↻ Regenerate all"
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
+
+                    if let Some(checkpoint) = interrupted() {
+                        if !*is_streaming.read() {
+                            ResumeBanner {
+                                theme,
+                                checkpoint,
+                                phase_label: "the responses".to_string(),
+                                can_resume: client.is_some() && !is_busy,
+                                on_resume: resume_round,
+                                on_discard: discard_checkpoint,
+                            }
+                        }
+                    }
                 }
 
                 if let Some((active_run_id, is_cancelling)) = cancel_bar_run.clone() {
@@ -1196,7 +1815,7 @@ pub fn Standard(props: StandardProps) -> Element {
                         class: "flex justify-between items-center gap-3",
                         button {
                             onclick: move |_| {
-                                temp_system_prompt.set("You are a helpful AI assistant.".to_string());
+                                temp_system_prompt.set(DEFAULT_SYSTEM_PROMPT.to_string());
                             },
                             class: "px-4 py-2 text-sm rounded border border-[var(--color-base-300)] bg-[var(--color-base-200)] text-[var(--color-base-content)] hover:bg-[var(--color-base-300)] transition-colors",
                             "Reset to Default"
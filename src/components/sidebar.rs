@@ -1,6 +1,32 @@
-use crate::utils::{ChatHistory, ChatSession, Theme};
+use crate::components::TrashPanel;
+use crate::utils::{
+    local_utc_offset, ChatHistory, ChatMode, ChatSession, ExportFormat, SearchIndex, SessionGroup, SessionLabels,
+    SidebarPreferences, Theme, TrashEntry,
+};
 use dioxus::prelude::*;
 
+/// Sessions rendered per page; more are added as the list is scrolled
+const PAGE_SIZE: usize = 50;
+
+/// A titled block of sessions in the chat list
+#[derive(Clone, PartialEq)]
+enum SidebarSection {
+    Pinned,
+    Folder(String),
+    Date(SessionGroup),
+}
+
+/// Copy of a listed session's labels with `edit` applied
+fn edited_labels(
+    sessions: &[ChatSession],
+    session_id: &str,
+    edit: impl FnOnce(&mut SessionLabels),
+) -> Option<SessionLabels> {
+    let mut labels = sessions.iter().find(|s| s.id == session_id)?.labels.clone();
+    edit(&mut labels);
+    Some(labels)
+}
+
 #[component]
 pub fn Sidebar(
     theme: Signal<Theme>,
@@ -9,10 +35,171 @@ pub fn Sidebar(
     collapsed: Signal<bool>,
     on_new_chat: EventHandler<()>,
     on_select_session: EventHandler<String>,
-    on_delete_session: EventHandler<String>,
+    on_delete_sessions: EventHandler<Vec<String>>,
+    on_rename_session: EventHandler<(String, String)>,
+    on_fork_session: EventHandler<String>,
+    on_export_session: EventHandler<(String, ExportFormat)>,
+    on_export_all: EventHandler<ExportFormat>,
+    on_open_search_result: EventHandler<(String, usize)>,
+    trash: Signal<Vec<TrashEntry>>,
+    on_restore_sessions: EventHandler<Vec<String>>,
+    on_purge_sessions: EventHandler<Vec<String>>,
+    on_update_labels: EventHandler<(String, SessionLabels)>,
+    preferences: Signal<SidebarPreferences>,
+    on_preferences_change: EventHandler<SidebarPreferences>,
 ) -> Element {
     let _ = theme.read();
     let is_collapsed = *collapsed.read();
+    let mut session_menu_open = use_signal(|| None::<String>);
+    let mut export_all_open = use_signal(|| false);
+    let mut renaming_session = use_signal(|| None::<String>);
+    let mut rename_input = use_signal(String::new);
+    let mut search_query = use_signal(String::new);
+    // Multi-select mode for deleting several chats at once
+    let mut selecting = use_signal(|| false);
+    let mut selected_sessions = use_signal(Vec::<String>::new);
+    let mut trash_open = use_signal(|| false);
+    let mut visible_count = use_signal(|| PAGE_SIZE);
+    let mut filters_open = use_signal(|| false);
+    let mut open_folders = use_signal(Vec::<String>::new);
+    let mut new_folder_input = use_signal(|| None::<String>);
+    let mut tag_editing = use_signal(|| None::<String>);
+    let mut tag_input = use_signal(String::new);
+    // Session being dragged onto a folder
+    let mut dragging = use_signal(|| None::<String>);
+
+    // Full-text search across saved sessions (debounced by restarting the resource)
+    let search_results = use_resource(move || {
+        let query = search_query.read().trim().to_string();
+        async move {
+            if query.is_empty() {
+                return None;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+            match tokio::task::spawn_blocking(move || SearchIndex::search(&query, 50)).await {
+                Ok(Ok(hits)) => Some(hits),
+                Ok(Err(e)) => {
+                    eprintln!("Search failed: {}", e);
+                    Some(Vec::new())
+                }
+                Err(e) => {
+                    eprintln!("Search task failed: {}", e);
+                    Some(Vec::new())
+                }
+            }
+        }
+    });
+    let is_searching = !is_collapsed && !search_query.read().trim().is_empty();
+    let is_trash_open = !is_collapsed && !is_searching && *trash_open.read();
+    let is_selecting = !is_collapsed && *selecting.read();
+    let selected_count = selected_sessions.read().len();
+    let trash_count = trash.read().len();
+
+    let prefs = preferences();
+    let is_filtering = prefs.is_filtering();
+    let filtered: Vec<ChatSession> = sessions
+        .read()
+        .iter()
+        .filter(|s| prefs.matches(s))
+        .cloned()
+        .collect();
+
+    // Choices for the filter bar and the "Move to folder" menu
+    let mut all_tags: Vec<String> = Vec::new();
+    let mut all_models: Vec<String> = Vec::new();
+    let mut folder_names = prefs.folders.clone();
+    for session in sessions.read().iter() {
+        for tag in &session.labels.tags {
+            if !all_tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                all_tags.push(tag.clone());
+            }
+        }
+        for model in &session.stats.models {
+            if !all_models.contains(model) {
+                all_models.push(model.clone());
+            }
+        }
+        if let Some(folder) = &session.labels.folder {
+            if !folder_names.contains(folder) {
+                folder_names.push(folder.clone());
+            }
+        }
+    }
+    all_tags.sort_by_key(|t| t.to_lowercase());
+    all_models.sort();
+
+    // Pinned first, then folders, then the remaining sessions paged into date sections.
+    // The collapsed rail has no folder headers, so filed sessions stay in the date list there.
+    let mut sections: Vec<(SidebarSection, Vec<ChatSession>, usize)> = Vec::new();
+    let pinned: Vec<ChatSession> = filtered.iter().filter(|s| s.labels.pinned).cloned().collect();
+    if !pinned.is_empty() {
+        let count = pinned.len();
+        sections.push((SidebarSection::Pinned, pinned, count));
+    }
+    if !is_collapsed {
+        for name in &folder_names {
+            let members: Vec<ChatSession> = filtered
+                .iter()
+                .filter(|s| !s.labels.pinned && s.labels.folder.as_ref() == Some(name))
+                .cloned()
+                .collect();
+            if is_filtering && members.is_empty() {
+                continue;
+            }
+            let count = members.len();
+            let shown = if open_folders.read().contains(name) { members } else { Vec::new() };
+            sections.push((SidebarSection::Folder(name.clone()), shown, count));
+        }
+    }
+    let unfiled: Vec<&ChatSession> = filtered
+        .iter()
+        .filter(|s| !s.labels.pinned && (is_collapsed || s.labels.folder.is_none()))
+        .collect();
+    let now = ChatHistory::format_timestamp().parse::<u64>().unwrap_or(0);
+    let utc_offset = local_utc_offset(now);
+    let remaining = unfiled.len().saturating_sub(visible_count());
+    for session in unfiled.into_iter().take(visible_count()) {
+        let section = SidebarSection::Date(SessionGroup::for_timestamp(&session.timestamp, now, utc_offset));
+        match sections.last_mut() {
+            Some((last, members, count)) if *last == section => {
+                members.push(session.clone());
+                *count += 1;
+            }
+            _ => sections.push((section, vec![session.clone()], 1)),
+        }
+    }
+
+    // Move the dragged session into `folder`, or out of any folder when `None`
+    let mut move_dragged = move |folder: Option<String>| {
+        let Some(session_id) = dragging.take() else { return };
+        if let Some(labels) = edited_labels(&sessions.read(), &session_id, |labels| labels.folder = folder) {
+            on_update_labels.call((session_id, labels));
+        }
+    };
+
+    let mut add_folder = move || {
+        let name = new_folder_input.read().clone().unwrap_or_default().trim().to_string();
+        new_folder_input.set(None);
+        if name.is_empty() {
+            return;
+        }
+        let mut prefs = preferences();
+        if !prefs.folders.contains(&name) {
+            prefs.folders.push(name.clone());
+            on_preferences_change.call(prefs);
+        }
+        if !open_folders.read().contains(&name) {
+            open_folders.write().push(name);
+        }
+    };
+
+    let mut save_tags = move |session_id: String| {
+        let tags = tag_input.read().split(',').map(|t| t.to_string()).collect();
+        tag_editing.set(None);
+        if let Some(labels) = edited_labels(&sessions.read(), &session_id, |labels| labels.tags = tags) {
+            on_update_labels.call((session_id, labels));
+        }
+    };
 
     let toggle_collapse = move |_| {
         let current = *collapsed.read();
@@ -48,14 +235,304 @@ pub fn Sidebar(
                     }
                 }
 
+                // Search box
+                if !is_collapsed {
+                    div {
+                        class: "mb-4",
+                        input {
+                            r#type: "search",
+                            value: "{search_query}",
+                            oninput: move |evt| search_query.set(evt.value()),
+                            onkeydown: move |evt: KeyboardEvent| {
+                                if evt.key() == Key::Escape {
+                                    search_query.set(String::new());
+                                }
+                            },
+                            placeholder: "Search chats...",
+                            class: "w-full px-3 py-2 rounded-lg text-xs bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] focus:outline-none focus:ring-1 focus:ring-[var(--color-primary)]",
+                        }
+                    }
+                }
+
+                // Search results
+                if is_searching {
+                    div {
+                        class: "flex-1 overflow-y-auto",
+                        h3 {
+                            class: "text-xs uppercase text-[var(--color-base-content)]/60 font-semibold px-3 mb-3",
+                            "Search Results"
+                        }
+                        match search_results.read().as_ref() {
+                            Some(Some(hits)) if !hits.is_empty() => rsx! {
+                                div {
+                                    class: "space-y-1",
+                                    for (idx, hit) in hits.iter().enumerate() {
+                                        {
+                                            let session_id = hit.session.id.clone();
+                                            let round = hit.round;
+                                            rsx! {
+                                                button {
+                                                    key: "{idx}",
+                                                    class: "w-full text-left px-2.5 py-2 rounded-lg hover:bg-[var(--color-base-300)]/50 transition-all duration-200",
+                                                    onclick: move |_| {
+                                                        on_open_search_result.call((session_id.clone(), round));
+                                                    },
+                                                    div {
+                                                        class: "text-xs font-medium text-[var(--color-base-content)] truncate",
+                                                        "{hit.session.title}"
+                                                    }
+                                                    div {
+                                                        class: "text-[10px] text-[var(--color-base-content)]/50 mt-0.5 truncate",
+                                                        "{hit.session.mode.name()} • Round {hit.round + 1} • {hit.source}"
+                                                    }
+                                                    div {
+                                                        class: "text-[11px] text-[var(--color-base-content)]/70 mt-1 line-clamp-2 break-words",
+                                                        "{hit.snippet}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Some(_)) => rsx! {
+                                p {
+                                    class: "px-3 text-xs text-[var(--color-base-content)]/60",
+                                    "No matches"
+                                }
+                            },
+                            _ => rsx! {
+                                p {
+                                    class: "px-3 text-xs text-[var(--color-base-content)]/60",
+                                    "Searching..."
+                                }
+                            },
+                        }
+                    }
+                }
+
+                // Trash
+                if is_trash_open {
+                    TrashPanel {
+                        theme,
+                        trash,
+                        on_restore: on_restore_sessions,
+                        on_purge: on_purge_sessions,
+                        on_close: move |_| trash_open.set(false),
+                    }
+                }
+
                 // Chat history section
                 div {
                     class: "flex-1 overflow-y-auto",
+                    class: if is_searching || is_trash_open { "hidden" } else { "" },
+                    onscroll: move |evt| {
+                        let near_bottom = evt.data().scroll_top() + f64::from(evt.data().client_height())
+                            >= f64::from(evt.data().scroll_height()) - 200.0;
+                        if near_bottom && remaining > 0 {
+                            visible_count += PAGE_SIZE;
+                        }
+                    },
 
                     if !is_collapsed {
-                        h3 {
-                            class: "text-xs uppercase text-[var(--color-base-content)]/60 font-semibold px-3 mb-3",
-                            "Recent Chats"
+                        div {
+                            class: "flex items-center justify-between px-3 mb-3",
+                            h3 {
+                                class: "text-xs uppercase text-[var(--color-base-content)]/60 font-semibold",
+                                "Recent Chats"
+                            }
+                            if !sessions.read().is_empty() {
+                                div {
+                                    class: "flex items-center gap-2",
+                                    button {
+                                        class: "text-[10px] uppercase font-semibold hover:text-[var(--color-primary)]",
+                                        class: if is_filtering { "text-[var(--color-primary)]" } else { "text-[var(--color-base-content)]/50" },
+                                        title: "Filter by tag, mode or model",
+                                        onclick: move |_| {
+                                            let current = *filters_open.read();
+                                            filters_open.set(!current);
+                                        },
+                                        "Filter"
+                                    }
+                                    button {
+                                        class: "text-[10px] uppercase font-semibold text-[var(--color-base-content)]/50 hover:text-[var(--color-primary)]",
+                                        title: "New folder",
+                                        onclick: move |_| new_folder_input.set(Some(String::new())),
+                                        "+ Folder"
+                                    }
+                                    button {
+                                        class: "text-[10px] uppercase font-semibold text-[var(--color-base-content)]/50 hover:text-[var(--color-primary)]",
+                                        title: "Select chats to delete",
+                                        onclick: move |_| {
+                                            let current = *selecting.read();
+                                            selecting.set(!current);
+                                            selected_sessions.set(Vec::new());
+                                        },
+                                        if is_selecting { "Done" } else { "Select" }
+                                    }
+                                    button {
+                                        class: "text-[10px] uppercase font-semibold text-[var(--color-base-content)]/50 hover:text-[var(--color-primary)]",
+                                        title: "Export all chats",
+                                        onclick: move |_| {
+                                            let current = *export_all_open.read();
+                                            export_all_open.set(!current);
+                                        },
+                                        "Export"
+                                    }
+                                }
+                            }
+                        }
+                        if *filters_open.read() {
+                            div {
+                                class: "mx-3 mb-3 space-y-2 rounded-lg border border-[var(--color-base-300)] bg-[var(--color-base-100)] p-2",
+                                select {
+                                    class: "w-full px-2 py-1 rounded text-xs bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)]",
+                                    value: prefs.filter_mode.map(|m| m.name()).unwrap_or(""),
+                                    onchange: move |evt| {
+                                        let mut prefs = preferences();
+                                        prefs.filter_mode = ChatMode::all().into_iter().find(|m| m.name() == evt.value());
+                                        on_preferences_change.call(prefs);
+                                    },
+                                    option { value: "", "All modes" }
+                                    for mode in ChatMode::all() {
+                                        option { key: "{mode.name()}", value: "{mode.name()}", "{mode.name()}" }
+                                    }
+                                }
+                                select {
+                                    class: "w-full px-2 py-1 rounded text-xs bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)]",
+                                    value: prefs.filter_model.clone().unwrap_or_default(),
+                                    onchange: move |evt| {
+                                        let mut prefs = preferences();
+                                        let model = evt.value();
+                                        prefs.filter_model = if model.is_empty() { None } else { Some(model) };
+                                        on_preferences_change.call(prefs);
+                                    },
+                                    option { value: "", "All models" }
+                                    for model in all_models.iter() {
+                                        option { key: "{model}", value: "{model}", "{model}" }
+                                    }
+                                }
+                                if !all_tags.is_empty() {
+                                    div {
+                                        class: "flex flex-wrap gap-1",
+                                        for tag in all_tags.iter() {
+                                            {
+                                                let active = prefs.filter_tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+                                                let tag_for_toggle = tag.clone();
+                                                rsx! {
+                                                    button {
+                                                        key: "{tag}",
+                                                        class: "px-1.5 py-0.5 rounded text-[10px] border",
+                                                        class: if active {
+                                                            "border-[var(--color-primary)] bg-[var(--color-primary)]/10 text-[var(--color-primary)]"
+                                                        } else {
+                                                            "border-[var(--color-base-300)] text-[var(--color-base-content)]/70"
+                                                        },
+                                                        onclick: move |_| {
+                                                            let mut prefs = preferences();
+                                                            if active {
+                                                                prefs.filter_tags.retain(|t| !t.eq_ignore_ascii_case(&tag_for_toggle));
+                                                            } else {
+                                                                prefs.filter_tags.push(tag_for_toggle.clone());
+                                                            }
+                                                            on_preferences_change.call(prefs);
+                                                        },
+                                                        "#{tag}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                if is_filtering {
+                                    button {
+                                        class: "text-[11px] text-[var(--color-base-content)]/70 hover:text-[var(--color-primary)]",
+                                        onclick: move |_| {
+                                            let mut prefs = preferences();
+                                            prefs.filter_tags.clear();
+                                            prefs.filter_mode = None;
+                                            prefs.filter_model = None;
+                                            on_preferences_change.call(prefs);
+                                        },
+                                        "Clear filters"
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(folder_name) = new_folder_input() {
+                            div {
+                                class: "mx-3 mb-3 flex items-center gap-1",
+                                input {
+                                    r#type: "text",
+                                    value: "{folder_name}",
+                                    autofocus: true,
+                                    placeholder: "Folder name",
+                                    oninput: move |evt| new_folder_input.set(Some(evt.value())),
+                                    onkeydown: move |evt: KeyboardEvent| {
+                                        if evt.key() == Key::Enter {
+                                            add_folder();
+                                        } else if evt.key() == Key::Escape {
+                                            new_folder_input.set(None);
+                                        }
+                                    },
+                                    class: "flex-1 min-w-0 px-2 py-1 rounded text-xs bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] focus:outline-none focus:ring-1 focus:ring-[var(--color-primary)]",
+                                }
+                                button {
+                                    class: "px-2 py-1 rounded text-xs bg-[var(--color-primary)] text-[var(--color-primary-content)] disabled:opacity-50",
+                                    disabled: folder_name.trim().is_empty(),
+                                    onclick: move |_| add_folder(),
+                                    "Add"
+                                }
+                            }
+                        }
+                        if is_selecting {
+                            div {
+                                class: "mx-3 mb-3 flex items-center justify-between gap-2",
+                                button {
+                                    class: "text-[11px] text-[var(--color-base-content)]/70 hover:text-[var(--color-primary)]",
+                                    onclick: move |_| {
+                                        let prefs = preferences();
+                                        let all: Vec<String> = sessions
+                                            .read()
+                                            .iter()
+                                            .filter(|s| prefs.matches(s))
+                                            .map(|s| s.id.clone())
+                                            .collect();
+                                        if selected_sessions.read().len() == all.len() {
+                                            selected_sessions.set(Vec::new());
+                                        } else {
+                                            selected_sessions.set(all);
+                                        }
+                                    },
+                                    "Select all"
+                                }
+                                button {
+                                    class: "px-2 py-1 rounded text-[11px] bg-red-600 text-white hover:bg-red-700 disabled:opacity-50",
+                                    disabled: selected_count == 0,
+                                    onclick: move |_| {
+                                        let ids = selected_sessions.take();
+                                        selecting.set(false);
+                                        on_delete_sessions.call(ids);
+                                    },
+                                    "Delete ({selected_count})"
+                                }
+                            }
+                        }
+                        if *export_all_open.read() {
+                            div {
+                                class: "mx-3 mb-3 rounded-lg border border-[var(--color-base-300)] bg-[var(--color-base-100)] p-1",
+                                for format in ExportFormat::all() {
+                                    button {
+                                        key: "{format.extension()}",
+                                        class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                        onclick: move |_| {
+                                            export_all_open.set(false);
+                                            on_export_all.call(format);
+                                        },
+                                        "Export all as {format.label()}"
+                                    }
+                                }
+                            }
                         }
                     }
 
@@ -78,125 +555,456 @@ pub fn Sidebar(
                                 }
                             }
                         } else {
-                            for session in sessions.read().iter() {
-                                {
-                                    let is_active = current_session.read().as_ref() == Some(&session.id);
-                                    let session_id_for_click = session.id.clone();
-                                    let session_id_for_delete = session.id.clone();
-
-                                    rsx! {
-                                        div {
-                                            key: "{session.id}",
-                                            class: "group relative w-full",
-
-                                            button {
-                                                class: "w-full rounded-lg transition-all duration-200",
-                                                class: if is_collapsed {
-                                                    "p-2 flex items-center justify-center"
-                                                } else {
-                                                    "text-left px-2.5 py-2"
-                                                },
-                                                class: if is_active {
-                                                    "bg-[var(--color-primary)]/10 border border border-[var(--color-primary)]"
-                                                } else {
-                                                    "hover:bg-[var(--color-base-300)]/50"
-                                                },
-                                                title: if is_collapsed { "{session.title}" } else { "" },
-                                                onclick: move |_| {
-                                                    let sid = session_id_for_click.clone();
-                                                    on_select_session.call(sid);
+                            if filtered.is_empty() && !is_collapsed {
+                                p {
+                                    class: "px-3 py-4 text-xs text-center text-[var(--color-base-content)]/60",
+                                    "No chats match the filters"
+                                }
+                            }
+                            for (section, section_sessions, count) in sections {
+                                if !is_collapsed {
+                                    match section {
+                                        SidebarSection::Pinned => rsx! {
+                                            h4 {
+                                                class: "px-3 pt-2 pb-1 text-[10px] uppercase tracking-wide text-[var(--color-base-content)]/40 font-semibold",
+                                                "Pinned"
+                                            }
+                                        },
+                                        SidebarSection::Folder(name) => {
+                                            let is_open = open_folders.read().contains(&name);
+                                            let name_for_toggle = name.clone();
+                                            let name_for_drop = name.clone();
+                                            let name_for_remove = name.clone();
+                                            rsx! {
+                                                div {
+                                                    key: "folder-{name}",
+                                                    class: "group/folder flex items-center gap-1 px-3 pt-2 pb-1 rounded hover:bg-[var(--color-base-300)]/30",
+                                                    ondragover: move |evt| evt.prevent_default(),
+                                                    ondrop: move |evt| {
+                                                        evt.prevent_default();
+                                                        move_dragged(Some(name_for_drop.clone()));
+                                                    },
+                                                    button {
+                                                        class: "flex-1 min-w-0 text-left text-[10px] uppercase tracking-wide text-[var(--color-base-content)]/60 font-semibold truncate",
+                                                        title: "Drop chats here to file them",
+                                                        onclick: move |_| {
+                                                            let mut open = open_folders.write();
+                                                            if let Some(pos) = open.iter().position(|f| f == &name_for_toggle) {
+                                                                open.remove(pos);
+                                                            } else {
+                                                                open.push(name_for_toggle.clone());
+                                                            }
+                                                        },
+                                                        if is_open { "▾ {name} ({count})" } else { "▸ {name} ({count})" }
+                                                    }
+                                                    button {
+                                                        class: "text-[10px] text-[var(--color-base-content)]/40 opacity-0 group-hover/folder:opacity-100 hover:text-red-500",
+                                                        title: "Remove folder (chats stay)",
+                                                        onclick: move |_| {
+                                                            let mut prefs = preferences();
+                                                            prefs.folders.retain(|f| f != &name_for_remove);
+                                                            on_preferences_change.call(prefs);
+                                                            let filed: Vec<ChatSession> = sessions
+                                                                .read()
+                                                                .iter()
+                                                                .filter(|s| s.labels.folder.as_ref() == Some(&name_for_remove))
+                                                                .cloned()
+                                                                .collect();
+                                                            for session in filed {
+                                                                let mut labels = session.labels.clone();
+                                                                labels.folder = None;
+                                                                on_update_labels.call((session.id, labels));
+                                                            }
+                                                        },
+                                                        "✕"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        SidebarSection::Date(group) => rsx! {
+                                            h4 {
+                                                key: "{group.label()}",
+                                                class: "px-3 pt-2 pb-1 text-[10px] uppercase tracking-wide text-[var(--color-base-content)]/40 font-semibold",
+                                                ondragover: move |evt| evt.prevent_default(),
+                                                ondrop: move |evt| {
+                                                    evt.prevent_default();
+                                                    move_dragged(None);
                                                 },
+                                                "{group.label()}"
+                                            }
+                                        },
+                                    }
+                                }
+                                for session in section_sessions.iter() {
+                                    {
+                                        let is_active = current_session.read().as_ref() == Some(&session.id);
+                                        let session_id_for_click = session.id.clone();
+                                        let session_id_for_delete = session.id.clone();
+                                        let session_id_for_menu = session.id.clone();
+                                        let menu_open = session_menu_open.read().as_ref() == Some(&session.id);
+                                        let is_renaming = renaming_session.read().as_ref() == Some(&session.id);
+                                        let session_id_for_rename = session.id.clone();
+                                        let session_title_for_rename = session.title.clone();
+                                        let is_selected = selected_sessions.read().contains(&session.id);
+                                        let is_editing_tags = tag_editing.read().as_ref() == Some(&session.id);
+                                        let session_id_for_drag = session.id.clone();
+                                        let session_id_for_tags = session.id.clone();
+                                        let session_id_for_pin = session.id.clone();
+                                        let tags_for_edit = session.labels.tags.join(", ");
+                                        let is_pinned = session.labels.pinned;
+                                        let current_folder = session.labels.folder.clone();
+                                        let details = format!(
+                                            "{}\n{} rounds • ~{} tokens (estimated from text length)",
+                                            session.stats.models.join(", "),
+                                            session.stats.rounds,
+                                            session.stats.approx_tokens
+                                        );
 
-                                                if is_collapsed {
-                                                    // Collapsed view: just the icon
-                                                    div {
-                                                        class: "shrink-0",
-                                                        match session.mode.name() {
-                                                            "Standard" => rsx! {
-                                                                img { src: asset!("/assets/message.svg"), class: "w-4 h-4", alt: "Standard" }
-                                                            },
-                                                            "PvP" => rsx! {
-                                                                img { src: asset!("/assets/pvp.svg"), class: "w-4 h-4", alt: "PvP" }
-                                                            },
-                                                            "Collaborative" => rsx! {
-                                                                img { src: asset!("/assets/collaborative.svg"), class: "w-4 h-4", alt: "Collaborative" }
-                                                            },
-                                                            "Competitive" => rsx! {
-                                                                img { src: asset!("/assets/competitive.svg"), class: "w-4 h-4", alt: "Competitive" }
-                                                            },
-                                                            "LLM's Choice" => rsx! {
-                                                                img { src: asset!("/assets/choice.svg"), class: "w-4 h-4", alt: "Choice" }
-                                                            },
-                                                            _ => rsx! {
-                                                                img { src: asset!("/assets/message.svg"), class: "w-4 h-4", alt: "Chat" }
-                                                            },
+                                        rsx! {
+                                            div {
+                                                key: "{session.id}",
+                                                class: "group relative w-full",
+                                                draggable: !is_collapsed && !is_selecting,
+                                                ondragstart: move |_| dragging.set(Some(session_id_for_drag.clone())),
+                                                ondragend: move |_| dragging.set(None),
+
+                                                button {
+                                                    class: "w-full rounded-lg transition-all duration-200",
+                                                    class: if is_collapsed {
+                                                        "p-2 flex items-center justify-center"
+                                                    } else {
+                                                        "text-left px-2.5 py-2"
+                                                    },
+                                                    class: if is_active {
+                                                        "bg-[var(--color-primary)]/10 border border border-[var(--color-primary)]"
+                                                    } else {
+                                                        "hover:bg-[var(--color-base-300)]/50"
+                                                    },
+                                                    title: if is_collapsed { "{session.title}" } else { "{details}" },
+                                                    onclick: move |_| {
+                                                        let sid = session_id_for_click.clone();
+                                                        if is_selecting {
+                                                            let mut selected = selected_sessions.write();
+                                                            if let Some(pos) = selected.iter().position(|s| s == &sid) {
+                                                                selected.remove(pos);
+                                                            } else {
+                                                                selected.push(sid);
+                                                            }
+                                                        } else {
+                                                            on_select_session.call(sid);
                                                         }
-                                                    }
-                                                } else {
-                                                    // Expanded view: full layout
-                                                    div {
-                                                        class: "flex items-center gap-2.5",
+                                                    },
 
-                                                        // Mode icon
+                                                    if is_collapsed {
+                                                        // Collapsed view: just the icon
                                                         div {
                                                             class: "shrink-0",
                                                             match session.mode.name() {
                                                                 "Standard" => rsx! {
-                                                                    img { src: asset!("/assets/message.svg"), class: "w-3.5 h-3.5", alt: "Standard" }
+                                                                    img { src: asset!("/assets/message.svg"), class: "w-4 h-4", alt: "Standard" }
                                                                 },
                                                                 "PvP" => rsx! {
-                                                                    img { src: asset!("/assets/pvp.svg"), class: "w-3.5 h-3.5", alt: "PvP" }
+                                                                    img { src: asset!("/assets/pvp.svg"), class: "w-4 h-4", alt: "PvP" }
                                                                 },
                                                                 "Collaborative" => rsx! {
-                                                                    img { src: asset!("/assets/collaborative.svg"), class: "w-3.5 h-3.5", alt: "Collaborative" }
+                                                                    img { src: asset!("/assets/collaborative.svg"), class: "w-4 h-4", alt: "Collaborative" }
                                                                 },
                                                                 "Competitive" => rsx! {
-                                                                    img { src: asset!("/assets/competitive.svg"), class: "w-3.5 h-3.5", alt: "Competitive" }
+                                                                    img { src: asset!("/assets/competitive.svg"), class: "w-4 h-4", alt: "Competitive" }
                                                                 },
                                                                 "LLM's Choice" => rsx! {
-                                                                    img { src: asset!("/assets/choice.svg"), class: "w-3.5 h-3.5", alt: "Choice" }
+                                                                    img { src: asset!("/assets/choice.svg"), class: "w-4 h-4", alt: "Choice" }
                                                                 },
                                                                 _ => rsx! {
-                                                                    img { src: asset!("/assets/message.svg"), class: "w-3.5 h-3.5", alt: "Chat" }
+                                                                    img { src: asset!("/assets/message.svg"), class: "w-4 h-4", alt: "Chat" }
                                                                 },
                                                             }
                                                         }
-
-                                                        // Session info
+                                                    } else {
+                                                        // Expanded view: full layout
                                                         div {
-                                                            class: "flex-1 min-w-0",
+                                                            class: "flex items-center gap-2.5",
+
+                                                            if is_selecting {
+                                                                input {
+                                                                    r#type: "checkbox",
+                                                                    class: "pointer-events-none",
+                                                                    checked: is_selected,
+                                                                    tabindex: "-1",
+                                                                }
+                                                            }
+
+                                                            // Mode icon
                                                             div {
-                                                                class: "text-xs font-medium text-[var(--color-base-content)] truncate",
-                                                                "{session.title}"
+                                                                class: "shrink-0",
+                                                                match session.mode.name() {
+                                                                    "Standard" => rsx! {
+                                                                        img { src: asset!("/assets/message.svg"), class: "w-3.5 h-3.5", alt: "Standard" }
+                                                                    },
+                                                                    "PvP" => rsx! {
+                                                                        img { src: asset!("/assets/pvp.svg"), class: "w-3.5 h-3.5", alt: "PvP" }
+                                                                    },
+                                                                    "Collaborative" => rsx! {
+                                                                        img { src: asset!("/assets/collaborative.svg"), class: "w-3.5 h-3.5", alt: "Collaborative" }
+                                                                    },
+                                                                    "Competitive" => rsx! {
+                                                                        img { src: asset!("/assets/competitive.svg"), class: "w-3.5 h-3.5", alt: "Competitive" }
+                                                                    },
+                                                                    "LLM's Choice" => rsx! {
+                                                                        img { src: asset!("/assets/choice.svg"), class: "w-3.5 h-3.5", alt: "Choice" }
+                                                                    },
+                                                                    _ => rsx! {
+                                                                        img { src: asset!("/assets/message.svg"), class: "w-3.5 h-3.5", alt: "Chat" }
+                                                                    },
+                                                                }
                                                             }
+
+                                                            // Session info
                                                             div {
-                                                                class: "text-[10px] text-[var(--color-base-content)]/50 mt-0.5",
-                                                                "{session.mode.name()} • {ChatHistory::format_timestamp_date(&session.timestamp)}"
+                                                                class: "flex-1 min-w-0",
+                                                                div {
+                                                                    class: "text-xs font-medium text-[var(--color-base-content)] truncate",
+                                                                    "{session.title}"
+                                                                }
+                                                                div {
+                                                                    class: "text-[10px] text-[var(--color-base-content)]/50 mt-0.5",
+                                                                    "{session.mode.name()} • {ChatHistory::format_timestamp_date(&session.timestamp)}"
+                                                                }
+                                                                if !session.labels.tags.is_empty() {
+                                                                    div {
+                                                                        class: "text-[10px] text-[var(--color-primary)]/80 mt-0.5 truncate",
+                                                                        for tag in session.labels.tags.iter() {
+                                                                            span { key: "{tag}", class: "mr-1", "#{tag}" }
+                                                                        }
+                                                                    }
+                                                                }
                                                             }
                                                         }
                                                     }
                                                 }
-                                            }
 
-                                            // Delete button — visible on hover, not on active session
-                                            if !is_active && !is_collapsed {
-                                                button {
-                                                    class: "absolute right-2 top-1/2 -translate-y-1/2 p-1 rounded opacity-0 group-hover:opacity-100 transition-opacity text-[var(--color-base-content)]/40 hover:text-red-500 hover:bg-red-500/10",
-                                                    title: "Delete chat",
-                                                    onclick: move |evt: MouseEvent| {
-                                                        evt.stop_propagation();
-                                                        let sid = session_id_for_delete.clone();
-                                                        on_delete_session.call(sid);
-                                                    },
+                                                // Session menu button — visible on hover
+                                                if !is_collapsed && !is_selecting {
+                                                    button {
+                                                        class: "absolute top-1/2 -translate-y-1/2 p-1 rounded opacity-0 group-hover:opacity-100 transition-opacity text-[var(--color-base-content)]/40 hover:text-[var(--color-primary)] hover:bg-[var(--color-base-300)]/50",
+                                                        class: if is_active { "right-2" } else { "right-8" },
+                                                        title: "More actions",
+                                                        onclick: move |evt: MouseEvent| {
+                                                            evt.stop_propagation();
+                                                            if session_menu_open.read().as_ref() == Some(&session_id_for_menu) {
+                                                                session_menu_open.set(None);
+                                                            } else {
+                                                                session_menu_open.set(Some(session_id_for_menu.clone()));
+                                                            }
+                                                        },
 
-                                                    svg {
-                                                        class: "w-3.5 h-3.5",
-                                                        fill: "none",
-                                                        view_box: "0 0 24 24",
-                                                        stroke: "currentColor",
-                                                        stroke_width: "2",
-                                                        path {
-                                                            d: "M19 7l-.867 12.142A2 2 0 0116.138 21H7.862a2 2 0 01-1.995-1.858L5 7m5 4v6m4-6v6m1-10V4a1 1 0 00-1-1h-4a1 1 0 00-1 1v3M4 7h16"
+                                                        svg {
+                                                            class: "w-3.5 h-3.5",
+                                                            fill: "currentColor",
+                                                            view_box: "0 0 24 24",
+                                                            circle { cx: "5", cy: "12", r: "2" }
+                                                            circle { cx: "12", cy: "12", r: "2" }
+                                                            circle { cx: "19", cy: "12", r: "2" }
+                                                        }
+                                                    }
+                                                }
+
+                                                // Delete button — visible on hover, not on active session
+                                                if !is_active && !is_collapsed && !is_selecting {
+                                                    button {
+                                                        class: "absolute right-2 top-1/2 -translate-y-1/2 p-1 rounded opacity-0 group-hover:opacity-100 transition-opacity text-[var(--color-base-content)]/40 hover:text-red-500 hover:bg-red-500/10",
+                                                        title: "Move to trash",
+                                                        onclick: move |evt: MouseEvent| {
+                                                            evt.stop_propagation();
+                                                            let sid = session_id_for_delete.clone();
+                                                            on_delete_sessions.call(vec![sid]);
+                                                        },
+
+                                                        svg {
+                                                            class: "w-3.5 h-3.5",
+                                                            fill: "none",
+                                                            view_box: "0 0 24 24",
+                                                            stroke: "currentColor",
+                                                            stroke_width: "2",
+                                                            path {
+                                                                d: "M19 7l-.867 12.142A2 2 0 0116.138 21H7.862a2 2 0 01-1.995-1.858L5 7m5 4v6m4-6v6m1-10V4a1 1 0 00-1-1h-4a1 1 0 00-1 1v3M4 7h16"
+                                                            }
+                                                        }
+                                                    }
+                                                }
+
+                                                // Inline rename form
+                                                if is_renaming && !is_collapsed {
+                                                    div {
+                                                        class: "mt-1 flex items-center gap-1",
+                                                        input {
+                                                            r#type: "text",
+                                                            value: "{rename_input}",
+                                                            autofocus: true,
+                                                            oninput: move |evt| rename_input.set(evt.value()),
+                                                            onkeydown: {
+                                                                let sid = session_id_for_rename.clone();
+                                                                move |evt: KeyboardEvent| {
+                                                                    if evt.key() == Key::Enter {
+                                                                        let title = rename_input.read().trim().to_string();
+                                                                        if !title.is_empty() {
+                                                                            on_rename_session.call((sid.clone(), title));
+                                                                        }
+                                                                        renaming_session.set(None);
+                                                                    } else if evt.key() == Key::Escape {
+                                                                        renaming_session.set(None);
+                                                                    }
+                                                                }
+                                                            },
+                                                            class: "flex-1 min-w-0 px-2 py-1 rounded text-xs bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] focus:outline-none focus:ring-1 focus:ring-[var(--color-primary)]",
+                                                        }
+                                                        button {
+                                                            class: "px-2 py-1 rounded text-xs bg-[var(--color-primary)] text-[var(--color-primary-content)] disabled:opacity-50",
+                                                            disabled: rename_input.read().trim().is_empty(),
+                                                            onclick: {
+                                                                let sid = session_id_for_rename.clone();
+                                                                move |_| {
+                                                                    let title = rename_input.read().trim().to_string();
+                                                                    on_rename_session.call((sid.clone(), title));
+                                                                    renaming_session.set(None);
+                                                                }
+                                                            },
+                                                            "Save"
+                                                        }
+                                                    }
+                                                }
+
+                                                // Inline tag editor
+                                                if is_editing_tags && !is_collapsed {
+                                                    div {
+                                                        class: "mt-1 flex items-center gap-1",
+                                                        input {
+                                                            r#type: "text",
+                                                            value: "{tag_input}",
+                                                            autofocus: true,
+                                                            placeholder: "tag, another tag",
+                                                            oninput: move |evt| tag_input.set(evt.value()),
+                                                            onkeydown: {
+                                                                let sid = session_id_for_tags.clone();
+                                                                move |evt: KeyboardEvent| {
+                                                                    if evt.key() == Key::Enter {
+                                                                        save_tags(sid.clone());
+                                                                    } else if evt.key() == Key::Escape {
+                                                                        tag_editing.set(None);
+                                                                    }
+                                                                }
+                                                            },
+                                                            class: "flex-1 min-w-0 px-2 py-1 rounded text-xs bg-[var(--color-base-100)] text-[var(--color-base-content)] border border-[var(--color-base-300)] focus:outline-none focus:ring-1 focus:ring-[var(--color-primary)]",
+                                                        }
+                                                        button {
+                                                            class: "px-2 py-1 rounded text-xs bg-[var(--color-primary)] text-[var(--color-primary-content)]",
+                                                            onclick: {
+                                                                let sid = session_id_for_tags.clone();
+                                                                move |_| save_tags(sid.clone())
+                                                            },
+                                                            "Save"
+                                                        }
+                                                    }
+                                                }
+
+                                                // Per-session menu
+                                                if menu_open && !is_collapsed {
+                                                    div {
+                                                        class: "mt-1 rounded-lg border border-[var(--color-base-300)] bg-[var(--color-base-100)] p-1",
+                                                        button {
+                                                            class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                                            onclick: move |_| {
+                                                                session_menu_open.set(None);
+                                                                rename_input.set(session_title_for_rename.clone());
+                                                                renaming_session.set(Some(session_id_for_rename.clone()));
+                                                            },
+                                                            "Rename"
+                                                        }
+                                                        button {
+                                                            class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                                            onclick: move |_| {
+                                                                session_menu_open.set(None);
+                                                                let sid = session_id_for_pin.clone();
+                                                                if let Some(labels) = edited_labels(&sessions.read(), &sid, |labels| labels.pinned = !is_pinned) {
+                                                                    on_update_labels.call((sid, labels));
+                                                                }
+                                                            },
+                                                            if is_pinned { "Unpin" } else { "Pin" }
+                                                        }
+                                                        button {
+                                                            class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                                            onclick: move |_| {
+                                                                session_menu_open.set(None);
+                                                                tag_input.set(tags_for_edit.clone());
+                                                                tag_editing.set(Some(session_id_for_tags.clone()));
+                                                            },
+                                                            "Edit tags…"
+                                                        }
+                                                        for name in folder_names.iter().filter(|f| current_folder.as_ref() != Some(*f)) {
+                                                            {
+                                                                let sid = session.id.clone();
+                                                                let folder = name.clone();
+                                                                rsx! {
+                                                                    button {
+                                                                        key: "move-{name}",
+                                                                        class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50 truncate",
+                                                                        onclick: move |_| {
+                                                                            session_menu_open.set(None);
+                                                                            let folder = folder.clone();
+                                                                            if let Some(labels) = edited_labels(&sessions.read(), &sid, |labels| labels.folder = Some(folder)) {
+                                                                                on_update_labels.call((sid.clone(), labels));
+                                                                            }
+                                                                        },
+                                                                        "Move to {name}"
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                        if current_folder.is_some() {
+                                                            {
+                                                                let sid = session.id.clone();
+                                                                rsx! {
+                                                                    button {
+                                                                        class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                                                        onclick: move |_| {
+                                                                            session_menu_open.set(None);
+                                                                            if let Some(labels) = edited_labels(&sessions.read(), &sid, |labels| labels.folder = None) {
+                                                                                on_update_labels.call((sid.clone(), labels));
+                                                                            }
+                                                                        },
+                                                                        "Remove from folder"
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                        {
+                                                            let sid = session.id.clone();
+                                                            rsx! {
+                                                                button {
+                                                                    class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                                                    onclick: move |_| {
+                                                                        session_menu_open.set(None);
+                                                                        on_fork_session.call(sid.clone());
+                                                                    },
+                                                                    "Fork into mode…"
+                                                                }
+                                                            }
+                                                        }
+                                                        for format in ExportFormat::all() {
+                                                            {
+                                                                let sid = session.id.clone();
+                                                                rsx! {
+                                                                    button {
+                                                                        key: "{format.extension()}",
+                                                                        class: "w-full text-left px-2 py-1.5 rounded text-xs text-[var(--color-base-content)] hover:bg-[var(--color-base-300)]/50",
+                                                                        onclick: move |_| {
+                                                                            session_menu_open.set(None);
+                                                                            on_export_session.call((sid.clone(), format));
+                                                                        },
+                                                                        "Export as {format.label()}"
+                                                                    }
+                                                                }
+                                                            }
                                                         }
                                                     }
                                                 }
@@ -205,14 +1013,50 @@ pub fn Sidebar(
                                     }
                                 }
                             }
+                            if remaining > 0 && !is_collapsed {
+                                button {
+                                    class: "w-full px-3 py-2 text-xs text-[var(--color-base-content)]/60 hover:text-[var(--color-primary)]",
+                                    onclick: move |_| visible_count += PAGE_SIZE,
+                                    "Show more ({remaining})"
+                                }
+                            }
                         }
                     }
                 }
             }
 
-            // Collapse/Expand button at the bottom
+            // Trash toggle and Collapse/Expand button at the bottom
             div {
                 class: "p-4 border-t border-[var(--color-base-300)]",
+                if !is_collapsed {
+                    button {
+                        onclick: move |_| {
+                            let current = *trash_open.read();
+                            trash_open.set(!current);
+                            search_query.set(String::new());
+                        },
+                        class: "w-full flex items-center gap-2 px-3 py-2 mb-2 rounded-lg text-sm text-[var(--color-base-content)]/70 hover:bg-[var(--color-base-300)]/50 transition-all duration-200",
+                        class: if is_trash_open { "bg-[var(--color-base-300)]/50" } else { "" },
+                        title: "Deleted chats",
+                        svg {
+                            class: "w-4 h-4",
+                            fill: "none",
+                            view_box: "0 0 24 24",
+                            stroke: "currentColor",
+                            stroke_width: "2",
+                            path {
+                                d: "M19 7l-.867 12.142A2 2 0 0116.138 21H7.862a2 2 0 01-1.995-1.858L5 7m5 4v6m4-6v6m1-10V4a1 1 0 00-1-1h-4a1 1 0 00-1 1v3M4 7h16"
+                            }
+                        }
+                        span { "Trash" }
+                        if trash_count > 0 {
+                            span {
+                                class: "ml-auto text-xs text-[var(--color-base-content)]/50",
+                                "{trash_count}"
+                            }
+                        }
+                    }
+                }
                 button {
                     onclick: toggle_collapse,
                     class: "w-full flex items-center justify-center bg-[var(--color-base-300)] hover:bg-[var(--color-base-300)]/80 text-[var(--color-base-content)] rounded-lg transition-all duration-200",
@@ -0,0 +1,162 @@
+use crate::components::ConfirmDialog;
+use crate::utils::{ChatHistory, Theme, TrashEntry};
+use dioxus::prelude::*;
+
+/// Sidebar view of deleted chats with restore and permanent delete
+#[component]
+pub fn TrashPanel(
+    theme: Signal<Theme>,
+    trash: Signal<Vec<TrashEntry>>,
+    on_restore: EventHandler<Vec<String>>,
+    on_purge: EventHandler<Vec<String>>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut selected = use_signal(Vec::<String>::new);
+    let mut confirm_open = use_signal(|| false);
+    // Sessions awaiting confirmation before being deleted forever
+    let mut pending_purge = use_signal(Vec::<String>::new);
+
+    // Ignore selections for sessions that have since left the trash
+    let selected_ids: Vec<String> = selected
+        .read()
+        .iter()
+        .filter(|id| trash.read().iter().any(|t| &t.entry.id == *id))
+        .cloned()
+        .collect();
+    let selected_count = selected_ids.len();
+    let selected_for_restore = selected_ids.clone();
+
+    let mut request_purge = move |ids: Vec<String>| {
+        if ids.is_empty() {
+            return;
+        }
+        pending_purge.set(ids);
+        confirm_open.set(true);
+    };
+
+    let purge_count = pending_purge.read().len();
+    let purge_message = if purge_count == 1 {
+        "This chat will be deleted permanently. This cannot be undone.".to_string()
+    } else {
+        format!("{} chats will be deleted permanently. This cannot be undone.", purge_count)
+    };
+
+    rsx! {
+        div {
+            class: "flex-1 overflow-y-auto",
+
+            div {
+                class: "flex items-center justify-between px-3 mb-3",
+                h3 {
+                    class: "text-xs uppercase text-[var(--color-base-content)]/60 font-semibold",
+                    "Trash"
+                }
+                button {
+                    class: "text-[10px] uppercase font-semibold text-[var(--color-base-content)]/50 hover:text-[var(--color-primary)]",
+                    onclick: move |_| on_close.call(()),
+                    "Back"
+                }
+            }
+
+            if trash.read().is_empty() {
+                p {
+                    class: "px-3 text-xs text-[var(--color-base-content)]/60",
+                    "Trash is empty"
+                }
+            } else {
+                div {
+                    class: "flex flex-wrap gap-1 px-3 mb-3",
+                    button {
+                        class: "px-2 py-1 rounded text-[11px] bg-[var(--color-primary)] text-[var(--color-primary-content)] disabled:opacity-50",
+                        disabled: selected_count == 0,
+                        onclick: move |_| {
+                            selected.set(Vec::new());
+                            on_restore.call(selected_for_restore.clone());
+                        },
+                        "Restore ({selected_count})"
+                    }
+                    button {
+                        class: "px-2 py-1 rounded text-[11px] text-red-500 hover:bg-red-500/10 disabled:opacity-50",
+                        disabled: selected_count == 0,
+                        onclick: move |_| request_purge(selected_ids.clone()),
+                        "Delete forever"
+                    }
+                    button {
+                        class: "px-2 py-1 rounded text-[11px] text-red-500 hover:bg-red-500/10",
+                        onclick: move |_| {
+                            let ids = trash.read().iter().map(|t| t.entry.id.clone()).collect();
+                            request_purge(ids);
+                        },
+                        "Empty trash"
+                    }
+                }
+
+                div {
+                    class: "space-y-1",
+                    for trashed in trash.read().iter() {
+                        {
+                            let id = trashed.entry.id.clone();
+                            let is_selected = selected.read().contains(&id);
+                            let id_for_toggle = id.clone();
+                            let id_for_restore = id.clone();
+
+                            rsx! {
+                                div {
+                                    key: "{id}",
+                                    class: "group flex items-center gap-2 px-2.5 py-2 rounded-lg hover:bg-[var(--color-base-300)]/50",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: is_selected,
+                                        onchange: move |_| {
+                                            let mut selected = selected.write();
+                                            if let Some(pos) = selected.iter().position(|s| s == &id_for_toggle) {
+                                                selected.remove(pos);
+                                            } else {
+                                                selected.push(id_for_toggle.clone());
+                                            }
+                                        },
+                                    }
+                                    div {
+                                        class: "flex-1 min-w-0",
+                                        div {
+                                            class: "text-xs font-medium text-[var(--color-base-content)] truncate",
+                                            "{trashed.entry.title}"
+                                        }
+                                        div {
+                                            class: "text-[10px] text-[var(--color-base-content)]/50 mt-0.5",
+                                            "{trashed.entry.mode.name()} • Deleted {ChatHistory::format_timestamp_date(&trashed.deleted_at)}"
+                                        }
+                                    }
+                                    button {
+                                        class: "text-[10px] font-semibold text-[var(--color-primary)] opacity-0 group-hover:opacity-100 hover:underline",
+                                        title: "Restore chat",
+                                        onclick: move |_| on_restore.call(vec![id_for_restore.clone()]),
+                                        "Restore"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            ConfirmDialog {
+                theme,
+                open: confirm_open,
+                title: "Delete forever?".to_string(),
+                message: purge_message,
+                confirm_label: "Delete".to_string(),
+                confirm_danger: true,
+                on_confirm: move |_| {
+                    let ids = pending_purge.take();
+                    selected.write().retain(|id| !ids.contains(id));
+                    on_purge.call(ids);
+                },
+                on_cancel: move |_| {
+                    confirm_open.set(false);
+                    pending_purge.set(Vec::new());
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::utils::{Settings, Theme, Vault};
+use dioxus::prelude::*;
+
+/// Passphrase prompt shown at startup while encrypted chats are locked
+#[component]
+pub fn UnlockScreen(on_unlock: EventHandler<()>) -> Element {
+    let theme = use_signal(|| {
+        Settings::load_without_api_key()
+            .ok()
+            .and_then(|settings| Theme::from_str(&settings.theme))
+            .unwrap_or(Theme::Dracula)
+    });
+    let mut passphrase = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut unlocking = use_signal(|| false);
+
+    let mut submit = move || {
+        let entered = passphrase.read().clone();
+        if entered.is_empty() || unlocking() {
+            return;
+        }
+        unlocking.set(true);
+        error.set(None);
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || Vault::unlock(&entered)).await;
+            unlocking.set(false);
+            match result {
+                Ok(Ok(())) => {
+                    passphrase.set(String::new());
+                    on_unlock.call(());
+                }
+                Ok(Err(e)) => error.set(Some(e)),
+                Err(e) => error.set(Some(format!("Failed to unlock: {}", e))),
+            }
+        });
+    };
+
+    let data_theme = theme.read().data_theme();
+
+    rsx! {
+        div {
+            "data-theme": "{data_theme}",
+            class: "font-inter antialiased bg-[var(--color-base-100)] text-[var(--color-base-content)] h-screen flex items-center justify-center",
+
+            div {
+                class: "w-full max-w-sm bg-[var(--color-base-200)] rounded-lg p-6 border border-[var(--color-base-300)] space-y-4",
+
+                h1 {
+                    class: "text-xl font-semibold text-[var(--color-base-content)]",
+                    "Unlock GTLLM"
+                }
+                p {
+                    class: "text-sm text-[var(--color-base-content)]/70",
+                    "Your chats and API key are encrypted. Enter your passphrase to continue."
+                }
+                input {
+                    r#type: "password",
+                    value: "{passphrase}",
+                    autofocus: true,
+                    disabled: unlocking(),
+                    oninput: move |evt| passphrase.set(evt.value()),
+                    onkeydown: move |evt: KeyboardEvent| {
+                        if evt.key() == Key::Enter {
+                            submit();
+                        }
+                    },
+                    placeholder: "Passphrase",
+                    class: "w-full px-4 py-2 rounded-lg bg-[var(--color-base-100)] text-[var(--color-base-content)] border-2 border-[var(--color-base-300)] focus:outline-none focus:ring-2 focus:ring-[var(--color-primary)] focus:border-transparent",
+                }
+                if let Some(message) = error() {
+                    p {
+                        class: "text-sm text-red-500",
+                        "{message}"
+                    }
+                }
+                button {
+                    onclick: move |_| submit(),
+                    disabled: passphrase.read().is_empty() || unlocking(),
+                    class: "w-full px-4 py-2 rounded-lg bg-[var(--color-primary)] text-[var(--color-primary-content)] hover:bg-[var(--color-primary)]/90 disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium",
+                    if unlocking() { "Unlocking..." } else { "Unlock" }
+                }
+            }
+        }
+    }
+}
@@ -7,14 +7,12 @@ mod components;
 mod utils;
 
 use components::{
-    Choice, Collaborative, Competitive, ConfirmDialog, Header, NewChat, PvP, Settings as SettingsView, Sidebar,
-    Standard, ToastContainer, ToastType, add_toast,
+    Choice, Collaborative, Competitive, ConfirmDialog, ForkDialog, Header, Leaderboard, NewChat, PvP, Settings as SettingsView, Sidebar,
+    Standard, ToastContainer, ToastType, UnlockScreen, add_toast,
 };
 use utils::{
-    ActiveRunRecord, AppView, ArenaMessage, ChatHistory, ChatMode, ChatSession, InputSettings, Message,
-    OpenRouterClient, RunStatus, Settings, SessionData, Theme,
-    StandardHistory, PvPHistory, CollaborativeHistory, CompetitiveHistory, LLMChoiceHistory,
-    ConversationHistory, SystemPrompts, PromptTemplates,
+    ActiveRunRecord, AppDirs, Backup, BackupSchedule, RestoreMode, TitleSettings, AppView, ArenaMessage, ChatExport, ChatFork, ChatHistory, ChatImport, ChatMode, ChatSession, ExportFormat, SessionLabels, SessionStats,
+    InputSettings, Message, OpenRouterClient, RunStatus, Settings, SessionData, SidebarPreferences, Theme, Trash, TrashEntry, Vault, VaultCommand, upsert_session,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -22,6 +20,13 @@ use std::sync::Arc;
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
+    // Move a pre-XDG ~/.gtllm into the config and data directories before anything reads them
+    match AppDirs::migrate_legacy() {
+        Ok(true) => eprintln!("Moved GTLLM data from ~/.gtllm to the XDG config and data directories"),
+        Ok(false) => {}
+        Err(e) => eprintln!("Failed to migrate ~/.gtllm: {}", e),
+    }
+
     #[cfg(feature = "desktop")]
     {
         let config = if cfg!(debug_assertions) {
@@ -41,48 +46,6 @@ fn main() {
     dioxus::launch(App);
 }
 
-/// Create an empty ChatHistory for a given mode, suitable for a draft session.
-fn empty_history_for_mode(mode: ChatMode) -> ChatHistory {
-    match mode {
-        ChatMode::Standard => ChatHistory::Standard(StandardHistory {
-            user_messages: Vec::new(),
-            model_responses: Vec::new(),
-            selected_models: Vec::new(),
-            system_prompt: String::new(),
-            conversation_history: ConversationHistory {
-                single_model: Vec::new(),
-                multi_model: HashMap::new(),
-            },
-        }),
-        ChatMode::PvP => ChatHistory::PvP(PvPHistory {
-            rounds: Vec::new(),
-            bot_models: Vec::new(),
-            moderator_model: None,
-            system_prompts: SystemPrompts {
-                bot: String::new(),
-                moderator: String::new(),
-            },
-        }),
-        ChatMode::Collaborative => ChatHistory::Collaborative(CollaborativeHistory {
-            rounds: Vec::new(),
-            selected_models: Vec::new(),
-            system_prompt: String::new(),
-        }),
-        ChatMode::Competitive => ChatHistory::Competitive(CompetitiveHistory {
-            rounds: Vec::new(),
-            selected_models: Vec::new(),
-            prompt_templates: PromptTemplates {
-                proposal: String::new(),
-                voting: String::new(),
-            },
-        }),
-        ChatMode::LLMChoice => ChatHistory::LLMChoice(LLMChoiceHistory {
-            rounds: Vec::new(),
-            selected_models: Vec::new(),
-        }),
-    }
-}
-
 #[component]
 fn App() -> Element {
     let active_runs = use_signal(HashMap::<String, ActiveRunRecord>::new);
@@ -113,6 +76,9 @@ fn App() -> Element {
                     .collect();
 
                 if active_ids.is_empty() {
+                    if let Err(e) = utils::SessionIndex::flush() {
+                        eprintln!("Failed to save session index: {}", e);
+                    }
                     window_for_close.set_close_behavior(WindowCloseBehaviour::WindowCloses);
                     window_for_close.close();
                     return;
@@ -156,6 +122,9 @@ fn App() -> Element {
                         }
                     }
 
+                    if let Err(e) = utils::SessionIndex::flush() {
+                        eprintln!("Failed to save session index: {}", e);
+                    }
                     window_for_shutdown.set_close_behavior(WindowCloseBehaviour::WindowCloses);
                     window_for_shutdown.close();
                 });
@@ -163,6 +132,30 @@ fn App() -> Element {
         });
     }
 
+    // Encrypted data stays closed until the passphrase is entered
+    let mut unlocked = use_signal(|| !Vault::is_locked());
+    use_context_provider(|| VaultUnlocked(unlocked));
+    if !unlocked() {
+        return rsx! {
+            document::Link { rel: "stylesheet", href: TAILWIND_CSS }
+            UnlockScreen { on_unlock: move |_| unlocked.set(true) }
+        };
+    }
+
+    rsx! {
+        Workspace {}
+    }
+}
+
+/// Whether the workspace is shown; cleared when an interrupted encryption change
+/// leaves the vault locked until the passphrase is entered again
+#[derive(Clone, Copy)]
+struct VaultUnlocked(Signal<bool>);
+
+#[component]
+fn Workspace() -> Element {
+    let active_runs = use_context::<Signal<HashMap<String, ActiveRunRecord>>>();
+
     // Load settings from disk on startup
     let mut app_settings = use_signal(|| {
         Settings::load().unwrap_or_else(|e| {
@@ -212,6 +205,24 @@ fn App() -> Element {
             })
     });
     use_context_provider(|| sessions);
+
+    // Encryption at rest
+    let mut vault_enabled = use_signal(Vault::is_enabled);
+    let mut vault_busy = use_signal(|| false);
+
+    // Deleted sessions; expired ones are purged once at startup
+    let trash = use_signal(|| {
+        Trash::list().unwrap_or_else(|e| {
+            eprintln!("Failed to load trash: {}", e);
+            Vec::new()
+        })
+    });
+    use_hook(|| {
+        let retention_days = app_settings.read().trash_retention_days;
+        spawn(async move {
+            purge_expired_trash(retention_days, trash).await;
+        });
+    });
     let mut current_session = use_signal(|| None::<String>);
 
     // Input settings
@@ -230,6 +241,9 @@ fn App() -> Element {
 
     // Confirmation dialog state (for navigating away during active streaming)
     let mut confirm_dialog_open = use_signal(|| false);
+    let mut fork_dialog_open = use_signal(|| false);
+    // (session id, title, mode, round prompts) of the session being forked
+    let mut fork_source = use_signal(|| None::<(String, String, ChatMode, Vec<String>)>);
     let mut pending_navigation = use_signal(|| None::<String>); // "new_chat" or a session_id
 
     // Check if there are active runs for the current session
@@ -382,7 +396,7 @@ fn App() -> Element {
     };
 
     // Handler for selecting a session
-    let select_session = {
+    let mut select_session = {
         let mut confirm_dialog_open = confirm_dialog_open.clone();
         let mut pending_navigation = pending_navigation.clone();
 
@@ -403,6 +417,25 @@ fn App() -> Element {
         }
     };
 
+    // Handler for opening a search result: navigate to the session, then scroll to the round
+    let open_search_result = move |(session_id, round): (String, usize)| {
+        select_session(session_id);
+        let _ = document::eval(&format!(
+            r#"
+                let attempts = 0;
+                const timer = setInterval(() => {{
+                    const el = document.getElementById("round-{round}");
+                    if (el) {{
+                        el.scrollIntoView({{ behavior: "smooth", block: "start" }});
+                        clearInterval(timer);
+                    }} else if (++attempts > 60) {{
+                        clearInterval(timer);
+                    }}
+                }}, 50);
+            "#
+        ));
+    };
+
     // Handler for creating new chat
     let new_chat = {
         let mut confirm_dialog_open = confirm_dialog_open.clone();
@@ -428,7 +461,7 @@ fn App() -> Element {
     let select_mode = move |mode: ChatMode| {
         let timestamp = ChatHistory::format_timestamp();
         let title = format!("New {} Chat", mode.name());
-        let session_id = ChatHistory::generate_session_id(mode, &timestamp, &title);
+        let session_id = ChatHistory::new_session_id(&timestamp);
 
         // Create an empty draft session and save to disk immediately
         let session = ChatSession {
@@ -436,13 +469,19 @@ fn App() -> Element {
             title: title.clone(),
             mode,
             timestamp: timestamp.clone(),
+            stats: SessionStats::default(),
+            labels: SessionLabels::default(),
         };
-        let history = empty_history_for_mode(mode);
+        let history = ChatHistory::empty(mode);
         let session_data = SessionData {
             session: session.clone(),
             history,
             created_at: timestamp.clone(),
             updated_at: timestamp,
+            forked_from: None,
+            renamed: false,
+            title_generated: false,
+            checkpoint: None,
         };
 
         let sd_for_save = session_data.clone();
@@ -472,16 +511,7 @@ fn App() -> Element {
     // Called by mode components when they save a session.
     // Always updates the session (updates title, etc.) in the sidebar.
     let on_session_saved = move |session: ChatSession| {
-        let mut found = false;
-        sessions.write().iter_mut().for_each(|s| {
-            if s.id == session.id {
-                *s = session.clone();
-                found = true;
-            }
-        });
-        if !found {
-            sessions.write().push(session);
-        }
+        upsert_session(sessions, session);
     };
 
     // Save error callback — surfaces errors as toasts
@@ -489,62 +519,413 @@ fn App() -> Element {
         add_toast(toasts, ToastType::Error, error_msg);
     };
 
-    // Handler for deleting a session
-    let delete_session = {
-        let mut active_runs = active_runs.clone();
-        let mut current_session = current_session.clone();
-        let mut current_view = current_view.clone();
-        let mut messages = messages.clone();
-        let mut arena_messages = arena_messages.clone();
+    // Handler for moving one or more sessions to the trash
+    let delete_sessions = move |session_ids: Vec<String>| {
+        let mut sessions = sessions;
+        let mut active_runs = active_runs;
+        let mut current_session = current_session;
+        let mut current_view = current_view;
+        let mut messages = messages;
+        let mut arena_messages = arena_messages;
 
-        move |session_id: String| {
-            let sid = session_id.clone();
-            let is_current = current_session.read().as_ref() == Some(&sid);
-            let mut toasts = toasts.clone();
-            let mut sessions = sessions.clone();
-            let mut active_runs = active_runs.clone();
-            let mut current_session = current_session.clone();
-            let mut current_view = current_view.clone();
-            let mut messages = messages.clone();
-            let mut arena_messages = arena_messages.clone();
+        spawn(async move {
+            // Cancel any active runs for these sessions
+            let run_ids: Vec<String> = active_runs
+                .read()
+                .iter()
+                .filter(|(_, run)| run.session_id.as_ref().is_some_and(|sid| session_ids.contains(sid)))
+                .map(|(id, _)| id.clone())
+                .collect();
+            for run_id in &run_ids {
+                if let Some(run) = active_runs.write().get_mut(run_id) {
+                    run.request_cancel();
+                    run.task.cancel();
+                }
+                active_runs.write().remove(run_id);
+            }
 
-            spawn(async move {
-                // Cancel any active runs for this session
-                let run_ids: Vec<String> = active_runs
-                    .read()
-                    .iter()
-                    .filter(|(_, run)| run.session_id == Some(sid.clone()))
-                    .map(|(id, _)| id.clone())
-                    .collect();
-                for run_id in &run_ids {
-                    if let Some(run) = active_runs.write().get_mut(run_id) {
-                        run.request_cancel();
-                        run.task.cancel();
+            // Move to the trash on disk
+            let ids_for_delete = session_ids.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let mut deleted = Vec::new();
+                for sid in ids_for_delete {
+                    match ChatHistory::delete_session(&sid) {
+                        Ok(()) => deleted.push(sid),
+                        Err(e) => return (deleted, Some(e)),
                     }
-                    active_runs.write().remove(run_id);
                 }
+                (deleted, None)
+            })
+            .await;
+
+            let (deleted, error) = match result {
+                Ok(outcome) => outcome,
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+            sessions.write().retain(|s| !deleted.contains(&s.id));
+            if current_session.read().as_ref().is_some_and(|sid| deleted.contains(sid)) {
+                current_session.set(None);
+                current_view.set(AppView::NewChat);
+                messages.write().clear();
+                arena_messages.write().clear();
+            }
+            refresh_trash(trash).await;
 
-                // Delete from disk
-                let sid_for_delete = sid.clone();
-                match tokio::task::spawn_blocking(move || ChatHistory::delete_session(&sid_for_delete)).await {
-                    Ok(Ok(_)) => {
-                        sessions.write().retain(|s| s.id != sid);
-                        if is_current {
-                            current_session.set(None);
-                            current_view.set(AppView::NewChat);
-                            messages.write().clear();
-                            arena_messages.write().clear();
+            if let Some(e) = error {
+                add_toast(toasts, ToastType::Error, format!("Failed to delete chat: {}", e));
+            } else if deleted.len() > 1 {
+                add_toast(toasts, ToastType::Info, format!("Moved {} chats to the trash", deleted.len()));
+            } else {
+                add_toast(toasts, ToastType::Info, "Moved chat to the trash".to_string());
+            }
+        });
+    };
+
+    // Handler for restoring sessions from the trash
+    let restore_sessions = move |session_ids: Vec<String>| {
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                let mut restored = Vec::new();
+                for sid in session_ids {
+                    match Trash::restore(&sid) {
+                        Ok(session) => restored.push(session),
+                        Err(e) => return (restored, Some(e)),
+                    }
+                }
+                (restored, None)
+            })
+            .await;
+
+            let (restored, error) = match result {
+                Ok(outcome) => outcome,
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+            let count = restored.len();
+            for session in restored {
+                upsert_session(sessions, session);
+            }
+            refresh_trash(trash).await;
+
+            if let Some(e) = error {
+                add_toast(toasts, ToastType::Error, format!("Failed to restore chat: {}", e));
+            } else if count > 1 {
+                add_toast(toasts, ToastType::Success, format!("Restored {} chats", count));
+            } else if count == 1 {
+                add_toast(toasts, ToastType::Success, "Restored chat".to_string());
+            }
+        });
+    };
+
+    // Handler for permanently deleting sessions from the trash
+    let purge_sessions = move |session_ids: Vec<String>| {
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                session_ids.iter().try_for_each(|sid| Trash::purge(sid))
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to delete chat: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to delete chat: {}", e));
+                }
+            }
+            refresh_trash(trash).await;
+        });
+    };
+
+    // Handler for enabling, re-keying or disabling encryption at rest
+    let mut vault_unlocked = use_context::<VaultUnlocked>();
+    let run_vault_command = move |command: VaultCommand| {
+        if vault_busy() {
+            return;
+        }
+        vault_busy.set(true);
+        let success = match &command {
+            VaultCommand::Enable { .. } => "Chats and API key are now encrypted",
+            VaultCommand::ChangePassphrase { .. } => "Passphrase changed",
+            VaultCommand::Disable { .. } => "Encryption turned off",
+        };
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || Vault::apply(command)).await;
+            vault_busy.set(false);
+            vault_enabled.set(Vault::is_enabled());
+            if Vault::is_locked() {
+                vault_unlocked.0.set(false);
+            }
+            match result {
+                Ok(Ok(())) => add_toast(toasts, ToastType::Success, success.to_string()),
+                Ok(Err(e)) => add_toast(toasts, ToastType::Error, format!("Encryption change failed: {}", e)),
+                Err(e) => add_toast(toasts, ToastType::Error, format!("Encryption change failed: {}", e)),
+            }
+        });
+    };
+
+    // Handler for changing how long deleted chats are kept
+    let change_trash_retention = move |days: u32| {
+        {
+            let mut settings = app_settings.write();
+            settings.trash_retention_days = days;
+            if let Err(e) = settings.save() {
+                eprintln!("Failed to save trash settings: {}", e);
+                return;
+            }
+        }
+        spawn(async move {
+            purge_expired_trash(days, trash).await;
+        });
+    };
+
+    // Sidebar filters and folders, persisted in settings
+    let mut sidebar_preferences = use_signal(|| app_settings.peek().sidebar.clone());
+    let change_sidebar_preferences = move |preferences: SidebarPreferences| {
+        sidebar_preferences.set(preferences.clone());
+        let mut settings = app_settings.write();
+        settings.sidebar = preferences;
+        if let Err(e) = settings.save() {
+            eprintln!("Failed to save sidebar settings: {}", e);
+        }
+    };
+
+    // Scheduled backups, checked every half hour while the app is open
+    let mut backup_busy = use_signal(|| false);
+    use_hook(|| {
+        spawn(async move {
+            loop {
+                let schedule = app_settings.peek().backup.clone();
+                if schedule.enabled && !backup_busy() {
+                    // Shares the busy flag so a manual backup or restore cannot run alongside
+                    backup_busy.set(true);
+                    let result = tokio::task::spawn_blocking(move || Backup::run_scheduled(&schedule)).await;
+                    backup_busy.set(false);
+                    match result {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => eprintln!("Scheduled backup failed: {}", e),
+                        Err(e) => eprintln!("Scheduled backup failed: {}", e),
+                    }
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(30 * 60)).await;
+            }
+        });
+    });
+
+    // Handler for writing a backup right away
+    let backup_now = move |include_api_key: bool| {
+        if backup_busy() {
+            return;
+        }
+        backup_busy.set(true);
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || Backup::create_now(include_api_key)).await;
+            backup_busy.set(false);
+            match result {
+                Ok(Ok(path)) => {
+                    add_toast(toasts, ToastType::Success, format!("Backup saved to {}", path.display()));
+                }
+                Ok(Err(e)) => add_toast(toasts, ToastType::Error, format!("Backup failed: {}", e)),
+                Err(e) => add_toast(toasts, ToastType::Error, format!("Backup failed: {}", e)),
+            }
+        });
+    };
+
+    // Handler for changing the automatic backup schedule
+    let change_backup_schedule = move |schedule: BackupSchedule| {
+        let mut settings = app_settings.write();
+        settings.backup = schedule;
+        if let Err(e) = settings.save() {
+            eprintln!("Failed to save backup settings: {}", e);
+        }
+    };
+
+    // Handler for changing how session titles are generated
+    let change_title_settings = move |titles: TitleSettings| {
+        let mut settings = app_settings.write();
+        settings.titles = titles;
+        if let Err(e) = settings.save() {
+            eprintln!("Failed to save title settings: {}", e);
+        }
+    };
+
+    // Handler for restoring a backup, then reloading everything it may have changed
+    let restore_backup = move |(path, mode): (String, RestoreMode)| {
+        if backup_busy() {
+            return;
+        }
+        backup_busy.set(true);
+        let mut sessions = sessions.clone();
+        spawn(async move {
+            let path = std::path::PathBuf::from(path);
+            let result = tokio::task::spawn_blocking(move || Backup::restore(&path, mode)).await;
+            backup_busy.set(false);
+            match result {
+                Ok(Ok(report)) => {
+                    let toast_type = if report.needs_unlock { ToastType::Info } else { ToastType::Success };
+                    add_toast(toasts, toast_type, report.summary());
+
+                    if report.settings_restored && !report.needs_unlock {
+                        match Settings::load() {
+                            Ok(settings) => {
+                                let client = settings
+                                    .get_api_key()
+                                    .and_then(|key| OpenRouterClient::new(key.to_string()).ok())
+                                    .map(Arc::new);
+                                openrouter_client.set(client);
+                                sidebar_preferences.set(settings.sidebar.clone());
+                                app_settings.set(settings);
+                            }
+                            Err(e) => eprintln!("Failed to reload settings: {}", e),
                         }
                     }
-                    Ok(Err(e)) => {
-                        add_toast(toasts, ToastType::Error, format!("Failed to delete chat: {}", e));
+                    let sessions_result = tokio::task::spawn_blocking(|| ChatHistory::list_sessions()).await;
+                    if let Ok(Ok(new_sessions)) = sessions_result {
+                        sessions.set(new_sessions);
                     }
-                    Err(e) => {
-                        add_toast(toasts, ToastType::Error, format!("Failed to delete chat: {}", e));
+                    refresh_trash(trash).await;
+                }
+                Ok(Err(e)) => add_toast(toasts, ToastType::Error, format!("Restore failed: {}", e)),
+                Err(e) => add_toast(toasts, ToastType::Error, format!("Restore failed: {}", e)),
+            }
+        });
+    };
+
+    // Handler for tagging, filing and pinning a session from the sidebar
+    let update_labels = move |(session_id, labels): (String, SessionLabels)| {
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatHistory::update_labels(&session_id, labels)).await {
+                Ok(Ok(session)) => upsert_session(sessions, session),
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to update chat: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to update chat: {}", e));
+                }
+            }
+        });
+    };
+
+    // Handler for renaming a session from the sidebar menu
+    let rename_session = move |(session_id, title): (String, String)| {
+        let sessions = sessions.clone();
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatHistory::rename_session(&session_id, &title)).await {
+                Ok(Ok(session)) => upsert_session(sessions, session),
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to rename chat: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to rename chat: {}", e));
+                }
+            }
+        });
+    };
+
+    // Handler for exporting a single session from the sidebar menu
+    let export_session = move |(session_id, format): (String, ExportFormat)| {
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatExport::export_session(&session_id, format)).await {
+                Ok(Ok(path)) => {
+                    add_toast(toasts, ToastType::Success, format!("Exported to {}", path.display()));
+                }
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to export chat: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to export chat: {}", e));
+                }
+            }
+        });
+    };
+
+    // Handler for exporting every saved session into one file
+    let export_all_sessions = move |format: ExportFormat| {
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatExport::export_all(format)).await {
+                Ok(Ok(path)) => {
+                    add_toast(toasts, ToastType::Success, format!("Exported to {}", path.display()));
+                }
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to export chats: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to export chats: {}", e));
+                }
+            }
+        });
+    };
+
+    // Handler for opening the fork dialog from the sidebar menu
+    let open_fork_dialog = move |session_id: String| {
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatHistory::load_session(&session_id)).await {
+                Ok(Ok(data)) => {
+                    let rounds = ChatFork::round_prompts(&data.history);
+                    fork_source.set(Some((data.session.id, data.session.title, data.session.mode, rounds)));
+                    fork_dialog_open.set(true);
+                }
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to load chat: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to load chat: {}", e));
+                }
+            }
+        });
+    };
+
+    // Handler for creating the forked session and switching to it
+    let fork_session = move |(round, mode): (usize, ChatMode)| {
+        let Some((session_id, _, _, _)) = fork_source.read().clone() else { return };
+        fork_source.set(None);
+        spawn(async move {
+            match tokio::task::spawn_blocking(move || ChatFork::fork_session(&session_id, round, mode)).await {
+                Ok(Ok(session)) => {
+                    let new_id = session.id.clone();
+                    upsert_session(sessions, session);
+                    add_toast(toasts, ToastType::Success, format!("Forked into {}", mode.name()));
+                    select_session(new_id);
+                }
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to fork chat: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to fork chat: {}", e));
+                }
+            }
+        });
+    };
+
+    // Handler for importing conversations from another tool or a GTLLM bundle
+    let import_conversations = move |path: String| {
+        let mut sessions = sessions.clone();
+        spawn(async move {
+            let path = std::path::PathBuf::from(path);
+            match tokio::task::spawn_blocking(move || ChatImport::import_file(&path)).await {
+                Ok(Ok(report)) => {
+                    for skipped in &report.skipped {
+                        eprintln!("Import skipped {}", skipped);
+                    }
+                    let toast_type = if report.imported.is_empty() {
+                        ToastType::Info
+                    } else {
+                        ToastType::Success
+                    };
+                    add_toast(toasts, toast_type, report.summary());
+
+                    let sessions_result = tokio::task::spawn_blocking(|| ChatHistory::list_sessions()).await;
+                    if let Ok(Ok(new_sessions)) = sessions_result {
+                        sessions.set(new_sessions);
                     }
                 }
-            });
-        }
+                Ok(Err(e)) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to import conversations: {}", e));
+                }
+                Err(e) => {
+                    add_toast(toasts, ToastType::Error, format!("Failed to import conversations: {}", e));
+                }
+            }
+        });
     };
 
     // Confirmation dialog handlers
@@ -574,7 +955,12 @@ fn App() -> Element {
         current_view.set(AppView::Settings);
     };
 
-    // Handler for closing settings
+    // Handler for opening the model leaderboard
+    let open_leaderboard = move |_| {
+        current_view.set(AppView::Leaderboard);
+    };
+
+    // Handler for closing settings or the leaderboard
     let close_settings = move |_| {
         if let Some(session_id) = current_session.read().clone() {
             if let Some(session) = sessions.read().iter().find(|s| s.id == session_id) {
@@ -745,7 +1131,18 @@ fn App() -> Element {
                     collapsed: sidebar_collapsed,
                     on_new_chat: new_chat,
                     on_select_session: select_session,
-                    on_delete_session: delete_session,
+                    on_delete_sessions: delete_sessions,
+                    on_rename_session: rename_session,
+                    on_fork_session: open_fork_dialog,
+                    on_export_session: export_session,
+                    on_export_all: export_all_sessions,
+                    on_open_search_result: open_search_result,
+                    trash,
+                    on_restore_sessions: restore_sessions,
+                    on_purge_sessions: purge_sessions,
+                    on_update_labels: update_labels,
+                    preferences: sidebar_preferences,
+                    on_preferences_change: change_sidebar_preferences,
                 }
 
                 // Main content area
@@ -758,6 +1155,7 @@ fn App() -> Element {
                         on_toggle_mode: toggle_mode,
                         on_theme_change: change_theme,
                         on_settings: open_settings,
+                        on_leaderboard: open_leaderboard,
                         client: openrouter_client,
                     }
 
@@ -836,6 +1234,23 @@ fn App() -> Element {
                                     input_settings,
                                     on_settings_change: change_input_settings,
                                     on_api_key_save: save_api_key,
+                                    on_import: import_conversations,
+                                    on_trash_retention_change: change_trash_retention,
+                                    vault_enabled,
+                                    vault_busy,
+                                    on_vault_command: run_vault_command,
+                                    backup_busy,
+                                    on_backup: backup_now,
+                                    on_backup_schedule_change: change_backup_schedule,
+                                    on_restore: restore_backup,
+                                    on_title_settings_change: change_title_settings,
+                                    on_close: close_settings,
+                                }
+                            },
+                            AppView::Leaderboard => rsx! {
+                                Leaderboard {
+                                    theme,
+                                    sessions,
                                     on_close: close_settings,
                                 }
                             },
@@ -872,6 +1287,19 @@ fn App() -> Element {
                     on_cancel: cancel_navigation,
                 }
 
+                // Fork a session at a chosen round into any mode
+                if let Some((_, title, mode, rounds)) = fork_source.read().clone() {
+                    ForkDialog {
+                        theme,
+                        open: fork_dialog_open,
+                        session_title: title,
+                        source_mode: mode,
+                        rounds,
+                        on_confirm: fork_session,
+                        on_cancel: move |_| fork_source.set(None),
+                    }
+                }
+
                 // Toast notifications
                 ToastContainer {
                     toasts,
@@ -880,3 +1308,23 @@ fn App() -> Element {
         }
     }
 }
+
+/// Reload the trash listing from disk
+async fn refresh_trash(mut trash: Signal<Vec<TrashEntry>>) {
+    match tokio::task::spawn_blocking(Trash::list).await {
+        Ok(Ok(entries)) => trash.set(entries),
+        Ok(Err(e)) => eprintln!("Failed to load trash: {}", e),
+        Err(e) => eprintln!("Failed to load trash: {}", e),
+    }
+}
+
+/// Permanently delete trashed sessions older than the retention period
+async fn purge_expired_trash(retention_days: u32, trash: Signal<Vec<TrashEntry>>) {
+    match tokio::task::spawn_blocking(move || Trash::purge_expired(retention_days)).await {
+        Ok(Ok(0)) => return,
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => eprintln!("Failed to purge trash: {}", e),
+        Err(e) => eprintln!("Failed to purge trash: {}", e),
+    }
+    refresh_trash(trash).await;
+}
@@ -0,0 +1,593 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::session_index::INDEX_FILENAME;
+use crate::utils::{
+    AppDirs, BackupSchedule, ChatHistory, SearchIndex, SessionData, SessionIndex, SessionIndexEntry,
+    Settings, Vault,
+};
+
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const MANIFEST_NAME: &str = "manifest.json";
+const BACKUPS_DIRNAME: &str = "backups";
+const BACKUP_PREFIX: &str = "gtllm-backup-";
+const BACKUP_EXTENSION: &str = ".tar.gz";
+// Marks the backup taken before a replacing restore, which rotation never removes
+const SAFETY_SUFFIX: &str = "-pre-restore";
+// Archive entries: the first two come from the config directory, chats from the data directory
+const SETTINGS_ENTRY: &str = "settings.toml";
+const VAULT_ENTRY: &str = "vault.json";
+const CHATS_PREFIX: &str = "chats/";
+
+/// Lists every file in a backup archive with its size and SHA-256 checksum
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupManifest {
+    pub version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    pub includes_api_key: bool,
+    /// Chat files are sealed by the vault and need its passphrase after restoring
+    pub encrypted: bool,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupFile {
+    /// Path relative to the data directory, `/`-separated
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// How a restore treats data already on this machine
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestoreMode {
+    /// Add sessions that are not here yet and keep everything else
+    Merge,
+    /// Swap settings, sessions and the trash for the backup's copies
+    Replace,
+}
+
+/// Outcome of a restore
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RestoreReport {
+    pub restored: usize,
+    /// Sessions left alone because one with the same ID already exists
+    pub skipped: usize,
+    pub settings_restored: bool,
+    /// The backup brought its own vault; GTLLM must restart and unlock with its passphrase
+    pub needs_unlock: bool,
+    /// Backup of the previous data taken before replacing it
+    pub safety_backup: Option<PathBuf>,
+}
+
+impl RestoreReport {
+    /// Short description suitable for a toast
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Restored {} chat{}",
+            self.restored,
+            if self.restored == 1 { "" } else { "s" }
+        );
+        if self.skipped > 0 {
+            summary.push_str(&format!(", skipped {} already present", self.skipped));
+        }
+        if self.settings_restored {
+            summary.push_str(" and settings");
+        }
+        if self.needs_unlock {
+            summary.push_str(". Restart GTLLM and unlock with the backup's passphrase");
+        }
+        summary
+    }
+}
+
+/// A validated backup: the manifest and the contents of every file it lists
+struct BackupArchive {
+    manifest: BackupManifest,
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+/// Compressed archives of the settings and chats
+pub struct Backup;
+
+impl Backup {
+    pub fn backups_dir() -> Result<PathBuf, String> {
+        Ok(AppDirs::current()?.data.join(BACKUPS_DIRNAME))
+    }
+
+    /// Write a backup to the backups folder and return its path
+    pub fn create_now(include_api_key: bool) -> Result<PathBuf, String> {
+        Self::create_in_folder(include_api_key, "")
+    }
+
+    fn create_in_folder(include_api_key: bool, suffix: &str) -> Result<PathBuf, String> {
+        let dir = Self::backups_dir()?;
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backups directory: {}", e))?;
+        let timestamp = ChatHistory::format_timestamp();
+        let mut path = dir.join(format!("{}{}{}{}", BACKUP_PREFIX, timestamp, suffix, BACKUP_EXTENSION));
+        let mut n = 1;
+        while path.exists() {
+            path = dir.join(format!("{}{}-{}{}{}", BACKUP_PREFIX, timestamp, n, suffix, BACKUP_EXTENSION));
+            n += 1;
+        }
+        Self::create(&path, include_api_key)?;
+        Ok(path)
+    }
+
+    /// Archive settings, sessions, the session index and the trash into `path`
+    pub fn create(path: &Path, include_api_key: bool) -> Result<BackupManifest, String> {
+        let data_dir = AppDirs::current()?.data;
+        let mut files: Vec<(String, PathBuf)> = [
+            (SETTINGS_ENTRY, Settings::settings_path()?),
+            (VAULT_ENTRY, Vault::header_path()?),
+        ]
+        .into_iter()
+        .filter(|(_, path)| path.is_file())
+        .map(|(entry, path)| (entry.to_string(), path))
+        .collect();
+
+        // Write pending index changes, then hold the index lock while the files are listed
+        // and read so no session is half-written while it is copied
+        SessionIndex::flush()?;
+        let entries = SessionIndex::locked(|| {
+            collect_files(&data_dir, &ChatHistory::chats_dir()?, &mut files)?;
+            let mut entries = Vec::new();
+            for (rel_path, full_path) in files {
+                let contents = if rel_path == SETTINGS_ENTRY && !include_api_key {
+                    let mut settings = Settings::read_file()?;
+                    settings.clear_api_key();
+                    toml::to_string_pretty(&settings)
+                        .map_err(|e| format!("Failed to serialize settings: {}", e))?
+                        .into_bytes()
+                } else {
+                    fs::read(&full_path).map_err(|e| format!("Failed to read {}: {}", full_path.display(), e))?
+                };
+                entries.push((rel_path, contents));
+            }
+            Ok(entries)
+        })?;
+
+        let encrypted = Vault::is_enabled();
+        let manifest = write_archive(path, &entries, include_api_key, encrypted)?;
+        Ok(manifest)
+    }
+
+    /// Backups in the backups folder, newest first
+    pub fn list() -> Result<Vec<PathBuf>, String> {
+        let dir = Self::backups_dir()?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read backups directory: {}", e))?;
+        let mut backups: Vec<(u64, PathBuf)> = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?.path();
+            let name = path.file_name().and_then(|s| s.to_str()).unwrap_or_default();
+            if let Some(timestamp) = backup_timestamp(name) {
+                backups.push((timestamp, path));
+            }
+        }
+        backups.sort_by(|a, b| b.cmp(a));
+        Ok(backups.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Delete all but the newest `keep` backups. Returns how many were removed.
+    /// Safety backups taken before a restore are left alone.
+    pub fn rotate(keep: usize) -> Result<usize, String> {
+        let backups = Self::list()?;
+        let mut removed = 0;
+        let rotating = backups
+            .iter()
+            .filter(|path| !path.file_name().and_then(|s| s.to_str()).is_some_and(is_safety_backup));
+        for path in rotating.skip(keep.max(1)) {
+            fs::remove_file(path).map_err(|e| format!("Failed to remove old backup: {}", e))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Take a backup when the schedule is on and the newest one is older than its interval
+    pub fn run_scheduled(schedule: &BackupSchedule) -> Result<Option<PathBuf>, String> {
+        if !schedule.enabled {
+            return Ok(None);
+        }
+        let now = ChatHistory::format_timestamp().parse::<u64>().unwrap_or(0);
+        let newest = Self::list()?
+            .first()
+            .and_then(|path| path.file_name().and_then(|s| s.to_str()).and_then(backup_timestamp));
+        if !schedule.is_due(newest, now) {
+            return Ok(None);
+        }
+        let path = Self::create_now(schedule.include_api_key)?;
+        Self::rotate(schedule.keep)?;
+        Ok(Some(path))
+    }
+
+    /// Restore a backup after validating it. Replacing first backs up the current data.
+    pub fn restore(path: &Path, mode: RestoreMode) -> Result<RestoreReport, String> {
+        let archive = read_archive(path)?;
+        let data_dir = AppDirs::current()?.data;
+        let vault_path = Vault::header_path()?;
+        let vault_entry = VAULT_ENTRY.to_string();
+
+        // Sealed files can be re-sealed when the vault is the same one. A backup with its own
+        // vault can only be adopted wholesale, onto an install without encryption.
+        let current_vault = fs::read(&vault_path).ok();
+        let adopt_vault = match archive.files.get(&vault_entry) {
+            None => false,
+            Some(header) if current_vault.as_ref() == Some(header) => false,
+            Some(_) if current_vault.is_none() && mode == RestoreMode::Replace => true,
+            Some(_) => {
+                return Err(
+                    "This backup was encrypted with a different passphrase. Replace is only possible while encryption is off here."
+                        .to_string(),
+                )
+            }
+        };
+
+        // Decode everything up front so a bad file aborts before anything is touched
+        let vault_header = match archive.files.get(&vault_entry) {
+            Some(header) if adopt_vault => Some(
+                String::from_utf8(header.clone()).map_err(|_| format!("{} is not a text file", VAULT_ENTRY))?,
+            ),
+            _ => None,
+        };
+        let mut text_files: Vec<(String, String)> = Vec::new();
+        for (rel_path, bytes) in &archive.files {
+            if rel_path != SETTINGS_ENTRY && !rel_path.starts_with(CHATS_PREFIX) {
+                continue;
+            }
+            let text = String::from_utf8(bytes.clone())
+                .map_err(|_| format!("{} is not a text file", rel_path))?;
+            let text = if adopt_vault || rel_path == SETTINGS_ENTRY {
+                text
+            } else {
+                Vault::guard().open(text).map_err(|e| format!("{}: {}", rel_path, e))?
+            };
+            text_files.push((rel_path.clone(), text));
+        }
+
+        let mut report = RestoreReport::default();
+        match mode {
+            RestoreMode::Merge => {
+                let chats_dir = ChatHistory::chats_dir()?;
+                fs::create_dir_all(&chats_dir)
+                    .map_err(|e| format!("Failed to create chats directory: {}", e))?;
+
+                SessionIndex::update(|index| {
+                    for (rel_path, text) in &text_files {
+                        let Some(session_id) = session_id_for(rel_path) else { continue };
+                        let path = ChatHistory::session_path(session_id)?;
+                        if path.exists() || index.get(session_id).is_some() {
+                            report.skipped += 1;
+                            continue;
+                        }
+                        let mut data: SessionData = serde_json::from_str(text)
+                            .map_err(|e| format!("Failed to parse {}: {}", rel_path, e))?;
+                        data.session.id = session_id.to_string();
+                        ChatHistory::write_private_file(&path, text)?;
+                        index.upsert(SessionIndexEntry::from_session_data(&data));
+                        report.restored += 1;
+                    }
+                    Ok(())
+                })?;
+            }
+            RestoreMode::Replace => {
+                report.safety_backup = Some(Self::create_in_folder(true, SAFETY_SUFFIX)?);
+                let settings_entry = text_files.iter().find(|(path, _)| path == SETTINGS_ENTRY);
+                let restored_settings = match settings_entry {
+                    Some((_, text)) => Some(restored_settings(text, &archive.manifest, adopt_vault)?),
+                    None => None,
+                };
+
+                let chats_dir = ChatHistory::chats_dir()?;
+                SessionIndex::replace_files(|| {
+                    // Written before the chats are cleared so a failure leaves them as they were
+                    if let Some(header) = &vault_header {
+                        if let Some(parent) = vault_path.parent() {
+                            fs::create_dir_all(parent)
+                                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                        }
+                        ChatHistory::write_file_atomic(&vault_path, header)?;
+                    }
+                    if chats_dir.exists() {
+                        fs::remove_dir_all(&chats_dir)
+                            .map_err(|e| format!("Failed to clear chats directory: {}", e))?;
+                    }
+                    fs::create_dir_all(&chats_dir)
+                        .map_err(|e| format!("Failed to create chats directory: {}", e))?;
+                    for (rel_path, text) in &text_files {
+                        if rel_path == SETTINGS_ENTRY {
+                            continue;
+                        }
+                        let path = data_dir.join(rel_path);
+                        if let Some(parent) = path.parent() {
+                            fs::create_dir_all(parent)
+                                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                        }
+                        if adopt_vault {
+                            ChatHistory::write_file_atomic(&path, text)?;
+                        } else {
+                            ChatHistory::write_private_file(&path, text)?;
+                        }
+                        if session_id_for(rel_path).is_some() {
+                            report.restored += 1;
+                        }
+                    }
+                    Ok(())
+                })?;
+
+                if let Some(settings) = restored_settings {
+                    settings.write_file()?;
+                    report.settings_restored = true;
+                }
+                report.needs_unlock = adopt_vault;
+            }
+        }
+
+        // The search index is rebuilt from the restored sessions on next use
+        if let Err(e) = SearchIndex::clear() {
+            eprintln!("Failed to clear search index: {}", e);
+        }
+        Ok(report)
+    }
+}
+
+/// Settings from a backup in their stored form, keeping this machine's API key when the
+/// backup was made without one
+fn restored_settings(text: &str, manifest: &BackupManifest, adopt_vault: bool) -> Result<Settings, String> {
+    let mut settings: Settings =
+        toml::from_str(text).map_err(|e| format!("Failed to parse backed up settings: {}", e))?;
+    let current_key = Settings::read_file()?.openrouter_api_key;
+    settings.openrouter_api_key = match settings.openrouter_api_key.take() {
+        Some(key) if manifest.includes_api_key && !adopt_vault => {
+            let vault = Vault::guard();
+            Some(vault.seal(&vault.open(key)?)?)
+        }
+        Some(key) if manifest.includes_api_key => Some(key),
+        _ => current_key,
+    };
+    Ok(settings)
+}
+
+/// Session ID of a top-level session file in the chats directory
+fn session_id_for(rel_path: &str) -> Option<&str> {
+    let name = rel_path.strip_prefix(CHATS_PREFIX)?;
+    if name.contains('/') || name == INDEX_FILENAME {
+        return None;
+    }
+    name.strip_suffix(".json")
+}
+
+fn relative_path(base: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(base).ok()?;
+    let parts: Vec<&str> = rel.components().map(|c| c.as_os_str().to_str()).collect::<Option<_>>()?;
+    Some(parts.join("/"))
+}
+
+fn collect_files(base: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?.path();
+        let Some(rel_path) = relative_path(base, &path) else { continue };
+        if path.is_dir() {
+            collect_files(base, &path, files)?;
+        } else if path.is_file() && path.extension().and_then(|s| s.to_str()) != Some("tmp") {
+            files.push((rel_path, path));
+        }
+    }
+    Ok(())
+}
+
+/// Relative, `/`-separated and free of `..` so it cannot escape the data directory
+fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.contains('\\')
+        && path.split('/').all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn is_safety_backup(name: &str) -> bool {
+    name.strip_suffix(BACKUP_EXTENSION)
+        .is_some_and(|stem| stem.ends_with(SAFETY_SUFFIX))
+}
+
+/// Unix timestamp in a backup's file name
+fn backup_timestamp(name: &str) -> Option<u64> {
+    let stem = name.strip_prefix(BACKUP_PREFIX)?.strip_suffix(BACKUP_EXTENSION)?;
+    stem.split('-').next()?.parse().ok()
+}
+
+fn write_archive(
+    path: &Path,
+    entries: &[(String, Vec<u8>)],
+    includes_api_key: bool,
+    encrypted: bool,
+) -> Result<BackupManifest, String> {
+    let manifest = BackupManifest {
+        version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: ChatHistory::format_timestamp(),
+        includes_api_key,
+        encrypted,
+        files: entries
+            .iter()
+            .map(|(rel_path, contents)| BackupFile {
+                path: rel_path.clone(),
+                size: contents.len() as u64,
+                sha256: sha256_hex(contents),
+            })
+            .collect(),
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+
+    let temp_path = path.with_extension("tmp");
+    let file = fs::File::create(&temp_path).map_err(|e| format!("Failed to create backup file: {}", e))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mtime = manifest.created_at.parse::<u64>().unwrap_or(0);
+    for (rel_path, contents) in std::iter::once((MANIFEST_NAME, &manifest_json))
+        .chain(entries.iter().map(|(rel_path, contents)| (rel_path.as_str(), contents)))
+    {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, rel_path, contents.as_slice())
+            .map_err(|e| format!("Failed to write {} to backup: {}", rel_path, e))?;
+    }
+    let encoder = builder.into_inner().map_err(|e| format!("Failed to write backup: {}", e))?;
+    encoder.finish().map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&temp_path, fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to set backup permissions: {}", e))?;
+    }
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to save backup: {}", e))?;
+    Ok(manifest)
+}
+
+/// Read an archive and check it against its manifest: every listed file present with the
+/// right size and checksum, nothing unlisted, and no paths escaping the data directory
+fn read_archive(path: &Path) -> Result<BackupArchive, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut manifest: Option<BackupManifest> = None;
+    let mut files = BTreeMap::new();
+
+    let entries = archive.entries().map_err(|e| format!("Failed to read backup: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Failed to read backup: {}", e))?;
+        if !entry.header().entry_type().is_file() {
+            return Err("Backup contains an entry that is not a regular file".to_string());
+        }
+        let name = entry
+            .path()
+            .map_err(|e| format!("Failed to read backup entry name: {}", e))?
+            .to_str()
+            .map(str::to_string)
+            .ok_or_else(|| "Backup entry name is not valid UTF-8".to_string())?;
+        let mut contents = Vec::new();
+        entry
+            .read_to_end(&mut contents)
+            .map_err(|e| format!("Failed to read {} from backup: {}", name, e))?;
+
+        if name == MANIFEST_NAME {
+            manifest = Some(
+                serde_json::from_slice(&contents)
+                    .map_err(|e| format!("Backup manifest is invalid: {}", e))?,
+            );
+        } else if !is_safe_path(&name) {
+            return Err(format!("Backup contains an unsafe path: {}", name));
+        } else if files.insert(name.clone(), contents).is_some() {
+            return Err(format!("Backup lists {} twice", name));
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| "Not a GTLLM backup: manifest is missing".to_string())?;
+    if manifest.version > BACKUP_FORMAT_VERSION {
+        return Err("This backup was made by a newer version of GTLLM".to_string());
+    }
+    for listed in &manifest.files {
+        let contents = files
+            .get(&listed.path)
+            .ok_or_else(|| format!("Backup is missing {}", listed.path))?;
+        if contents.len() as u64 != listed.size || sha256_hex(contents) != listed.sha256 {
+            return Err(format!("Checksum mismatch for {}", listed.path));
+        }
+    }
+    if files.len() != manifest.files.len() {
+        return Err("Backup contains files not listed in its manifest".to_string());
+    }
+
+    Ok(BackupArchive { manifest, files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_archive(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("gtllm-backup-test-{}-{}.tar.gz", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_archive_round_trip_verifies_checksums() {
+        let path = temp_archive("roundtrip");
+        let entries = vec![
+            ("settings.toml".to_string(), b"theme = \"dracula\"\n".to_vec()),
+            ("chats/abc.json".to_string(), b"{}".to_vec()),
+        ];
+        let manifest = write_archive(&path, &entries, false, false).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(manifest.files[1].sha256, sha256_hex(b"{}"));
+
+        let archive = read_archive(&path).unwrap();
+        assert_eq!(archive.manifest, manifest);
+        assert_eq!(archive.files["chats/abc.json"], b"{}".to_vec());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_tampered_archive_is_rejected() {
+        let path = temp_archive("tampered");
+        let entries = vec![("chats/abc.json".to_string(), b"{}".to_vec())];
+        write_archive(&path, &entries, false, false).unwrap();
+
+        // Rewrite the same manifest with different file contents
+        let mut archive = read_archive(&path).unwrap();
+        archive.files.insert("chats/abc.json".to_string(), b"{\"x\":1}".to_vec());
+        let manifest_json = serde_json::to_vec(&archive.manifest).unwrap();
+        let file = fs::File::create(&path).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        for (name, contents) in [(MANIFEST_NAME.to_string(), manifest_json)].into_iter().chain(archive.files) {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o600);
+            header.set_cksum();
+            builder.append_data(&mut header, name, contents.as_slice()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let error = read_archive(&path).err().unwrap();
+        assert!(error.contains("Checksum mismatch"), "{}", error);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_unsafe_paths_and_backup_names() {
+        assert!(is_safe_path("chats/trash/abc.json"));
+        assert!(!is_safe_path("../settings.toml"));
+        assert!(!is_safe_path("/etc/passwd"));
+        assert!(!is_safe_path("chats//abc.json"));
+
+        assert_eq!(backup_timestamp("gtllm-backup-1700000000.tar.gz"), Some(1700000000));
+        assert_eq!(backup_timestamp("gtllm-backup-1700000000-2.tar.gz"), Some(1700000000));
+        assert_eq!(backup_timestamp("notes.tar.gz"), None);
+        assert_eq!(backup_timestamp("gtllm-backup-1700000000-pre-restore.tar.gz"), Some(1700000000));
+        assert!(is_safety_backup("gtllm-backup-1700000000-1-pre-restore.tar.gz"));
+        assert!(!is_safety_backup("gtllm-backup-1700000000-1.tar.gz"));
+        assert_eq!(session_id_for("chats/abc.json"), Some("abc"));
+        assert_eq!(session_id_for("chats/index.json"), None);
+        assert_eq!(session_id_for("chats/trash/abc.json"), None);
+    }
+}
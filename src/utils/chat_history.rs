@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use crate::utils::{ChatSession, ChatMode};
+use std::path::{Path, PathBuf};
+use crate::utils::{ChatSession, ChatMode, ContextPolicy, DebateFormat, DebateTurn, PriorRound, RunCheckpoint, SearchIndex, SessionIndex, SessionIndexEntry, SessionLabels, Trash, Vault, JudgePanel, JudgingOptions, ResponseTiming, Rubric, TieBreak, Verdict, VotingMethod};
+
+/// System prompt for new Standard sessions
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You are a helpful AI assistant.";
 
 /// Represents the full conversation history for a chat session
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -28,6 +31,28 @@ pub struct StandardHistory {
     pub selected_models: Vec<String>,
     pub system_prompt: String,
     pub conversation_history: ConversationHistory,
+    /// Every turn sent in this session, including edited and regenerated branches
+    #[serde(default)]
+    pub turns: Vec<StandardTurn>,
+    /// Indices into `turns` for the branch currently shown, root first.
+    /// `user_messages` and `model_responses` mirror this branch.
+    #[serde(default)]
+    pub active_path: Vec<usize>,
+    /// Earlier exchanges carried over from the parent session when forked
+    #[serde(default)]
+    pub context: Vec<(String, String)>,
+}
+
+/// One user message and the responses it received. Turns sharing a parent are
+/// alternative branches of the conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StandardTurn {
+    pub parent: Option<usize>,
+    pub user_message: String,
+    pub model_responses: Vec<ModelResponse>,
+    /// System prompt the turn was sent with; unknown for turns saved before it was kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -35,6 +60,9 @@ pub struct ModelResponse {
     pub model_id: String,
     pub content: String,
     pub error_message: Option<String>,
+    /// Latency of the request that produced this, when it was streamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -50,14 +78,97 @@ pub struct PvPHistory {
     pub bot_models: Vec<String>,
     pub moderator_model: Option<String>,
     pub system_prompts: SystemPrompts,
+    /// Earlier exchanges carried over from the parent session when forked
+    #[serde(default)]
+    pub context: Vec<(String, String)>,
+    /// How much of this session's earlier rounds each new round is sent
+    #[serde(default)]
+    pub context_policy: ContextPolicy,
+    /// Criteria the moderator scores each round on
+    #[serde(default)]
+    pub rubric: Rubric,
+    #[serde(default)]
+    pub judging: JudgingOptions,
+    /// Moderators judging each round; `moderator_model` keeps the first for older readers
+    #[serde(default)]
+    pub panel: JudgePanel,
+    /// Rebuttal and closing turns after the opening answers
+    #[serde(default)]
+    pub debate: DebateFormat,
+}
+
+impl PvPHistory {
+    /// The judges of this session, including the lone moderator of sessions saved before panels
+    pub fn judges(&self) -> Vec<String> {
+        if self.panel.judges.is_empty() {
+            self.moderator_model.iter().cloned().collect()
+        } else {
+            self.panel.judges.clone()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ConversationRound {
     pub user_message: String,
+    /// Opening answers
     pub bot1_response: BotResponse,
     pub bot2_response: BotResponse,
+    /// Debate turns after the openings, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub turns: Vec<DebateTurn>,
     pub moderator_judgment: Option<ModeratorResponse>,
+    /// Prompts the round ran with; unknown for rounds saved before they were kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompts: Option<SystemPrompts>,
+}
+
+impl ConversationRound {
+    /// The round as a context policy sees it. The judged winner's answer is the round's
+    /// answer; a tie or an unjudged round keeps both.
+    pub fn prior_round(&self) -> PriorRound {
+        let mut prior = PriorRound::new(self.user_message.clone());
+        let openings: Vec<&BotResponse> = [&self.bot1_response, &self.bot2_response]
+            .into_iter()
+            .filter(|bot| bot.error_message.is_none() && !bot.content.trim().is_empty())
+            .collect();
+        for bot in &openings {
+            prior.section(bot.model_id.clone(), &bot.content);
+        }
+        for turn in &self.turns {
+            for bot in [&turn.bot1_response, &turn.bot2_response] {
+                if bot.error_message.is_none() {
+                    prior.section(format!("{} ({})", bot.model_id, turn.kind.label()), &bot.content);
+                }
+            }
+        }
+        let judgment = self.moderator_judgment.as_ref().filter(|m| m.error_message.is_none());
+        if let Some(judgment) = judgment {
+            if judgment.judges.is_empty() {
+                prior.section("Moderator", &judgment.content);
+            }
+            for judge in judgment.judges.iter().filter(|j| j.error_message.is_none()) {
+                prior.section(format!("Moderator {}", judge.model_id), &judge.content);
+            }
+        }
+
+        let winner = judgment
+            .and_then(|m| m.verdict.as_ref())
+            .and_then(|v| v.winner.as_ref())
+            .and_then(|winner| openings.iter().find(|bot| &bot.model_id == winner));
+        prior.final_answer = match (winner, openings.as_slice()) {
+            (Some(bot), _) => Some(bot.content.clone()),
+            (None, []) => None,
+            (None, [only]) => Some(only.content.clone()),
+            (None, both) => Some(
+                both.iter()
+                    .map(|bot| format!("**{}**:\n{}", bot.model_id, bot.content))
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+            ),
+        };
+        prior
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -65,12 +176,51 @@ pub struct BotResponse {
     pub model_id: String,
     pub content: String,
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ModeratorResponse {
     pub content: String,
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
+    /// Rubric scores and winner, when the moderator answered in the requested format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<Verdict>,
+    /// Bot whose response the moderator read first, as Response A
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shown_first: Option<String>,
+    /// Verdict from judging again with the responses in the opposite order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swapped_verdict: Option<Verdict>,
+    /// The two orders produced different winners
+    #[serde(default)]
+    pub inconsistent: bool,
+    /// Individual verdicts when a panel judged the round; the fields above then hold the
+    /// combined verdict
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub judges: Vec<JudgeResponse>,
+    /// Share of judge pairs that picked the same winner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agreement: Option<f64>,
+}
+
+/// One panel judge's verdict on a PvP round
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JudgeResponse {
+    pub model_id: String,
+    pub content: String,
+    pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<Verdict>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swapped_verdict: Option<Verdict>,
+    #[serde(default)]
+    pub inconsistent: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -79,12 +229,93 @@ pub struct SystemPrompts {
     pub moderator: String,
 }
 
+impl Default for SystemPrompts {
+    fn default() -> Self {
+        Self {
+            bot: "You are a competitive AI assistant in a debate. Provide the best possible answer to demonstrate your capabilities.".to_string(),
+            moderator: "You are an impartial judge evaluating responses from AI models. Be objective, fair, and thorough in your analysis.".to_string(),
+        }
+    }
+}
+
+impl SystemPrompts {
+    /// Fill prompts left blank by older drafts with the defaults
+    pub fn or_defaults(self) -> Self {
+        let defaults = Self::default();
+        Self {
+            bot: non_empty_or(self.bot, defaults.bot),
+            moderator: non_empty_or(self.moderator, defaults.moderator),
+        }
+    }
+
+    /// Prompts edited away from the default, with their labels
+    pub fn customized(&self) -> Vec<(&'static str, String)> {
+        let defaults = Self::default();
+        customized([
+            ("Bot", &self.bot, &defaults.bot),
+            ("Moderator", &self.moderator, &defaults.moderator),
+        ])
+    }
+}
+
 /// Collaborative mode history
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CollaborativeHistory {
     pub rounds: Vec<CollaborativeRound>,
     pub selected_models: Vec<String>,
+    /// Unused; Collaborative mode is driven by `prompt_templates`
+    #[serde(default)]
     pub system_prompt: String,
+    #[serde(default)]
+    pub prompt_templates: CollaborativePrompts,
+    /// Earlier exchanges carried over from the parent session when forked
+    #[serde(default)]
+    pub context: Vec<(String, String)>,
+    /// How much of this session's earlier rounds each new round is sent
+    #[serde(default)]
+    pub context_policy: ContextPolicy,
+}
+
+/// Templates for the three Collaborative phases
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CollaborativePrompts {
+    pub initial_response: String,
+    pub cross_review: String,
+    pub consensus: String,
+}
+
+impl Default for CollaborativePrompts {
+    fn default() -> Self {
+        Self {
+            initial_response: "You are part of a collaborative AI team working together to answer questions. Provide your best answer to this question:\n\n{user_question}".to_string(),
+
+            cross_review: "Review the following responses from other AI models. Provide constructive feedback on their strengths and areas for improvement.\n\nUser Question: {user_question}\n\nOther responses:\n{other_responses}\n\nProvide your analysis:".to_string(),
+
+            consensus: "Based on all the initial responses and reviews below, synthesize a final collaborative answer that combines the best insights from all models.\n\nUser Question: {user_question}\n\nInitial Responses:\n{initial_responses}\n\nReviews:\n{reviews}\n\nSynthesize the best collaborative answer:".to_string(),
+        }
+    }
+}
+
+impl CollaborativePrompts {
+    /// Fill templates left blank with the defaults
+    pub fn or_defaults(self) -> Self {
+        let defaults = Self::default();
+        Self {
+            initial_response: non_empty_or(self.initial_response, defaults.initial_response),
+            cross_review: non_empty_or(self.cross_review, defaults.cross_review),
+            consensus: non_empty_or(self.consensus, defaults.consensus),
+        }
+    }
+
+    /// Templates edited away from the default, with their labels
+    pub fn customized(&self) -> Vec<(&'static str, String)> {
+        let defaults = Self::default();
+        customized([
+            ("Initial response", &self.initial_response, &defaults.initial_response),
+            ("Review", &self.cross_review, &defaults.cross_review),
+            ("Consensus", &self.consensus, &defaults.consensus),
+        ])
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -92,6 +323,9 @@ pub struct CollaborativeRound {
     pub user_message: String,
     pub model_responses: Vec<ModelResponse>,
     pub final_consensus: Option<String>,
+    /// Templates the round ran with; unknown for rounds saved before they were kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_templates: Option<CollaborativePrompts>,
 }
 
 /// Competitive mode history
@@ -100,6 +334,21 @@ pub struct CompetitiveHistory {
     pub rounds: Vec<CompetitiveRound>,
     pub selected_models: Vec<String>,
     pub prompt_templates: PromptTemplates,
+    /// Earlier exchanges carried over from the parent session when forked
+    #[serde(default)]
+    pub context: Vec<(String, String)>,
+    /// How much of this session's earlier rounds each new round is sent
+    #[serde(default)]
+    pub context_policy: ContextPolicy,
+    /// How votes are cast and counted; sessions saved before this used plurality
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    /// How a tied vote is settled
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    /// Model that decides ties under `TieBreak::Chair`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tie_break_chair: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -110,6 +359,32 @@ pub struct CompetitiveRound {
     pub vote_tallies: Vec<VoteTally>,
     pub winners: Vec<String>,
     pub current_phase: String, // "proposal", "voting", "tallying", "complete"
+    /// Method this round's votes were counted with
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    /// Tie-break policy in force when this round was voted on
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    /// Extra phases held to settle a tied vote, in order; `winners` is the final outcome
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runoffs: Vec<RunoffPhase>,
+    /// Templates the round ran with; unknown for rounds saved before they were kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_templates: Option<PromptTemplates>,
+}
+
+/// A tie-break phase of a competitive round: a runoff vote, the chair's decision or the user's pick
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunoffPhase {
+    pub tie_break: TieBreak,
+    /// The tied proposals this phase chose between
+    pub candidates: Vec<String>,
+    /// Runoff votes, or the chair's single decision
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub votes: Vec<ModelVote>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vote_tallies: Vec<VoteTally>,
+    pub winners: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -117,21 +392,36 @@ pub struct ModelProposal {
     pub model_id: String,
     pub content: String,
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ModelVote {
     pub voter_id: String,
+    /// The voter's first choice
     pub voted_for: Option<String>,
+    /// The full ballot for ranked and approval voting, best first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranking: Vec<String>,
     pub raw_response: String,
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VoteTally {
     pub model_id: String,
+    /// Votes, points or approvals depending on the voting method; the last count for instant runoff
     pub vote_count: usize,
     pub voters: Vec<String>,
+    /// Instant runoff count in each round the proposal was still in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub round_counts: Vec<usize>,
+    /// Instant runoff round the proposal was eliminated in, from 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eliminated_in: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -140,18 +430,148 @@ pub struct PromptTemplates {
     pub voting: String,
 }
 
+impl Default for PromptTemplates {
+    fn default() -> Self {
+        Self {
+            proposal: "You are participating in a competitive problem-solving challenge with other AI models. Provide your best solution to this question:\n\n{user_question}".to_string(),
+
+            voting: "You are voting on the best solution among the proposals below. You CANNOT vote for your own response.\n\nUser Question: {user_question}\n\nAll Proposals:\n{all_proposals}\n\nYour Proposal:\n{your_proposal}\n\nVote for the BEST proposal by responding with ONLY the model ID of your choice (e.g., 'anthropic/claude-3.5-sonnet'). Do not vote for yourself.".to_string(),
+        }
+    }
+}
+
+impl PromptTemplates {
+    /// Fill templates left blank by older drafts with the defaults
+    pub fn or_defaults(self) -> Self {
+        let defaults = Self::default();
+        Self {
+            proposal: non_empty_or(self.proposal, defaults.proposal),
+            voting: non_empty_or(self.voting, defaults.voting),
+        }
+    }
+
+    /// Templates edited away from the default, with their labels
+    pub fn customized(&self) -> Vec<(&'static str, String)> {
+        let defaults = Self::default();
+        customized([
+            ("Proposal", &self.proposal, &defaults.proposal),
+            ("Voting", &self.voting, &defaults.voting),
+        ])
+    }
+}
+
 /// LLM Choice mode history
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LLMChoiceHistory {
     pub rounds: Vec<LLMChoiceRound>,
     pub selected_models: Vec<String>,
+    #[serde(default)]
+    pub system_prompts: ChoicePrompts,
+    /// Earlier exchanges carried over from the parent session when forked
+    #[serde(default)]
+    pub context: Vec<(String, String)>,
+    /// How much of this session's earlier rounds each new round is sent
+    #[serde(default)]
+    pub context_policy: ContextPolicy,
+}
+
+/// System prompts for the strategy decision and each strategy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChoicePrompts {
+    pub decision: String,
+    pub collaborative: String,
+    pub competitive: String,
+}
+
+impl Default for ChoicePrompts {
+    fn default() -> Self {
+        Self {
+            decision: "You are part of a team of AI models deciding on the best approach to answer a question. Consider whether collaboration or competition would yield better results.".to_string(),
+            collaborative: "You are part of a collaborative AI team working together to provide the best answer.".to_string(),
+            competitive: "You are in a competitive challenge. Provide your best solution and vote fairly for the best proposal.".to_string(),
+        }
+    }
+}
+
+impl ChoicePrompts {
+    /// Fill prompts left blank with the defaults
+    pub fn or_defaults(self) -> Self {
+        let defaults = Self::default();
+        Self {
+            decision: non_empty_or(self.decision, defaults.decision),
+            collaborative: non_empty_or(self.collaborative, defaults.collaborative),
+            competitive: non_empty_or(self.competitive, defaults.competitive),
+        }
+    }
+
+    /// Prompts edited away from the default, with their labels
+    pub fn customized(&self) -> Vec<(&'static str, String)> {
+        let defaults = Self::default();
+        customized([
+            ("Decision", &self.decision, &defaults.decision),
+            ("Collaborative", &self.collaborative, &defaults.collaborative),
+            ("Competitive", &self.competitive, &defaults.competitive),
+        ])
+    }
+}
+
+fn customized<const N: usize>(prompts: [(&'static str, &String, &String); N]) -> Vec<(&'static str, String)> {
+    prompts
+        .into_iter()
+        .filter(|(_, prompt, default)| prompt != default)
+        .map(|(label, prompt, _)| (label, prompt.clone()))
+        .collect()
+}
+
+fn non_empty_or(value: String, default: String) -> String {
+    if value.trim().is_empty() {
+        default
+    } else {
+        value
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LLMChoiceRound {
     pub user_message: String,
-    pub decision: String, // "collaborate" or "compete"
-    pub content: Option<String>, // The actual response content
+    pub decision: String, // "collaborate", "compete" or "undecided"
+    pub content: Option<String>, // The final answer: the consensus or the winning proposal
+    /// Each model's strategy vote
+    #[serde(default)]
+    pub decisions: Vec<ChoiceDecision>,
+    /// Full record of the strategy that ran; sessions saved before this was kept have neither
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collaborative: Option<ChoiceCollaborativeResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub competitive: Option<ChoiceCompetitiveResult>,
+    /// Prompts the round ran with; unknown for rounds saved before they were kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompts: Option<ChoicePrompts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChoiceDecision {
+    pub model_id: String,
+    pub decision: Option<String>, // "collaborate" or "compete"
+    pub reasoning: String,
+    pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<ResponseTiming>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChoiceCollaborativeResult {
+    pub initial_responses: Vec<ModelResponse>,
+    pub reviews: Vec<ModelResponse>,
+    pub consensus: Option<ModelResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChoiceCompetitiveResult {
+    pub proposals: Vec<ModelProposal>,
+    pub votes: Vec<ModelVote>,
+    pub vote_tallies: Vec<VoteTally>,
+    pub winners: Vec<String>,
 }
 
 /// Complete session data with metadata and history
@@ -161,62 +581,121 @@ pub struct SessionData {
     pub history: ChatHistory,
     pub created_at: String,
     pub updated_at: String,
+    /// Set when this session was forked from another one
+    #[serde(default)]
+    pub forked_from: Option<ForkOrigin>,
+    /// Set once the user renames the session; automatic titles no longer overwrite it.
+    /// Kept here so an index rebuild preserves it.
+    #[serde(default)]
+    pub renamed: bool,
+    /// Set once a model-written title is applied, so later saves keep it. Unlike a
+    /// rename, another generated title may still replace it.
+    #[serde(default)]
+    pub title_generated: bool,
+    /// Unfinished round saved while streaming; cleared when the round is saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<RunCheckpoint>,
 }
 
-impl ChatHistory {
-    /// Get the chats directory path
-    pub fn chats_dir() -> Result<PathBuf, String> {
-        let base_dir = crate::utils::Settings::settings_dir()?;
-        Ok(base_dir.join("chats"))
-    }
+/// The session and round a forked session was branched off from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForkOrigin {
+    pub session_id: String,
+    /// Last round of the parent session carried into the fork (0-based)
+    pub round: usize,
+}
 
-    /// Sanitize a title for use in filename
-    /// Removes invalid filesystem characters and limits length to 100 chars
-    fn sanitize_filename(title: &str) -> String {
-        let mut sanitized = String::with_capacity(title.len().min(100));
-        
-        for ch in title.chars() {
-            // Invalid filesystem characters: / \ : * ? " < > |
-            if matches!(ch, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
-                sanitized.push('_');
-            } else if ch.is_whitespace() {
-                sanitized.push('_');
-            } else {
-                sanitized.push(ch);
-            }
-            
-            // Limit to 100 characters
-            if sanitized.len() >= 100 {
-                break;
-            }
+impl StandardHistory {
+    /// The conversation tree and active branch. Sessions saved before branching
+    /// existed only have the flat message lists, which become a single linear branch.
+    pub fn branch_tree(&self) -> (Vec<StandardTurn>, Vec<usize>) {
+        let path_is_valid = !self.active_path.is_empty()
+            && self.active_path.iter().all(|&idx| idx < self.turns.len());
+        if path_is_valid {
+            return (self.turns.clone(), self.active_path.clone());
         }
-        
-        // Remove trailing underscores
-        sanitized = sanitized.trim_end_matches('_').to_string();
-        
-        // If empty after sanitization, use default
-        if sanitized.is_empty() {
-            "New_Chat".to_string()
-        } else {
-            sanitized
+
+        let turns: Vec<StandardTurn> = self
+            .user_messages
+            .iter()
+            .zip(self.model_responses.iter())
+            .enumerate()
+            .map(|(idx, (user_message, responses))| StandardTurn {
+                parent: idx.checked_sub(1),
+                user_message: user_message.clone(),
+                model_responses: responses.clone(),
+                system_prompt: None,
+            })
+            .collect();
+        let path = (0..turns.len()).collect();
+        (turns, path)
+    }
+}
+
+impl ChatHistory {
+    /// Create an empty history for a given mode, suitable for a draft session
+    pub fn empty(mode: ChatMode) -> ChatHistory {
+        match mode {
+            ChatMode::Standard => ChatHistory::Standard(StandardHistory {
+                user_messages: Vec::new(),
+                model_responses: Vec::new(),
+                selected_models: Vec::new(),
+                system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+                conversation_history: ConversationHistory {
+                    single_model: Vec::new(),
+                    multi_model: HashMap::new(),
+                },
+                turns: Vec::new(),
+                active_path: Vec::new(),
+                context: Vec::new(),
+            }),
+            ChatMode::PvP => ChatHistory::PvP(PvPHistory {
+                rounds: Vec::new(),
+                bot_models: Vec::new(),
+                moderator_model: None,
+                system_prompts: SystemPrompts::default(),
+                context: Vec::new(),
+                rubric: Rubric::default(),
+                judging: JudgingOptions::default(),
+                panel: JudgePanel::default(),
+                debate: DebateFormat::default(),
+                context_policy: ContextPolicy::default(),
+            }),
+            ChatMode::Collaborative => ChatHistory::Collaborative(CollaborativeHistory {
+                rounds: Vec::new(),
+                selected_models: Vec::new(),
+                system_prompt: String::new(),
+                prompt_templates: CollaborativePrompts::default(),
+                context: Vec::new(),
+                context_policy: ContextPolicy::default(),
+            }),
+            ChatMode::Competitive => ChatHistory::Competitive(CompetitiveHistory {
+                rounds: Vec::new(),
+                selected_models: Vec::new(),
+                prompt_templates: PromptTemplates::default(),
+                context: Vec::new(),
+                context_policy: ContextPolicy::default(),
+                voting_method: VotingMethod::default(),
+                tie_break: TieBreak::default(),
+                tie_break_chair: None,
+            }),
+            ChatMode::LLMChoice => ChatHistory::LLMChoice(LLMChoiceHistory {
+                rounds: Vec::new(),
+                selected_models: Vec::new(),
+                system_prompts: ChoicePrompts::default(),
+                context: Vec::new(),
+                context_policy: ContextPolicy::default(),
+            }),
         }
     }
 
-    /// Generate filename from session metadata
-    /// Format: <mode>_<timestamp>_<sanitized-title>.json
-    fn generate_filename(mode: ChatMode, timestamp: &str, title: &str) -> String {
-        let mode_str = match mode {
-            ChatMode::Standard => "standard",
-            ChatMode::PvP => "pvp",
-            ChatMode::Collaborative => "collaborative",
-            ChatMode::Competitive => "competitive",
-            ChatMode::LLMChoice => "llm_choice",
-        };
-        let sanitized_title = Self::sanitize_filename(title);
-        format!("{}_{}_{}.json", mode_str, timestamp, sanitized_title)
+    /// Get the chats directory path
+    pub fn chats_dir() -> Result<PathBuf, String> {
+        let base_dir = crate::utils::AppDirs::current()?.data;
+        Ok(base_dir.join("chats"))
     }
 
-    /// Parse filename to extract metadata
+    /// Parse a legacy `<mode>_<timestamp>_<title>` filename to extract metadata
     /// Returns (mode, timestamp, title) if successful
     fn parse_filename(filename: &str) -> Option<(ChatMode, String, String)> {
         // Remove .json extension if present
@@ -262,67 +741,30 @@ impl ChatHistory {
 
     /// Extract the unix timestamp segment from a session ID.
     pub fn session_timestamp_from_id(session_id: &str) -> Option<String> {
+        if let Some((timestamp, _)) = session_id.split_once('-') {
+            if timestamp.parse::<u64>().is_ok() {
+                return Some(timestamp.to_string());
+            }
+        }
         let filename = format!("{}.json", session_id);
         Self::parse_filename(&filename).map(|(_, timestamp, _)| timestamp)
     }
 
+    /// Returns true if the ID still uses the title-derived legacy naming scheme
+    pub(crate) fn is_legacy_session_id(session_id: &str) -> bool {
+        Self::parse_filename(&format!("{}.json", session_id)).is_some()
+    }
+
     /// Get the path to a specific session file
-    /// session_id is now the full filename (without .json extension)
+    /// session_id is the filename without .json extension
     pub fn session_path(session_id: &str) -> Result<PathBuf, String> {
         Ok(Self::chats_dir()?.join(format!("{}.json", session_id)))
     }
 
-    /// List all saved sessions.
-    /// Reads session metadata from each file to keep title/mode in sync.
-    /// Deduplicates by session ID to prevent duplicate entries.
+    /// List all saved sessions from the metadata index.
     pub fn list_sessions() -> Result<Vec<ChatSession>, String> {
-        let chats_dir = Self::chats_dir()?;
-        
-        if !chats_dir.exists() {
-            return Ok(Vec::new());
-        }
-
-        let mut sessions = Vec::new();
-        let mut seen_ids = std::collections::HashSet::new();
-        let entries = fs::read_dir(&chats_dir)
-            .map_err(|e| format!("Failed to read chats directory: {}", e))?;
-
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-            let path = entry.path();
-            
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
-                if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-                    let session_id = file_name.strip_suffix(".json").unwrap_or(file_name).to_string();
-                    if seen_ids.contains(&session_id) {
-                        continue;
-                    }
-                    seen_ids.insert(session_id.clone());
-
-                    // Optimization: Try to parse metadata from filename first to avoid reading file content
-                    if let Some((mode, timestamp, title)) = Self::parse_filename(file_name) {
-                        sessions.push(ChatSession {
-                            id: session_id,
-                            title,
-                            mode,
-                            timestamp,
-                        });
-                    } else {
-                        // Fallback: Load file only if filename parsing fails
-                        match Self::load_session_file(&path) {
-                            Ok(mut data) => {
-                                data.session.id = session_id.clone();
-                                sessions.push(data.session);
-                            }
-                            Err(_) => {
-                                // Skip invalid files
-                                continue;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let index = SessionIndex::load()?;
+        let mut sessions: Vec<ChatSession> = index.entries().map(|e| e.to_session()).collect();
 
         // Sort by timestamp descending (most recent first)
         sessions.sort_by(|a, b| {
@@ -354,8 +796,8 @@ impl ChatHistory {
     }
 
     /// Load a session from a specific file path
-    fn load_session_file(path: &PathBuf) -> Result<SessionData, String> {
-        let contents = fs::read_to_string(path)
+    pub(crate) fn load_session_file(path: &Path) -> Result<SessionData, String> {
+        let contents = Self::read_private_file(path)
             .map_err(|e| format!("Failed to read session file: {}", e))?;
         
         let session_data: SessionData = serde_json::from_str(&contents)
@@ -364,32 +806,26 @@ impl ChatHistory {
         Ok(session_data)
     }
 
-    /// Save a session to disk using atomic write (temp file + rename).
-    /// Uses the existing session ID as the canonical filename to keep IDs stable.
-    pub fn save_session(session_data: &SessionData) -> Result<Option<String>, String> {
-        let chats_dir = Self::chats_dir()?;
-        
-        // Create directory if it doesn't exist
-        if !chats_dir.exists() {
-            fs::create_dir_all(&chats_dir)
-                .map_err(|e| format!("Failed to create chats directory: {}", e))?;
-        }
+    /// Read a private data file, decrypting it when it was written with the vault enabled.
+    pub(crate) fn read_private_file(path: &Path) -> Result<String, String> {
+        let vault = Vault::guard();
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        vault.open(contents)
+    }
 
-        let session_id = session_data.session.id.clone();
-        let filename = format!("{}.json", session_id);
-        let new_path = chats_dir.join(&filename);
-        let temp_path = new_path.with_extension("tmp");
+    /// Write a private data file atomically, encrypted when the vault is enabled.
+    pub(crate) fn write_private_file(path: &Path, contents: &str) -> Result<(), String> {
+        let vault = Vault::guard();
+        Self::write_file_atomic(path, &vault.seal(contents)?)
+    }
 
-        // Persist a self-consistent session payload.
-        let mut updated_session_data = session_data.clone();
-        updated_session_data.session.id = session_id;
-        
-        let contents = serde_json::to_string_pretty(&updated_session_data)
-            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+    /// Write `contents` to `path` atomically (temp file + rename) with owner-only permissions.
+    pub(crate) fn write_file_atomic(path: &Path, contents: &str) -> Result<(), String> {
+        let temp_path = path.with_extension("tmp");
 
         // Write to temporary file first
         fs::write(&temp_path, contents)
-            .map_err(|e| format!("Failed to write temporary session file: {}", e))?;
+            .map_err(|e| format!("Failed to write temporary file: {}", e))?;
 
         // Set proper permissions on Unix-like systems before rename
         #[cfg(unix)]
@@ -404,29 +840,186 @@ impl ChatHistory {
         }
 
         // Atomic rename (replaces existing file safely)
-        fs::rename(&temp_path, &new_path)
-            .map_err(|e| format!("Failed to rename temporary file to session file: {}", e))?;
+        fs::rename(&temp_path, path)
+            .map_err(|e| format!("Failed to rename temporary file: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Serialize a session and write it to `path` atomically.
+    pub(crate) fn write_session_file(path: &Path, session_data: &SessionData) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(session_data)
+            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+        Self::write_private_file(path, &contents)
+    }
+
+    /// Save a session to disk using atomic write (temp file + rename) and update the index.
+    /// A title set through `rename_session` is kept over the automatically generated one.
+    /// Returns the session metadata as persisted.
+    pub fn save_session(session_data: &SessionData) -> Result<ChatSession, String> {
+        let chats_dir = Self::chats_dir()?;
+        
+        // Create directory if it doesn't exist
+        if !chats_dir.exists() {
+            fs::create_dir_all(&chats_dir)
+                .map_err(|e| format!("Failed to create chats directory: {}", e))?;
+        }
+
+        let saved = SessionIndex::update(|index| {
+            let mut updated_session_data = session_data.clone();
+            let existing = index.get(&session_data.session.id).cloned();
+            if let Some(existing) = &existing {
+                if existing.renamed || existing.title_generated {
+                    updated_session_data.session.title = existing.title.clone();
+                    updated_session_data.renamed = existing.renamed;
+                    updated_session_data.title_generated = existing.title_generated;
+                }
+                updated_session_data.created_at = existing.created_at.clone();
+                updated_session_data.session.labels = existing.labels.clone();
+                if updated_session_data.forked_from.is_none() {
+                    updated_session_data.forked_from = existing.forked_from.clone();
+                }
+            }
 
-        Ok(None)
+            let path = chats_dir.join(format!("{}.json", updated_session_data.session.id));
+            Self::write_session_file(&path, &updated_session_data)?;
+
+            let entry = SessionIndexEntry::from_session_data(&updated_session_data);
+            let session = entry.to_session();
+            index.upsert(entry);
+
+            Ok((updated_session_data, session))
+        })?;
+
+        if let Err(e) = SearchIndex::update_session(&saved.0) {
+            eprintln!("Failed to update search index: {}", e);
+        }
+
+        Ok(saved.1)
     }
 
-    /// Delete a session from disk
+    /// Delete a session by moving it to the trash, where it can be restored until purged
     pub fn delete_session(session_id: &str) -> Result<(), String> {
+        Trash::move_to_trash(session_id)
+    }
+
+    /// Give a session a user-chosen title. The ID and filename stay unchanged.
+    pub fn rename_session(session_id: &str, title: &str) -> Result<ChatSession, String> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err("Title cannot be empty".to_string());
+        }
+
         let path = Self::session_path(session_id)?;
-        
-        if path.exists() {
-            fs::remove_file(&path)
-                .map_err(|e| format!("Failed to delete session file: {}", e))?;
+        SessionIndex::update(|index| {
+            let mut session_data = Self::load_session_file(&path)?;
+            session_data.session.id = session_id.to_string();
+            session_data.session.title = title.to_string();
+            session_data.renamed = true;
+            Self::write_session_file(&path, &session_data)?;
+
+            let mut entry = index
+                .get(session_id)
+                .cloned()
+                .unwrap_or_else(|| SessionIndexEntry::from_session_data(&session_data));
+            entry.title = title.to_string();
+            entry.renamed = true;
+            let session = entry.to_session();
+            index.upsert(entry);
+
+            Ok(session)
+        })
+    }
+
+    /// Change a saved session's history in place, e.g. to keep a prompt edited between
+    /// rounds. Sessions without a file yet are left alone.
+    pub fn update_history(session_id: &str, update: impl FnOnce(&mut ChatHistory)) -> Result<(), String> {
+        let path = Self::session_path(session_id)?;
+        SessionIndex::locked(|| {
+            if !path.exists() {
+                return Ok(());
+            }
+            let mut session_data = Self::load_session_file(&path)?;
+            session_data.session.id = session_id.to_string();
+            update(&mut session_data.history);
+            Self::write_session_file(&path, &session_data)
+        })
+    }
+
+    /// Give a session a model-written title, unless the user has already renamed it.
+    /// The title is kept on later saves without counting as a rename. Returns `None` when skipped.
+    pub fn apply_generated_title(session_id: &str, title: &str) -> Result<Option<ChatSession>, String> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err("Title cannot be empty".to_string());
         }
 
-        Ok(())
+        let path = Self::session_path(session_id)?;
+        SessionIndex::update(|index| {
+            if index.get(session_id).is_some_and(|entry| entry.renamed) {
+                return Ok(None);
+            }
+            let mut session_data = Self::load_session_file(&path)?;
+            if session_data.renamed {
+                return Ok(None);
+            }
+            session_data.session.id = session_id.to_string();
+            session_data.session.title = title.to_string();
+            session_data.title_generated = true;
+            Self::write_session_file(&path, &session_data)?;
+
+            let mut entry = index
+                .get(session_id)
+                .cloned()
+                .unwrap_or_else(|| SessionIndexEntry::from_session_data(&session_data));
+            entry.title = title.to_string();
+            entry.title_generated = true;
+            let session = entry.to_session();
+            index.upsert(entry);
+
+            Ok(Some(session))
+        })
     }
 
-    /// Generate a session ID (filename) from mode, timestamp, and title
-    /// Returns the filename without .json extension
-    pub fn generate_session_id(mode: ChatMode, timestamp: &str, title: &str) -> String {
-        let filename = Self::generate_filename(mode, timestamp, title);
-        filename.strip_suffix(".json").unwrap_or(&filename).to_string()
+    /// Set a session's tags, folder and pinned flag
+    pub fn update_labels(session_id: &str, labels: SessionLabels) -> Result<ChatSession, String> {
+        let labels = labels.normalized();
+        let path = Self::session_path(session_id)?;
+        SessionIndex::update(|index| {
+            let mut session_data = Self::load_session_file(&path)?;
+            session_data.session.id = session_id.to_string();
+            session_data.session.labels = labels.clone();
+            Self::write_session_file(&path, &session_data)?;
+
+            let mut entry = index
+                .get(session_id)
+                .cloned()
+                .unwrap_or_else(|| SessionIndexEntry::from_session_data(&session_data));
+            entry.labels = labels;
+            let session = entry.to_session();
+            index.upsert(entry);
+
+            Ok(session)
+        })
+    }
+
+    /// Generate a new opaque session ID: the creation timestamp plus a random suffix.
+    /// IDs never depend on the title, so sessions can be renamed freely.
+    pub fn new_session_id(timestamp: &str) -> String {
+        use std::collections::hash_map::RandomState;
+        use std::hash::{BuildHasher, Hasher};
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u128(nanos);
+
+        format!("{}-{:012x}", timestamp, hasher.finish() & 0xffff_ffff_ffff)
     }
     
     /// Returns true if the history has at least one user message or round (i.e. real content).
@@ -451,22 +1044,22 @@ impl ChatHistory {
             ChatHistory::LLMChoice(h) => h.rounds.first().map(|r| &r.user_message),
         };
         
-        if let Some(msg) = first_message {
-            // Take first 60 characters, truncate at word boundary if possible
-            let trimmed = msg.trim();
-            if trimmed.len() <= 60 {
-                trimmed.to_string()
-            } else {
-                // Try to find a word boundary
-                let truncated = &trimmed[..60];
-                if let Some(last_space) = truncated.rfind(' ') {
-                    format!("{}...", &trimmed[..last_space])
-                } else {
-                    format!("{}...", truncated)
-                }
-            }
-        } else {
-            "New Chat".to_string()
+        match first_message {
+            Some(msg) => Self::truncate_title(msg, 60),
+            None => "New Chat".to_string(),
+        }
+    }
+
+    /// Shorten to at most `max_chars` characters, at a word boundary if possible
+    pub fn truncate_title(text: &str, max_chars: usize) -> String {
+        let trimmed = text.trim();
+        if trimmed.chars().count() <= max_chars {
+            return trimmed.to_string();
+        }
+        let truncated: String = trimmed.chars().take(max_chars).collect();
+        match truncated.rfind(' ') {
+            Some(last_space) => format!("{}...", truncated[..last_space].trim_end()),
+            None => format!("{}...", truncated),
         }
     }
 
@@ -636,11 +1229,106 @@ mod tests {
         assert!(!timestamp.is_empty());
     }
     
+    #[test]
+    fn test_session_id_is_opaque_and_keeps_timestamp() {
+        let a = ChatHistory::new_session_id("1700000000");
+        let b = ChatHistory::new_session_id("1700000000");
+        assert_ne!(a, b);
+        assert!(!ChatHistory::is_legacy_session_id(&a));
+        assert_eq!(ChatHistory::session_timestamp_from_id(&a).as_deref(), Some("1700000000"));
+        assert_eq!(
+            ChatHistory::session_timestamp_from_id("pvp_1700000000_Old_title").as_deref(),
+            Some("1700000000")
+        );
+        assert!(ChatHistory::is_legacy_session_id("llm_choice_1700000000_Old_title"));
+    }
+
+    #[test]
+    fn test_legacy_standard_history_becomes_linear_branch() {
+        let json = r#"{
+            "user_messages": ["hi", "again"],
+            "model_responses": [
+                [{"model_id": "a/m", "content": "hello", "error_message": null}],
+                [{"model_id": "a/m", "content": "hello again", "error_message": null}]
+            ],
+            "selected_models": ["a/m"],
+            "system_prompt": "sys",
+            "conversation_history": {"single_model": [], "multi_model": {}}
+        }"#;
+        let history: StandardHistory = serde_json::from_str(json).unwrap();
+        assert!(history.turns.is_empty());
+
+        let (turns, path) = history.branch_tree();
+        assert_eq!(path, vec![0, 1]);
+        assert_eq!(turns[0].parent, None);
+        assert_eq!(turns[1].parent, Some(0));
+        assert_eq!(turns[1].user_message, "again");
+    }
+
+    #[test]
+    fn test_legacy_choice_round_loads_without_details() {
+        let json = r#"{"user_message": "q", "decision": "compete", "content": "answer"}"#;
+        let round: LLMChoiceRound = serde_json::from_str(json).unwrap();
+        assert!(round.decisions.is_empty());
+        assert!(round.collaborative.is_none() && round.competitive.is_none());
+
+        let saved = serde_json::to_string(&round).unwrap();
+        assert!(!saved.contains("collaborative"));
+    }
+
+    #[test]
+    fn test_drafts_and_legacy_histories_get_default_prompts() {
+        match ChatHistory::empty(ChatMode::PvP) {
+            ChatHistory::PvP(h) => assert_eq!(h.system_prompts, SystemPrompts::default()),
+            _ => unreachable!(),
+        }
+
+        let blank = SystemPrompts { bot: String::new(), moderator: "Judge strictly".to_string() };
+        let filled = blank.or_defaults();
+        assert_eq!(filled.bot, SystemPrompts::default().bot);
+        assert_eq!(filled.moderator, "Judge strictly");
+
+        let json = r#"{"rounds": [], "selected_models": ["a/m"], "system_prompt": ""}"#;
+        let history: CollaborativeHistory = serde_json::from_str(json).unwrap();
+        assert_eq!(history.prompt_templates, CollaborativePrompts::default());
+    }
+
+    #[test]
+    fn test_rounds_keep_the_prompts_they_ran_with() {
+        let legacy: CollaborativeRound =
+            serde_json::from_str(r#"{"user_message": "Q", "model_responses": [], "final_consensus": null}"#).unwrap();
+        assert_eq!(legacy.prompt_templates, None);
+
+        let prompts = CollaborativePrompts {
+            consensus: "Merge the answers".to_string(),
+            ..CollaborativePrompts::default()
+        };
+        let round = CollaborativeRound { prompt_templates: Some(prompts.clone()), ..legacy };
+        let saved: CollaborativeRound = serde_json::from_str(&serde_json::to_string(&round).unwrap()).unwrap();
+        assert_eq!(saved.prompt_templates, Some(prompts.clone()));
+        assert_eq!(prompts.customized(), vec![("Consensus", "Merge the answers".to_string())]);
+        assert!(CollaborativePrompts::default().customized().is_empty());
+    }
+
     #[test]
     fn test_format_timestamp_display() {
         let now = ChatHistory::format_timestamp();
         let display = ChatHistory::format_timestamp_display(&now);
         assert!(!display.is_empty());
     }
+
+    #[test]
+    fn test_truncate_title_respects_char_boundaries() {
+        assert_eq!(ChatHistory::truncate_title("  short  ", 60), "short");
+
+        // 70 multi-byte characters; the 60th byte falls inside one of them
+        let emoji = "🦀".repeat(70);
+        let title = ChatHistory::truncate_title(&emoji, 60);
+        assert_eq!(title, format!("{}...", "🦀".repeat(60)));
+
+        let words = "héllo wörld ".repeat(10);
+        let title = ChatHistory::truncate_title(&words, 20);
+        assert_eq!(title, "héllo wörld héllo...");
+    }
 }
 
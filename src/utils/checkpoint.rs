@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::utils::{ChatHistory, SessionIndex};
+
+/// Minimum time between checkpoint writes while text is streaming
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Progress of a round that has not finished yet. Saved into the session file while
+/// the round streams so an interrupted run can be resumed after a crash or restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RunCheckpoint {
+    pub user_message: String,
+    /// Mode-specific name of the phase being run
+    pub phase: String,
+    /// Responses that completed, tagged with their phase
+    pub finished: Vec<CheckpointResponse>,
+    /// Text streamed so far by models that had not finished `phase`
+    pub partial: Vec<CheckpointResponse>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckpointResponse {
+    pub phase: String,
+    pub model_id: String,
+    pub content: String,
+    pub error_message: Option<String>,
+}
+
+impl RunCheckpoint {
+    pub fn new(user_message: &str, phase: &str) -> Self {
+        Self {
+            user_message: user_message.to_string(),
+            phase: phase.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// A model's successful response in `phase`. Failed responses are asked again on resume.
+    pub fn succeeded(&self, phase: &str, model_id: &str) -> Option<&CheckpointResponse> {
+        self.finished
+            .iter()
+            .find(|r| r.phase == phase && r.model_id == model_id && r.error_message.is_none())
+    }
+
+    /// Start `phase`, dropping partial text left over from the previous one. Streams
+    /// that run side by side each enter their shared phase, so entering the current
+    /// phase again keeps what they have streamed.
+    pub fn enter_phase(&mut self, phase: &str) {
+        if self.phase != phase {
+            self.phase = phase.to_string();
+            self.partial.clear();
+        }
+    }
+
+    pub fn record_partial(&mut self, model_id: &str, content: &str) {
+        match self.partial.iter_mut().find(|r| r.model_id == model_id) {
+            Some(partial) => partial.content = content.to_string(),
+            None => self.partial.push(CheckpointResponse {
+                phase: self.phase.clone(),
+                model_id: model_id.to_string(),
+                content: content.to_string(),
+                error_message: None,
+            }),
+        }
+    }
+
+    pub fn record_finished(&mut self, model_id: &str, content: &str, error_message: Option<String>) {
+        self.partial.retain(|r| r.model_id != model_id);
+        let phase = self.phase.clone();
+        self.finished.retain(|r| !(r.phase == phase && r.model_id == model_id));
+        self.finished.push(CheckpointResponse {
+            phase,
+            model_id: model_id.to_string(),
+            content: content.to_string(),
+            error_message,
+        });
+    }
+}
+
+impl ChatHistory {
+    /// Store or clear the unfinished round of a session, leaving its saved rounds untouched
+    pub fn save_checkpoint(session_id: &str, checkpoint: Option<RunCheckpoint>) -> Result<(), String> {
+        let path = Self::session_path(session_id)?;
+        SessionIndex::locked(|| {
+            // The draft file is written when the session is created; if it has gone
+            // (deleted mid-run) there is nothing to resume into.
+            if !path.exists() {
+                return Ok(());
+            }
+            let mut session_data = Self::load_session_file(&path)?;
+            session_data.session.id = session_id.to_string();
+            session_data.checkpoint = checkpoint;
+            Self::write_session_file(&path, &session_data)
+        })
+    }
+}
+
+/// Owns a running round's checkpoint and writes it to the session file as it changes.
+/// Partial text is written at most every `CHECKPOINT_INTERVAL`; phase changes and
+/// finished responses are written straight away.
+///
+/// Shared by every stream of a round: the checkpoint is only locked long enough to
+/// update it and take a copy, and the copy is written with the lock released so a
+/// slow disk never holds up the other streams.
+pub struct Checkpointer {
+    session_id: Option<String>,
+    state: Mutex<CheckpointState>,
+    /// Version of the newest copy on disk, so a write that lost a race to a newer
+    /// one does not put older progress back
+    written: Arc<Mutex<u64>>,
+}
+
+struct CheckpointState {
+    checkpoint: RunCheckpoint,
+    last_write: Option<Instant>,
+    version: u64,
+}
+
+impl Checkpointer {
+    pub fn new(session_id: Option<String>, checkpoint: RunCheckpoint) -> Self {
+        Self {
+            session_id,
+            state: Mutex::new(CheckpointState {
+                checkpoint,
+                last_write: None,
+                version: 0,
+            }),
+            written: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// A model's successful response in `phase`, see [`RunCheckpoint::succeeded`]
+    pub fn succeeded(&self, phase: &str, model_id: &str) -> Option<CheckpointResponse> {
+        self.state().checkpoint.succeeded(phase, model_id).cloned()
+    }
+
+    pub async fn enter_phase(&self, phase: &str) {
+        let snapshot = {
+            let mut state = self.state();
+            state.checkpoint.enter_phase(phase);
+            Self::snapshot(&mut state)
+        };
+        self.write(snapshot).await;
+    }
+
+    pub async fn partial(&self, model_id: &str, content: &str) {
+        let snapshot = {
+            let mut state = self.state();
+            state.checkpoint.record_partial(model_id, content);
+            if state
+                .last_write
+                .is_some_and(|last| last.elapsed() < CHECKPOINT_INTERVAL)
+            {
+                return;
+            }
+            Self::snapshot(&mut state)
+        };
+        self.write(snapshot).await;
+    }
+
+    pub async fn finished(&self, model_id: &str, content: &str, error_message: Option<String>) {
+        let snapshot = {
+            let mut state = self.state();
+            state.checkpoint.record_finished(model_id, content, error_message);
+            Self::snapshot(&mut state)
+        };
+        self.write(snapshot).await;
+    }
+
+    fn state(&self) -> MutexGuard<'_, CheckpointState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn snapshot(state: &mut CheckpointState) -> (u64, RunCheckpoint) {
+        state.last_write = Some(Instant::now());
+        state.version += 1;
+        state.checkpoint.updated_at = ChatHistory::format_timestamp();
+        (state.version, state.checkpoint.clone())
+    }
+
+    async fn write(&self, (version, checkpoint): (u64, RunCheckpoint)) {
+        let Some(session_id) = self.session_id.clone() else { return };
+        let written = self.written.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let mut written = written.lock().unwrap_or_else(|e| e.into_inner());
+            if *written >= version {
+                return Ok(());
+            }
+            ChatHistory::save_checkpoint(&session_id, Some(checkpoint))?;
+            *written = version;
+            Ok::<(), String>(())
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("Failed to save checkpoint: {}", e),
+            Err(e) => eprintln!("Failed to save checkpoint: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finishing_a_model_replaces_its_partial_text() {
+        let mut checkpoint = RunCheckpoint::new("Question", "initial");
+        checkpoint.record_partial("a", "Hel");
+        checkpoint.record_partial("a", "Hello");
+        checkpoint.record_partial("b", "Hi");
+        assert_eq!(checkpoint.partial.len(), 2);
+
+        checkpoint.record_finished("a", "Hello there", None);
+        assert_eq!(checkpoint.partial.len(), 1);
+        assert_eq!(checkpoint.succeeded("initial", "a").unwrap().content, "Hello there");
+        assert!(checkpoint.succeeded("initial", "b").is_none());
+
+        checkpoint.record_finished("b", "", Some("timeout".to_string()));
+        assert!(checkpoint.succeeded("initial", "b").is_none());
+    }
+
+    #[test]
+    fn test_phases_keep_finished_responses_apart() {
+        let mut checkpoint = RunCheckpoint::new("Question", "initial");
+        checkpoint.record_finished("a", "answer", None);
+        checkpoint.record_partial("b", "unfinished");
+        checkpoint.enter_phase("initial");
+        assert_eq!(checkpoint.partial.len(), 1);
+        checkpoint.enter_phase("review");
+        checkpoint.record_finished("a", "review", None);
+
+        assert!(checkpoint.partial.is_empty());
+        assert_eq!(checkpoint.finished.len(), 2);
+        assert_eq!(checkpoint.succeeded("initial", "a").unwrap().content, "answer");
+        assert_eq!(checkpoint.succeeded("review", "a").unwrap().content, "review");
+        assert!(checkpoint.succeeded("review", "b").is_none());
+    }
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+/// Most text, in characters, carried into a round from the ones before it (about 24K
+/// tokens), so long sessions stay inside the context window of smaller models
+const CONTEXT_BUDGET_CHARS: usize = 96_000;
+
+/// What a round is sent of the rounds before it in the same session
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum ContextPolicy {
+    /// Each round stands alone
+    #[default]
+    None,
+    /// The outcome of each earlier round: the consensus, winning proposal or winning answer
+    FinalAnswers,
+    /// Everything each earlier round produced, labelled by speaker
+    FullTranscript,
+}
+
+impl ContextPolicy {
+    pub const ALL: [ContextPolicy; 3] = [ContextPolicy::None, ContextPolicy::FinalAnswers, ContextPolicy::FullTranscript];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContextPolicy::None => "No history",
+            ContextPolicy::FinalAnswers => "Final answers",
+            ContextPolicy::FullTranscript => "Full transcript",
+        }
+    }
+
+    /// Stable name for form values
+    pub fn key(&self) -> &'static str {
+        match self {
+            ContextPolicy::None => "none",
+            ContextPolicy::FinalAnswers => "final",
+            ContextPolicy::FullTranscript => "full",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|p| p.key() == key).unwrap_or_default()
+    }
+
+    /// Earlier rounds as (user message, answer) pairs; rounds with nothing to show are
+    /// skipped, and the oldest are dropped once they no longer fit `CONTEXT_BUDGET_CHARS`
+    pub fn exchanges(&self, rounds: &[PriorRound]) -> Vec<(String, String)> {
+        within_budget(self.all_exchanges(rounds), CONTEXT_BUDGET_CHARS)
+    }
+
+    fn all_exchanges(&self, rounds: &[PriorRound]) -> Vec<(String, String)> {
+        if *self == ContextPolicy::None {
+            return Vec::new();
+        }
+        rounds
+            .iter()
+            .filter_map(|round| {
+                let transcript = match self {
+                    ContextPolicy::FullTranscript => round.transcript(),
+                    _ => None,
+                };
+                transcript
+                    .or_else(|| round.final_answer.clone().filter(|a| !a.trim().is_empty()))
+                    .map(|answer| (round.user_message.clone(), answer))
+            })
+            .collect()
+    }
+}
+
+/// The newest exchanges whose text fits in `budget` characters
+fn within_budget(mut exchanges: Vec<(String, String)>, budget: usize) -> Vec<(String, String)> {
+    let mut used = 0;
+    let kept = exchanges
+        .iter()
+        .rev()
+        .take_while(|(user_message, answer)| {
+            used += user_message.chars().count() + answer.chars().count();
+            used <= budget
+        })
+        .count();
+    exchanges.split_off(exchanges.len() - kept)
+}
+
+/// An earlier round reduced to what a context policy can send
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriorRound {
+    pub user_message: String,
+    pub final_answer: Option<String>,
+    /// (speaker, content) in the order they were produced
+    pub sections: Vec<(String, String)>,
+}
+
+impl PriorRound {
+    pub fn new(user_message: impl Into<String>) -> Self {
+        Self {
+            user_message: user_message.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a section, skipping empty content
+    pub fn section(&mut self, speaker: impl Into<String>, content: &str) {
+        if !content.trim().is_empty() {
+            self.sections.push((speaker.into(), content.to_string()));
+        }
+    }
+
+    fn transcript(&self) -> Option<String> {
+        if self.sections.is_empty() {
+            return None;
+        }
+        Some(
+            self.sections
+                .iter()
+                .map(|(speaker, content)| format!("**{}**:\n{}", speaker, content))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounds() -> Vec<PriorRound> {
+        let mut first = PriorRound::new("Write a haiku");
+        first.section("model-a", "Haiku A");
+        first.section("model-b", "  ");
+        first.section("Consensus", "Haiku C");
+        first.final_answer = Some("Haiku C".to_string());
+        // An interrupted round with nothing to carry forward
+        let second = PriorRound::new("Make it rhyme");
+        vec![first, second]
+    }
+
+    #[test]
+    fn test_policies_select_answers_or_transcripts() {
+        let rounds = rounds();
+        assert!(ContextPolicy::None.exchanges(&rounds).is_empty());
+        assert_eq!(
+            ContextPolicy::FinalAnswers.exchanges(&rounds),
+            vec![("Write a haiku".to_string(), "Haiku C".to_string())]
+        );
+        assert_eq!(
+            ContextPolicy::FullTranscript.exchanges(&rounds),
+            vec![(
+                "Write a haiku".to_string(),
+                "**model-a**:\nHaiku A\n\n**Consensus**:\nHaiku C".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_transcript_falls_back_to_final_answer() {
+        let mut round = PriorRound::new("Question");
+        round.final_answer = Some("Answer".to_string());
+        assert_eq!(
+            ContextPolicy::FullTranscript.exchanges(&[round]),
+            vec![("Question".to_string(), "Answer".to_string())]
+        );
+        assert_eq!(ContextPolicy::from_key("full"), ContextPolicy::FullTranscript);
+        assert_eq!(ContextPolicy::from_key("bogus"), ContextPolicy::None);
+    }
+
+    #[test]
+    fn test_oldest_rounds_are_dropped_past_the_budget() {
+        let exchange = |n: usize| (format!("Q{}", n), "a".repeat(8));
+        let exchanges: Vec<_> = (1..=4).map(exchange).collect();
+        assert_eq!(within_budget(exchanges.clone(), 100), exchanges);
+        assert_eq!(within_budget(exchanges.clone(), 25), vec![exchange(3), exchange(4)]);
+        // A round too long to fit on its own is dropped rather than overflowing the window
+        assert!(within_budget(exchanges, 5).is_empty());
+
+        let mut long = PriorRound::new("Long");
+        long.section("model-a", &"x".repeat(CONTEXT_BUDGET_CHARS));
+        let mut short = PriorRound::new("Short");
+        short.section("model-a", "y");
+        let kept = ContextPolicy::FullTranscript.exchanges(&[long, short]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].0, "Short");
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::BotResponse;
+
+/// Turns PvP bots take after their opening answers
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DebateFormat {
+    /// Rebuttal turns; each bot answers the opponent's previous argument
+    pub rebuttals: u32,
+    /// A closing statement from each bot after the rebuttals
+    pub closing: bool,
+}
+
+impl DebateFormat {
+    /// Turns to run after the openings, in order
+    pub fn stages(&self) -> Vec<TurnKind> {
+        let mut stages: Vec<TurnKind> = (1..=self.rebuttals).map(TurnKind::Rebuttal).collect();
+        if self.closing {
+            stages.push(TurnKind::Closing);
+        }
+        stages
+    }
+
+    pub fn is_debate(&self) -> bool {
+        self.rebuttals > 0 || self.closing
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TurnKind {
+    /// Numbered from 1
+    Rebuttal(u32),
+    Closing,
+}
+
+impl TurnKind {
+    pub fn label(&self) -> String {
+        match self {
+            TurnKind::Rebuttal(n) => format!("Rebuttal {}", n),
+            TurnKind::Closing => "Closing Statement".to_string(),
+        }
+    }
+
+    /// Checkpoint phase name for this turn
+    pub fn phase(&self) -> String {
+        match self {
+            TurnKind::Rebuttal(n) => format!("rebuttal_{}", n),
+            TurnKind::Closing => "closing".to_string(),
+        }
+    }
+
+    /// What a bot is asked to write, given its own and its opponent's latest arguments
+    pub fn prompt(&self, question: &str, own: &str, opponent: &str) -> String {
+        let task = match self {
+            TurnKind::Rebuttal(_) => {
+                "Write a rebuttal: point out weaknesses or mistakes in your opponent's argument \
+                and strengthen your own position. Do not simply repeat yourself."
+            }
+            TurnKind::Closing => {
+                "Give a short closing statement: summarize why your answer is the better one, \
+                conceding any points your opponent got right."
+            }
+        };
+        format!(
+            "Debate question: {}\n\n\
+            Your latest argument:\n{}\n\n\
+            Your opponent's latest argument:\n{}\n\n\
+            {}",
+            question, own, opponent, task
+        )
+    }
+}
+
+/// Both bots' answers for one turn after the openings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DebateTurn {
+    pub kind: TurnKind,
+    pub bot1_response: BotResponse,
+    pub bot2_response: BotResponse,
+}
+
+impl DebateTurn {
+    /// Everything one side said, for the moderator. Rounds without turns keep the opening as is.
+    pub fn side_transcript(opening: &str, turns: &[DebateTurn], second_bot: bool) -> String {
+        if turns.is_empty() {
+            return opening.to_string();
+        }
+        let mut transcript = format!("Opening:\n{}", opening);
+        for turn in turns {
+            let response = if second_bot { &turn.bot2_response } else { &turn.bot1_response };
+            transcript.push_str(&format!("\n\n{}:\n{}", turn.kind.label(), response.content));
+        }
+        transcript
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stages_and_transcript() {
+        let format = DebateFormat { rebuttals: 2, closing: true };
+        assert_eq!(format.stages(), vec![TurnKind::Rebuttal(1), TurnKind::Rebuttal(2), TurnKind::Closing]);
+        assert!(!DebateFormat::default().is_debate());
+        assert!(DebateFormat::default().stages().is_empty());
+
+        let bot = |content: &str| BotResponse {
+            model_id: "m".to_string(),
+            content: content.to_string(),
+            error_message: None,
+            timing: None,
+        };
+        let turns = vec![DebateTurn { kind: TurnKind::Rebuttal(1), bot1_response: bot("r1"), bot2_response: bot("r2") }];
+        assert_eq!(DebateTurn::side_transcript("open", &[], false), "open");
+        assert_eq!(DebateTurn::side_transcript("open", &turns, true), "Opening:\nopen\n\nRebuttal 1:\nr2");
+    }
+}
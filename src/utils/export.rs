@@ -0,0 +1,791 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::formatting::ListItem;
+use crate::utils::{
+    parse_inline_elements, parse_message_content, ChatHistory, ContentSegment, InlineSegment,
+    ListBlock, ModelProposal, ModelVote, SessionData, TableAlignment, TableBlock, VoteTally,
+    VotingMethod,
+};
+
+/// Identifier written into every JSON bundle so importers can recognise it.
+pub const BUNDLE_FORMAT: &str = "gtllm-bundle";
+/// Current version of the JSON bundle layout.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Output formats supported by the exporter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn all() -> [ExportFormat; 3] {
+        [ExportFormat::Markdown, ExportFormat::Html, ExportFormat::Json]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Html => "HTML",
+            ExportFormat::Json => "JSON bundle",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Portable bundle of one or more sessions, readable by the importer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub sessions: Vec<SessionData>,
+}
+
+impl ExportBundle {
+    pub fn new(sessions: Vec<SessionData>) -> Self {
+        Self {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at: ChatHistory::format_timestamp(),
+            sessions,
+        }
+    }
+}
+
+// ============================================================================
+// Document Model
+// ============================================================================
+
+/// Mode-independent view of a session, rendered by both Markdown and HTML writers
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Heading { level: usize, text: String },
+    Note(String),
+    User(String),
+    Entry {
+        label: String,
+        content: String,
+        error: Option<String>,
+    },
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+fn entry(label: impl Into<String>, content: &str, error: &Option<String>) -> Block {
+    Block::Entry {
+        label: label.into(),
+        content: content.to_string(),
+        error: error.clone(),
+    }
+}
+
+fn history_blocks(history: &ChatHistory) -> Vec<Block> {
+    let mut blocks = Vec::new();
+
+    match history {
+        ChatHistory::Standard(h) => {
+            if !h.selected_models.is_empty() {
+                blocks.push(Block::Note(format!("Models: {}", h.selected_models.join(", "))));
+            }
+            if !h.system_prompt.trim().is_empty() {
+                blocks.push(Block::Note(format!("System prompt: {}", h.system_prompt.trim())));
+            }
+            for (idx, user_message) in h.user_messages.iter().enumerate() {
+                blocks.push(Block::Heading { level: 2, text: format!("Turn {}", idx + 1) });
+                blocks.push(Block::User(user_message.clone()));
+                for response in h.model_responses.get(idx).into_iter().flatten() {
+                    blocks.push(entry(&response.model_id, &response.content, &response.error_message));
+                }
+            }
+        }
+        ChatHistory::PvP(h) => {
+            if !h.bot_models.is_empty() {
+                blocks.push(Block::Note(format!("Bots: {}", h.bot_models.join(" vs "))));
+            }
+            match h.judges().as_slice() {
+                [] => {}
+                [moderator] => blocks.push(Block::Note(format!("Moderator: {}", moderator))),
+                judges => blocks.push(Block::Note(format!("Moderators: {}", judges.join(", ")))),
+            }
+            for (idx, round) in h.rounds.iter().enumerate() {
+                blocks.push(Block::Heading { level: 2, text: format!("Round {}", idx + 1) });
+                blocks.push(Block::User(round.user_message.clone()));
+                for bot in [&round.bot1_response, &round.bot2_response] {
+                    blocks.push(entry(&bot.model_id, &bot.content, &bot.error_message));
+                }
+                for turn in &round.turns {
+                    blocks.push(Block::Heading { level: 3, text: turn.kind.label() });
+                    for bot in [&turn.bot1_response, &turn.bot2_response] {
+                        blocks.push(entry(&bot.model_id, &bot.content, &bot.error_message));
+                    }
+                }
+                if let Some(judgment) = &round.moderator_judgment {
+                    blocks.push(Block::Heading { level: 3, text: "Moderator Judgment".to_string() });
+                    if judgment.judges.is_empty() {
+                        let label = h.moderator_model.clone().unwrap_or_else(|| "Moderator".to_string());
+                        blocks.push(entry(label, &judgment.content, &judgment.error_message));
+                    }
+                    for judge in &judgment.judges {
+                        blocks.push(entry(&judge.model_id, &judge.content, &judge.error_message));
+                    }
+                    if let Some(verdict) = judgment.verdict.as_ref().filter(|_| !judgment.judges.is_empty()) {
+                        let winner = verdict.winner.as_deref().unwrap_or("tie");
+                        blocks.push(Block::Note(match judgment.agreement {
+                            Some(agreement) => format!("Panel verdict: {} ({:.0}% agreement)", winner, agreement * 100.0),
+                            None => format!("Panel verdict: {}", winner),
+                        }));
+                    }
+                }
+            }
+        }
+        ChatHistory::Collaborative(h) => {
+            if !h.selected_models.is_empty() {
+                blocks.push(Block::Note(format!("Models: {}", h.selected_models.join(", "))));
+            }
+            for (idx, round) in h.rounds.iter().enumerate() {
+                blocks.push(Block::Heading { level: 2, text: format!("Round {}", idx + 1) });
+                blocks.push(Block::User(round.user_message.clone()));
+                if !round.model_responses.is_empty() {
+                    blocks.push(Block::Heading { level: 3, text: "Responses".to_string() });
+                }
+                for response in &round.model_responses {
+                    blocks.push(entry(&response.model_id, &response.content, &response.error_message));
+                }
+                if let Some(consensus) = &round.final_consensus {
+                    blocks.push(Block::Heading { level: 3, text: "Consensus".to_string() });
+                    blocks.push(entry("Consensus", consensus, &None));
+                }
+            }
+        }
+        ChatHistory::Competitive(h) => {
+            if !h.selected_models.is_empty() {
+                blocks.push(Block::Note(format!("Models: {}", h.selected_models.join(", "))));
+            }
+            for (idx, round) in h.rounds.iter().enumerate() {
+                blocks.push(Block::Heading { level: 2, text: format!("Round {}", idx + 1) });
+                blocks.push(Block::User(round.user_question.clone()));
+                if round.voting_method != VotingMethod::Plurality {
+                    blocks.push(Block::Note(format!("Voting: {}", round.voting_method.label())));
+                }
+                // With a tie-break the vote's own winners are the proposals the first phase chose between
+                let vote_winners = round.runoffs.first().map(|r| &r.candidates).unwrap_or(&round.winners);
+                competitive_blocks(
+                    &mut blocks,
+                    &round.phase1_proposals,
+                    &round.phase2_votes,
+                    &round.vote_tallies,
+                    vote_winners,
+                    round.voting_method,
+                );
+                for (number, runoff) in round.runoffs.iter().enumerate() {
+                    blocks.push(Block::Heading { level: 3, text: runoff.tie_break.phase_title(number + 1) });
+                    blocks.push(Block::Note(format!("Tied: {}", runoff.candidates.join(", "))));
+                    competitive_blocks(&mut blocks, &[], &runoff.votes, &runoff.vote_tallies, &runoff.winners, VotingMethod::Plurality);
+                }
+            }
+        }
+        ChatHistory::LLMChoice(h) => {
+            if !h.selected_models.is_empty() {
+                blocks.push(Block::Note(format!("Models: {}", h.selected_models.join(", "))));
+            }
+            for (idx, round) in h.rounds.iter().enumerate() {
+                blocks.push(Block::Heading { level: 2, text: format!("Round {}", idx + 1) });
+                blocks.push(Block::User(round.user_message.clone()));
+                if !round.decisions.is_empty() {
+                    blocks.push(Block::Heading { level: 3, text: "Strategy Decision".to_string() });
+                    blocks.push(Block::Table {
+                        headers: vec!["Model".to_string(), "Decision".to_string(), "Note".to_string()],
+                        rows: round
+                            .decisions
+                            .iter()
+                            .map(|decision| {
+                                vec![
+                                    decision.model_id.clone(),
+                                    decision.decision.clone().unwrap_or_else(|| "none".to_string()),
+                                    decision.error_message.clone().unwrap_or_default(),
+                                ]
+                            })
+                            .collect(),
+                    });
+                }
+                blocks.push(Block::Note(format!("Chosen strategy: {}", round.decision)));
+                if let Some(collab) = &round.collaborative {
+                    for (title, responses) in [("Responses", &collab.initial_responses), ("Reviews", &collab.reviews)] {
+                        if !responses.is_empty() {
+                            blocks.push(Block::Heading { level: 3, text: title.to_string() });
+                        }
+                        for response in responses {
+                            blocks.push(entry(&response.model_id, &response.content, &response.error_message));
+                        }
+                    }
+                    if let Some(consensus) = &collab.consensus {
+                        blocks.push(Block::Heading { level: 3, text: "Consensus".to_string() });
+                        blocks.push(entry(&consensus.model_id, &consensus.content, &consensus.error_message));
+                    }
+                } else if let Some(comp) = &round.competitive {
+                    competitive_blocks(
+                        &mut blocks,
+                        &comp.proposals,
+                        &comp.votes,
+                        &comp.vote_tallies,
+                        &comp.winners,
+                        VotingMethod::Plurality,
+                    );
+                } else if let Some(content) = &round.content {
+                    blocks.push(entry("Result", content, &None));
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Proposals, the vote table, the tally and the winners of a competitive round
+fn competitive_blocks(
+    blocks: &mut Vec<Block>,
+    proposals: &[ModelProposal],
+    votes: &[ModelVote],
+    vote_tallies: &[VoteTally],
+    winners: &[String],
+    method: VotingMethod,
+) {
+    if !proposals.is_empty() {
+        blocks.push(Block::Heading { level: 3, text: "Proposals".to_string() });
+    }
+    for proposal in proposals {
+        blocks.push(entry(&proposal.model_id, &proposal.content, &proposal.error_message));
+    }
+    if !votes.is_empty() {
+        blocks.push(Block::Heading { level: 3, text: "Votes".to_string() });
+        blocks.push(Block::Table {
+            headers: vec![
+                "Voter".to_string(),
+                match method {
+                    VotingMethod::Plurality => "Voted for",
+                    VotingMethod::Approval => "Approved",
+                    _ => "Ranking",
+                }
+                .to_string(),
+                "Note".to_string(),
+            ],
+            rows: votes
+                .iter()
+                .map(|vote| {
+                    vec![
+                        vote.voter_id.clone(),
+                        match method {
+                            VotingMethod::Plurality => vote.voted_for.clone().unwrap_or_else(|| "none".to_string()),
+                            _ if vote.ranking.is_empty() => "none".to_string(),
+                            VotingMethod::Approval => vote.ranking.join(", "),
+                            _ => vote.ranking.join(" > "),
+                        },
+                        vote.error_message.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        });
+    }
+    if !vote_tallies.is_empty() {
+        blocks.push(Block::Heading { level: 3, text: "Tally".to_string() });
+        let count_header = match method {
+            VotingMethod::Borda => "Points",
+            VotingMethod::Approval => "Approvals",
+            _ => "Votes",
+        };
+        let runoff = vote_tallies.iter().any(|tally| !tally.round_counts.is_empty());
+        let mut headers = vec!["Model".to_string(), count_header.to_string(), "Voters".to_string()];
+        if runoff {
+            headers.push("By round".to_string());
+        }
+        blocks.push(Block::Table {
+            headers,
+            rows: vote_tallies
+                .iter()
+                .map(|tally| {
+                    let mut row = vec![
+                        tally.model_id.clone(),
+                        tally.vote_count.to_string(),
+                        tally.voters.join(", "),
+                    ];
+                    if runoff {
+                        let counts: Vec<String> = tally.round_counts.iter().map(|c| c.to_string()).collect();
+                        row.push(match tally.eliminated_in {
+                            Some(round) => format!("{} (out in round {})", counts.join(" → "), round),
+                            None => counts.join(" → "),
+                        });
+                    }
+                    row
+                })
+                .collect(),
+        });
+    }
+    if !winners.is_empty() {
+        blocks.push(Block::Note(format!("Winner(s): {}", winners.join(", "))));
+    }
+}
+
+// ============================================================================
+// Markdown
+// ============================================================================
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown(session: &SessionData) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# {}\n\n", session.session.title));
+    out.push_str(&format!(
+        "_{} • created {} • updated {}_\n\n",
+        session.session.mode.name(),
+        ChatHistory::format_timestamp_date(&session.created_at),
+        ChatHistory::format_timestamp_date(&session.updated_at)
+    ));
+
+    for block in history_blocks(&session.history) {
+        match block {
+            Block::Heading { level, text } => {
+                out.push_str(&format!("{} {}\n\n", "#".repeat(level), text));
+            }
+            Block::Note(text) => out.push_str(&format!("_{}_\n\n", text)),
+            Block::User(text) => {
+                out.push_str("**You:**\n\n");
+                for line in text.trim().lines() {
+                    out.push_str(&format!("> {}\n", line));
+                }
+                out.push('\n');
+            }
+            Block::Entry { label, content, error } => {
+                out.push_str(&format!("**{}:**\n\n", label));
+                if let Some(error) = error {
+                    out.push_str(&format!("> ⚠️ Error: {}\n\n", error));
+                }
+                if !content.trim().is_empty() {
+                    out.push_str(content.trim());
+                    out.push_str("\n\n");
+                }
+            }
+            Block::Table { headers, rows } => {
+                out.push_str(&format!(
+                    "| {} |\n",
+                    headers.iter().map(|h| markdown_cell(h)).collect::<Vec<_>>().join(" | ")
+                ));
+                out.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                for row in rows {
+                    out.push_str(&format!(
+                        "| {} |\n",
+                        row.iter().map(|c| markdown_cell(c)).collect::<Vec<_>>().join(" | ")
+                    ));
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
+
+// ============================================================================
+// HTML
+// ============================================================================
+
+const HTML_STYLE: &str = "body{font-family:system-ui,-apple-system,Segoe UI,sans-serif;max-width:860px;margin:2rem auto;padding:0 1rem;color:#1f2937;line-height:1.6}\
+h1{margin-bottom:.25rem}.meta{color:#6b7280;font-size:.9rem}.note{color:#4b5563;font-style:italic}\
+.user{background:#eef2ff;border-left:4px solid #6366f1;padding:.5rem 1rem;border-radius:6px;margin:1rem 0}\
+.entry{border:1px solid #e5e7eb;border-radius:8px;padding:.75rem 1rem;margin:.75rem 0}\
+.label{font-weight:600;margin-bottom:.25rem}.error{color:#b91c1c}\
+pre{background:#f3f4f6;padding:.75rem;border-radius:6px;overflow-x:auto}code{font-family:ui-monospace,monospace}\
+table{border-collapse:collapse;margin:.75rem 0}th,td{border:1px solid #e5e7eb;padding:.35rem .6rem;text-align:left;vertical-align:top}\
+blockquote{border-left:3px solid #d1d5db;margin:.5rem 0;padding-left:1rem;color:#4b5563}.formula{font-family:ui-monospace,monospace}\
+p{white-space:pre-wrap}hr.session{margin:3rem 0}";
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Only web and mail links survive into exported HTML; anything else
+/// (`javascript:`, `data:`, relative paths) is rendered as plain text.
+fn is_safe_href(url: &str) -> bool {
+    let lower = url.trim().to_ascii_lowercase();
+    ["http://", "https://", "mailto:"].iter().any(|scheme| lower.starts_with(scheme))
+}
+
+fn inline_html(segments: &[InlineSegment]) -> String {
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            InlineSegment::Text(text) => out.push_str(&escape_html(text)),
+            InlineSegment::Bold(children) => {
+                out.push_str(&format!("<strong>{}</strong>", inline_html(children)))
+            }
+            InlineSegment::Italic(children) => out.push_str(&format!("<em>{}</em>", inline_html(children))),
+            InlineSegment::BoldItalic(children) => {
+                out.push_str(&format!("<strong><em>{}</em></strong>", inline_html(children)))
+            }
+            InlineSegment::Link { text, url } if is_safe_href(url) => out.push_str(&format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(url),
+                inline_html(text)
+            )),
+            InlineSegment::Link { text, .. } => out.push_str(&inline_html(text)),
+            InlineSegment::InlineCode(code) => out.push_str(&format!("<code>{}</code>", escape_html(code))),
+            InlineSegment::Formula(formula) => {
+                out.push_str(&format!("<span class=\"formula\">{}</span>", escape_html(formula)))
+            }
+        }
+    }
+    out
+}
+
+fn list_html(list: &ListBlock) -> String {
+    let tag = if list.ordered { "ol" } else { "ul" };
+    let items: String = list.items.iter().map(list_item_html).collect();
+    format!("<{tag}>{items}</{tag}>")
+}
+
+fn list_item_html(item: &ListItem) -> String {
+    let children: String = item.children.iter().map(list_html).collect();
+    format!("<li>{}{}</li>", inline_html(&parse_inline_elements(&item.text)), children)
+}
+
+fn table_html(table: &TableBlock) -> String {
+    let align = |idx: usize| match table.alignments.get(idx).unwrap_or(&TableAlignment::None) {
+        TableAlignment::Center => " style=\"text-align:center\"",
+        TableAlignment::Right => " style=\"text-align:right\"",
+        TableAlignment::Left | TableAlignment::None => "",
+    };
+    let mut out = String::from("<table><thead><tr>");
+    for (idx, header) in table.headers.iter().enumerate() {
+        out.push_str(&format!("<th{}>{}</th>", align(idx), inline_html(&parse_inline_elements(header))));
+    }
+    out.push_str("</tr></thead><tbody>");
+    for row in &table.rows {
+        out.push_str("<tr>");
+        for (idx, cell) in row.iter().enumerate() {
+            out.push_str(&format!("<td{}>{}</td>", align(idx), inline_html(&parse_inline_elements(cell))));
+        }
+        out.push_str("</tr>");
+    }
+    out.push_str("</tbody></table>");
+    out
+}
+
+fn segments_html(segments: &[ContentSegment]) -> String {
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            ContentSegment::Paragraph(text) => {
+                out.push_str(&format!("<p>{}</p>", inline_html(&parse_inline_elements(text))))
+            }
+            ContentSegment::Header { level, text } => {
+                let level = (*level).clamp(1, 6);
+                out.push_str(&format!(
+                    "<h{level}>{}</h{level}>",
+                    inline_html(&parse_inline_elements(text))
+                ));
+            }
+            ContentSegment::CodeBlock { language, code } => out.push_str(&format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>",
+                escape_html(language),
+                escape_html(code)
+            )),
+            ContentSegment::FormulaBlock(formula) => {
+                out.push_str(&format!("<div class=\"formula\">{}</div>", escape_html(formula)))
+            }
+            ContentSegment::Table(table) => out.push_str(&table_html(table)),
+            ContentSegment::List(list) => out.push_str(&list_html(list)),
+            ContentSegment::Blockquote(children) => {
+                out.push_str(&format!("<blockquote>{}</blockquote>", segments_html(children)))
+            }
+            ContentSegment::HorizontalRule => out.push_str("<hr>"),
+        }
+    }
+    out
+}
+
+fn render_html_body(session: &SessionData) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("<h1>{}</h1>", escape_html(&session.session.title)));
+    out.push_str(&format!(
+        "<div class=\"meta\">{} • created {} • updated {}</div>",
+        session.session.mode.name(),
+        escape_html(&ChatHistory::format_timestamp_date(&session.created_at)),
+        escape_html(&ChatHistory::format_timestamp_date(&session.updated_at))
+    ));
+
+    for block in history_blocks(&session.history) {
+        match block {
+            Block::Heading { level, text } => {
+                out.push_str(&format!("<h{level}>{}</h{level}>", escape_html(&text)))
+            }
+            Block::Note(text) => out.push_str(&format!("<p class=\"note\">{}</p>", escape_html(&text))),
+            Block::User(text) => out.push_str(&format!(
+                "<div class=\"user\"><div class=\"label\">You</div>{}</div>",
+                segments_html(&parse_message_content(&text))
+            )),
+            Block::Entry { label, content, error } => {
+                out.push_str(&format!("<div class=\"entry\"><div class=\"label\">{}</div>", escape_html(&label)));
+                if let Some(error) = error {
+                    out.push_str(&format!("<p class=\"error\">Error: {}</p>", escape_html(&error)));
+                }
+                out.push_str(&segments_html(&parse_message_content(&content)));
+                out.push_str("</div>");
+            }
+            Block::Table { headers, rows } => {
+                out.push_str("<table><thead><tr>");
+                for header in &headers {
+                    out.push_str(&format!("<th>{}</th>", escape_html(header)));
+                }
+                out.push_str("</tr></thead><tbody>");
+                for row in &rows {
+                    out.push_str("<tr>");
+                    for cell in row {
+                        out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                    }
+                    out.push_str("</tr>");
+                }
+                out.push_str("</tbody></table>");
+            }
+        }
+    }
+
+    out
+}
+
+fn wrap_html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        escape_html(title),
+        HTML_STYLE,
+        body
+    )
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+pub struct ChatExport;
+
+impl ChatExport {
+    /// Directory where exported files are written
+    pub fn exports_dir() -> Result<PathBuf, String> {
+        let base_dir = crate::utils::AppDirs::current()?.data;
+        Ok(base_dir.join("exports"))
+    }
+
+    /// Render a single session as Markdown
+    pub fn to_markdown(session: &SessionData) -> String {
+        render_markdown(session)
+    }
+
+    /// Render a single session as a self-contained HTML document
+    pub fn to_html(session: &SessionData) -> String {
+        wrap_html_document(&session.session.title, &render_html_body(session))
+    }
+
+    /// Serialize sessions into a portable JSON bundle
+    pub fn to_bundle(sessions: Vec<SessionData>) -> Result<String, String> {
+        serde_json::to_string_pretty(&ExportBundle::new(sessions))
+            .map_err(|e| format!("Failed to serialize export bundle: {}", e))
+    }
+
+    /// Render any number of sessions into a single document of the given format
+    pub fn render(sessions: Vec<SessionData>, format: ExportFormat) -> Result<String, String> {
+        match format {
+            ExportFormat::Markdown => Ok(sessions
+                .iter()
+                .map(Self::to_markdown)
+                .collect::<Vec<_>>()
+                .join("\n---\n\n")),
+            ExportFormat::Html if sessions.len() == 1 => Ok(Self::to_html(&sessions[0])),
+            ExportFormat::Html => {
+                let title = format!("GTLLM export ({} sessions)", sessions.len());
+                let body = sessions
+                    .iter()
+                    .map(render_html_body)
+                    .collect::<Vec<_>>()
+                    .join("<hr class=\"session\">");
+                Ok(wrap_html_document(&title, &body))
+            }
+            ExportFormat::Json => Self::to_bundle(sessions),
+        }
+    }
+
+    /// Export one session and return the path of the written file
+    pub fn export_session(session_id: &str, format: ExportFormat) -> Result<PathBuf, String> {
+        let session = ChatHistory::load_session(session_id)?;
+        let contents = Self::render(vec![session], format)?;
+        Self::write_export(session_id, format, &contents)
+    }
+
+    /// Export every saved session into a single file and return its path
+    pub fn export_all(format: ExportFormat) -> Result<PathBuf, String> {
+        let sessions: Vec<SessionData> = ChatHistory::list_sessions()?
+            .iter()
+            .filter_map(|s| ChatHistory::load_session(&s.id).ok())
+            .filter(|s| ChatHistory::has_content(&s.history))
+            .collect();
+        if sessions.is_empty() {
+            return Err("No sessions to export".to_string());
+        }
+        let contents = Self::render(sessions, format)?;
+        let name = format!("gtllm_export_{}", ChatHistory::format_timestamp());
+        Self::write_export(&name, format, &contents)
+    }
+
+    fn write_export(name: &str, format: ExportFormat, contents: &str) -> Result<PathBuf, String> {
+        let exports_dir = Self::exports_dir()?;
+        if !exports_dir.exists() {
+            fs::create_dir_all(&exports_dir)
+                .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+        }
+
+        let path = exports_dir.join(format!("{}.{}", name, format.extension()));
+        fs::write(&path, contents).map_err(|e| format!("Failed to write export file: {}", e))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&path)
+                .map_err(|e| format!("Failed to get export file metadata: {}", e))?
+                .permissions();
+            perms.set_mode(0o600);
+            fs::set_permissions(&path, perms)
+                .map_err(|e| format!("Failed to set export file permissions: {}", e))?;
+        }
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{
+        ChatMode, ChatSession, SessionLabels, SessionStats, CompetitiveHistory, CompetitiveRound, ContextPolicy, ModelProposal, ModelVote,
+        PromptTemplates, TieBreak, VoteTally, VotingMethod,
+    };
+
+    fn competitive_session() -> SessionData {
+        SessionData {
+            session: ChatSession {
+                id: "competitive_1700000000_Test".to_string(),
+                title: "Test <export>".to_string(),
+                mode: ChatMode::Competitive,
+                timestamp: "1700000000".to_string(),
+                stats: SessionStats::default(),
+                labels: SessionLabels::default(),
+            },
+            history: ChatHistory::Competitive(CompetitiveHistory {
+                rounds: vec![CompetitiveRound {
+                    user_question: "Which is best?".to_string(),
+                    phase1_proposals: vec![ModelProposal {
+                        model_id: "a/one".to_string(),
+                        content: "**Bold** answer".to_string(),
+                        error_message: None,
+                        timing: None,
+                    }],
+                    phase2_votes: vec![ModelVote {
+                        voter_id: "b/two".to_string(),
+                        voted_for: Some("a/one".to_string()),
+                        ranking: Vec::new(),
+                        raw_response: "a/one".to_string(),
+                        error_message: None,
+                        timing: None,
+                    }],
+                    vote_tallies: vec![VoteTally {
+                        model_id: "a/one".to_string(),
+                        vote_count: 1,
+                        voters: vec!["b/two".to_string()],
+                        round_counts: Vec::new(),
+                        eliminated_in: None,
+                    }],
+                    winners: vec!["a/one".to_string()],
+                    current_phase: "complete".to_string(),
+                    voting_method: VotingMethod::default(),
+                    tie_break: TieBreak::default(),
+                    runoffs: Vec::new(),
+                    prompt_templates: None,
+                }],
+                selected_models: vec!["a/one".to_string(), "b/two".to_string()],
+                prompt_templates: PromptTemplates {
+                    proposal: String::new(),
+                    voting: String::new(),
+                },
+                context: Vec::new(),
+                context_policy: ContextPolicy::default(),
+                voting_method: VotingMethod::default(),
+                tie_break: TieBreak::default(),
+                tie_break_chair: None,
+            }),
+            created_at: "1700000000".to_string(),
+            updated_at: "1700000000".to_string(),
+            forked_from: None,
+            renamed: false,
+            title_generated: false,
+            checkpoint: None,
+        }
+    }
+
+    #[test]
+    fn test_markdown_includes_votes_and_winners() {
+        let markdown = ChatExport::to_markdown(&competitive_session());
+        assert!(markdown.contains("### Proposals"));
+        assert!(markdown.contains("| b/two | a/one |"));
+        assert!(markdown.contains("Winner(s): a/one"));
+    }
+
+    #[test]
+    fn test_html_is_escaped_and_formatted() {
+        let html = ChatExport::to_html(&competitive_session());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Test &lt;export&gt;"));
+        assert!(html.contains("<strong>Bold</strong>"));
+    }
+
+    #[test]
+    fn test_html_drops_unsafe_link_schemes() {
+        let html = inline_html(&parse_inline_elements(
+            "[ok](https://example.com) [bad](javascript:alert(1)) [mail](mailto:a@b.c)",
+        ));
+        assert!(html.contains("<a href=\"https://example.com\">ok</a>"));
+        assert!(html.contains("<a href=\"mailto:a@b.c\">mail</a>"));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("bad"));
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let json = ChatExport::to_bundle(vec![competitive_session()]).unwrap();
+        let bundle: ExportBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(bundle.format, BUNDLE_FORMAT);
+        assert_eq!(bundle.sessions, vec![competitive_session()]);
+    }
+}
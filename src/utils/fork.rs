@@ -0,0 +1,340 @@
+use crate::utils::{
+    ChatHistory, ChatMode, ChatSession, SessionLabels, SessionStats, ContextPolicy, ConversationHistory, ConversationRound, ForkOrigin, ModelResponse, PriorRound, SessionData,
+};
+
+
+/// Branches a session off at a chosen round, optionally into a different mode
+pub struct ChatFork;
+
+impl ChatFork {
+    /// The user prompt of every round, in order; a fork point is an index into this list
+    pub fn round_prompts(history: &ChatHistory) -> Vec<String> {
+        match history {
+            ChatHistory::Standard(h) => h.user_messages.clone(),
+            ChatHistory::PvP(h) => h.rounds.iter().map(|r| r.user_message.clone()).collect(),
+            ChatHistory::Collaborative(h) => h.rounds.iter().map(|r| r.user_message.clone()).collect(),
+            ChatHistory::Competitive(h) => h.rounds.iter().map(|r| r.user_question.clone()).collect(),
+            ChatHistory::LLMChoice(h) => h.rounds.iter().map(|r| r.user_message.clone()).collect(),
+        }
+    }
+
+    /// Prior exchanges up to and including `round`, as (user message, answer) pairs.
+    /// Context the session itself was forked with comes first; rounds without an answer are skipped.
+    fn exchanges(history: &ChatHistory, round: usize) -> Vec<(String, String)> {
+        let answered = |user_message: &str, answer: Option<String>| {
+            let mut prior = PriorRound::new(user_message);
+            prior.final_answer = answer;
+            prior
+        };
+        let (carried, rounds): (&[(String, String)], Vec<PriorRound>) = match history {
+            ChatHistory::Standard(h) => (
+                &h.context,
+                h.user_messages
+                    .iter()
+                    .zip(h.model_responses.iter())
+                    .map(|(user_msg, responses)| answered(user_msg, labelled_answers(responses)))
+                    .collect(),
+            ),
+            // A PvP round carries everything said in it, the same way its own follow-ups do
+            ChatHistory::PvP(h) => (&h.context, h.rounds.iter().map(ConversationRound::prior_round).collect()),
+            ChatHistory::Collaborative(h) => (
+                &h.context,
+                h.rounds
+                    .iter()
+                    .map(|r| {
+                        let answer = r
+                            .final_consensus
+                            .clone()
+                            .filter(|c| !c.trim().is_empty())
+                            .or_else(|| labelled_answers(&r.model_responses));
+                        answered(&r.user_message, answer)
+                    })
+                    .collect(),
+            ),
+            ChatHistory::Competitive(h) => (
+                &h.context,
+                h.rounds
+                    .iter()
+                    .map(|r| {
+                        let winning: Vec<String> = r
+                            .phase1_proposals
+                            .iter()
+                            .filter(|p| p.error_message.is_none() && r.winners.contains(&p.model_id))
+                            .map(|p| format!("**{}**:\n{}", p.model_id, p.content))
+                            .collect();
+                        answered(&r.user_question, join_sections(winning))
+                    })
+                    .collect(),
+            ),
+            ChatHistory::LLMChoice(h) => (
+                &h.context,
+                h.rounds
+                    .iter()
+                    .map(|r| answered(&r.user_message, r.content.clone()))
+                    .collect(),
+            ),
+        };
+
+        let kept = &rounds[..rounds.len().min(round + 1)];
+        carried
+            .iter()
+            .cloned()
+            .chain(ContextPolicy::FullTranscript.exchanges(kept))
+            .collect()
+    }
+
+    /// Build the history for a fork at `round`. Forking within the same mode keeps the
+    /// rounds and configuration; forking into another mode starts empty with the prior
+    /// exchanges carried as context.
+    pub fn fork_history(history: &ChatHistory, round: usize, mode: ChatMode) -> ChatHistory {
+        let keep = round + 1;
+        match (history, mode) {
+            (ChatHistory::Standard(h), ChatMode::Standard) => {
+                let mut forked = h.clone();
+                forked.user_messages.truncate(keep);
+                forked.model_responses.truncate(keep);
+                forked.turns.clear();
+                forked.active_path.clear();
+                forked.conversation_history = standard_conversation(
+                    &forked.user_messages,
+                    &forked.model_responses,
+                    &forked.selected_models,
+                );
+                ChatHistory::Standard(forked)
+            }
+            (ChatHistory::PvP(h), ChatMode::PvP) => {
+                let mut forked = h.clone();
+                forked.rounds.truncate(keep);
+                ChatHistory::PvP(forked)
+            }
+            (ChatHistory::Collaborative(h), ChatMode::Collaborative) => {
+                let mut forked = h.clone();
+                forked.rounds.truncate(keep);
+                ChatHistory::Collaborative(forked)
+            }
+            (ChatHistory::Competitive(h), ChatMode::Competitive) => {
+                let mut forked = h.clone();
+                forked.rounds.truncate(keep);
+                ChatHistory::Competitive(forked)
+            }
+            (ChatHistory::LLMChoice(h), ChatMode::LLMChoice) => {
+                let mut forked = h.clone();
+                forked.rounds.truncate(keep);
+                ChatHistory::LLMChoice(forked)
+            }
+            _ => {
+                let context = Self::exchanges(history, round);
+                let mut forked = ChatHistory::empty(mode);
+                match &mut forked {
+                    ChatHistory::Standard(h) => h.context = context,
+                    ChatHistory::PvP(h) => h.context = context,
+                    ChatHistory::Collaborative(h) => h.context = context,
+                    ChatHistory::Competitive(h) => h.context = context,
+                    ChatHistory::LLMChoice(h) => h.context = context,
+                }
+                forked
+            }
+        }
+    }
+
+    /// Fork a saved session at `round` into `mode`, saving the result as a new linked session
+    pub fn fork_session(session_id: &str, round: usize, mode: ChatMode) -> Result<ChatSession, String> {
+        let parent = ChatHistory::load_session(session_id)?;
+        let rounds = Self::round_prompts(&parent.history).len();
+        if round >= rounds {
+            return Err(format!("Round {} does not exist in this chat", round + 1));
+        }
+
+        let timestamp = ChatHistory::format_timestamp();
+        let session_data = SessionData {
+            session: ChatSession {
+                id: ChatHistory::new_session_id(&timestamp),
+                title: format!("Fork of {}", parent.session.title),
+                mode,
+                timestamp: timestamp.clone(),
+                stats: SessionStats::default(),
+                labels: SessionLabels::default(),
+            },
+            history: Self::fork_history(&parent.history, round, mode),
+            created_at: timestamp.clone(),
+            updated_at: timestamp,
+            forked_from: Some(ForkOrigin {
+                session_id: session_id.to_string(),
+                round,
+            }),
+            renamed: false,
+            title_generated: false,
+            checkpoint: None,
+        };
+        ChatHistory::save_session(&session_data)
+    }
+}
+
+/// A single response as-is, or several labelled with their model
+fn labelled_answers(responses: &[ModelResponse]) -> Option<String> {
+    let ok: Vec<&ModelResponse> = responses
+        .iter()
+        .filter(|r| r.error_message.is_none() && !r.content.is_empty())
+        .collect();
+    match ok.as_slice() {
+        [] => None,
+        [only] => Some(only.content.clone()),
+        many => join_sections(
+            many.iter()
+                .map(|r| format!("**{}**:\n{}", r.model_id, r.content))
+                .collect(),
+        ),
+    }
+}
+
+fn join_sections(sections: Vec<String>) -> Option<String> {
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections.join("\n\n"))
+    }
+}
+
+/// Per-model API context for a linear Standard conversation
+fn standard_conversation(
+    user_messages: &[String],
+    model_responses: &[Vec<ModelResponse>],
+    models: &[String],
+) -> ConversationHistory {
+    let mut history = ConversationHistory {
+        single_model: Vec::new(),
+        multi_model: models.iter().map(|model_id| (model_id.clone(), Vec::new())).collect(),
+    };
+    for (user_msg, responses) in user_messages.iter().zip(model_responses) {
+        for response in responses.iter().filter(|r| r.error_message.is_none()) {
+            if models.len() == 1 {
+                if response.model_id == models[0] {
+                    history.single_model.push((user_msg.clone(), response.content.clone()));
+                }
+            } else if let Some(model_history) = history.multi_model.get_mut(&response.model_id) {
+                model_history.push((user_msg.clone(), response.content.clone()));
+            }
+        }
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::StandardHistory;
+    use std::collections::HashMap;
+
+    fn standard(rounds: &[(&str, &[(&str, &str)])]) -> ChatHistory {
+        ChatHistory::Standard(StandardHistory {
+            user_messages: rounds.iter().map(|(u, _)| u.to_string()).collect(),
+            model_responses: rounds
+                .iter()
+                .map(|(_, responses)| {
+                    responses
+                        .iter()
+                        .map(|(model, content)| ModelResponse {
+                            model_id: model.to_string(),
+                            content: content.to_string(),
+                            error_message: None,
+                            timing: None,
+                        })
+                        .collect()
+                })
+                .collect(),
+            selected_models: vec!["a/one".to_string()],
+            system_prompt: "sys".to_string(),
+            conversation_history: ConversationHistory {
+                single_model: Vec::new(),
+                multi_model: HashMap::new(),
+            },
+            turns: Vec::new(),
+            active_path: Vec::new(),
+            context: vec![("earlier".to_string(), "carried".to_string())],
+        })
+    }
+
+    #[test]
+    fn test_fork_into_other_mode_carries_exchanges() {
+        let history = standard(&[
+            ("first", &[("a/one", "one")]),
+            ("second", &[("a/one", "two"), ("b/two", "deux")]),
+            ("third", &[("a/one", "three")]),
+        ]);
+
+        let ChatHistory::Competitive(forked) = ChatFork::fork_history(&history, 1, ChatMode::Competitive) else {
+            panic!("expected competitive history");
+        };
+        assert!(forked.rounds.is_empty());
+        assert_eq!(forked.context.len(), 3);
+        assert_eq!(forked.context[0], ("earlier".to_string(), "carried".to_string()));
+        assert_eq!(forked.context[1], ("first".to_string(), "one".to_string()));
+        assert!(forked.context[2].1.contains("**b/two**:\ndeux"));
+    }
+
+    #[test]
+    fn test_fork_of_pvp_carries_the_same_transcript_as_its_follow_ups() {
+        use crate::utils::{BotResponse, DebateFormat, JudgePanel, JudgingOptions, ModeratorResponse, PvPHistory, Rubric, SystemPrompts};
+
+        let bot = |model: &str, content: &str| BotResponse {
+            model_id: model.to_string(),
+            content: content.to_string(),
+            error_message: None,
+            timing: None,
+        };
+        let round = ConversationRound {
+            user_message: "Which is faster?".to_string(),
+            bot1_response: bot("a/one", "Rust"),
+            bot2_response: bot("b/two", "C"),
+            turns: Vec::new(),
+            moderator_judgment: Some(ModeratorResponse {
+                content: "Both are fast".to_string(),
+                error_message: None,
+                timing: None,
+                verdict: None,
+                shown_first: None,
+                swapped_verdict: None,
+                inconsistent: false,
+                judges: Vec::new(),
+                agreement: None,
+            }),
+            system_prompts: None,
+        };
+        let history = ChatHistory::PvP(PvPHistory {
+            rounds: vec![round.clone()],
+            bot_models: vec!["a/one".to_string(), "b/two".to_string()],
+            moderator_model: Some("a/one".to_string()),
+            system_prompts: SystemPrompts {
+                bot: String::new(),
+                moderator: String::new(),
+            },
+            context: Vec::new(),
+            context_policy: ContextPolicy::FullTranscript,
+            rubric: Rubric::default(),
+            judging: JudgingOptions::default(),
+            panel: JudgePanel::default(),
+            debate: DebateFormat::default(),
+        });
+
+        let ChatHistory::Standard(forked) = ChatFork::fork_history(&history, 0, ChatMode::Standard) else {
+            panic!("expected standard history");
+        };
+        assert_eq!(forked.context, ContextPolicy::FullTranscript.exchanges(&[round.prior_round()]));
+        assert!(forked.context[0].1.contains("**Moderator**:\nBoth are fast"));
+    }
+
+    #[test]
+    fn test_fork_within_mode_truncates_rounds() {
+        let history = standard(&[("first", &[("a/one", "one")]), ("second", &[("a/one", "two")])]);
+
+        let ChatHistory::Standard(forked) = ChatFork::fork_history(&history, 0, ChatMode::Standard) else {
+            panic!("expected standard history");
+        };
+        assert_eq!(forked.user_messages, vec!["first".to_string()]);
+        assert_eq!(
+            forked.conversation_history.single_model,
+            vec![("first".to_string(), "one".to_string())]
+        );
+        assert_eq!(forked.context.len(), 1);
+    }
+}
@@ -0,0 +1,541 @@
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::utils::{
+    ChatHistory, ChatMode, ChatSession, SessionLabels, SessionStats, ConversationHistory, ExportBundle, ModelResponse,
+    SessionData, StandardHistory, BUNDLE_FORMAT, DEFAULT_SYSTEM_PROMPT,
+};
+
+const IMPORTED_MODEL_FALLBACK: &str = "imported/assistant";
+
+/// Outcome of an import run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub imported: Vec<ChatSession>,
+    /// Human-readable reasons for every conversation that was not imported
+    pub skipped: Vec<String>,
+}
+
+impl ImportReport {
+    /// Short description suitable for a toast
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Imported {} conversation{}",
+            self.imported.len(),
+            if self.imported.len() == 1 { "" } else { "s" }
+        );
+        if !self.skipped.is_empty() {
+            summary.push_str(&format!(", skipped {}", self.skipped.len()));
+            let shown: Vec<&str> = self.skipped.iter().take(3).map(|s| s.as_str()).collect();
+            summary.push_str(&format!(" ({})", shown.join("; ")));
+            if self.skipped.len() > shown.len() {
+                summary.push_str(", ...");
+            }
+        }
+        summary
+    }
+}
+
+/// A conversation parsed from an external file, before conversion to a session
+#[derive(Debug, Clone, PartialEq)]
+struct ParsedConversation {
+    title: Option<String>,
+    created_at: Option<u64>,
+    messages: Vec<ParsedMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ParsedMessage {
+    role: String,
+    content: String,
+    model: Option<String>,
+}
+
+// ============================================================================
+// Format Detection & Parsing
+// ============================================================================
+
+/// Extract plain text from an OpenAI-style `content` field (string or parts array)
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                Value::String(text) => Some(text.clone()),
+                Value::Object(obj) => obj.get("text").and_then(|t| t.as_str()).map(|t| t.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn parse_openai_messages(messages: &[Value], default_model: Option<&str>) -> Vec<ParsedMessage> {
+    messages
+        .iter()
+        .filter_map(|message| {
+            let role = message.get("role")?.as_str()?.to_string();
+            let content = content_text(message.get("content")?);
+            Some(ParsedMessage {
+                role,
+                content,
+                model: message
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .or(default_model)
+                    .map(|m| m.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Walk a ChatGPT `mapping` tree from `current_node` back to the root
+fn parse_chatgpt_conversation(conversation: &Value) -> Option<ParsedConversation> {
+    let mapping = conversation.get("mapping")?.as_object()?;
+    let mut node_id = conversation
+        .get("current_node")
+        .and_then(|n| n.as_str())
+        .map(|n| n.to_string())
+        .or_else(|| {
+            // Fall back to the most recently written leaf when current_node is missing
+            let created = |node: &Value| {
+                node.pointer("/message/create_time")
+                    .and_then(|t| t.as_f64())
+                    .unwrap_or(f64::MIN)
+            };
+            mapping
+                .iter()
+                .filter(|(_, node)| {
+                    node.get("children")
+                        .and_then(|c| c.as_array())
+                        .is_none_or(|c| c.is_empty())
+                })
+                .max_by(|(_, a), (_, b)| created(a).total_cmp(&created(b)))
+                .map(|(id, _)| id.clone())
+        })?;
+
+    let mut messages = Vec::new();
+    let mut visited = HashSet::new();
+    while visited.insert(node_id.clone()) {
+        let Some(node) = mapping.get(&node_id) else { break };
+        if let Some(message) = node.get("message").filter(|m| !m.is_null()) {
+            let role = message
+                .pointer("/author/role")
+                .and_then(|r| r.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let content = message
+                .pointer("/content/parts")
+                .map(content_text)
+                .or_else(|| message.pointer("/content/text").and_then(|t| t.as_str()).map(|t| t.to_string()))
+                .unwrap_or_default();
+            let model = message
+                .pointer("/metadata/model_slug")
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string());
+            messages.push(ParsedMessage { role, content, model });
+        }
+        match node.get("parent").and_then(|p| p.as_str()) {
+            Some(parent) => node_id = parent.to_string(),
+            None => break,
+        }
+    }
+    messages.reverse();
+
+    Some(ParsedConversation {
+        title: conversation.get("title").and_then(|t| t.as_str()).map(|t| t.to_string()),
+        created_at: conversation.get("create_time").and_then(|t| t.as_f64()).map(|t| t as u64),
+        messages,
+    })
+}
+
+enum ParsedFile {
+    Bundle(ExportBundle),
+    Conversations(Vec<Result<ParsedConversation, String>>),
+}
+
+fn parse_import_file(contents: &str) -> Result<ParsedFile, String> {
+    let value: Value =
+        serde_json::from_str(contents).map_err(|e| format!("Failed to parse import file: {}", e))?;
+
+    // The app's own export bundle
+    if value.get("format").and_then(|f| f.as_str()) == Some(BUNDLE_FORMAT) {
+        let bundle: ExportBundle = serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse export bundle: {}", e))?;
+        return Ok(ParsedFile::Bundle(bundle));
+    }
+
+    // OpenAI-format `{ "messages": [...] }`, optionally with a model
+    if let Some(messages) = value.get("messages").and_then(|m| m.as_array()) {
+        let model = value.get("model").and_then(|m| m.as_str());
+        return Ok(ParsedFile::Conversations(vec![Ok(ParsedConversation {
+            title: value.get("title").and_then(|t| t.as_str()).map(|t| t.to_string()),
+            created_at: None,
+            messages: parse_openai_messages(messages, model),
+        })]));
+    }
+
+    let items = match &value {
+        Value::Array(items) => items.clone(),
+        Value::Object(_) if value.get("mapping").is_some() => vec![value.clone()],
+        _ => return Err("Unrecognized import format".to_string()),
+    };
+
+    // A bare OpenAI `messages` array
+    if items.iter().all(|item| item.get("role").is_some()) {
+        return Ok(ParsedFile::Conversations(vec![Ok(ParsedConversation {
+            title: None,
+            created_at: None,
+            messages: parse_openai_messages(&items, None),
+        })]));
+    }
+
+    // ChatGPT `conversations.json`
+    Ok(ParsedFile::Conversations(
+        items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                parse_chatgpt_conversation(item).ok_or_else(|| {
+                    let title = item.get("title").and_then(|t| t.as_str()).unwrap_or("untitled");
+                    format!("conversation {} \"{}\": unrecognized structure", idx + 1, title)
+                })
+            })
+            .collect(),
+    ))
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+/// Normalize a model name to an OpenRouter-style ID so imported chats can be rerun
+fn normalize_model_id(model: Option<&str>) -> String {
+    match model.map(str::trim).filter(|m| !m.is_empty()) {
+        Some(model) if model.contains('/') => model.to_string(),
+        Some(model) if ["gpt", "chatgpt", "o1", "o3", "o4"].iter().any(|p| model.starts_with(p)) => {
+            format!("openai/{}", model)
+        }
+        Some(model) => model.to_string(),
+        None => IMPORTED_MODEL_FALLBACK.to_string(),
+    }
+}
+
+/// Convert a flat list of messages into a Standard history
+fn to_standard_history(messages: &[ParsedMessage]) -> StandardHistory {
+    let mut system_prompt = None;
+    let mut user_messages: Vec<String> = Vec::new();
+    let mut model_responses: Vec<Vec<ModelResponse>> = Vec::new();
+    let mut selected_models: Vec<String> = Vec::new();
+
+    for message in messages {
+        let content = message.content.trim();
+        if content.is_empty() {
+            continue;
+        }
+        match message.role.as_str() {
+            "system" | "developer" if system_prompt.is_none() => {
+                system_prompt = Some(content.to_string());
+            }
+            "user" => {
+                user_messages.push(content.to_string());
+                model_responses.push(Vec::new());
+            }
+            "assistant" => {
+                // Assistant text before any user turn has nothing to answer
+                let Some(responses) = model_responses.last_mut() else { continue };
+                let model_id = normalize_model_id(message.model.as_deref());
+                if !selected_models.contains(&model_id) {
+                    selected_models.push(model_id.clone());
+                }
+                if let Some(existing) = responses.iter_mut().find(|r| r.model_id == model_id) {
+                    existing.content.push_str("\n\n");
+                    existing.content.push_str(content);
+                } else {
+                    responses.push(ModelResponse {
+                        model_id,
+                        content: content.to_string(),
+                        error_message: None,
+                        timing: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut conversation_history = ConversationHistory {
+        single_model: Vec::new(),
+        multi_model: HashMap::new(),
+    };
+    for model_id in &selected_models {
+        let turns: Vec<(String, String)> = user_messages
+            .iter()
+            .zip(model_responses.iter())
+            .filter_map(|(user, responses)| {
+                responses
+                    .iter()
+                    .find(|r| &r.model_id == model_id)
+                    .map(|r| (user.clone(), r.content.clone()))
+            })
+            .collect();
+        if selected_models.len() == 1 {
+            conversation_history.single_model = turns;
+        } else {
+            conversation_history.multi_model.insert(model_id.clone(), turns);
+        }
+    }
+
+    StandardHistory {
+        user_messages,
+        model_responses,
+        selected_models,
+        system_prompt: system_prompt.unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.to_string()),
+        conversation_history,
+        turns: Vec::new(),
+        active_path: Vec::new(),
+        context: Vec::new(),
+    }
+}
+
+/// Stable 64-bit FNV-1a hash, used to recognise already-imported conversations
+fn fnv1a(bytes: &[u8], mut hash: u64) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Hash of a history's user-visible content, independent of titles and timestamps
+pub fn content_hash(history: &ChatHistory) -> u64 {
+    let mut hash = 0xcbf29ce484222325;
+    let mut feed = |text: &str| {
+        hash = fnv1a(text.trim().as_bytes(), hash);
+        hash = fnv1a(&[0x1f], hash);
+    };
+
+    match history {
+        ChatHistory::Standard(h) => {
+            feed("standard");
+            for (idx, user) in h.user_messages.iter().enumerate() {
+                feed(user);
+                for response in h.model_responses.get(idx).into_iter().flatten() {
+                    feed(&response.content);
+                }
+            }
+        }
+        ChatHistory::PvP(h) => {
+            feed("pvp");
+            for round in &h.rounds {
+                feed(&round.user_message);
+                feed(&round.bot1_response.content);
+                feed(&round.bot2_response.content);
+            }
+        }
+        ChatHistory::Collaborative(h) => {
+            feed("collaborative");
+            for round in &h.rounds {
+                feed(&round.user_message);
+                for response in &round.model_responses {
+                    feed(&response.content);
+                }
+            }
+        }
+        ChatHistory::Competitive(h) => {
+            feed("competitive");
+            for round in &h.rounds {
+                feed(&round.user_question);
+                for proposal in &round.phase1_proposals {
+                    feed(&proposal.content);
+                }
+            }
+        }
+        ChatHistory::LLMChoice(h) => {
+            feed("llm_choice");
+            for round in &h.rounds {
+                feed(&round.user_message);
+                feed(round.content.as_deref().unwrap_or_default());
+            }
+        }
+    }
+
+    hash
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+pub struct ChatImport;
+
+impl ChatImport {
+    /// Import every conversation found in the file at `path`.
+    /// Supports ChatGPT `conversations.json`, OpenAI `messages` JSON and GTLLM export bundles.
+    pub fn import_file(path: &Path) -> Result<ImportReport, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read import file: {}", e))?;
+        Self::import_str(&contents)
+    }
+
+    /// Import conversations from raw file contents
+    pub fn import_str(contents: &str) -> Result<ImportReport, String> {
+        let parsed = parse_import_file(contents)?;
+
+        let mut known_hashes: HashSet<u64> = ChatHistory::list_sessions()?
+            .iter()
+            .filter_map(|s| ChatHistory::load_session(&s.id).ok())
+            .map(|s| content_hash(&s.history))
+            .collect();
+
+        let mut report = ImportReport::default();
+        let mut candidates: Vec<(String, SessionData)> = Vec::new();
+
+        match parsed {
+            ParsedFile::Bundle(bundle) => {
+                for data in bundle.sessions {
+                    let label = format!("\"{}\"", data.session.title);
+                    candidates.push((label, data));
+                }
+            }
+            ParsedFile::Conversations(conversations) => {
+                for (idx, conversation) in conversations.into_iter().enumerate() {
+                    let conversation = match conversation {
+                        Ok(conversation) => conversation,
+                        Err(reason) => {
+                            report.skipped.push(reason);
+                            continue;
+                        }
+                    };
+                    let history = ChatHistory::Standard(to_standard_history(&conversation.messages));
+                    let title = conversation
+                        .title
+                        .filter(|t| !t.trim().is_empty())
+                        .unwrap_or_else(|| ChatHistory::generate_chat_summary(&history));
+                    let timestamp = conversation
+                        .created_at
+                        .map(|t| t.to_string())
+                        .unwrap_or_else(ChatHistory::format_timestamp);
+                    let label = format!("conversation {} \"{}\"", idx + 1, title);
+                    candidates.push((
+                        label,
+                        SessionData {
+                            session: ChatSession {
+                                id: String::new(),
+                                title,
+                                mode: ChatMode::Standard,
+                                timestamp: timestamp.clone(),
+                                stats: SessionStats::default(),
+                                labels: SessionLabels::default(),
+                            },
+                            history,
+                            created_at: timestamp.clone(),
+                            updated_at: timestamp,
+                            forked_from: None,
+                            renamed: false,
+                            title_generated: false,
+                            checkpoint: None,
+                        },
+                    ));
+                }
+            }
+        }
+
+        for (label, mut data) in candidates {
+            if !ChatHistory::has_content(&data.history) {
+                report.skipped.push(format!("{}: no messages", label));
+                continue;
+            }
+            if !known_hashes.insert(content_hash(&data.history)) {
+                report.skipped.push(format!("{}: already imported", label));
+                continue;
+            }
+
+            data.session.id = ChatHistory::new_session_id(&data.session.timestamp);
+            match ChatHistory::save_session(&data) {
+                Ok(saved) => report.imported.push(saved),
+                Err(e) => report.skipped.push(format!("{}: {}", label, e)),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openai_messages() {
+        let json = r#"{"model":"gpt-4o","messages":[
+            {"role":"system","content":"Be terse."},
+            {"role":"user","content":"Hi"},
+            {"role":"assistant","content":[{"type":"text","text":"Hello"}]}
+        ]}"#;
+        let Ok(ParsedFile::Conversations(conversations)) = parse_import_file(json) else {
+            panic!("expected conversations");
+        };
+        let conversation = conversations[0].clone().unwrap();
+        let history = to_standard_history(&conversation.messages);
+        assert_eq!(history.system_prompt, "Be terse.");
+        assert_eq!(history.user_messages, vec!["Hi".to_string()]);
+        assert_eq!(history.selected_models, vec!["openai/gpt-4o".to_string()]);
+        assert_eq!(history.model_responses[0][0].content, "Hello");
+        assert_eq!(history.conversation_history.single_model.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_chatgpt_export_follows_current_branch() {
+        let json = r#"[{"title":"Trip","create_time":1700000000.5,"current_node":"c","mapping":{
+            "root":{"message":null,"parent":null,"children":["a"]},
+            "a":{"message":{"author":{"role":"user"},"content":{"parts":["Plan a trip"]}},"parent":"root","children":["b","x"]},
+            "x":{"message":{"author":{"role":"assistant"},"content":{"parts":["Old draft"]}},"parent":"a","children":[]},
+            "b":{"message":{"author":{"role":"assistant"},"content":{"parts":["Sure"]},"metadata":{"model_slug":"gpt-4"}},"parent":"a","children":["c"]},
+            "c":{"message":{"author":{"role":"user"},"content":{"parts":["Thanks"]}},"parent":"b","children":[]}
+        }}]"#;
+        let Ok(ParsedFile::Conversations(conversations)) = parse_import_file(json) else {
+            panic!("expected conversations");
+        };
+        let conversation = conversations[0].clone().unwrap();
+        assert_eq!(conversation.title.as_deref(), Some("Trip"));
+        assert_eq!(conversation.created_at, Some(1700000000));
+        let history = to_standard_history(&conversation.messages);
+        assert_eq!(history.user_messages, vec!["Plan a trip".to_string(), "Thanks".to_string()]);
+        assert_eq!(history.model_responses[0][0].content, "Sure");
+        assert!(history.model_responses[1].is_empty());
+    }
+
+    #[test]
+    fn test_parse_chatgpt_export_without_current_node_takes_the_newest_leaf() {
+        let json = r#"[{"title":"Trip","mapping":{
+            "root":{"message":null,"parent":null,"children":["a"]},
+            "a":{"message":{"author":{"role":"user"},"content":{"parts":["Plan a trip"]},"create_time":1.0},"parent":"root","children":["b","b0"]},
+            "b":{"message":{"author":{"role":"assistant"},"content":{"parts":["Sure"]},"create_time":3.0},"parent":"a","children":["c"]},
+            "c":{"message":{"author":{"role":"user"},"content":{"parts":["Thanks"]},"create_time":4.0},"parent":"b","children":[]},
+            "b0":{"message":{"author":{"role":"assistant"},"content":{"parts":["Old draft"]},"create_time":2.0},"parent":"a","children":[]}
+        }}]"#;
+        let Ok(ParsedFile::Conversations(conversations)) = parse_import_file(json) else {
+            panic!("expected conversations");
+        };
+        let history = to_standard_history(&conversations[0].clone().unwrap().messages);
+        assert_eq!(history.user_messages, vec!["Plan a trip".to_string(), "Thanks".to_string()]);
+        assert_eq!(history.model_responses[0][0].content, "Sure");
+    }
+
+    #[test]
+    fn test_content_hash_ignores_whitespace_only_changes() {
+        let messages = |text: &str| {
+            vec![ParsedMessage { role: "user".to_string(), content: text.to_string(), model: None }]
+        };
+        let a = ChatHistory::Standard(to_standard_history(&messages("Hello")));
+        let b = ChatHistory::Standard(to_standard_history(&messages("  Hello \n")));
+        let c = ChatHistory::Standard(to_standard_history(&messages("Goodbye")));
+        assert_eq!(content_hash(&a), content_hash(&b));
+        assert_ne!(content_hash(&a), content_hash(&c));
+    }
+}
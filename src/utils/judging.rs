@@ -0,0 +1,443 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Scores are asked for on this scale
+const MIN_SCORE: f64 = 1.0;
+const MAX_SCORE: f64 = 10.0;
+
+/// One thing the PvP moderator scores, weighted against the other criteria
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RubricCriterion {
+    pub name: String,
+    pub weight: f64,
+}
+
+/// Criteria the PvP moderator scores both responses on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Rubric {
+    pub criteria: Vec<RubricCriterion>,
+}
+
+impl Default for Rubric {
+    fn default() -> Self {
+        let criterion = |name: &str, weight: f64| RubricCriterion { name: name.to_string(), weight };
+        Self {
+            criteria: vec![
+                criterion("Accuracy", 3.0),
+                criterion("Completeness", 2.0),
+                criterion("Clarity", 2.0),
+                criterion("Conciseness", 1.0),
+            ],
+        }
+    }
+}
+
+/// Controls for the moderator's bias towards the response it reads first
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct JudgingOptions {
+    /// Show responses as "Response A/B" without the model IDs
+    pub anonymize: bool,
+    /// Pick at random which response the moderator reads first
+    pub randomize_order: bool,
+    /// Judge a second time with the order swapped and flag verdicts that change
+    pub swap_check: bool,
+}
+
+/// How a panel's verdicts are combined into the round's verdict
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum PanelAggregation {
+    /// The winner picked by the most judges
+    #[default]
+    Majority,
+    /// The bot with the higher mean weighted rubric score
+    MeanScore,
+}
+
+impl PanelAggregation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PanelAggregation::Majority => "Majority vote",
+            PanelAggregation::MeanScore => "Mean rubric score",
+        }
+    }
+}
+
+/// Moderators judging each PvP round
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct JudgePanel {
+    pub judges: Vec<String>,
+    pub aggregation: PanelAggregation,
+}
+
+impl JudgePanel {
+    /// Combine the judges' verdicts. Each rubric criterion's scores are averaged over the
+    /// judges that scored it, whichever way the winner is picked; criteria no judge scored
+    /// are left out. `None` without any verdicts.
+    pub fn aggregate(&self, rubric: &Rubric, verdicts: &[&Verdict], bot1_id: &str, bot2_id: &str) -> Option<Verdict> {
+        if verdicts.is_empty() {
+            return None;
+        }
+        let scores: Vec<CriterionScore> = rubric
+            .criteria
+            .iter()
+            .filter_map(|criterion| {
+                let scored: Vec<&CriterionScore> = verdicts
+                    .iter()
+                    .filter_map(|v| v.scores.iter().find(|s| s.criterion.eq_ignore_ascii_case(&criterion.name)))
+                    .collect();
+                if scored.is_empty() {
+                    return None;
+                }
+                let mean = |score: fn(&CriterionScore) -> f64| {
+                    scored.iter().map(|s| score(s)).sum::<f64>() / scored.len() as f64
+                };
+                Some(CriterionScore {
+                    criterion: criterion.name.clone(),
+                    weight: criterion.weight,
+                    bot1: mean(|s| s.bot1),
+                    bot2: mean(|s| s.bot2),
+                })
+            })
+            .collect();
+        let mut verdict = Verdict { winner: None, scores };
+
+        verdict.winner = match (self.aggregation, verdict.totals()) {
+            (PanelAggregation::MeanScore, Some((bot1, bot2))) if bot1 > bot2 => Some(bot1_id.to_string()),
+            (PanelAggregation::MeanScore, Some((bot1, bot2))) if bot2 > bot1 => Some(bot2_id.to_string()),
+            (PanelAggregation::MeanScore, Some(_)) => None,
+            // Without rubric scores the mean can't separate the bots, so fall back to the vote
+            _ => majority(verdicts),
+        };
+        Some(verdict)
+    }
+}
+
+/// The outcome picked by more judges than any other, a tie counting as an outcome;
+/// `None` when the top outcomes are level
+fn majority(verdicts: &[&Verdict]) -> Option<String> {
+    let mut counts: Vec<(Option<&String>, usize)> = Vec::new();
+    for verdict in verdicts {
+        match counts.iter_mut().find(|(winner, _)| *winner == verdict.winner.as_ref()) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((verdict.winner.as_ref(), 1)),
+        }
+    }
+    counts.sort_by_key(|c| std::cmp::Reverse(c.1));
+    match counts.as_slice() {
+        [(winner, top), rest @ ..] if rest.first().is_none_or(|(_, next)| next < top) => winner.cloned(),
+        _ => None,
+    }
+}
+
+/// Share of judge pairs that picked the same outcome, or `None` with fewer than two verdicts
+pub fn agreement(verdicts: &[&Verdict]) -> Option<f64> {
+    let mut pairs = 0;
+    let mut agreeing = 0;
+    for (i, a) in verdicts.iter().enumerate() {
+        for b in &verdicts[i + 1..] {
+            pairs += 1;
+            if a.winner == b.winner {
+                agreeing += 1;
+            }
+        }
+    }
+    (pairs > 0).then(|| agreeing as f64 / pairs as f64)
+}
+
+/// How the two bot responses are laid out for one judging pass
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Presentation {
+    /// Bot 2 is shown as Response A
+    pub bot2_first: bool,
+    pub anonymous: bool,
+}
+
+impl Presentation {
+    /// The same layout with the responses in the opposite order
+    pub fn swapped(self) -> Self {
+        Self { bot2_first: !self.bot2_first, ..self }
+    }
+
+    /// The bots in the order the moderator sees them
+    pub fn order<T>(&self, bot1: T, bot2: T) -> (T, T) {
+        if self.bot2_first {
+            (bot2, bot1)
+        } else {
+            (bot1, bot2)
+        }
+    }
+
+    /// Prompt asking the moderator to judge both responses against `rubric`.
+    /// `bot1` and `bot2` are `(model ID, response)` pairs.
+    pub fn moderator_prompt(&self, question: &str, bot1: (&str, &str), bot2: (&str, &str), rubric: &Rubric) -> String {
+        let (first, second) = self.order(bot1, bot2);
+        let heading = |label: &str, model_id: &str| {
+            if self.anonymous {
+                format!("Response {}", label)
+            } else {
+                format!("Response {} ({})", label, model_id)
+            }
+        };
+        format!(
+            "User Question: {}\n\n\
+            {}:\n{}\n\n\
+            {}:\n{}\n\n\
+            Please evaluate both responses and determine which one is better. \
+            Explain your reasoning and declare a winner. Be specific about what makes \
+            one response superior to the other.\n\n{}",
+            question,
+            heading("A", first.0),
+            first.1,
+            heading("B", second.0),
+            second.1,
+            rubric.instructions()
+        )
+    }
+}
+
+/// A moderator's score for both bots on one criterion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CriterionScore {
+    pub criterion: String,
+    pub weight: f64,
+    pub bot1: f64,
+    pub bot2: f64,
+}
+
+/// Machine-readable outcome of a PvP judgment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Verdict {
+    /// Model ID of the better response; `None` for a tie
+    pub winner: Option<String>,
+    pub scores: Vec<CriterionScore>,
+}
+
+impl Verdict {
+    /// Weighted mean score of each bot, or `None` without any scores
+    pub fn totals(&self) -> Option<(f64, f64)> {
+        let weight: f64 = self.scores.iter().map(|s| s.weight).sum();
+        if self.scores.is_empty() || weight <= 0.0 {
+            return None;
+        }
+        let total = |score: fn(&CriterionScore) -> f64| {
+            self.scores.iter().map(|s| s.weight * score(s)).sum::<f64>() / weight
+        };
+        Some((total(|s| s.bot1), total(|s| s.bot2)))
+    }
+}
+
+impl Rubric {
+    /// Trim names and drop criteria without a name or a positive weight
+    pub fn cleaned(self) -> Self {
+        Self {
+            criteria: self
+                .criteria
+                .into_iter()
+                .map(|c| RubricCriterion { name: c.name.trim().to_string(), weight: c.weight })
+                .filter(|c| !c.name.is_empty() && c.weight.is_finite() && c.weight > 0.0)
+                .collect(),
+        }
+    }
+
+    /// Appended to the moderator prompt so the verdict ends with a JSON block
+    pub fn instructions(&self) -> String {
+        let example_scores: Vec<String> = self
+            .criteria
+            .iter()
+            .map(|c| format!("{:?}: {{\"A\": 7, \"B\": 5}}", c.name))
+            .collect();
+        let mut text = String::new();
+        if !self.criteria.is_empty() {
+            text.push_str(&format!(
+                "Score both responses from {} to {} on each of these criteria (a higher weight counts for more):\n",
+                MIN_SCORE, MAX_SCORE
+            ));
+            for c in &self.criteria {
+                text.push_str(&format!("- {} (weight {})\n", c.name, c.weight));
+            }
+            text.push('\n');
+        }
+        text.push_str(&format!(
+            "After your explanation, end with a JSON block in exactly this form, using \"A\", \"B\" or \"tie\" as the winner:\n\
+            ```json\n{{\"scores\": {{{}}}, \"winner\": \"A\"}}\n```",
+            example_scores.join(", ")
+        ));
+        text
+    }
+
+    /// Read the JSON block at the end of a judgment made with `presentation`.
+    /// Returns the judgment text without the block and the verdict, or `None` when the
+    /// moderator didn't follow the format.
+    pub fn parse_verdict(
+        &self,
+        judgment: &str,
+        bot1_id: &str,
+        bot2_id: &str,
+        presentation: Presentation,
+    ) -> Option<(String, Verdict)> {
+        let (bot1_label, bot2_label) = presentation.order("A", "B");
+        let (range, value) = find_json_block(judgment)?;
+        let object = value.as_object()?;
+
+        let scores_by_name = object.get("scores").and_then(Value::as_object);
+        let scores: Vec<CriterionScore> = self
+            .criteria
+            .iter()
+            .filter_map(|c| {
+                let entry = scores_by_name?
+                    .iter()
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case(&c.name))?
+                    .1;
+                Some(CriterionScore {
+                    criterion: c.name.clone(),
+                    weight: c.weight,
+                    bot1: score(entry, bot1_label)?,
+                    bot2: score(entry, bot2_label)?,
+                })
+            })
+            .collect();
+
+        let stated_winner = object.get("winner").and_then(Value::as_str).map(|w| {
+            w.trim()
+                .trim_start_matches("Response")
+                .trim_start_matches("response")
+                .trim()
+                .to_ascii_uppercase()
+        });
+        let winner = match stated_winner.as_deref() {
+            Some(label) if label == bot1_label => Some(bot1_id.to_string()),
+            Some(label) if label == bot2_label => Some(bot2_id.to_string()),
+            Some("TIE") | Some("DRAW") => None,
+            _ => return None,
+        };
+
+        let before = judgment[..range.start].trim();
+        let after = judgment[range.end..].trim();
+        let text = if after.is_empty() { before.to_string() } else { format!("{}\n\n{}", before, after) };
+        Some((text.trim().to_string(), Verdict { winner, scores }))
+    }
+}
+
+/// Score for one side of a criterion, clamped to the scale
+fn score(entry: &Value, side: &str) -> Option<f64> {
+    let (_, value) = entry.as_object()?.iter().find(|(key, _)| key.trim().eq_ignore_ascii_case(side))?;
+    let number = value.as_f64().or_else(|| value.as_str()?.trim().parse().ok())?;
+    Some(number.clamp(MIN_SCORE, MAX_SCORE))
+}
+
+/// The last fenced code block that parses as JSON, or else the last bare `{...}` span
+fn find_json_block(text: &str) -> Option<(std::ops::Range<usize>, Value)> {
+    let fences: Vec<usize> = text.match_indices("```").map(|(i, _)| i).collect();
+    // Consecutive fences; a span of prose between two blocks won't parse as JSON
+    for pair in fences.windows(2).rev() {
+        let (open, close) = (pair[0], pair[1]);
+        let body = &text[open + 3..close];
+        let body = body.strip_prefix("json").or_else(|| body.strip_prefix("JSON")).unwrap_or(body);
+        if let Ok(value) = serde_json::from_str::<Value>(body.trim()) {
+            return Some((open..close + 3, value));
+        }
+    }
+
+    let end = text.rfind('}')?;
+    text[..end]
+        .match_indices('{')
+        .map(|(start, _)| start)
+        .find_map(|start| {
+            let value = serde_json::from_str::<Value>(&text[start..=end]).ok()?;
+            value.get("winner").is_some().then_some((start..end + 1, value))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict_reads_scores_and_strips_block() {
+        let rubric = Rubric::default();
+        let judgment = "B cites its sources.\n\n```json\n{\"scores\": {\"accuracy\": {\"A\": 6, \"B\": 9}, \"Clarity\": {\"A\": \"8\", \"B\": 12}}, \"winner\": \"Response B\"}\n```";
+        let (text, verdict) = rubric.parse_verdict(judgment, "a/one", "b/two", Presentation::default()).unwrap();
+
+        assert_eq!(text, "B cites its sources.");
+        assert_eq!(verdict.winner.as_deref(), Some("b/two"));
+        assert_eq!(verdict.scores.len(), 2);
+        assert_eq!(verdict.scores[0], CriterionScore { criterion: "Accuracy".to_string(), weight: 3.0, bot1: 6.0, bot2: 9.0 });
+        assert_eq!(verdict.scores[1].bot2, 10.0);
+        assert_eq!(verdict.totals(), Some(((3.0 * 6.0 + 2.0 * 8.0) / 5.0, (3.0 * 9.0 + 2.0 * 10.0) / 5.0)));
+    }
+
+    #[test]
+    fn test_parse_verdict_accepts_bare_json_and_ties() {
+        let rubric = Rubric::default();
+        let (text, verdict) = rubric
+            .parse_verdict("Both are fine. {\"scores\": {}, \"winner\": \"tie\"}", "a/one", "b/two", Presentation::default())
+            .unwrap();
+        assert_eq!(text, "Both are fine.");
+        assert_eq!(verdict.winner, None);
+        assert_eq!(verdict.totals(), None);
+
+        assert!(rubric.parse_verdict("The winner is a/one.", "a/one", "b/two", Presentation::default()).is_none());
+        assert!(rubric.parse_verdict("```json\n{\"winner\": \"C\"}\n```", "a/one", "b/two", Presentation::default()).is_none());
+    }
+
+    #[test]
+    fn test_swapped_presentation_maps_labels_back_to_bots() {
+        let rubric = Rubric::default();
+        let presentation = Presentation { bot2_first: true, anonymous: true };
+        let prompt = presentation.moderator_prompt("Q?", ("a/one", "first answer"), ("b/two", "second answer"), &rubric);
+        assert!(prompt.find("second answer") < prompt.find("first answer"));
+        assert!(!prompt.contains("a/one") && !prompt.contains("b/two"));
+
+        let judgment = "```json\n{\"scores\": {\"Accuracy\": {\"A\": 9, \"B\": 4}}, \"winner\": \"A\"}\n```";
+        let (_, verdict) = rubric.parse_verdict(judgment, "a/one", "b/two", presentation).unwrap();
+        assert_eq!(verdict.winner.as_deref(), Some("b/two"));
+        assert_eq!((verdict.scores[0].bot1, verdict.scores[0].bot2), (4.0, 9.0));
+    }
+
+    #[test]
+    fn test_panel_aggregates_by_majority_or_mean_score() {
+        let score = |criterion: &str, bot1: f64, bot2: f64| CriterionScore { criterion: criterion.to_string(), weight: 1.0, bot1, bot2 };
+        let verdict = |winner: Option<&str>, scores: Vec<CriterionScore>| Verdict { winner: winner.map(str::to_string), scores };
+        // The first judge skipped Clarity, so its criteria must not decide the panel's
+        let verdicts = [
+            verdict(Some("a/one"), vec![score("Accuracy", 6.0, 5.0)]),
+            verdict(Some("a/one"), vec![score("Accuracy", 7.0, 6.0), score("Clarity", 8.0, 4.0)]),
+            verdict(Some("b/two"), vec![score("Accuracy", 2.0, 10.0), score("Clarity", 6.0, 8.0)]),
+        ];
+        let refs: Vec<&Verdict> = verdicts.iter().collect();
+        let rubric = Rubric {
+            criteria: ["Accuracy", "Clarity", "Depth"]
+                .map(|name| RubricCriterion { name: name.to_string(), weight: 2.0 })
+                .to_vec(),
+        };
+
+        let mut panel = JudgePanel { judges: Vec::new(), aggregation: PanelAggregation::Majority };
+        let combined = panel.aggregate(&rubric, &refs, "a/one", "b/two").unwrap();
+        assert_eq!(combined.winner.as_deref(), Some("a/one"));
+        assert_eq!(combined.scores.len(), 2);
+        assert_eq!((combined.scores[0].bot1, combined.scores[0].bot2, combined.scores[0].weight), (5.0, 7.0, 2.0));
+        assert_eq!((combined.scores[1].criterion.as_str(), combined.scores[1].bot1, combined.scores[1].bot2), ("Clarity", 7.0, 6.0));
+
+        panel.aggregation = PanelAggregation::MeanScore;
+        assert_eq!(panel.aggregate(&rubric, &refs, "a/one", "b/two").unwrap().winner.as_deref(), Some("b/two"));
+        assert_eq!(panel.aggregate(&rubric, &[], "a/one", "b/two"), None);
+
+        assert_eq!(majority(&refs[1..]), None);
+        assert_eq!(agreement(&refs), Some(1.0 / 3.0));
+        assert_eq!(agreement(&refs[..1]), None);
+    }
+
+    #[test]
+    fn test_cleaned_drops_unnamed_and_unweighted_criteria() {
+        let rubric = Rubric {
+            criteria: vec![
+                RubricCriterion { name: "  Depth ".to_string(), weight: 2.0 },
+                RubricCriterion { name: " ".to_string(), weight: 1.0 },
+                RubricCriterion { name: "Style".to_string(), weight: 0.0 },
+            ],
+        };
+        assert_eq!(rubric.cleaned().criteria, vec![RubricCriterion { name: "Depth".to_string(), weight: 2.0 }]);
+    }
+}
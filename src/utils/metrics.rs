@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::utils::{ChatHistory, ModelResponse, SessionData};
+
+/// Rough characters per token, used because streamed chunks carry no token counts
+const CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Timing of one streamed request, measured from when it was sent
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ResponseTiming {
+    /// Time to first token; absent when nothing was streamed
+    pub ttft_ms: Option<u64>,
+    pub total_ms: u64,
+    pub output_chars: usize,
+}
+
+impl ResponseTiming {
+    pub fn time_to_first_token(&self) -> Option<Duration> {
+        self.ttft_ms.map(Duration::from_millis)
+    }
+
+    pub fn total_time(&self) -> Duration {
+        Duration::from_millis(self.total_ms)
+    }
+
+    /// Estimated output tokens per second, counted from the first token
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        let generation_ms = self.total_ms.saturating_sub(self.ttft_ms?);
+        if generation_ms == 0 || self.output_chars == 0 {
+            return None;
+        }
+        Some(self.output_chars as f64 / CHARS_PER_TOKEN / (generation_ms as f64 / 1000.0))
+    }
+
+    pub fn format_duration(duration: Duration) -> String {
+        let millis = duration.as_millis();
+        if millis < 1000 {
+            format!("{}ms", millis)
+        } else {
+            let secs = duration.as_secs_f64();
+            if secs < 60.0 {
+                format!("{:.2}s", secs)
+            } else {
+                format!("{:.1}m", secs / 60.0)
+            }
+        }
+    }
+}
+
+/// Records timing as a stream is consumed
+#[derive(Debug, Clone)]
+pub(crate) struct TimingRecorder {
+    sent_at: Instant,
+    first_token_at: Option<Instant>,
+    output_chars: usize,
+}
+
+impl TimingRecorder {
+    pub(crate) fn start() -> Self {
+        Self { sent_at: Instant::now(), first_token_at: None, output_chars: 0 }
+    }
+
+    pub(crate) fn content(&mut self, chunk: &str) {
+        self.first_token_at.get_or_insert_with(Instant::now);
+        self.output_chars += chunk.chars().count();
+    }
+
+    pub(crate) fn finish(&self) -> ResponseTiming {
+        ResponseTiming {
+            ttft_ms: self
+                .first_token_at
+                .map(|at| at.duration_since(self.sent_at).as_millis() as u64),
+            total_ms: self.sent_at.elapsed().as_millis() as u64,
+            output_chars: self.output_chars,
+        }
+    }
+}
+
+/// Latency percentiles for one model across saved sessions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelLatency {
+    pub model_id: String,
+    pub samples: usize,
+    pub ttft_p50: Option<Duration>,
+    pub ttft_p95: Option<Duration>,
+    pub tokens_per_second_p50: Option<f64>,
+    pub tokens_per_second_p95: Option<f64>,
+}
+
+/// Per-model latency aggregated over every saved session
+pub struct LatencyStats;
+
+impl LatencyStats {
+    /// Load every saved session and summarize its recorded timings, slowest p50 TTFT first
+    pub fn collect() -> Result<Vec<ModelLatency>, String> {
+        let mut samples: BTreeMap<String, Vec<ResponseTiming>> = BTreeMap::new();
+        for session in ChatHistory::list_sessions()? {
+            match ChatHistory::load_session(&session.id) {
+                Ok(data) => add_session_timings(&data, &mut samples),
+                Err(e) => eprintln!("Skipping session {} in latency stats: {}", session.id, e),
+            }
+        }
+        Ok(summarize(samples))
+    }
+}
+
+fn add_session_timings(data: &SessionData, samples: &mut BTreeMap<String, Vec<ResponseTiming>>) {
+    let mut push = |model_id: &str, timing: &Option<ResponseTiming>| {
+        if let Some(timing) = timing {
+            samples.entry(model_id.to_string()).or_default().push(timing.clone());
+        }
+    };
+
+    match &data.history {
+        ChatHistory::Standard(h) => {
+            // Sessions saved before branching only have the flat list
+            let responses: Vec<&ModelResponse> = if h.turns.is_empty() {
+                h.model_responses.iter().flatten().collect()
+            } else {
+                h.turns.iter().flat_map(|turn| &turn.model_responses).collect()
+            };
+            for response in responses {
+                push(&response.model_id, &response.timing);
+            }
+        }
+        ChatHistory::PvP(h) => {
+            for round in &h.rounds {
+                push(&round.bot1_response.model_id, &round.bot1_response.timing);
+                push(&round.bot2_response.model_id, &round.bot2_response.timing);
+                for turn in &round.turns {
+                    push(&turn.bot1_response.model_id, &turn.bot1_response.timing);
+                    push(&turn.bot2_response.model_id, &turn.bot2_response.timing);
+                }
+                if let Some(judgment) = &round.moderator_judgment {
+                    if judgment.judges.is_empty() {
+                        if let Some(moderator) = &h.moderator_model {
+                            push(moderator, &judgment.timing);
+                        }
+                    }
+                    for judge in &judgment.judges {
+                        push(&judge.model_id, &judge.timing);
+                    }
+                }
+            }
+        }
+        ChatHistory::Collaborative(h) => {
+            for round in &h.rounds {
+                for response in &round.model_responses {
+                    push(&response.model_id, &response.timing);
+                }
+            }
+        }
+        ChatHistory::Competitive(h) => {
+            for round in &h.rounds {
+                for proposal in &round.phase1_proposals {
+                    push(&proposal.model_id, &proposal.timing);
+                }
+                for vote in &round.phase2_votes {
+                    push(&vote.voter_id, &vote.timing);
+                }
+                for vote in round.runoffs.iter().flat_map(|r| &r.votes) {
+                    push(&vote.voter_id, &vote.timing);
+                }
+            }
+        }
+        ChatHistory::LLMChoice(h) => {
+            for round in &h.rounds {
+                for decision in &round.decisions {
+                    push(&decision.model_id, &decision.timing);
+                }
+                if let Some(collab) = &round.collaborative {
+                    for response in collab.initial_responses.iter().chain(&collab.reviews).chain(&collab.consensus) {
+                        push(&response.model_id, &response.timing);
+                    }
+                }
+                if let Some(comp) = &round.competitive {
+                    for proposal in &comp.proposals {
+                        push(&proposal.model_id, &proposal.timing);
+                    }
+                    for vote in &comp.votes {
+                        push(&vote.voter_id, &vote.timing);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn summarize(samples: BTreeMap<String, Vec<ResponseTiming>>) -> Vec<ModelLatency> {
+    let mut stats: Vec<ModelLatency> = samples
+        .into_iter()
+        .map(|(model_id, timings)| {
+            let mut ttft: Vec<f64> = timings.iter().filter_map(|t| t.ttft_ms).map(|ms| ms as f64).collect();
+            let mut tps: Vec<f64> = timings.iter().filter_map(ResponseTiming::tokens_per_second).collect();
+            ModelLatency {
+                model_id,
+                samples: timings.len(),
+                ttft_p50: percentile(&mut ttft, 50.0).map(|ms| Duration::from_millis(ms as u64)),
+                ttft_p95: percentile(&mut ttft, 95.0).map(|ms| Duration::from_millis(ms as u64)),
+                tokens_per_second_p50: percentile(&mut tps, 50.0),
+                tokens_per_second_p95: percentile(&mut tps, 95.0),
+            }
+        })
+        .collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.ttft_p50));
+    stats
+}
+
+/// Nearest-rank percentile
+fn percentile(values: &mut [f64], pct: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let rank = ((pct / 100.0) * values.len() as f64).ceil() as usize;
+    Some(values[rank.clamp(1, values.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let mut values: Vec<f64> = (1..=20).map(|v| v as f64).collect();
+        assert_eq!(percentile(&mut values, 50.0), Some(10.0));
+        assert_eq!(percentile(&mut values, 95.0), Some(19.0));
+        assert_eq!(percentile(&mut [], 50.0), None);
+        assert_eq!(percentile(&mut [7.0], 95.0), Some(7.0));
+    }
+
+    #[test]
+    fn test_tokens_per_second_counts_from_first_token() {
+        let timing = ResponseTiming { ttft_ms: Some(500), total_ms: 1500, output_chars: 400 };
+        assert_eq!(timing.tokens_per_second(), Some(100.0));
+
+        let empty = ResponseTiming { ttft_ms: None, total_ms: 800, output_chars: 0 };
+        assert_eq!(empty.tokens_per_second(), None);
+    }
+}
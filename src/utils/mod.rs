@@ -1,18 +1,52 @@
+mod backup;
 mod chat_history;
+mod checkpoint;
+mod context;
+mod debate;
+mod export;
+mod fork;
 mod formatting;
+mod import;
+mod judging;
+mod metrics;
 mod openrouter;
+mod paths;
+mod ratings;
 mod run_control;
+mod search;
+mod session_index;
 mod settings;
 mod theme;
+mod titles;
+mod trash;
 mod types;
+mod vault;
+mod voting;
 
+pub use backup::{Backup, RestoreMode};
 pub use chat_history::*;
+pub use checkpoint::{Checkpointer, RunCheckpoint};
+pub use context::{ContextPolicy, PriorRound};
+pub use debate::{DebateFormat, DebateTurn};
+pub use export::{ChatExport, ExportBundle, ExportFormat, BUNDLE_FORMAT};
+pub use fork::ChatFork;
 pub use formatting::{
     parse_inline_elements, parse_message_content, ContentSegment, InlineSegment, ListBlock,
     TableAlignment, TableBlock,
 };
+pub use import::ChatImport;
+pub use judging::{agreement, JudgePanel, JudgingOptions, PanelAggregation, Presentation, Rubric, RubricCriterion, Verdict};
+pub use metrics::{LatencyStats, ModelLatency, ResponseTiming};
 pub use openrouter::*;
+pub use paths::AppDirs;
+pub use ratings::{LeaderboardFilter, ModelRatings, ModelStanding};
 pub use run_control::*;
-pub use settings::{Settings, ThemeMode};
+pub use search::SearchIndex;
+pub use session_index::{local_utc_offset, SessionGroup, SessionIndex, SessionIndexEntry};
+pub use settings::{BackupSchedule, Settings, SidebarPreferences, ThemeMode, TitleSettings};
 pub use theme::Theme;
-pub use types::{ActiveRunRecord, AppView, ArenaMessage, ChatMode, ChatSession, InputSettings, Message, RunStatus};
+pub use titles::TitleGenerator;
+pub use trash::{Trash, TrashEntry};
+pub use vault::{Vault, VaultCommand};
+pub use voting::{mention_positions, TieBreak, VotingMethod};
+pub use types::{ActiveRunRecord, AppView, ArenaMessage, ChatMode, ChatSession, InputSettings, Message, RunStatus, SessionLabels, SessionStats};
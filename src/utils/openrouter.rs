@@ -7,6 +7,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use crate::utils::metrics::{ResponseTiming, TimingRecorder};
 
 // ============================================================================
 // Constants
@@ -61,6 +62,21 @@ impl ChatMessage {
             content: content.into(),
         }
     }
+
+    /// System prompt, earlier exchanges as alternating user/assistant turns, then the new user message
+    pub fn with_context(
+        system: impl Into<String>,
+        context: &[(String, String)],
+        user: impl Into<String>,
+    ) -> Vec<ChatMessage> {
+        let mut messages = vec![Self::system(system)];
+        for (user_msg, assistant_msg) in context {
+            messages.push(Self::user(user_msg.clone()));
+            messages.push(Self::assistant(assistant_msg.clone()));
+        }
+        messages.push(Self::user(user));
+        messages
+    }
 }
 
 // ============================================================================
@@ -197,7 +213,8 @@ impl CreditsData {
 #[derive(Debug, Clone)]
 pub enum StreamEvent {
     Content(String),
-    Done,
+    /// Carries the request's timing once it has passed through the client
+    Done(Option<ResponseTiming>),
     Error(String),
 }
 
@@ -220,6 +237,7 @@ pub struct OpenRouterClient {
 
 struct LimitedStream {
     inner: Pin<Box<dyn Stream<Item = StreamEvent> + Send>>,
+    timing: TimingRecorder,
     _permit: OwnedSemaphorePermit,
 }
 
@@ -230,7 +248,15 @@ impl Stream for LimitedStream {
         mut self: Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        self.inner.as_mut().poll_next(cx)
+        let this = &mut *self;
+        this.inner.as_mut().poll_next(cx).map(|event| match event {
+            Some(StreamEvent::Content(content)) => {
+                this.timing.content(&content);
+                Some(StreamEvent::Content(content))
+            }
+            Some(StreamEvent::Done(_)) => Some(StreamEvent::Done(Some(this.timing.finish()))),
+            other => other,
+        })
     }
 }
 
@@ -370,6 +396,8 @@ impl OpenRouterClient {
             .acquire_owned()
             .await
             .map_err(|e| format!("Failed to acquire concurrency permit: {}", e))?;
+        // Measured from here so time spent queued behind the concurrency limit isn't counted
+        let timing = TimingRecorder::start();
 
         let request = ChatCompletionRequest {
             model: model_id,
@@ -452,6 +480,7 @@ impl OpenRouterClient {
 
         Ok(Box::pin(LimitedStream {
             inner: Box::pin(stream),
+            timing,
             _permit: permit,
         }))
     }
@@ -488,7 +517,7 @@ impl OpenRouterClient {
                             }
 
                             // If we hit Done or Error, stop this stream
-                            if matches!(event, StreamEvent::Done | StreamEvent::Error(_)) {
+                            if matches!(event, StreamEvent::Done(_) | StreamEvent::Error(_)) {
                                 break;
                             }
                         }
@@ -610,7 +639,7 @@ fn parse_sse_line(line: &str, events: &mut Vec<StreamEvent>) {
 
     if let Some(data) = line.strip_prefix("data: ") {
         if data == "[DONE]" {
-            events.push(StreamEvent::Done);
+            events.push(StreamEvent::Done(None));
             return;
         }
 
@@ -636,7 +665,7 @@ fn parse_sse_line(line: &str, events: &mut Vec<StreamEvent>) {
                                 "Stream terminated with error".to_string(),
                             ));
                         } else if !finish_reason.is_empty() {
-                            events.push(StreamEvent::Done);
+                            events.push(StreamEvent::Done(None));
                         }
                     }
                 }
@@ -707,7 +736,7 @@ mod tests {
         let chunk = "data: [DONE]\n";
         let events = parse_sse_chunk(chunk);
         assert_eq!(events.len(), 1);
-        assert!(matches!(events[0], StreamEvent::Done));
+        assert!(matches!(events[0], StreamEvent::Done(_)));
     }
 
     #[test]
@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const APP_DIRNAME: &str = "gtllm";
+/// Set to keep everything in one directory, for portable installs and tests
+const HOME_OVERRIDE_VAR: &str = "GTLLM_HOME";
+/// Files from the pre-XDG `~/.gtllm` directory that belong with the config
+const CONFIG_FILES: [&str; 2] = ["settings.toml", "vault.json"];
+
+/// Where GTLLM keeps its config, data and rebuildable caches
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppDirs {
+    /// settings.toml and the vault header
+    pub config: PathBuf,
+    /// Chats, exports and backups
+    pub data: PathBuf,
+    /// Files that can be regenerated, such as the search index
+    pub cache: PathBuf,
+}
+
+impl AppDirs {
+    /// Resolve the directories from `GTLLM_HOME`, the XDG variables on Linux or the platform defaults
+    pub fn current() -> Result<Self, String> {
+        if let Some(home) = env_path(HOME_OVERRIDE_VAR) {
+            return Ok(Self::portable(home));
+        }
+
+        if cfg!(any(target_os = "windows", target_os = "macos")) {
+            // Windows: %APPDATA%\gtllm, macOS: ~/Library/Application Support/gtllm
+            let dir = dirs::config_dir()
+                .ok_or("Could not find config directory")?
+                .join(APP_DIRNAME);
+            let cache = dirs::cache_dir()
+                .map(|dir| dir.join(APP_DIRNAME))
+                .unwrap_or_else(|| dir.join("cache"));
+            return Ok(Self { config: dir.clone(), data: dir, cache });
+        }
+
+        let home = dirs::home_dir().ok_or("Could not find home directory")?;
+        Ok(Self::xdg(
+            &home,
+            env_path("XDG_CONFIG_HOME"),
+            env_path("XDG_DATA_HOME"),
+            env_path("XDG_CACHE_HOME"),
+        ))
+    }
+
+    /// Everything under one directory
+    fn portable(home: PathBuf) -> Self {
+        Self { config: home.clone(), data: home.clone(), cache: home.join("cache") }
+    }
+
+    /// XDG base directories, falling back to the spec's defaults under `home`
+    fn xdg(home: &Path, config: Option<PathBuf>, data: Option<PathBuf>, cache: Option<PathBuf>) -> Self {
+        Self {
+            config: config.unwrap_or_else(|| home.join(".config")).join(APP_DIRNAME),
+            data: data.unwrap_or_else(|| home.join(".local").join("share")).join(APP_DIRNAME),
+            cache: cache.unwrap_or_else(|| home.join(".cache")).join(APP_DIRNAME),
+        }
+    }
+
+    /// Move a pre-XDG `~/.gtllm` directory into the XDG locations, once, and drop the
+    /// search index an older version kept next to the chats. Returns whether anything was moved.
+    pub fn migrate_legacy() -> Result<bool, String> {
+        let current = Self::current()?;
+        // The search index now lives in the cache directory and is rebuilt there
+        let _ = fs::remove_file(current.data.join("chats").join("search.idx"));
+
+        // Same platforms `current` puts in the XDG locations
+        if cfg!(any(target_os = "windows", target_os = "macos")) || env_path(HOME_OVERRIDE_VAR).is_some() {
+            return Ok(false);
+        }
+        let home = dirs::home_dir().ok_or("Could not find home directory")?;
+        current.migrate_from(&home.join(".gtllm"))
+    }
+
+    /// Move config files and data out of `legacy`, leaving anything already at the
+    /// destination alone. The legacy directory is removed once it is empty.
+    fn migrate_from(&self, legacy: &Path) -> Result<bool, String> {
+        if !legacy.is_dir() || legacy == self.config || legacy == self.data {
+            return Ok(false);
+        }
+
+        // The search index now lives in the cache directory and is rebuilt there
+        let _ = fs::remove_file(legacy.join("chats").join("search.idx"));
+
+        let entries = fs::read_dir(legacy).map_err(|e| format!("Failed to read {}: {}", legacy.display(), e))?;
+        let mut moved = false;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let name = entry.file_name();
+            let is_config = name.to_str().is_some_and(|name| CONFIG_FILES.contains(&name));
+            let target_dir = if is_config { &self.config } else { &self.data };
+            let target = target_dir.join(&name);
+            if target.exists() {
+                continue;
+            }
+            fs::create_dir_all(target_dir)
+                .map_err(|e| format!("Failed to create {}: {}", target_dir.display(), e))?;
+            move_path(&entry.path(), &target)?;
+            moved = true;
+        }
+
+        // Only fails when something was left behind because it already existed
+        let _ = fs::remove_dir(legacy);
+        Ok(moved)
+    }
+}
+
+/// An absolute path from the environment; the XDG spec says relative ones are ignored
+fn env_path(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+/// Rename, or copy then delete when the destination is on another filesystem
+fn move_path(from: &Path, to: &Path) -> Result<(), String> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+    .map_err(|e| format!("Failed to remove {}: {}", from.display(), e))
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<(), String> {
+    if from.is_dir() {
+        fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+        let entries = fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("gtllm-paths-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_xdg_defaults_and_overrides() {
+        let home = Path::new("/home/user");
+        let dirs = AppDirs::xdg(home, None, None, None);
+        assert_eq!(dirs.config, PathBuf::from("/home/user/.config/gtllm"));
+        assert_eq!(dirs.data, PathBuf::from("/home/user/.local/share/gtllm"));
+        assert_eq!(dirs.cache, PathBuf::from("/home/user/.cache/gtllm"));
+
+        let dirs = AppDirs::xdg(home, Some(PathBuf::from("/cfg")), None, Some(PathBuf::from("/tmp/cache")));
+        assert_eq!(dirs.config, PathBuf::from("/cfg/gtllm"));
+        assert_eq!(dirs.cache, PathBuf::from("/tmp/cache/gtllm"));
+
+        let portable = AppDirs::portable(PathBuf::from("/portable"));
+        assert_eq!(portable.config, portable.data);
+        assert_eq!(portable.cache, PathBuf::from("/portable/cache"));
+    }
+
+    #[test]
+    fn test_migrate_splits_config_and_data() {
+        let root = temp_root("migrate");
+        let legacy = root.join(".gtllm");
+        fs::create_dir_all(legacy.join("chats")).unwrap();
+        fs::write(legacy.join("settings.toml"), "theme = \"dracula\"").unwrap();
+        fs::write(legacy.join("chats").join("abc.json"), "{}").unwrap();
+
+        let dirs = AppDirs::xdg(&root, None, None, None);
+        assert!(dirs.migrate_from(&legacy).unwrap());
+        assert!(dirs.config.join("settings.toml").exists());
+        assert!(dirs.data.join("chats").join("abc.json").exists());
+        assert!(!legacy.exists());
+
+        // A second run has nothing left to move
+        assert!(!dirs.migrate_from(&legacy).unwrap());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_migrate_keeps_existing_destination() {
+        let root = temp_root("existing");
+        let legacy = root.join(".gtllm");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("settings.toml"), "old").unwrap();
+
+        let dirs = AppDirs::xdg(&root, None, None, None);
+        fs::create_dir_all(&dirs.config).unwrap();
+        fs::write(dirs.config.join("settings.toml"), "new").unwrap();
+
+        assert!(!dirs.migrate_from(&legacy).unwrap());
+        assert_eq!(fs::read_to_string(dirs.config.join("settings.toml")).unwrap(), "new");
+        assert!(legacy.join("settings.toml").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::SystemTime;
+
+use crate::utils::{mention_positions, ChatHistory, ChatSession, SessionIndex, SessionIndexEntry};
+
+// TrueSkill defaults: skill starts at 25 with a standard deviation of 25/3
+const INITIAL_MU: f64 = 25.0;
+const INITIAL_SIGMA: f64 = INITIAL_MU / 3.0;
+/// Performance variation within a single round
+const BETA: f64 = INITIAL_SIGMA / 2.0;
+/// Added uncertainty per round so ratings can keep moving as models change
+const TAU: f64 = INITIAL_SIGMA / 100.0;
+/// Two-sided 95% normal quantile
+const Z_95: f64 = 1.96;
+
+/// One model judged or voted better than another in a single round
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preference {
+    pub winner: String,
+    pub loser: String,
+}
+
+/// A TrueSkill rating: estimated skill and its uncertainty
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self { mu: INITIAL_MU, sigma: INITIAL_SIGMA }
+    }
+}
+
+impl Rating {
+    /// 95% confidence interval for the model's skill
+    pub fn interval(&self) -> (f64, f64) {
+        (self.mu - Z_95 * self.sigma, self.mu + Z_95 * self.sigma)
+    }
+
+    /// Skill the model is very likely above; used for ranking so one lucky win doesn't top the table
+    pub fn conservative(&self) -> f64 {
+        self.mu - 3.0 * self.sigma
+    }
+
+    /// Update both ratings after `winner` beat `loser`
+    fn update(winner: Rating, loser: Rating) -> (Rating, Rating) {
+        let winner_var = winner.sigma.powi(2) + TAU.powi(2);
+        let loser_var = loser.sigma.powi(2) + TAU.powi(2);
+        let c = (2.0 * BETA.powi(2) + winner_var + loser_var).sqrt();
+        let t = (winner.mu - loser.mu) / c;
+
+        // Truncated-Gaussian corrections for a win with no draw margin
+        let cdf = normal_cdf(t);
+        let v = if cdf > 1e-12 { normal_pdf(t) / cdf } else { -t };
+        let w = v * (v + t);
+
+        let updated = |rating: Rating, var: f64, sign: f64| Rating {
+            mu: rating.mu + sign * var / c * v,
+            sigma: (var * (1.0 - var / c.powi(2) * w)).max(1e-6).sqrt(),
+        };
+        (updated(winner, winner_var, 1.0), updated(loser, loser_var, -1.0))
+    }
+}
+
+/// Sessions counted on the leaderboard
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LeaderboardFilter {
+    /// Unix seconds; sessions created before this are skipped
+    pub since: Option<u64>,
+    /// Unix seconds; sessions created after this are skipped
+    pub until: Option<u64>,
+    pub tag: Option<String>,
+}
+
+impl LeaderboardFilter {
+    /// Build from `YYYY-MM-DD` date inputs; `until` covers the whole day. Blank or invalid dates are ignored.
+    pub fn from_dates(since: &str, until: &str, tag: Option<String>) -> Self {
+        Self {
+            since: parse_date(since),
+            until: parse_date(until).map(|start| start + 86_399),
+            tag: tag.filter(|tag| !tag.trim().is_empty()),
+        }
+    }
+
+    pub fn matches(&self, session: &ChatSession) -> bool {
+        if let Some(tag) = &self.tag {
+            if !session.labels.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(created) = created_secs(session) else {
+            return false;
+        };
+        self.since.is_none_or(|since| created >= since) && self.until.is_none_or(|until| created <= until)
+    }
+}
+
+/// A model's place on the leaderboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelStanding {
+    pub model_id: String,
+    pub rating: Rating,
+    pub wins: usize,
+    pub losses: usize,
+}
+
+/// Ratings for every model, built from PvP judgments and Competitive and Choice votes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelRatings {
+    filter: LeaderboardFilter,
+    standings: HashMap<String, ModelStanding>,
+    /// Per session: the version already read and the preferences applied
+    applied: HashMap<String, (SessionVersion, Vec<Preference>)>,
+}
+
+/// When a session was last saved: the index's `updated_at`, which counts whole seconds,
+/// and the file's modified time, which tells apart saves within the same second
+#[derive(Debug, Clone, PartialEq)]
+struct SessionVersion {
+    updated_at: String,
+    modified: Option<SystemTime>,
+}
+
+impl SessionVersion {
+    fn of(entry: &SessionIndexEntry) -> Self {
+        let modified = ChatHistory::session_path(&entry.id)
+            .ok()
+            .and_then(|path| fs::metadata(path).ok())
+            .and_then(|metadata| metadata.modified().ok());
+        Self { updated_at: entry.updated_at.clone(), modified }
+    }
+}
+
+impl ModelRatings {
+    pub fn new(filter: LeaderboardFilter) -> Self {
+        Self { filter, ..Default::default() }
+    }
+
+    pub fn filter(&self) -> &LeaderboardFilter {
+        &self.filter
+    }
+
+    /// Apply preferences from sessions that are new or have had rounds added since the last
+    /// refresh, oldest session first. Returns how many preferences were applied.
+    ///
+    /// Ratings depend on the order results arrive in, so when a saved round changes (a tie
+    /// settled by hand, say) or a counted session is deleted or no longer matches the
+    /// filter, every session is replayed from the start.
+    pub fn refresh(&mut self) -> Result<usize, String> {
+        let index = SessionIndex::load()?;
+        let mut sessions: Vec<(ChatSession, SessionVersion)> = index
+            .entries()
+            .map(|entry| (entry.to_session(), entry))
+            .filter(|(session, _)| self.filter.matches(session))
+            .map(|(session, entry)| (session, SessionVersion::of(entry)))
+            .collect();
+        if self.lost_any(sessions.iter().map(|(session, _)| session.id.as_str())) {
+            *self = Self::new(self.filter.clone());
+        }
+        sessions.retain(|(session, version)| {
+            self.applied
+                .get(&session.id)
+                .is_none_or(|(applied, _)| applied != version || version.modified.is_none())
+        });
+        sessions.sort_by_key(|(session, _)| created_secs(session).unwrap_or(0));
+
+        let mut applied = 0;
+        for (session, version) in sessions {
+            let data = match ChatHistory::load_session(&session.id) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("Skipping session {} in ratings: {}", session.id, e);
+                    continue;
+                }
+            };
+            match self.apply_session(session.id, version, Self::preferences(&data.history)) {
+                Some(count) => applied += count,
+                None => {
+                    *self = Self::new(self.filter.clone());
+                    return self.refresh();
+                }
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Whether a session already counted is missing from `current`
+    fn lost_any<'a>(&self, current: impl Iterator<Item = &'a str>) -> bool {
+        let current: Vec<&str> = current.collect();
+        self.applied.keys().any(|id| !current.contains(&id.as_str()))
+    }
+
+    /// Apply the preferences a session has gained since it was last read and return how
+    /// many; `None`, applying nothing, when ones already applied have changed
+    fn apply_session(&mut self, session_id: String, version: SessionVersion, preferences: Vec<Preference>) -> Option<usize> {
+        let already = match self.applied.get(&session_id) {
+            Some((_, applied)) if !preferences.starts_with(applied) => return None,
+            Some((_, applied)) => applied.len(),
+            None => 0,
+        };
+        for preference in &preferences[already..] {
+            self.apply(preference);
+        }
+        let added = preferences.len() - already;
+        self.applied.insert(session_id, (version, preferences));
+        Some(added)
+    }
+
+    pub fn apply(&mut self, preference: &Preference) {
+        let rating_of = |standings: &HashMap<String, ModelStanding>, id: &str| {
+            standings.get(id).map(|s| s.rating).unwrap_or_default()
+        };
+        let (winner, loser) = Rating::update(
+            rating_of(&self.standings, &preference.winner),
+            rating_of(&self.standings, &preference.loser),
+        );
+
+        let winner_standing = self.standing_mut(&preference.winner);
+        winner_standing.rating = winner;
+        winner_standing.wins += 1;
+        let loser_standing = self.standing_mut(&preference.loser);
+        loser_standing.rating = loser;
+        loser_standing.losses += 1;
+    }
+
+    fn standing_mut(&mut self, model_id: &str) -> &mut ModelStanding {
+        self.standings
+            .entry(model_id.to_string())
+            .or_insert_with(|| ModelStanding {
+                model_id: model_id.to_string(),
+                rating: Rating::default(),
+                wins: 0,
+                losses: 0,
+            })
+    }
+
+    /// Best first, by conservative rating
+    pub fn standings(&self) -> Vec<ModelStanding> {
+        let mut standings: Vec<ModelStanding> = self.standings.values().cloned().collect();
+        standings.sort_by(|a, b| {
+            b.rating
+                .conservative()
+                .total_cmp(&a.rating.conservative())
+                .then_with(|| a.model_id.cmp(&b.model_id))
+        });
+        standings
+    }
+
+    /// Every pairwise preference in a session, in round order
+    pub fn preferences(history: &ChatHistory) -> Vec<Preference> {
+        let mut preferences = Vec::new();
+        match history {
+            ChatHistory::PvP(h) => {
+                for round in &h.rounds {
+                    let bots = [&round.bot1_response, &round.bot2_response];
+                    if bots.iter().any(|bot| bot.error_message.is_some()) || bots[0].model_id == bots[1].model_id {
+                        continue;
+                    }
+                    // A verdict that flipped when the order was swapped says more about position than quality
+                    let Some(judgment) = round
+                        .moderator_judgment
+                        .as_ref()
+                        .filter(|j| j.error_message.is_none() && !j.inconsistent)
+                    else {
+                        continue;
+                    };
+                    let ids = [bots[0].model_id.clone(), bots[1].model_id.clone()];
+                    // Structured verdicts are authoritative; older judgments fall back to the text
+                    let winner = match &judgment.verdict {
+                        Some(verdict) => verdict.winner.as_ref().and_then(|w| ids.iter().position(|id| id == w)),
+                        None => judged_winner(&judgment.content, &ids),
+                    };
+                    if let Some(winner) = winner {
+                        preferences.push(Preference {
+                            winner: ids[winner].clone(),
+                            loser: ids[1 - winner].clone(),
+                        });
+                    }
+                }
+            }
+            ChatHistory::Competitive(h) => {
+                for round in &h.rounds {
+                    let entrants: Vec<&String> = round
+                        .phase1_proposals
+                        .iter()
+                        .filter(|p| p.error_message.is_none())
+                        .map(|p| &p.model_id)
+                        .collect();
+                    preferences.extend(winners_over_rest(&round.winners, &entrants));
+                }
+            }
+            ChatHistory::LLMChoice(h) => {
+                for competitive in h.rounds.iter().filter_map(|r| r.competitive.as_ref()) {
+                    let entrants: Vec<&String> = competitive
+                        .proposals
+                        .iter()
+                        .filter(|p| p.error_message.is_none())
+                        .map(|p| &p.model_id)
+                        .collect();
+                    preferences.extend(winners_over_rest(&competitive.winners, &entrants));
+                }
+            }
+            ChatHistory::Standard(_) | ChatHistory::Collaborative(_) => {}
+        }
+        preferences
+    }
+}
+
+/// Each vote winner beats every entrant that didn't win; tied winners aren't compared
+fn winners_over_rest(winners: &[String], entrants: &[&String]) -> Vec<Preference> {
+    let mut preferences = Vec::new();
+    for winner in winners.iter().filter(|w| entrants.contains(w)) {
+        for loser in entrants.iter().filter(|e| !winners.contains(e)) {
+            preferences.push(Preference {
+                winner: winner.clone(),
+                loser: (*loser).clone(),
+            });
+        }
+    }
+    preferences
+}
+
+/// Index into `ids` of the model a free-text judgment declares the winner, read from the
+/// last line that mentions a winner. `None` when it names neither or calls a tie.
+/// When both are named, the first one mentioned is taken as the winner.
+fn judged_winner(judgment: &str, ids: &[String; 2]) -> Option<usize> {
+    let lower = judgment.to_lowercase();
+    let start = lower.rfind("winner")?;
+    // The verdict may continue on the line after "Winner:"
+    let verdict = lower[start..]
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let calls_tie = verdict
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| matches!(word, "tie" | "tied" | "draw"));
+    if calls_tie {
+        return None;
+    }
+
+    match mention_positions(&verdict, ids)[..] {
+        [Some(a), Some(b)] => Some(if a <= b { 0 } else { 1 }),
+        [Some(_), None] => Some(0),
+        [None, Some(_)] => Some(1),
+        _ => None,
+    }
+}
+
+/// Creation time in Unix seconds, from the timestamp every session ID starts with
+fn created_secs(session: &ChatSession) -> Option<u64> {
+    ChatHistory::session_timestamp_from_id(&session.id)?.parse().ok()
+}
+
+/// `YYYY-MM-DD` to Unix seconds at midnight UTC
+fn parse_date(date: &str) -> Option<u64> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Days from civil date (Howard Hinnant's algorithm)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    u64::try_from(days * 86_400).ok()
+}
+
+fn normal_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function, accurate to about 1e-7
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98 + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(a: &str, b: &str) -> [String; 2] {
+        [a.to_string(), b.to_string()]
+    }
+
+    #[test]
+    fn test_win_moves_ratings_and_shrinks_uncertainty() {
+        let (winner, loser) = Rating::update(Rating::default(), Rating::default());
+        assert!(winner.mu > INITIAL_MU && loser.mu < INITIAL_MU);
+        assert!(winner.sigma < INITIAL_SIGMA && loser.sigma < INITIAL_SIGMA);
+        assert!((winner.mu - INITIAL_MU - (INITIAL_MU - loser.mu)).abs() < 1e-9);
+
+        // An upset moves ratings further than an expected result
+        let strong = Rating { mu: 35.0, sigma: 3.0 };
+        let weak = Rating { mu: 15.0, sigma: 3.0 };
+        let (expected, _) = Rating::update(strong, weak);
+        let (upset, _) = Rating::update(weak, strong);
+        assert!(upset.mu - weak.mu > expected.mu - strong.mu);
+    }
+
+    #[test]
+    fn test_judged_winner_reads_the_verdict() {
+        let pair = ids("openai/gpt-4o", "anthropic/claude-3.5-sonnet");
+        let text = "gpt-4o was concise, but claude-3.5-sonnet explained more.\n\n**Winner:** anthropic/claude-3.5-sonnet";
+        assert_eq!(judged_winner(text, &pair), Some(1));
+        assert_eq!(judged_winner("The winner is gpt-4o, narrowly.", &pair), Some(0));
+        assert_eq!(judged_winner("Winner: it's a tie between gpt-4o and claude-3.5-sonnet", &pair), None);
+        assert_eq!(judged_winner("Both answers were fine.", &pair), None);
+    }
+
+    #[test]
+    fn test_judged_winner_does_not_match_inside_a_longer_id() {
+        let pair = ids("openai/gpt-4o", "openai/gpt-4o-mini");
+        assert_eq!(judged_winner("Winner: openai/gpt-4o-mini", &pair), Some(1));
+        assert_eq!(judged_winner("Winner: gpt-4o-mini, ahead of gpt-4o", &pair), Some(1));
+        assert_eq!(judged_winner("Winner: gpt-4o.", &pair), Some(0));
+        assert_eq!(
+            judged_winner("Winner: gpt-4o-mini, whose properties had no drawback", &pair),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_vote_winners_beat_the_rest_and_ratings_accumulate() {
+        let (a, b, c) = ("a/one".to_string(), "b/two".to_string(), "c/three".to_string());
+        let prefs = winners_over_rest(std::slice::from_ref(&a), &[&a, &b, &c]);
+        assert_eq!(prefs.len(), 2);
+        assert!(winners_over_rest(&[a.clone(), b.clone()], &[&a, &b]).is_empty());
+
+        let mut ratings = ModelRatings::default();
+        for pref in prefs.iter().chain(prefs.iter()) {
+            ratings.apply(pref);
+        }
+        let standings = ratings.standings();
+        assert_eq!(standings[0].model_id, a);
+        assert_eq!((standings[0].wins, standings[0].losses), (4, 0));
+    }
+
+    #[test]
+    fn test_sessions_only_add_preferences_until_a_saved_round_changes() {
+        let pref = |winner: &str, loser: &str| Preference { winner: winner.to_string(), loser: loser.to_string() };
+        let version = |updated_at: &str| SessionVersion { updated_at: updated_at.to_string(), modified: None };
+        let mut ratings = ModelRatings::default();
+        let session = || "session".to_string();
+
+        assert_eq!(ratings.apply_session(session(), version("1"), vec![pref("a", "b")]), Some(1));
+        assert_eq!(
+            ratings.apply_session(session(), version("2"), vec![pref("a", "b"), pref("c", "d")]),
+            Some(1)
+        );
+        assert_eq!(ratings.standings().len(), 4);
+
+        // Settling an earlier tie puts a preference ahead of ones already applied
+        let settled = vec![pref("a", "b"), pref("e", "f"), pref("c", "d")];
+        assert_eq!(ratings.apply_session(session(), version("3"), settled.clone()), None);
+        assert_eq!(ratings.standings().len(), 4);
+        let mut rebuilt = ModelRatings::default();
+        assert_eq!(rebuilt.apply_session(session(), version("3"), settled), Some(3));
+        assert_eq!(rebuilt.standings().len(), 6);
+
+        // A counted session that was deleted or filtered out calls for a rebuild
+        rebuilt.apply_session("other".to_string(), version("4"), vec![pref("a", "f")]);
+        assert!(!rebuilt.lost_any(["other", "session", "new"].into_iter()));
+        assert!(rebuilt.lost_any(["other"].into_iter()));
+    }
+
+    #[test]
+    fn test_parse_date_and_filter_range() {
+        assert_eq!(parse_date("1970-01-02"), Some(86_400));
+        assert_eq!(parse_date("2024-03-01"), Some(1_709_251_200));
+        assert_eq!(parse_date("not a date"), None);
+
+        let filter = LeaderboardFilter::from_dates("2024-03-01", "2024-03-01", Some(" ".to_string()));
+        assert_eq!(filter.until, Some(1_709_251_200 + 86_399));
+        assert_eq!(filter.tag, None);
+    }
+}
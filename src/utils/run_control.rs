@@ -1,5 +1,5 @@
-use crate::utils::{ActiveRunRecord, ChatHistory, ChatMode, RunStatus};
-use dioxus::core::Task;
+use crate::utils::{ActiveRunRecord, ChatHistory, ChatMode, OpenRouterClient, RunStatus, Settings, TitleGenerator};
+use dioxus::core::{spawn_forever, Task};
 use dioxus::prelude::{ReadableExt, Signal, WritableExt};
 use futures::Stream;
 use futures::StreamExt;
@@ -66,12 +66,50 @@ pub fn upsert_session(
     mut sessions: Signal<Vec<crate::utils::ChatSession>>,
     session: crate::utils::ChatSession,
 ) {
+    // Keep the list ordered by last activity, newest first
     let mut sessions_write = sessions.write();
-    if let Some(existing) = sessions_write.iter_mut().find(|existing| existing.id == session.id) {
-        *existing = session;
-    } else {
-        sessions_write.push(session);
+    sessions_write.retain(|existing| existing.id != session.id);
+    let timestamp = session.timestamp.parse::<u64>().unwrap_or(0);
+    let position = sessions_write
+        .iter()
+        .position(|existing| existing.timestamp.parse::<u64>().unwrap_or(0) <= timestamp)
+        .unwrap_or(sessions_write.len());
+    sessions_write.insert(position, session);
+}
+
+/// After a session's first round, ask the configured title model for a better title in
+/// the background. The first-message title stays when disabled, offline or renamed.
+pub fn generate_title_in_background(
+    client: Arc<OpenRouterClient>,
+    sessions: Signal<Vec<crate::utils::ChatSession>>,
+    session_id: String,
+    history: ChatHistory,
+) {
+    if !TitleGenerator::wants_title(&history) {
+        return;
     }
+    spawn_forever(async move {
+        let titles = match tokio::task::spawn_blocking(Settings::load_without_api_key).await {
+            Ok(Ok(settings)) => settings.titles,
+            _ => return,
+        };
+        if !titles.enabled {
+            return;
+        }
+        let title = match TitleGenerator::generate(&client, &titles.model, &history).await {
+            Ok(title) => title,
+            Err(e) => {
+                eprintln!("Failed to generate title for {}: {}", session_id, e);
+                return;
+            }
+        };
+        match tokio::task::spawn_blocking(move || ChatHistory::apply_generated_title(&session_id, &title)).await {
+            Ok(Ok(Some(session))) => upsert_session(sessions, session),
+            Ok(Ok(None)) => {}
+            Ok(Err(e)) => eprintln!("Failed to save generated title: {}", e),
+            Err(e) => eprintln!("Failed to save generated title: {}", e),
+        }
+    });
 }
 
 pub fn find_run_for_session(
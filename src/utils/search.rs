@@ -0,0 +1,486 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::utils::{AppDirs, ChatHistory, ChatSession, SessionData, SessionIndex};
+
+/// Single-file index written by earlier versions, removed on first use
+const LEGACY_INDEX_FILENAME: &str = "search.idx";
+const SEARCH_DIRNAME: &str = "search";
+const SEGMENT_EXTENSION: &str = "idx";
+const SEARCH_INDEX_VERSION: u32 = 2;
+const MAX_TOKEN_LEN: usize = 40;
+const SNIPPET_RADIUS: usize = 60;
+
+/// Segments merged on the first search, then kept current by updates
+static SEARCH_CACHE: Mutex<Option<SearchIndex>> = Mutex::new(None);
+
+/// A piece of searchable text inside a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDocument {
+    pub round: usize,
+    /// "You" for user messages, otherwise the model (or role) that produced the text
+    pub source: String,
+    pub text: String,
+}
+
+/// A single search result
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub session: ChatSession,
+    pub round: usize,
+    pub source: String,
+    pub snippet: String,
+    pub score: usize,
+}
+
+/// Split text into lowercase alphanumeric tokens
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty() && token.chars().count() <= MAX_TOKEN_LEN)
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+/// Every user message and model output in a history, tagged with its round
+pub fn session_documents(history: &ChatHistory) -> Vec<SearchDocument> {
+    let mut docs = Vec::new();
+    let mut push = |round: usize, source: &str, text: &str| {
+        if !text.trim().is_empty() {
+            docs.push(SearchDocument {
+                round,
+                source: source.to_string(),
+                text: text.to_string(),
+            });
+        }
+    };
+
+    match history {
+        ChatHistory::Standard(h) => {
+            for (round, user_message) in h.user_messages.iter().enumerate() {
+                push(round, "You", user_message);
+                for response in h.model_responses.get(round).into_iter().flatten() {
+                    push(round, &response.model_id, &response.content);
+                }
+            }
+        }
+        ChatHistory::PvP(h) => {
+            for (round, r) in h.rounds.iter().enumerate() {
+                push(round, "You", &r.user_message);
+                push(round, &r.bot1_response.model_id, &r.bot1_response.content);
+                push(round, &r.bot2_response.model_id, &r.bot2_response.content);
+                for turn in &r.turns {
+                    push(round, &turn.bot1_response.model_id, &turn.bot1_response.content);
+                    push(round, &turn.bot2_response.model_id, &turn.bot2_response.content);
+                }
+                if let Some(judgment) = &r.moderator_judgment {
+                    push(round, "Moderator", &judgment.content);
+                    for judge in &judgment.judges {
+                        push(round, &judge.model_id, &judge.content);
+                    }
+                }
+            }
+        }
+        ChatHistory::Collaborative(h) => {
+            for (round, r) in h.rounds.iter().enumerate() {
+                push(round, "You", &r.user_message);
+                for response in &r.model_responses {
+                    push(round, &response.model_id, &response.content);
+                }
+                if let Some(consensus) = &r.final_consensus {
+                    push(round, "Consensus", consensus);
+                }
+            }
+        }
+        ChatHistory::Competitive(h) => {
+            for (round, r) in h.rounds.iter().enumerate() {
+                push(round, "You", &r.user_question);
+                for proposal in &r.phase1_proposals {
+                    push(round, &proposal.model_id, &proposal.content);
+                }
+                for vote in &r.phase2_votes {
+                    push(round, &vote.voter_id, &vote.raw_response);
+                }
+            }
+        }
+        ChatHistory::LLMChoice(h) => {
+            for (round, r) in h.rounds.iter().enumerate() {
+                push(round, "You", &r.user_message);
+                if let Some(content) = &r.content {
+                    push(round, "Result", content);
+                }
+                if let Some(collab) = &r.collaborative {
+                    for response in collab.initial_responses.iter().chain(&collab.reviews) {
+                        push(round, &response.model_id, &response.content);
+                    }
+                }
+                if let Some(comp) = &r.competitive {
+                    for proposal in &comp.proposals {
+                        push(round, &proposal.model_id, &proposal.content);
+                    }
+                }
+            }
+        }
+    }
+
+    docs
+}
+
+/// Lowercase `text`, keeping for every byte of the result the byte offset in `text` of the
+/// character it came from. Lowercasing can change byte lengths outside ASCII.
+fn lowercase_with_offsets(text: &str) -> (String, Vec<usize>) {
+    let mut lower = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len());
+    for (offset, ch) in text.char_indices() {
+        for lower_ch in ch.to_lowercase() {
+            lower.push(lower_ch);
+            offsets.extend(std::iter::repeat_n(offset, lower_ch.len_utf8()));
+        }
+    }
+    (lower, offsets)
+}
+
+/// Build a snippet of text around the first occurrence of any query token
+fn make_snippet(text: &str, tokens: &[String]) -> String {
+    let (lower, offsets) = lowercase_with_offsets(text);
+    let position = tokens
+        .iter()
+        .filter_map(|t| lower.find(t.as_str()))
+        .min()
+        .map(|pos| offsets[pos])
+        .unwrap_or(0);
+
+    let mut start = position.saturating_sub(SNIPPET_RADIUS);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (position + SNIPPET_RADIUS * 2).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let mut snippet = text[start..end].split_whitespace().collect::<Vec<_>>().join(" ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// One session's postings, stored as its own file so a save only rewrites that session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct SearchSegment {
+    version: u32,
+    /// token -> round -> term frequency
+    postings: HashMap<String, BTreeMap<usize, usize>>,
+}
+
+impl SearchSegment {
+    fn from_history(history: &ChatHistory) -> Self {
+        let mut postings: HashMap<String, BTreeMap<usize, usize>> = HashMap::new();
+        for doc in session_documents(history) {
+            for token in tokenize(&doc.text) {
+                *postings.entry(token).or_default().entry(doc.round).or_insert(0) += 1;
+            }
+        }
+        Self {
+            version: SEARCH_INDEX_VERSION,
+            postings,
+        }
+    }
+}
+
+/// Inverted index from token to the session rounds that contain it, merged in memory
+/// from the per-session segment files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchIndex {
+    /// token -> session_id -> round -> term frequency
+    postings: HashMap<String, HashMap<String, BTreeMap<usize, usize>>>,
+    /// session_id -> tokens indexed for it, so updates can drop stale postings
+    session_tokens: HashMap<String, Vec<String>>,
+}
+
+impl SearchIndex {
+    /// Directory holding one segment file per session
+    pub fn index_dir() -> Result<PathBuf, String> {
+        Ok(AppDirs::current()?.cache.join(SEARCH_DIRNAME))
+    }
+
+    fn segment_path(session_id: &str) -> Result<PathBuf, String> {
+        Ok(Self::index_dir()?.join(format!("{}.{}", session_id, SEGMENT_EXTENSION)))
+    }
+
+    fn remove(&mut self, session_id: &str) {
+        if let Some(tokens) = self.session_tokens.remove(session_id) {
+            for token in tokens {
+                if let Some(sessions) = self.postings.get_mut(&token) {
+                    sessions.remove(session_id);
+                    if sessions.is_empty() {
+                        self.postings.remove(&token);
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert_segment(&mut self, session_id: &str, segment: SearchSegment) {
+        self.remove(session_id);
+
+        let tokens: Vec<String> = segment.postings.keys().cloned().collect();
+        for (token, rounds) in segment.postings {
+            self.postings
+                .entry(token)
+                .or_default()
+                .insert(session_id.to_string(), rounds);
+        }
+        self.session_tokens.insert(session_id.to_string(), tokens);
+    }
+
+    #[cfg(test)]
+    fn insert(&mut self, session_id: &str, history: &ChatHistory) {
+        self.insert_segment(session_id, SearchSegment::from_history(history));
+    }
+
+    /// Rounds matching every query token (prefix match), with a relevance score
+    fn matches(&self, tokens: &[String]) -> Vec<(String, usize, usize)> {
+        let mut combined: Option<HashMap<(String, usize), usize>> = None;
+
+        for query_token in tokens {
+            let mut token_hits: HashMap<(String, usize), usize> = HashMap::new();
+            for (token, sessions) in &self.postings {
+                if !token.starts_with(query_token.as_str()) {
+                    continue;
+                }
+                for (session_id, rounds) in sessions {
+                    for (round, freq) in rounds {
+                        *token_hits.entry((session_id.clone(), *round)).or_insert(0) += freq;
+                    }
+                }
+            }
+
+            combined = Some(match combined {
+                None => token_hits,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(key, score)| token_hits.get(&key).map(|s| (key, score + s)))
+                    .collect(),
+            });
+        }
+
+        let mut results: Vec<(String, usize, usize)> = combined
+            .unwrap_or_default()
+            .into_iter()
+            .map(|((session_id, round), score)| (session_id, round, score))
+            .collect();
+        results.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+        results
+    }
+
+    /// Merge every segment file, writing segments for sessions that have none yet
+    fn load_or_rebuild() -> Result<SearchIndex, String> {
+        // Indexes written before segments were a single file that is no longer read
+        if let Ok(legacy) = Self::legacy_index_path() {
+            let _ = fs::remove_file(legacy);
+        }
+
+        let dir = Self::index_dir()?;
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create search index directory: {}", e))?;
+
+        let mut index = SearchIndex::default();
+        for entry in SessionIndex::load()?.entries() {
+            let segment = Self::segment_path(&entry.id)?;
+            let stored = ChatHistory::read_private_file(&segment)
+                .ok()
+                .and_then(|contents| serde_json::from_str::<SearchSegment>(&contents).ok())
+                .filter(|segment| segment.version == SEARCH_INDEX_VERSION);
+            let segment = match stored {
+                Some(segment) => segment,
+                None => {
+                    let Ok(data) = ChatHistory::load_session(&entry.id) else { continue };
+                    let segment = SearchSegment::from_history(&data.history);
+                    Self::write_segment(&entry.id, &segment)?;
+                    segment
+                }
+            };
+            index.insert_segment(&entry.id, segment);
+        }
+        Ok(index)
+    }
+
+    fn legacy_index_path() -> Result<PathBuf, String> {
+        Ok(AppDirs::current()?.cache.join(LEGACY_INDEX_FILENAME))
+    }
+
+    fn write_segment(session_id: &str, segment: &SearchSegment) -> Result<(), String> {
+        let path = Self::segment_path(session_id)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create search index directory: {}", e))?;
+        }
+        let contents = serde_json::to_string(segment)
+            .map_err(|e| format!("Failed to serialize search index: {}", e))?;
+        ChatHistory::write_private_file(&path, &contents)
+    }
+
+    /// Re-index one session after it was saved. Only that session's segment is written.
+    pub fn update_session(session_data: &SessionData) -> Result<(), String> {
+        let mut cache = SEARCH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let segment = SearchSegment::from_history(&session_data.history);
+        Self::write_segment(&session_data.session.id, &segment)?;
+        if let Some(index) = cache.as_mut() {
+            index.insert_segment(&session_data.session.id, segment);
+        }
+        Ok(())
+    }
+
+    /// Drop a deleted session from the index
+    pub fn remove_session(session_id: &str) -> Result<(), String> {
+        let mut cache = SEARCH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let path = Self::segment_path(session_id)?;
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove search index segment: {}", e))?;
+        }
+        if let Some(index) = cache.as_mut() {
+            index.remove(session_id);
+        }
+        Ok(())
+    }
+
+    /// Delete every segment so the index is rebuilt from the sessions on next use
+    pub fn clear() -> Result<(), String> {
+        let mut cache = SEARCH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        *cache = None;
+        let dir = Self::index_dir()?;
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear search index: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Search all saved sessions. Returns at most `limit` hits, best first.
+    pub fn search(query: &str, limit: usize) -> Result<Vec<SearchHit>, String> {
+        let tokens = tokenize(query);
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let matches = {
+            let mut cache = SEARCH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+            if cache.is_none() {
+                *cache = Some(Self::load_or_rebuild()?);
+            }
+            cache.as_ref().map(|index| index.matches(&tokens)).unwrap_or_default()
+        };
+        let sessions = SessionIndex::load()?;
+
+        let mut hits = Vec::new();
+        let mut loaded: HashMap<String, Vec<SearchDocument>> = HashMap::new();
+        for (session_id, round, score) in matches.into_iter().take(limit) {
+            let Some(entry) = sessions.get(&session_id) else { continue };
+            if !loaded.contains_key(&session_id) {
+                let docs = ChatHistory::load_session(&session_id)
+                    .map(|data| session_documents(&data.history))
+                    .unwrap_or_default();
+                loaded.insert(session_id.clone(), docs);
+            }
+
+            // Prefer the document in the round that actually contains the first query token
+            let docs = &loaded[&session_id];
+            let doc = docs
+                .iter()
+                .filter(|d| d.round == round)
+                .find(|d| tokenize(&d.text).iter().any(|t| t.starts_with(tokens[0].as_str())))
+                .or_else(|| docs.iter().find(|d| d.round == round));
+            let Some(doc) = doc else { continue };
+
+            hits.push(SearchHit {
+                session: entry.to_session(),
+                round,
+                source: doc.source.clone(),
+                snippet: make_snippet(&doc.text, &tokens),
+                score,
+            });
+        }
+
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{ConversationHistory, ModelResponse, StandardHistory};
+
+    fn history(messages: &[(&str, &str)]) -> ChatHistory {
+        ChatHistory::Standard(StandardHistory {
+            user_messages: messages.iter().map(|(u, _)| u.to_string()).collect(),
+            model_responses: messages
+                .iter()
+                .map(|(_, r)| {
+                    vec![ModelResponse {
+                        model_id: "a/model".to_string(),
+                        content: r.to_string(),
+                        error_message: None,
+                        timing: None,
+                    }]
+                })
+                .collect(),
+            selected_models: vec!["a/model".to_string()],
+            system_prompt: String::new(),
+            conversation_history: ConversationHistory {
+                single_model: Vec::new(),
+                multi_model: HashMap::new(),
+            },
+            turns: Vec::new(),
+            active_path: Vec::new(),
+            context: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_index_matches_all_tokens_by_round() {
+        let mut index = SearchIndex::default();
+        index.insert("s1", &history(&[("rust borrow checker", "ok"), ("python", "Borrowing is fine")]));
+        index.insert("s2", &history(&[("borrow money", "no")]));
+
+        let hits = index.matches(&tokenize("borrow checker"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].0.as_str(), hits[0].1), ("s1", 0));
+
+        // Prefix matching picks up "borrowing" in the second round
+        let hits = index.matches(&tokenize("borrow"));
+        assert_eq!(hits.len(), 3);
+    }
+
+    #[test]
+    fn test_reindex_drops_stale_postings() {
+        let mut index = SearchIndex::default();
+        index.insert("s1", &history(&[("alpha", "beta")]));
+        index.insert("s1", &history(&[("gamma", "delta")]));
+        assert!(index.matches(&tokenize("alpha")).is_empty());
+        assert_eq!(index.matches(&tokenize("gamma")).len(), 1);
+
+        index.remove("s1");
+        assert!(index.postings.is_empty());
+    }
+
+    #[test]
+    fn test_snippet_centers_on_match_in_non_ascii_text() {
+        // "İ" lowercases to two characters, shifting every later byte offset
+        let text = format!("{} needle {}", "İ".repeat(200), "x".repeat(200));
+        let snippet = make_snippet(&text, &["needle".to_string()]);
+        assert!(snippet.contains("needle"));
+        assert!(snippet.starts_with('…'));
+    }
+
+    #[test]
+    fn test_snippet_respects_char_boundaries() {
+        let text = format!("{}needle{}", "é".repeat(80), "ü".repeat(80));
+        let snippet = make_snippet(&text, &["needle".to_string()]);
+        assert!(snippet.contains("needle"));
+    }
+}
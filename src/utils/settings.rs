@@ -2,11 +2,134 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::utils::{AppDirs, ChatMode, ChatSession, Vault};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub openrouter_api_key: Option<String>,
     pub theme: String,
     pub theme_mode: ThemeMode, // dark or light
+    /// Days deleted chats stay in the trash; 0 keeps them until emptied by hand
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Sidebar filters and folders, kept across restarts
+    #[serde(default)]
+    pub sidebar: SidebarPreferences,
+    /// Automatic rotating backups of the data directory
+    #[serde(default)]
+    pub backup: BackupSchedule,
+    /// Optional model-written session titles
+    #[serde(default)]
+    pub titles: TitleSettings,
+}
+
+/// Whether a model names new sessions after their first round, and which one
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TitleSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_title_model")]
+    pub model: String,
+}
+
+impl Default for TitleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: default_title_model(),
+        }
+    }
+}
+
+fn default_title_model() -> String {
+    "openai/gpt-4o-mini".to_string()
+}
+
+/// When automatic backups run and how many are kept
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupSchedule {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u32,
+    /// Older backups beyond this count are deleted
+    #[serde(default = "default_backup_keep")]
+    pub keep: usize,
+    #[serde(default)]
+    pub include_api_key: bool,
+}
+
+impl Default for BackupSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_backup_interval_hours(),
+            keep: default_backup_keep(),
+            include_api_key: false,
+        }
+    }
+}
+
+impl BackupSchedule {
+    /// Whether a backup is due, given the Unix time of the newest one
+    pub fn is_due(&self, newest: Option<u64>, now: u64) -> bool {
+        match newest {
+            Some(newest) => now.saturating_sub(newest) >= u64::from(self.interval_hours.max(1)) * 3600,
+            None => true,
+        }
+    }
+}
+
+fn default_backup_interval_hours() -> u32 {
+    24
+}
+
+fn default_backup_keep() -> usize {
+    7
+}
+
+/// Sidebar filtering and folder list
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SidebarPreferences {
+    /// Show only sessions carrying all of these tags
+    #[serde(default)]
+    pub filter_tags: Vec<String>,
+    #[serde(default)]
+    pub filter_mode: Option<ChatMode>,
+    #[serde(default)]
+    pub filter_model: Option<String>,
+    /// Folder names, including empty ones
+    #[serde(default)]
+    pub folders: Vec<String>,
+}
+
+impl SidebarPreferences {
+    pub fn is_filtering(&self) -> bool {
+        !self.filter_tags.is_empty() || self.filter_mode.is_some() || self.filter_model.is_some()
+    }
+
+    /// Whether a session passes the active tag, mode and model filters
+    pub fn matches(&self, session: &ChatSession) -> bool {
+        if self.filter_mode.as_ref().is_some_and(|mode| mode != &session.mode) {
+            return false;
+        }
+        if let Some(model) = &self.filter_model {
+            if !session.stats.models.iter().any(|m| m == model) {
+                return false;
+            }
+        }
+        self.filter_tags.iter().all(|tag| {
+            session
+                .labels
+                .tags
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(tag))
+        })
+    }
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,6 +145,10 @@ impl Default for Settings {
             openrouter_api_key: None,
             theme: "dracula".to_string(),
             theme_mode: ThemeMode::Dark,
+            trash_retention_days: default_trash_retention_days(),
+            sidebar: SidebarPreferences::default(),
+            backup: BackupSchedule::default(),
+            titles: TitleSettings::default(),
         }
     }
 }
@@ -29,24 +156,7 @@ impl Default for Settings {
 impl Settings {
     /// Get the platform-specific settings directory
     pub fn settings_dir() -> Result<PathBuf, String> {
-        let config_dir = if cfg!(target_os = "windows") {
-            // Windows: %APPDATA%\gtllm
-            dirs::config_dir()
-                .ok_or("Could not find config directory")?
-                .join("gtllm")
-        } else if cfg!(target_os = "macos") {
-            // macOS: ~/Library/Application Support/gtllm
-            dirs::config_dir()
-                .ok_or("Could not find config directory")?
-                .join("gtllm")
-        } else {
-            // Linux/Unix: $HOME/.gtllm
-            dirs::home_dir()
-                .ok_or("Could not find home directory")?
-                .join(".gtllm")
-        };
-
-        Ok(config_dir)
+        Ok(AppDirs::current()?.config)
     }
 
     /// Get the full path to the settings file
@@ -54,8 +164,27 @@ impl Settings {
         Ok(Self::settings_dir()?.join("settings.toml"))
     }
 
-    /// Load settings from the config file
+    /// Load settings from the config file, decrypting the API key when the vault is enabled
     pub fn load() -> Result<Self, String> {
+        let mut settings = Self::read_file()?;
+        if let Some(api_key) = settings.openrouter_api_key.take() {
+            let api_key = Vault::guard()
+                .open(api_key)
+                .map_err(|e| format!("Failed to read API key: {}", e))?;
+            settings.openrouter_api_key = Some(api_key);
+        }
+        Ok(settings)
+    }
+
+    /// Load settings without the API key, for use before the vault is unlocked
+    pub fn load_without_api_key() -> Result<Self, String> {
+        let mut settings = Self::read_file()?;
+        settings.openrouter_api_key = None;
+        Ok(settings)
+    }
+
+    /// Parse the config file as stored, leaving an encrypted API key sealed
+    pub(crate) fn read_file() -> Result<Self, String> {
         let path = Self::settings_path()?;
 
         if !path.exists() {
@@ -72,8 +201,18 @@ impl Settings {
         Ok(settings)
     }
 
-    /// Save settings to the config file
+    /// Save settings to the config file, encrypting the API key when the vault is enabled
     pub fn save(&self) -> Result<(), String> {
+        let vault = Vault::guard();
+        let mut stored = self.clone();
+        if let Some(api_key) = &self.openrouter_api_key {
+            stored.openrouter_api_key = Some(vault.seal(api_key)?);
+        }
+        stored.write_file()
+    }
+
+    /// Write the config file as-is
+    pub(crate) fn write_file(&self) -> Result<(), String> {
         let dir = Self::settings_dir()?;
 
         // Create directory if it doesn't exist
@@ -135,6 +274,58 @@ mod tests {
         assert_eq!(settings.openrouter_api_key, None);
         assert_eq!(settings.theme, "dracula");
         assert_eq!(settings.theme_mode, ThemeMode::Dark);
+        assert_eq!(settings.trash_retention_days, 30);
+    }
+
+    #[test]
+    fn test_missing_trash_retention_uses_default() {
+        let settings: Settings = toml::from_str("theme = \"dracula\"\ntheme_mode = \"dark\"\n").unwrap();
+        assert_eq!(settings.trash_retention_days, 30);
+    }
+
+    #[test]
+    fn test_backup_schedule_is_due_after_interval() {
+        let schedule = BackupSchedule { enabled: true, ..Default::default() };
+        assert!(schedule.is_due(None, 1_700_000_000));
+        assert!(!schedule.is_due(Some(1_700_000_000), 1_700_000_000 + 3600));
+        assert!(schedule.is_due(Some(1_700_000_000), 1_700_000_000 + 24 * 3600));
+    }
+
+    #[test]
+    fn test_sidebar_preferences_round_trip() {
+        let mut settings = Settings::default();
+        settings.sidebar.filter_mode = Some(ChatMode::PvP);
+        settings.sidebar.filter_tags = vec!["work".to_string()];
+        settings.sidebar.folders = vec!["Research".to_string()];
+        let toml = toml::to_string_pretty(&settings).unwrap();
+        let loaded: Settings = toml::from_str(&toml).unwrap();
+        assert_eq!(loaded.sidebar, settings.sidebar);
+    }
+
+    #[test]
+    fn test_sidebar_filter_matches_tags_mode_and_model() {
+        let mut session = ChatSession {
+            id: "1".to_string(),
+            title: "Chat".to_string(),
+            mode: ChatMode::Standard,
+            timestamp: "1700000000".to_string(),
+            stats: Default::default(),
+            labels: Default::default(),
+        };
+        session.labels.tags = vec!["Work".to_string(), "draft".to_string()];
+        session.stats.models = vec!["openai/gpt-4o".to_string()];
+
+        let mut prefs = SidebarPreferences::default();
+        assert!(prefs.matches(&session));
+        prefs.filter_tags = vec!["work".to_string()];
+        assert!(prefs.matches(&session));
+        prefs.filter_model = Some("openai/gpt-4o".to_string());
+        assert!(prefs.matches(&session));
+        prefs.filter_mode = Some(ChatMode::PvP);
+        assert!(!prefs.matches(&session));
+        prefs.filter_mode = None;
+        prefs.filter_tags.push("urgent".to_string());
+        assert!(!prefs.matches(&session));
     }
 
     #[test]
@@ -0,0 +1,81 @@
+use crate::utils::{ChatFork, ChatHistory, ChatMessage, OpenRouterClient};
+
+/// Longest title kept from the model, matching the first-message heuristic
+const MAX_TITLE_CHARS: usize = 60;
+/// The opening message is cut to this many characters before being sent
+const MAX_PROMPT_CHARS: usize = 2000;
+
+const TITLE_PROMPT: &str = "You name chat conversations. Reply with a short, specific title of at most six words for a conversation that starts with the user's message below. Reply with the title only: no quotes, no trailing punctuation.";
+
+/// Asks a cheap model for a short session title
+pub struct TitleGenerator;
+
+impl TitleGenerator {
+    /// Titles are generated once, when a session has just finished its first round
+    pub fn wants_title(history: &ChatHistory) -> bool {
+        ChatFork::round_prompts(history).len() == 1
+    }
+
+    /// Ask `model` for a title for the session's opening message
+    pub async fn generate(client: &OpenRouterClient, model: &str, history: &ChatHistory) -> Result<String, String> {
+        let first_message = ChatFork::round_prompts(history)
+            .into_iter()
+            .next()
+            .ok_or("Session has no messages to title")?;
+        let excerpt: String = first_message.trim().chars().take(MAX_PROMPT_CHARS).collect();
+        let messages = vec![ChatMessage::system(TITLE_PROMPT), ChatMessage::user(excerpt)];
+
+        let response = client.chat_completion(model.to_string(), messages).await?;
+        let raw = response
+            .choices
+            .first()
+            .and_then(|choice| choice.message.as_ref())
+            .and_then(|message| message.content.clone())
+            .unwrap_or_default();
+        Self::clean(&raw).ok_or_else(|| "Title model returned an empty title".to_string())
+    }
+
+    /// First non-empty line without a "Title:" prefix, wrapping quotes or a trailing full stop
+    fn clean(raw: &str) -> Option<String> {
+        let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+        let line = ["Title:", "title:", "**Title:**"]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .unwrap_or(line)
+            .trim()
+            .trim_matches(|c: char| matches!(c, '"' | '\'' | '“' | '”' | '*' | '#' | '`'))
+            .trim_end_matches('.')
+            .trim();
+        if line.is_empty() {
+            return None;
+        }
+        Some(ChatHistory::truncate_title(line, MAX_TITLE_CHARS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ChatMode;
+
+    #[test]
+    fn test_clean_strips_labels_quotes_and_extra_lines() {
+        assert_eq!(TitleGenerator::clean("\"Rust lifetimes explained.\""), Some("Rust lifetimes explained".to_string()));
+        assert_eq!(TitleGenerator::clean("\n Title: Sourdough starter tips\nHope this helps!"), Some("Sourdough starter tips".to_string()));
+        assert_eq!(TitleGenerator::clean("  \n \"\" "), None);
+    }
+
+    #[test]
+    fn test_wants_title_only_after_first_round() {
+        let mut history = ChatHistory::empty(ChatMode::Standard);
+        assert!(!TitleGenerator::wants_title(&history));
+        if let ChatHistory::Standard(h) = &mut history {
+            h.user_messages.push("How do I bake bread?".to_string());
+        }
+        assert!(TitleGenerator::wants_title(&history));
+        if let ChatHistory::Standard(h) = &mut history {
+            h.user_messages.push("And rolls?".to_string());
+        }
+        assert!(!TitleGenerator::wants_title(&history));
+    }
+}
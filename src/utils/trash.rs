@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::utils::{ChatHistory, ChatSession, SearchIndex, SessionIndex, SessionIndexEntry};
+
+/// Subdirectory of the chats directory holding deleted sessions
+const TRASH_DIRNAME: &str = "trash";
+const TRASH_MANIFEST: &str = "trash.json";
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Serializes read-modify-write cycles on the trash manifest
+static TRASH_LOCK: Mutex<()> = Mutex::new(());
+
+/// A deleted session, with the index metadata needed to restore it unchanged
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrashEntry {
+    pub entry: SessionIndexEntry,
+    /// Unix timestamp (seconds) of the deletion
+    pub deleted_at: String,
+}
+
+impl TrashEntry {
+    fn is_expired(&self, retention_days: u32, now: u64) -> bool {
+        if retention_days == 0 {
+            return false;
+        }
+        let deleted_at = self.deleted_at.parse::<u64>().unwrap_or(0);
+        now.saturating_sub(deleted_at) >= u64::from(retention_days) * SECS_PER_DAY
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TrashManifest {
+    sessions: Vec<TrashEntry>,
+}
+
+/// Deleted sessions waiting to be restored or purged
+pub struct Trash;
+
+impl Trash {
+    pub fn trash_dir() -> Result<PathBuf, String> {
+        Ok(ChatHistory::chats_dir()?.join(TRASH_DIRNAME))
+    }
+
+    fn manifest_path() -> Result<PathBuf, String> {
+        Ok(Self::trash_dir()?.join(TRASH_MANIFEST))
+    }
+
+    fn session_path(session_id: &str) -> Result<PathBuf, String> {
+        Ok(Self::trash_dir()?.join(format!("{}.json", session_id)))
+    }
+
+    fn load_manifest() -> Result<TrashManifest, String> {
+        let path = Self::manifest_path()?;
+        if !path.exists() {
+            return Ok(TrashManifest::default());
+        }
+        let contents = ChatHistory::read_private_file(&path)
+            .map_err(|e| format!("Failed to read trash manifest: {}", e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse trash manifest: {}", e))
+    }
+
+    fn save_manifest(manifest: &TrashManifest) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(manifest)
+            .map_err(|e| format!("Failed to serialize trash manifest: {}", e))?;
+        ChatHistory::write_private_file(&Self::manifest_path()?, &contents)
+    }
+
+    /// Trashed sessions, most recently deleted first
+    pub fn list() -> Result<Vec<TrashEntry>, String> {
+        let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut sessions = Self::load_manifest()?.sessions;
+        sessions.sort_by(|a, b| {
+            let a = a.deleted_at.parse::<u64>().unwrap_or(0);
+            let b = b.deleted_at.parse::<u64>().unwrap_or(0);
+            b.cmp(&a)
+        });
+        Ok(sessions)
+    }
+
+    /// Move a session file into the trash and drop it from the session and search indexes
+    pub fn move_to_trash(session_id: &str) -> Result<(), String> {
+        let path = ChatHistory::session_path(session_id)?;
+
+        SessionIndex::update(|index| {
+            let entry = match index.get(session_id) {
+                Some(entry) => entry.clone(),
+                None if path.exists() => {
+                    let mut data = ChatHistory::load_session_file(&path)?;
+                    data.session.id = session_id.to_string();
+                    SessionIndexEntry::from_session_data(&data)
+                }
+                None => return Ok(()),
+            };
+
+            if path.exists() {
+                let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+                fs::create_dir_all(Self::trash_dir()?)
+                    .map_err(|e| format!("Failed to create trash directory: {}", e))?;
+                fs::rename(&path, Self::session_path(session_id)?)
+                    .map_err(|e| format!("Failed to move session to trash: {}", e))?;
+
+                let mut manifest = Self::load_manifest()?;
+                manifest.sessions.retain(|t| t.entry.id != session_id);
+                manifest.sessions.push(TrashEntry {
+                    entry,
+                    deleted_at: ChatHistory::format_timestamp(),
+                });
+                Self::save_manifest(&manifest)?;
+            }
+            index.remove(session_id);
+            Ok(())
+        })?;
+
+        if let Err(e) = SearchIndex::remove_session(session_id) {
+            eprintln!("Failed to update search index: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Move a trashed session back into the chats directory
+    pub fn restore(session_id: &str) -> Result<ChatSession, String> {
+        let target = ChatHistory::session_path(session_id)?;
+
+        let entry = SessionIndex::update(|index| {
+            let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut manifest = Self::load_manifest()?;
+            let position = manifest
+                .sessions
+                .iter()
+                .position(|t| t.entry.id == session_id)
+                .ok_or_else(|| "Chat is not in the trash".to_string())?;
+            if target.exists() {
+                return Err("A chat with this ID already exists".to_string());
+            }
+
+            fs::rename(Self::session_path(session_id)?, &target)
+                .map_err(|e| format!("Failed to restore session: {}", e))?;
+            let trashed = manifest.sessions.remove(position);
+            Self::save_manifest(&manifest)?;
+
+            index.upsert(trashed.entry.clone());
+            Ok(trashed.entry)
+        })?;
+
+        match ChatHistory::load_session(session_id) {
+            Ok(data) => {
+                if let Err(e) = SearchIndex::update_session(&data) {
+                    eprintln!("Failed to update search index: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Failed to reindex restored session: {}", e),
+        }
+        Ok(entry.to_session())
+    }
+
+    /// Permanently delete a trashed session
+    pub fn purge(session_id: &str) -> Result<(), String> {
+        let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self::purge_locked(&[session_id.to_string()])
+    }
+
+    /// Permanently delete sessions that have been in the trash longer than
+    /// `retention_days`. A retention of 0 keeps them until purged by hand.
+    pub fn purge_expired(retention_days: u32) -> Result<usize, String> {
+        let _guard = TRASH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let now = ChatHistory::format_timestamp().parse::<u64>().unwrap_or(0);
+        let expired: Vec<String> = Self::load_manifest()?
+            .sessions
+            .iter()
+            .filter(|t| t.is_expired(retention_days, now))
+            .map(|t| t.entry.id.clone())
+            .collect();
+        Self::purge_locked(&expired)?;
+        Ok(expired.len())
+    }
+
+    fn purge_locked(session_ids: &[String]) -> Result<(), String> {
+        if session_ids.is_empty() {
+            return Ok(());
+        }
+        let mut manifest = Self::load_manifest()?;
+        for session_id in session_ids {
+            let path = Self::session_path(session_id)?;
+            if path.exists() {
+                fs::remove_file(&path)
+                    .map_err(|e| format!("Failed to delete session file: {}", e))?;
+            }
+            manifest.sessions.retain(|t| &t.entry.id != session_id);
+        }
+        Self::save_manifest(&manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{ChatMode, SessionLabels, SessionStats};
+
+    fn trashed(deleted_at: u64) -> TrashEntry {
+        TrashEntry {
+            entry: SessionIndexEntry {
+                id: "1700000000-abc".to_string(),
+                title: "Old chat".to_string(),
+                mode: ChatMode::Standard,
+                timestamp: "1700000000".to_string(),
+                created_at: "1700000000".to_string(),
+                updated_at: "1700000000".to_string(),
+                renamed: true,
+                title_generated: false,
+                forked_from: None,
+                stats: SessionStats::default(),
+                labels: SessionLabels::default(),
+            },
+            deleted_at: deleted_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expiry_respects_retention() {
+        let now = 1_700_000_000 + 31 * SECS_PER_DAY;
+        let entry = trashed(1_700_000_000);
+        assert!(entry.is_expired(30, now));
+        assert!(!entry.is_expired(45, now));
+        assert!(!entry.is_expired(0, now));
+    }
+
+    #[test]
+    fn test_trash_entry_keeps_index_metadata() {
+        let json = serde_json::to_string(&trashed(1_700_000_000)).unwrap();
+        let entry: TrashEntry = serde_json::from_str(&json).unwrap();
+        assert!(entry.entry.renamed);
+        assert_eq!(entry.entry.to_session().title, "Old chat");
+    }
+}
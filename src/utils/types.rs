@@ -10,6 +10,7 @@ pub enum AppView {
     NewChat,
     ChatMode(ChatMode),
     Settings,
+    Leaderboard,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
@@ -28,6 +29,16 @@ pub enum ChatMode {
 }
 
 impl ChatMode {
+    pub fn all() -> [ChatMode; 5] {
+        [
+            ChatMode::Standard,
+            ChatMode::PvP,
+            ChatMode::Collaborative,
+            ChatMode::Competitive,
+            ChatMode::LLMChoice,
+        ]
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             ChatMode::Standard => "Standard",
@@ -73,6 +84,49 @@ pub struct ChatSession {
     pub title: String,
     pub mode: ChatMode,
     pub timestamp: String,
+    /// Filled in from the session index; not stored in session files
+    #[serde(skip)]
+    pub stats: SessionStats,
+    #[serde(default)]
+    pub labels: SessionLabels,
+}
+
+/// User-assigned organisation for a session
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SessionLabels {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl SessionLabels {
+    /// Trim names, drop empty ones and remove duplicate tags
+    pub fn normalized(mut self) -> Self {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        self.tags = tags;
+        self.folder = self
+            .folder
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty());
+        self
+    }
+}
+
+/// Summary figures kept in the session index so the sidebar never opens session files
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub models: Vec<String>,
+    pub rounds: usize,
+    /// Rough size of all prompts and outputs, at about four characters per token
+    pub approx_tokens: u64,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
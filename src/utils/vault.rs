@@ -0,0 +1,524 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard};
+
+use crate::utils::{ChatHistory, SearchIndex, Settings, Trash};
+
+/// Prefix marking a sealed (encrypted) file or value
+const SEALED_PREFIX: &str = "gtllm-vault:v1:";
+const VAULT_FILENAME: &str = "vault.json";
+/// Known plaintext sealed into the header to check a passphrase
+const CHECK_PLAINTEXT: &str = "gtllm-vault-check";
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+type VaultKey = [u8; KEY_LEN];
+
+/// The unlocked key, if any. Readers hold a read guard for each file they seal or open;
+/// enabling, re-keying and disabling hold the write lock while every file is rewritten.
+static VAULT_KEY: RwLock<Option<VaultKey>> = RwLock::new(None);
+
+/// Key derivation parameters and check value for one passphrase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeySlot {
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    salt: String,
+    check: String,
+}
+
+impl KeySlot {
+    fn new(passphrase: &str) -> Result<(KeySlot, VaultKey), String> {
+        Self::with_params(passphrase, Params::DEFAULT_M_COST, Params::DEFAULT_T_COST, Params::DEFAULT_P_COST)
+    }
+
+    fn with_params(passphrase: &str, m_cost: u32, t_cost: u32, p_cost: u32) -> Result<(KeySlot, VaultKey), String> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let mut slot = KeySlot {
+            m_cost,
+            t_cost,
+            p_cost,
+            salt: BASE64.encode(salt),
+            check: String::new(),
+        };
+        let key = slot.derive_key(passphrase)?;
+        slot.check = seal_with(&key, CHECK_PLAINTEXT)?;
+        Ok((slot, key))
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<VaultKey, String> {
+        let salt = BASE64
+            .decode(&self.salt)
+            .map_err(|e| format!("Failed to read vault salt: {}", e))?;
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(KEY_LEN))
+            .map_err(|e| format!("Invalid vault parameters: {}", e))?;
+        let mut key = [0u8; KEY_LEN];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| format!("Failed to derive vault key: {}", e))?;
+        Ok(key)
+    }
+
+    /// Derive the key and confirm it opens the slot's check value
+    fn unlock(&self, passphrase: &str) -> Result<VaultKey, String> {
+        let key = self.derive_key(passphrase)?;
+        match open_with(&key, &self.check) {
+            Ok(check) if check == CHECK_PLAINTEXT => Ok(key),
+            _ => Err("Incorrect passphrase".to_string()),
+        }
+    }
+}
+
+/// A change to the encryption that was started but may not have reached every file.
+/// It is written to the header before any file is touched and cleared once all are done,
+/// so an interrupted change is finished on the next unlock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PendingChange {
+    /// Some files may still be plaintext
+    Enable,
+    /// Some files may still be sealed with the previous passphrase's key. Each key is also
+    /// stored sealed with the other, so either passphrase recovers both.
+    Rekey {
+        previous: KeySlot,
+        /// The previous key, sealed with the new one
+        previous_key: String,
+        /// The new key, sealed with the previous one
+        current_key: String,
+    },
+    /// Some files may already be plaintext; the header is removed once all are
+    Disable,
+}
+
+/// Key derivation parameters and passphrase check, stored next to settings.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultHeader {
+    version: u32,
+    #[serde(flatten)]
+    slot: KeySlot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending: Option<PendingChange>,
+}
+
+impl VaultHeader {
+    fn new(slot: KeySlot) -> VaultHeader {
+        VaultHeader {
+            version: 1,
+            slot,
+            pending: None,
+        }
+    }
+
+    /// Header for moving from `self` (unlocked with `old_key`) to a new slot and key
+    fn rekeyed(&self, old_key: &VaultKey, new_slot: KeySlot, new_key: &VaultKey) -> Result<VaultHeader, String> {
+        Ok(VaultHeader {
+            version: 1,
+            slot: new_slot,
+            pending: Some(PendingChange::Rekey {
+                previous: self.slot.clone(),
+                previous_key: seal_with(new_key, &BASE64.encode(old_key))?,
+                current_key: seal_with(old_key, &BASE64.encode(new_key))?,
+            }),
+        })
+    }
+
+    /// Unlock with `passphrase`. Returns the key to use from now on and every key that may
+    /// still seal a file; during an unfinished re-key either passphrase is accepted.
+    fn unlock(&self, passphrase: &str) -> Result<(VaultKey, Vec<VaultKey>), String> {
+        let Some(PendingChange::Rekey { previous, previous_key, current_key }) = &self.pending else {
+            let key = self.slot.unlock(passphrase)?;
+            return Ok((key, vec![key]));
+        };
+
+        let (old_key, new_key) = match self.slot.unlock(passphrase) {
+            Ok(new_key) => (decode_key(&open_with(&new_key, previous_key)?)?, new_key),
+            Err(_) => {
+                let old_key = previous.unlock(passphrase)?;
+                (old_key, decode_key(&open_with(&old_key, current_key)?)?)
+            }
+        };
+        Ok((new_key, vec![old_key, new_key]))
+    }
+}
+
+fn decode_key(encoded: &str) -> Result<VaultKey, String> {
+    BASE64
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| VaultKey::try_from(bytes.as_slice()).ok())
+        .ok_or_else(|| "Vault file holds an invalid key".to_string())
+}
+
+fn is_sealed(contents: &str) -> bool {
+    contents.starts_with(SEALED_PREFIX)
+}
+
+fn seal_with(key: &VaultKey, plaintext: &str) -> Result<String, String> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| "Failed to encrypt data".to_string())?;
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(payload)))
+}
+
+fn open_with(key: &VaultKey, sealed: &str) -> Result<String, String> {
+    let encoded = sealed
+        .strip_prefix(SEALED_PREFIX)
+        .ok_or_else(|| "Data is not encrypted".to_string())?;
+    let payload = BASE64
+        .decode(encoded.trim())
+        .map_err(|e| format!("Failed to decode encrypted data: {}", e))?;
+    if payload.len() < NONCE_LEN {
+        return Err("Encrypted data is truncated".to_string());
+    }
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let plaintext = XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Failed to decrypt data: wrong key or corrupted file".to_string())?;
+    String::from_utf8(plaintext).map_err(|e| format!("Decrypted data is not valid UTF-8: {}", e))
+}
+
+/// Open with `key`, or pass plaintext through unchanged
+fn open_optional(key: Option<&VaultKey>, contents: String) -> Result<String, String> {
+    if !is_sealed(&contents) {
+        return Ok(contents);
+    }
+    match key {
+        Some(key) => open_with(key, &contents),
+        None => Err("The vault is locked".to_string()),
+    }
+}
+
+/// Open with whichever of `keys` sealed the contents, or pass plaintext through unchanged
+fn open_any(keys: &[VaultKey], contents: String) -> Result<String, String> {
+    if !is_sealed(&contents) {
+        return Ok(contents);
+    }
+    keys.iter()
+        .find_map(|key| open_with(key, &contents).ok())
+        .ok_or_else(|| "Failed to decrypt data: wrong key or corrupted file".to_string())
+}
+
+/// Seal with `key`, or leave as plaintext when encryption is off
+fn seal_optional(key: Option<&VaultKey>, plaintext: &str) -> Result<String, String> {
+    match key {
+        Some(key) => seal_with(key, plaintext),
+        None => Ok(plaintext.to_string()),
+    }
+}
+
+/// Holds the vault key steady while a single file is sealed or opened
+pub(crate) struct VaultGuard(RwLockReadGuard<'static, Option<VaultKey>>);
+
+impl VaultGuard {
+    /// Encrypt `plaintext` when the vault is enabled; refuses to write while it is locked
+    pub(crate) fn seal(&self, plaintext: &str) -> Result<String, String> {
+        if self.0.is_none() && Vault::is_enabled() {
+            return Err("The vault is locked".to_string());
+        }
+        seal_optional(self.0.as_ref(), plaintext)
+    }
+
+    /// Decrypt sealed contents; plaintext (written before encryption was enabled) passes through
+    pub(crate) fn open(&self, contents: String) -> Result<String, String> {
+        open_optional(self.0.as_ref(), contents)
+    }
+}
+
+/// Changes to the vault requested from settings
+#[derive(Debug, Clone, PartialEq)]
+pub enum VaultCommand {
+    Enable { passphrase: String },
+    ChangePassphrase { current: String, new: String },
+    Disable { current: String },
+}
+
+/// Optional passphrase-protected encryption of chat files and the API key
+pub struct Vault;
+
+impl Vault {
+    pub(crate) fn header_path() -> Result<PathBuf, String> {
+        Ok(Settings::settings_dir()?.join(VAULT_FILENAME))
+    }
+
+    fn load_header() -> Result<Option<VaultHeader>, String> {
+        let path = Self::header_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read vault file: {}", e))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse vault file: {}", e))
+    }
+
+    /// True when a passphrase has been set
+    pub fn is_enabled() -> bool {
+        Self::header_path().map(|path| path.exists()).unwrap_or(false)
+    }
+
+    /// True when data is encrypted and the passphrase has not been entered yet
+    pub fn is_locked() -> bool {
+        Self::is_enabled() && VAULT_KEY.read().unwrap_or_else(|e| e.into_inner()).is_none()
+    }
+
+    pub(crate) fn guard() -> VaultGuard {
+        VaultGuard(VAULT_KEY.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Derive the key from `passphrase` and keep it in memory for this run.
+    /// Finishes an encryption change that was interrupted before it reached every file.
+    pub fn unlock(passphrase: &str) -> Result<(), String> {
+        let mut state = VAULT_KEY.write().unwrap_or_else(|e| e.into_inner());
+        let header = Self::load_header()?.ok_or_else(|| "Encryption is not enabled".to_string())?;
+        let (key, keys) = header.unlock(passphrase)?;
+        match header.pending {
+            None => {}
+            Some(PendingChange::Disable) => {
+                Self::write_all(Self::read_all(&keys)?, None)?;
+                fs::remove_file(Self::header_path()?)
+                    .map_err(|e| format!("Failed to remove vault file: {}", e))?;
+                *state = None;
+                return Ok(());
+            }
+            Some(PendingChange::Enable) | Some(PendingChange::Rekey { .. }) => {
+                Self::write_all(Self::read_all(&keys)?, Some(&key))?;
+                Self::write_header(&VaultHeader::new(header.slot))?;
+            }
+        }
+        *state = Some(key);
+        Ok(())
+    }
+
+    pub fn apply(command: VaultCommand) -> Result<(), String> {
+        match command {
+            VaultCommand::Enable { passphrase } => Self::enable(&passphrase),
+            VaultCommand::ChangePassphrase { current, new } => Self::rekey(&current, &new),
+            VaultCommand::Disable { current } => Self::disable(&current),
+        }
+    }
+
+    /// Load the header of an enabled vault with no unfinished change
+    fn settled_header() -> Result<VaultHeader, String> {
+        let header = Self::load_header()?.ok_or_else(|| "Encryption is not enabled".to_string())?;
+        if header.pending.is_some() {
+            return Err("An earlier encryption change did not finish; unlock again to complete it".to_string());
+        }
+        Ok(header)
+    }
+
+    /// Set a passphrase and encrypt every chat file and the API key with it
+    pub fn enable(passphrase: &str) -> Result<(), String> {
+        check_passphrase(passphrase)?;
+        let mut state = VAULT_KEY.write().unwrap_or_else(|e| e.into_inner());
+        if Self::load_header()?.is_some() {
+            return Err("Encryption is already enabled".to_string());
+        }
+        let plaintexts = Self::read_all(&[])?;
+        let (slot, key) = KeySlot::new(passphrase)?;
+        let mut header = VaultHeader::new(slot);
+        header.pending = Some(PendingChange::Enable);
+        Self::write_header(&header)?;
+
+        // Until the header is settled, files may be plaintext or sealed with the new key
+        *state = Some(key);
+        Self::write_all(plaintexts, Some(&key)).map_err(|e| interrupted(&e))?;
+        header.pending = None;
+        Self::write_header(&header)
+    }
+
+    /// Re-encrypt everything under a new passphrase
+    pub fn rekey(current: &str, new: &str) -> Result<(), String> {
+        check_passphrase(new)?;
+        let mut state = VAULT_KEY.write().unwrap_or_else(|e| e.into_inner());
+        let header = Self::settled_header()?;
+        let old_key = header.slot.unlock(current)?;
+        let plaintexts = Self::read_all(&[old_key])?;
+        let (new_slot, new_key) = KeySlot::new(new)?;
+        let mut new_header = header.rekeyed(&old_key, new_slot, &new_key)?;
+        Self::write_header(&new_header)?;
+
+        // Files sealed with either key are only readable again after an unlock finishes the change
+        *state = None;
+        Self::write_all(plaintexts, Some(&new_key)).map_err(|e| interrupted(&e))?;
+        new_header.pending = None;
+        Self::write_header(&new_header).map_err(|e| interrupted(&e))?;
+        *state = Some(new_key);
+        Ok(())
+    }
+
+    /// Decrypt everything back to plaintext and remove the passphrase
+    pub fn disable(current: &str) -> Result<(), String> {
+        let mut state = VAULT_KEY.write().unwrap_or_else(|e| e.into_inner());
+        let mut header = Self::settled_header()?;
+        let old_key = header.slot.unlock(current)?;
+        let plaintexts = Self::read_all(&[old_key])?;
+        header.pending = Some(PendingChange::Disable);
+        Self::write_header(&header)?;
+
+        // Plaintext passes through, so the old key still reads every file until the end
+        Self::write_all(plaintexts, None).map_err(|e| interrupted(&e))?;
+        fs::remove_file(Self::header_path()?)
+            .map_err(|e| format!("Failed to remove vault file: {}", e))?;
+        *state = None;
+        Ok(())
+    }
+
+    fn write_header(header: &VaultHeader) -> Result<(), String> {
+        let dir = Settings::settings_dir()?;
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create settings directory: {}", e))?;
+        let contents = serde_json::to_string_pretty(header)
+            .map_err(|e| format!("Failed to serialize vault file: {}", e))?;
+        ChatHistory::write_file_atomic(&Self::header_path()?, &contents)
+    }
+
+    /// Every file holding chat data: sessions, indexes and the trash
+    fn data_files() -> Result<Vec<PathBuf>, String> {
+        let chats_dir = ChatHistory::chats_dir()?;
+        let mut files = Vec::new();
+        collect_data_files(&chats_dir, &mut files)?;
+        collect_data_files(&Trash::trash_dir()?, &mut files)?;
+        collect_data_files(&SearchIndex::index_dir()?, &mut files)?;
+        Ok(files)
+    }
+
+    /// Decrypt every data file and the API key, opening whatever is sealed with any of `keys`.
+    /// Nothing is written, so a wrong key or corrupt file stops a change before it starts.
+    fn read_all(keys: &[VaultKey]) -> Result<Plaintexts, String> {
+        let mut files = Vec::new();
+        for path in Self::data_files()? {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let plaintext = open_any(keys, contents)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            files.push((path, plaintext));
+        }
+        let api_key = Settings::read_file()?
+            .openrouter_api_key
+            .map(|api_key| open_any(keys, api_key))
+            .transpose()?;
+        Ok(Plaintexts { files, api_key })
+    }
+
+    /// Write everything back sealed with `new_key` (None meaning plaintext). Each file is
+    /// replaced atomically, so a failure part way leaves every file readable with either
+    /// the old or the new key, and the pending change in the header finishes the rest.
+    fn write_all(plaintexts: Plaintexts, new_key: Option<&VaultKey>) -> Result<(), String> {
+        if let Some(api_key) = plaintexts.api_key {
+            let mut settings = Settings::read_file()?;
+            settings.openrouter_api_key = Some(seal_optional(new_key, &api_key)?);
+            settings.write_file()?;
+        }
+        for (path, plaintext) in plaintexts.files {
+            ChatHistory::write_file_atomic(&path, &seal_optional(new_key, &plaintext)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decrypted contents of every file a change re-encrypts
+struct Plaintexts {
+    files: Vec<(PathBuf, String)>,
+    api_key: Option<String>,
+}
+
+fn interrupted(error: &str) -> String {
+    format!("Encryption change did not finish ({}); unlock to complete it", error)
+}
+
+fn check_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < 8 {
+        return Err("Passphrase must be at least 8 characters".to_string());
+    }
+    Ok(())
+}
+
+fn collect_data_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?.path();
+        let data_file = matches!(path.extension().and_then(|s| s.to_str()), Some("json") | Some("idx"));
+        if path.is_file() && data_file {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_roundtrip_and_wrong_key() {
+        let key = [7u8; KEY_LEN];
+        let sealed = seal_with(&key, "{\"hello\": \"wörld\"}").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("hello"));
+        assert_eq!(open_with(&key, &sealed).unwrap(), "{\"hello\": \"wörld\"}");
+        assert!(open_with(&[8u8; KEY_LEN], &sealed).is_err());
+    }
+
+    #[test]
+    fn test_plaintext_passes_through_and_locked_refuses_sealed() {
+        let key = [1u8; KEY_LEN];
+        assert_eq!(open_optional(None, "{}".to_string()).unwrap(), "{}");
+        assert_eq!(open_optional(Some(&key), "{}".to_string()).unwrap(), "{}");
+        let sealed = seal_with(&key, "secret").unwrap();
+        assert!(open_optional(None, sealed).is_err());
+    }
+
+    #[test]
+    fn test_header_checks_passphrase() {
+        let (slot, key) = KeySlot::with_params("correct horse", 64, 1, 1).unwrap();
+        assert_eq!(slot.unlock("correct horse").unwrap(), key);
+        assert!(slot.unlock("wrong horse").is_err());
+    }
+
+    #[test]
+    fn test_interrupted_rekey_is_recoverable_with_either_passphrase() {
+        let (old_slot, old_key) = KeySlot::with_params("old passphrase", 64, 1, 1).unwrap();
+        let header = VaultHeader::new(old_slot);
+        let files = ["{\"a\": 1}", "{\"b\": 2}"].map(|text| seal_with(&old_key, text).unwrap());
+
+        // The pending header is written first; the process then dies after rewriting one
+        // file and before the settled header replaces it
+        let (new_slot, new_key) = KeySlot::with_params("new passphrase", 64, 1, 1).unwrap();
+        let pending = header.rekeyed(&old_key, new_slot, &new_key).unwrap();
+        let stored: VaultHeader =
+            serde_json::from_str(&serde_json::to_string(&pending).unwrap()).unwrap();
+        let rewritten = [
+            seal_with(&new_key, &open_with(&old_key, &files[0]).unwrap()).unwrap(),
+            files[1].clone(),
+        ];
+
+        for passphrase in ["old passphrase", "new passphrase"] {
+            let (key, keys) = stored.unlock(passphrase).unwrap();
+            assert_eq!(key, new_key);
+            assert_eq!(open_any(&keys, rewritten[0].clone()).unwrap(), "{\"a\": 1}");
+            assert_eq!(open_any(&keys, rewritten[1].clone()).unwrap(), "{\"b\": 2}");
+        }
+        assert!(stored.unlock("wrong passphrase").is_err());
+
+        // A header written before pending changes existed still loads
+        let legacy = r#"{"version":1,"m_cost":64,"t_cost":1,"p_cost":1,"salt":"AAAAAAAAAAAAAAAAAAAAAA==","check":""}"#;
+        let legacy: VaultHeader = serde_json::from_str(legacy).unwrap();
+        assert!(legacy.pending.is_none());
+    }
+}
@@ -0,0 +1,477 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::utils::VoteTally;
+
+/// How Competitive voters fill in their ballots and how the ballots are counted
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum VotingMethod {
+    /// One vote each; the most votes win
+    #[default]
+    Plurality,
+    /// Ranked ballots; the weakest proposal is eliminated and its votes move on until one has a majority
+    InstantRunoff,
+    /// Ranked ballots; a proposal scores more points the higher it is ranked
+    Borda,
+    /// Each voter approves any number of proposals; the most approvals win
+    Approval,
+}
+
+impl VotingMethod {
+    pub const ALL: [VotingMethod; 4] = [
+        VotingMethod::Plurality,
+        VotingMethod::InstantRunoff,
+        VotingMethod::Borda,
+        VotingMethod::Approval,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VotingMethod::Plurality => "Plurality",
+            VotingMethod::InstantRunoff => "Ranked choice (instant runoff)",
+            VotingMethod::Borda => "Borda count",
+            VotingMethod::Approval => "Approval",
+        }
+    }
+
+    /// Stable name for form values
+    pub fn key(&self) -> &'static str {
+        match self {
+            VotingMethod::Plurality => "plurality",
+            VotingMethod::InstantRunoff => "irv",
+            VotingMethod::Borda => "borda",
+            VotingMethod::Approval => "approval",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|m| m.key() == key).unwrap_or_default()
+    }
+
+    /// What a tally's count means under this method, e.g. "3 points"
+    pub fn count_label(&self, count: usize) -> String {
+        let unit = match self {
+            VotingMethod::Borda => "point",
+            VotingMethod::Approval => "approval",
+            _ => "vote",
+        };
+        format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+    }
+
+    /// Ballot instructions added after the voting prompt; plurality uses the template's own
+    pub fn ballot_instructions(&self, candidates: &[String]) -> Option<String> {
+        let list = candidates.join("\n");
+        match self {
+            VotingMethod::Plurality => None,
+            VotingMethod::InstantRunoff | VotingMethod::Borda => Some(format!(
+                "Ballot format (this replaces any instruction above to name a single model): rank every \
+                proposal except your own from best to worst, one model ID per line, best first. \
+                Proposals to rank:\n{}",
+                list
+            )),
+            VotingMethod::Approval => Some(format!(
+                "Ballot format (this replaces any instruction above to name a single model): list the \
+                model ID of every proposal except your own that you consider a good answer, one per line. \
+                Proposals you may approve:\n{}",
+                list
+            )),
+        }
+    }
+
+    /// A voter's ballot read from its reply: a single vote for plurality, otherwise the model IDs
+    /// in the order they appear. The voter's own proposal never counts.
+    pub fn parse_ballot(&self, response: &str, voter_id: &str, candidates: &[String]) -> Vec<String> {
+        match self {
+            VotingMethod::Plurality => parse_single(response, voter_id, candidates).into_iter().collect(),
+            _ => parse_ranking(response, voter_id, candidates),
+        }
+    }
+
+    /// Count (voter ID, ballot) pairs for `candidates`; tallies come back best first, with every
+    /// proposal sharing the top result as a winner
+    pub fn tally(&self, ballots: &[(String, Vec<String>)], candidates: &[String]) -> (Vec<VoteTally>, Vec<String>) {
+        match self {
+            VotingMethod::Plurality => ranked_tallies(candidates, |candidate| {
+                ballots
+                    .iter()
+                    .filter(|(_, ballot)| ballot.first() == Some(candidate))
+                    .map(|(voter, _)| (voter.clone(), 1))
+                    .collect()
+            }),
+            VotingMethod::Approval => ranked_tallies(candidates, |candidate| {
+                ballots
+                    .iter()
+                    .filter(|(_, ballot)| ballot.contains(candidate))
+                    .map(|(voter, _)| (voter.clone(), 1))
+                    .collect()
+            }),
+            VotingMethod::Borda => ranked_tallies(candidates, |candidate| {
+                ballots
+                    .iter()
+                    .filter_map(|(voter, ballot)| {
+                        let position = ballot.iter().position(|c| c == candidate)?;
+                        Some((voter.clone(), borda_points(candidates.len(), position)))
+                    })
+                    .collect()
+            }),
+            VotingMethod::InstantRunoff => instant_runoff(ballots, candidates),
+        }
+    }
+}
+
+/// How a Competitive round settles a vote that ends with several proposals level
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum TieBreak {
+    /// The tied proposals share the win
+    #[default]
+    Shared,
+    /// Voters vote again, one vote each, on the tied proposals only
+    Runoff,
+    /// A designated chair model picks one of the tied proposals
+    Chair,
+    /// The user picks one of the tied proposals
+    User,
+}
+
+impl TieBreak {
+    pub const ALL: [TieBreak; 4] = [TieBreak::Shared, TieBreak::Runoff, TieBreak::Chair, TieBreak::User];
+
+    /// Runoff votes held before a tie is left standing
+    pub const MAX_RUNOFFS: usize = 2;
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TieBreak::Shared => "Shared win",
+            TieBreak::Runoff => "Runoff vote",
+            TieBreak::Chair => "Chair decides",
+            TieBreak::User => "I decide",
+        }
+    }
+
+    /// Stable name for form values
+    pub fn key(&self) -> &'static str {
+        match self {
+            TieBreak::Shared => "shared",
+            TieBreak::Runoff => "runoff",
+            TieBreak::Chair => "chair",
+            TieBreak::User => "user",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|t| t.key() == key).unwrap_or_default()
+    }
+
+    /// Heading for a tie-break phase, `number` counting from 1
+    pub fn phase_title(&self, number: usize) -> String {
+        match self {
+            TieBreak::Runoff => format!("Runoff {}", number),
+            TieBreak::Chair => "Chair's Decision".to_string(),
+            TieBreak::User => "User's Decision".to_string(),
+            TieBreak::Shared => "Shared Win".to_string(),
+        }
+    }
+
+    /// Prompt asking a runoff voter or the chair to choose among the tied (model ID, proposal) pairs;
+    /// a tied proposal of the model's own is shown but marked ineligible
+    pub fn prompt(&self, question: &str, tied: &[(String, String)], voter_id: &str) -> String {
+        let proposals = tied
+            .iter()
+            .map(|(model_id, content)| format!("Model: {}\n{}\n", model_id, content))
+            .collect::<Vec<_>>()
+            .join("\n---\n\n");
+        let role = match self {
+            TieBreak::Chair => "You are the chair. The vote on the question below ended in a tie, and you decide which of the tied proposals wins.",
+            _ => "The vote on the question below ended in a tie. Vote again, choosing only among the tied proposals.",
+        };
+        let own = if tied.iter().any(|(model_id, _)| model_id == voter_id) {
+            format!(" Your own proposal ({}) cannot be chosen.", voter_id)
+        } else {
+            String::new()
+        };
+        format!(
+            "{}{}\n\nQuestion: {}\n\nTied proposals:\n\n{}\nRespond with the model ID of the proposal you choose.",
+            role, own, question, proposals
+        )
+    }
+}
+
+/// Points for ranking a proposal at `position` (from 0) among `candidates` proposals, where
+/// voters rank everyone but themselves: first place gets one point per other proposal, last gets 1
+fn borda_points(candidates: usize, position: usize) -> usize {
+    candidates.saturating_sub(1).saturating_sub(position).max(1)
+}
+
+/// Tallies from each candidate's (voter, points) list, sorted by total, winners being everyone on the top total
+fn ranked_tallies(
+    candidates: &[String],
+    mut support: impl FnMut(&String) -> Vec<(String, usize)>,
+) -> (Vec<VoteTally>, Vec<String>) {
+    let mut tallies: Vec<VoteTally> = candidates
+        .iter()
+        .map(|candidate| {
+            let support = support(candidate);
+            VoteTally {
+                model_id: candidate.clone(),
+                vote_count: support.iter().map(|(_, points)| points).sum(),
+                voters: support.into_iter().map(|(voter, _)| voter).collect(),
+                round_counts: Vec::new(),
+                eliminated_in: None,
+            }
+        })
+        .collect();
+    tallies.sort_by_key(|t| std::cmp::Reverse(t.vote_count));
+    let winners = top_of(&tallies);
+    (tallies, winners)
+}
+
+fn top_of(tallies: &[VoteTally]) -> Vec<String> {
+    let max = tallies.iter().map(|t| t.vote_count).max().unwrap_or(0);
+    tallies
+        .iter()
+        .filter(|t| max > 0 && t.vote_count == max)
+        .map(|t| t.model_id.clone())
+        .collect()
+}
+
+/// Count each ballot for its highest-ranked proposal still in the race; a proposal with more than
+/// half of those votes wins, otherwise the last-placed one is eliminated. A tie for last place goes
+/// to the lower Borda score, and proposals still level on both are eliminated together unless
+/// they're all that's left, in which case they share the win.
+fn instant_runoff(ballots: &[(String, Vec<String>)], candidates: &[String]) -> (Vec<VoteTally>, Vec<String>) {
+    let borda: HashMap<&String, usize> = candidates
+        .iter()
+        .map(|candidate| {
+            let points = ballots
+                .iter()
+                .filter_map(|(_, ballot)| ballot.iter().position(|c| c == candidate))
+                .map(|position| borda_points(candidates.len(), position))
+                .sum();
+            (candidate, points)
+        })
+        .collect();
+
+    let mut tallies: Vec<VoteTally> = candidates
+        .iter()
+        .map(|candidate| VoteTally {
+            model_id: candidate.clone(),
+            vote_count: 0,
+            voters: Vec::new(),
+            round_counts: Vec::new(),
+            eliminated_in: None,
+        })
+        .collect();
+    let mut remaining: Vec<String> = candidates.to_vec();
+    let mut winners: Vec<String> = Vec::new();
+    let mut round = 0;
+
+    while !remaining.is_empty() {
+        round += 1;
+        for tally in tallies.iter_mut().filter(|t| remaining.contains(&t.model_id)) {
+            tally.voters = ballots
+                .iter()
+                .filter(|(_, ballot)| ballot.iter().find(|c| remaining.contains(c)) == Some(&tally.model_id))
+                .map(|(voter, _)| voter.clone())
+                .collect();
+            tally.vote_count = tally.voters.len();
+            tally.round_counts.push(tally.vote_count);
+        }
+        let standing: Vec<&VoteTally> = tallies.iter().filter(|t| remaining.contains(&t.model_id)).collect();
+        let active: usize = standing.iter().map(|t| t.vote_count).sum();
+        if active == 0 {
+            break;
+        }
+        let top = standing.iter().map(|t| t.vote_count).max().unwrap_or(0);
+        if top * 2 > active || remaining.len() == 1 {
+            winners = standing.iter().filter(|t| t.vote_count == top).map(|t| t.model_id.clone()).collect();
+            break;
+        }
+
+        let bottom = standing.iter().map(|t| t.vote_count).min().unwrap_or(0);
+        let last: Vec<&String> = standing.iter().filter(|t| t.vote_count == bottom).map(|t| &t.model_id).collect();
+        let lowest_borda = last.iter().map(|c| borda[*c]).min().unwrap_or(0);
+        let out: Vec<String> = last.into_iter().filter(|c| borda[*c] == lowest_borda).cloned().collect();
+        if out.len() == remaining.len() {
+            winners = out;
+            break;
+        }
+        for tally in tallies.iter_mut().filter(|t| out.contains(&t.model_id)) {
+            tally.eliminated_in = Some(round);
+        }
+        remaining.retain(|c| !out.contains(c));
+    }
+
+    // Winners first, then the rest by how long they lasted and their last count
+    tallies.sort_by(|a, b| {
+        winners
+            .contains(&b.model_id)
+            .cmp(&winners.contains(&a.model_id))
+            .then(b.eliminated_in.unwrap_or(usize::MAX).cmp(&a.eliminated_in.unwrap_or(usize::MAX)))
+            .then(b.vote_count.cmp(&a.vote_count))
+    });
+    (tallies, winners)
+}
+
+/// Where each candidate is first named in `text`, by its full ID or the part after the last
+/// `/`, ignoring case. A name only counts as a whole token, and longer names are matched first
+/// with their spans masked, so `gpt-4o` is never found inside `gpt-4o-mini`.
+pub fn mention_positions(text: &str, candidates: &[String]) -> Vec<Option<usize>> {
+    let lower = text.to_lowercase();
+    let mut names: Vec<(String, usize)> = Vec::new();
+    for (idx, candidate) in candidates.iter().enumerate() {
+        let full = candidate.to_lowercase();
+        let short = full.rsplit('/').next().unwrap_or(&full).to_string();
+        names.push((full, idx));
+        names.push((short, idx));
+    }
+    names.retain(|(name, _)| !name.is_empty());
+    names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+    let mut masked = vec![false; lower.len()];
+    let mut positions = vec![None; candidates.len()];
+    for (name, idx) in &names {
+        for (start, _) in lower.match_indices(name.as_str()) {
+            let end = start + name.len();
+            if masked[start..end].iter().any(|&m| m) || !is_whole_token(&lower, start, end) {
+                continue;
+            }
+            masked[start..end].iter_mut().for_each(|m| *m = true);
+            let first: &mut Option<usize> = &mut positions[*idx];
+            *first = Some(first.map_or(start, |p| p.min(start)));
+        }
+    }
+    positions
+}
+
+/// Neither end of `text[start..end]` runs on into more of a model ID. A `.` or `:` after
+/// the name only continues it when more of the ID follows, as in `claude-3.5` or `model:free`.
+fn is_whole_token(text: &str, start: usize, end: usize) -> bool {
+    let continues = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '/');
+    let before = text[..start].chars().next_back();
+    let mut after = text[end..].chars();
+    let after_ok = match after.next() {
+        Some('.' | ':') => !after.next().is_some_and(char::is_alphanumeric),
+        Some(c) => !continues(c),
+        None => true,
+    };
+    !before.is_some_and(continues) && after_ok
+}
+
+/// The first other model ID named in a single-vote reply; naming only oneself is no vote
+fn parse_single(response: &str, voter_id: &str, candidates: &[String]) -> Option<String> {
+    parse_ranking(response, voter_id, candidates).into_iter().next()
+}
+
+/// Every other candidate named in the reply, ordered by where it's first mentioned. The
+/// voter's own ID is still matched so it cannot be read as a shorter candidate's name.
+fn parse_ranking(response: &str, voter_id: &str, candidates: &[String]) -> Vec<String> {
+    let mut found: Vec<(usize, &String)> = mention_positions(response, candidates)
+        .into_iter()
+        .zip(candidates)
+        .filter(|(_, candidate)| candidate.as_str() != voter_id)
+        .filter_map(|(position, candidate)| Some((position?, candidate)))
+        .collect();
+    found.sort_by_key(|(position, _)| *position);
+    found.into_iter().map(|(_, candidate)| candidate.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn ballot(voter: &str, ranking: &[&str]) -> (String, Vec<String>) {
+        (voter.to_string(), ids(ranking))
+    }
+
+    #[test]
+    fn test_parse_ranking_skips_self_and_orders_by_mention() {
+        let candidates = ids(&["a/alpha", "b/beta", "c/gamma"]);
+        let reply = "1. c/gamma\n2. a/alpha (mine is b/beta)";
+        assert_eq!(
+            VotingMethod::Borda.parse_ballot(reply, "b/beta", &candidates),
+            ids(&["c/gamma", "a/alpha"])
+        );
+        assert_eq!(VotingMethod::Plurality.parse_ballot("beta", "a/alpha", &candidates), ids(&["b/beta"]));
+        assert!(VotingMethod::Plurality.parse_ballot("b/beta", "b/beta", &candidates).is_empty());
+    }
+
+    #[test]
+    fn test_ballots_do_not_match_an_id_inside_a_longer_one() {
+        let candidates = ids(&["openai/gpt-4o", "openai/gpt-4o-mini", "c/gamma"]);
+        assert_eq!(
+            VotingMethod::Borda.parse_ballot("1. gpt-4o-mini\n2. gpt-4o", "c/gamma", &candidates),
+            ids(&["openai/gpt-4o-mini", "openai/gpt-4o"])
+        );
+        assert_eq!(
+            VotingMethod::Borda.parse_ballot("1. c/gamma (not mine, openai/gpt-4o-mini)", "openai/gpt-4o-mini", &candidates),
+            ids(&["c/gamma"])
+        );
+        assert_eq!(
+            VotingMethod::Plurality.parse_ballot("openai/gpt-4o-mini", "c/gamma", &candidates),
+            ids(&["openai/gpt-4o-mini"])
+        );
+    }
+
+    #[test]
+    fn test_plurality_ties_where_borda_and_approval_separate() {
+        let candidates = ids(&["a", "b", "c"]);
+        let ballots = vec![ballot("a", &["b", "c"]), ballot("b", &["c", "a"]), ballot("c", &["a", "b"])];
+        let (_, winners) = VotingMethod::Plurality.tally(&ballots, &candidates);
+        assert_eq!(winners.len(), 3);
+
+        let lopsided = vec![ballot("a", &["b", "c"]), ballot("b", &["a", "c"]), ballot("c", &["b", "a"])];
+        let (tallies, winners) = VotingMethod::Borda.tally(&lopsided, &candidates);
+        assert_eq!(winners, ids(&["b"]));
+        assert_eq!(tallies[0].vote_count, 4);
+
+        let approvals = vec![ballot("a", &["b", "c"]), ballot("b", &["c"]), ballot("c", &["b"])];
+        let (tallies, winners) = VotingMethod::Approval.tally(&approvals, &candidates);
+        assert_eq!(winners, ids(&["b", "c"]));
+        assert_eq!(tallies[0].voters.len(), 2);
+    }
+
+    #[test]
+    fn test_instant_runoff_eliminates_and_transfers_votes() {
+        let candidates = ids(&["a", "b", "c", "d"]);
+        let ballots = vec![
+            ballot("a", &["d", "b", "c"]),
+            ballot("b", &["a", "c", "d"]),
+            ballot("c", &["b", "a", "d"]),
+            ballot("d", &["b", "c", "a"]),
+        ];
+        let (tallies, winners) = VotingMethod::InstantRunoff.tally(&ballots, &candidates);
+        assert_eq!(winners, ids(&["b"]));
+        // c has no first choices; a and d tie next and d goes on the lower Borda score, passing its vote to b
+        let b = tallies.iter().find(|t| t.model_id == "b").unwrap();
+        assert_eq!(b.round_counts, vec![2, 2, 3]);
+        assert_eq!(tallies.iter().find(|t| t.model_id == "d").unwrap().eliminated_in, Some(2));
+        assert_eq!(tallies.last().unwrap().model_id, "c");
+
+        // Level on first choices and on Borda points: a genuine tie
+        let cycle = vec![ballot("a", &["b", "c"]), ballot("b", &["c", "a"]), ballot("c", &["a", "b"])];
+        let (_, winners) = VotingMethod::InstantRunoff.tally(&cycle, &ids(&["a", "b", "c"]));
+        assert_eq!(winners.len(), 3);
+    }
+
+    #[test]
+    fn test_runoff_is_restricted_to_the_tied_proposals() {
+        let tied = vec![("a".to_string(), "Answer A".to_string()), ("b".to_string(), "Answer B".to_string())];
+        let prompt = TieBreak::Runoff.prompt("Which?", &tied, "a");
+        assert!(prompt.contains("Your own proposal (a) cannot be chosen."));
+        assert!(!TieBreak::Chair.prompt("Which?", &tied, "c").contains("Your own proposal"));
+
+        let candidates = ids(&["a", "b"]);
+        let ballots: Vec<(String, Vec<String>)> = [("a", "b"), ("b", "a"), ("c", "I prefer c, then b")]
+            .iter()
+            .map(|(voter, reply)| (voter.to_string(), VotingMethod::Plurality.parse_ballot(reply, voter, &candidates)))
+            .collect();
+        let (_, winners) = VotingMethod::Plurality.tally(&ballots, &candidates);
+        assert_eq!(winners, ids(&["b"]));
+        assert_eq!(TieBreak::from_key("chair"), TieBreak::Chair);
+        assert_eq!(TieBreak::from_key("bogus"), TieBreak::Shared);
+    }
+}